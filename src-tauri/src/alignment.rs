@@ -0,0 +1,96 @@
+//! Realign transcript segments to a recording using word timestamps
+//!
+//! Live and post-hoc transcripts only carry wall-clock timestamps. Re-transcribing the
+//! recording with word timestamps and matching those words back onto the segments gives
+//! each segment a start/end offset into the WAV, for subtitles and click-to-seek.
+
+use crate::TranscriptSegment;
+
+/// How far ahead of the last match to look for a segment's word. Keeps a misheard or
+/// repeated word from jumping the alignment to a much later part of the recording.
+const SEARCH_WINDOW: usize = 30;
+
+/// A recognized word with its offsets from the start of the recording
+#[derive(Debug, Clone, PartialEq)]
+pub struct TimedWord {
+    pub text: String,
+    pub start_ms: u64,
+    pub end_ms: u64,
+}
+
+/// Lowercase a word and strip punctuation, so "Launch," matches "launch"
+fn normalize(word: &str) -> String {
+    word.chars()
+        .filter(|c| c.is_alphanumeric() || *c == '\'')
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+/// Set `start_ms`/`end_ms` on each segment from the first and last of its words found in
+/// `words`, matching in order. Segments with no matching words keep their old times.
+/// Returns how many segments were aligned.
+pub fn align_segments(segments: &mut [TranscriptSegment], words: &[TimedWord]) -> usize {
+    let recognized: Vec<String> = words.iter().map(|w| normalize(&w.text)).collect();
+    let mut cursor = 0;
+    let mut aligned = 0;
+
+    for segment in segments.iter_mut() {
+        let mut first: Option<usize> = None;
+        let mut last: Option<usize> = None;
+
+        for token in segment.text.split_whitespace().map(normalize).filter(|t| !t.is_empty()) {
+            let window_end = (cursor + SEARCH_WINDOW).min(recognized.len());
+            if let Some(offset) = recognized[cursor..window_end].iter().position(|w| *w == token) {
+                let index = cursor + offset;
+                first.get_or_insert(index);
+                last = Some(index);
+                cursor = index + 1;
+            }
+        }
+
+        if let (Some(first), Some(last)) = (first, last) {
+            segment.start_ms = Some(words[first].start_ms);
+            segment.end_ms = Some(words[last].end_ms);
+            aligned += 1;
+        }
+    }
+
+    aligned
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn word(text: &str, start_ms: u64) -> TimedWord {
+        TimedWord { text: text.to_string(), start_ms, end_ms: start_ms + 300 }
+    }
+
+    fn segment(text: &str) -> TranscriptSegment {
+        TranscriptSegment { text: text.to_string(), ..Default::default() }
+    }
+
+    #[test]
+    fn test_align_segments_in_order() {
+        let words = vec![
+            word("Let's", 1_000),
+            word("review", 1_400),
+            word("the", 1_800),
+            word("launch.", 2_100),
+            word("Sounds", 5_000),
+            word("good", 5_400),
+            word("to", 5_800),
+            word("me", 6_100),
+        ];
+        let mut segments = vec![
+            segment("Let's review the launch"),
+            segment("(inaudible)"),
+            segment("Sounds good, to me!"),
+        ];
+
+        assert_eq!(align_segments(&mut segments, &words), 2);
+        assert_eq!((segments[0].start_ms, segments[0].end_ms), (Some(1_000), Some(2_400)));
+        assert_eq!(segments[1].start_ms, None);
+        assert_eq!((segments[2].start_ms, segments[2].end_ms), (Some(5_000), Some(6_400)));
+    }
+}
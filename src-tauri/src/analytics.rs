@@ -0,0 +1,330 @@
+//! Transcript analytics (talk-time, word counts, speaking style, keywords)
+//!
+//! Pure functions over transcript segments so they can be reused by
+//! commands and tested without an app handle.
+
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashSet};
+
+use crate::TranscriptSegment;
+
+/// Average speaking rate used when a segment has no word timestamps
+const ESTIMATED_WORDS_PER_MINUTE: f64 = 150.0;
+
+/// Talk-time statistics for a single speaker
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SpeakerTalkTime {
+    pub seconds: f64,
+    pub words: usize,
+    pub percent: f64,
+}
+
+/// Estimate how long a piece of text took to say from its word count
+pub fn estimate_speech_seconds(words: usize) -> f64 {
+    words as f64 * 60.0 / ESTIMATED_WORDS_PER_MINUTE
+}
+
+/// Duration of a segment in seconds.
+/// Uses word timestamps when the provider supplied them, else estimates from word count.
+pub fn segment_duration_seconds(segment: &TranscriptSegment) -> f64 {
+    match (segment.start_ms, segment.end_ms) {
+        (Some(start), Some(end)) if end > start => (end - start) as f64 / 1000.0,
+        _ => estimate_speech_seconds(segment.text.split_whitespace().count()),
+    }
+}
+
+/// Compute per-speaker talk-time, word count and share of total talk-time
+pub fn talk_time_stats(segments: &[TranscriptSegment]) -> BTreeMap<String, SpeakerTalkTime> {
+    let mut stats: BTreeMap<String, SpeakerTalkTime> = BTreeMap::new();
+
+    for segment in segments {
+        let words = segment.text.split_whitespace().count();
+        if words == 0 {
+            continue;
+        }
+
+        let entry = stats.entry(segment.speaker.clone()).or_insert(SpeakerTalkTime {
+            seconds: 0.0,
+            words: 0,
+            percent: 0.0,
+        });
+        entry.seconds += segment_duration_seconds(segment);
+        entry.words += words;
+    }
+
+    let total_seconds: f64 = stats.values().map(|s| s.seconds).sum();
+    if total_seconds > 0.0 {
+        for entry in stats.values_mut() {
+            entry.percent = entry.seconds / total_seconds * 100.0;
+        }
+    }
+
+    stats
+}
+
+/// Speaking-rate and filler-word stats for a single speaker
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+pub struct SpeakingStyle {
+    pub words: usize,
+    /// Words per minute over segments with word timestamps (None without them)
+    pub words_per_minute: Option<f64>,
+    /// Filler stats are None when the transcript had fillers cleaned out
+    pub filler_count: Option<usize>,
+    /// Fillers per 100 words
+    pub filler_rate: Option<f64>,
+    /// How often each filler word/phrase was used
+    pub fillers: Option<BTreeMap<String, usize>>,
+}
+
+/// Lowercase words with surrounding punctuation stripped ("Um," -> "um")
+fn normalized_words(text: &str) -> Vec<String> {
+    text.split_whitespace()
+        .map(|w| w.trim_matches(|c: char| !c.is_alphanumeric() && c != '\'').to_lowercase())
+        .filter(|w| !w.is_empty())
+        .collect()
+}
+
+/// Count whole-word occurrences of a (possibly multi-word) phrase
+fn count_phrase(words: &[String], phrase: &[String]) -> usize {
+    if phrase.is_empty() || phrase.len() > words.len() {
+        return 0;
+    }
+    words.windows(phrase.len()).filter(|w| *w == phrase).count()
+}
+
+/// Compute per-speaker words per minute and filler-word usage. Pass no `filler_words` for
+/// text that had fillers removed, where counting them would report zero.
+/// WPM only counts segments with word timestamps, since estimated durations assume a fixed rate.
+pub fn speaking_style(segments: &[TranscriptSegment], filler_words: Option<&[String]>) -> BTreeMap<String, SpeakingStyle> {
+    let fillers: Option<Vec<(String, Vec<String>)>> = filler_words.map(|filler_words| {
+        filler_words
+            .iter()
+            .map(|f| (f.trim().to_lowercase(), normalized_words(f)))
+            .filter(|(_, words)| !words.is_empty())
+            .collect()
+    });
+
+    let mut stats: BTreeMap<String, SpeakingStyle> = BTreeMap::new();
+    let mut timed: BTreeMap<String, (usize, f64)> = BTreeMap::new();
+
+    for segment in segments {
+        let words = normalized_words(&segment.text);
+        if words.is_empty() {
+            continue;
+        }
+
+        let entry = stats.entry(segment.speaker.clone()).or_default();
+        entry.words += words.len();
+        if let Some(fillers) = &fillers {
+            let counts = entry.fillers.get_or_insert_with(BTreeMap::new);
+            for (filler, phrase) in fillers {
+                let count = count_phrase(&words, phrase);
+                if count > 0 {
+                    *counts.entry(filler.clone()).or_insert(0) += count;
+                }
+            }
+        }
+
+        if let (Some(start), Some(end)) = (segment.start_ms, segment.end_ms) {
+            if end > start {
+                let (timed_words, seconds) = timed.entry(segment.speaker.clone()).or_insert((0, 0.0));
+                *timed_words += words.len();
+                *seconds += (end - start) as f64 / 1000.0;
+            }
+        }
+    }
+
+    for (speaker, entry) in stats.iter_mut() {
+        entry.filler_count = entry.fillers.as_ref().map(|counts| counts.values().sum());
+        entry.filler_rate = entry.filler_count.map(|count| count as f64 * 100.0 / entry.words as f64);
+        entry.words_per_minute = timed
+            .get(speaker)
+            .filter(|(_, seconds)| *seconds > 0.0)
+            .map(|(words, seconds)| *words as f64 * 60.0 / seconds);
+    }
+
+    stats
+}
+
+/// Common words that never make a useful keyword
+const STOP_WORDS: &[&str] = &[
+    "about", "after", "again", "all", "also", "and", "any", "are", "because", "been", "before",
+    "being", "but", "can", "could", "did", "does", "doing", "don't", "down", "each", "even", "for",
+    "from", "get", "going", "gonna", "got", "had", "has", "have", "having", "her", "here", "him",
+    "his", "how", "i'm", "it's", "its", "just", "know", "let's", "like", "made", "make", "many",
+    "maybe", "more", "most", "much", "need", "not", "now", "okay", "one", "only", "other", "our",
+    "out", "over", "really", "right", "said", "say", "see", "she", "should", "some", "something",
+    "still", "such", "sure", "than", "that", "that's", "the", "their", "them", "then", "there",
+    "there's", "these", "they", "thing", "things", "think", "this", "those", "through", "too",
+    "up", "very", "want", "was", "way", "we'll", "we're", "well", "were", "what", "when", "where",
+    "which", "while", "who", "why", "will", "with", "would", "yeah", "yes", "you", "you're", "your",
+];
+
+/// A salient term or two-word phrase, weighted 0.0..=1.0 relative to the top keyword
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Keyword {
+    pub term: String,
+    pub weight: f64,
+    pub count: usize,
+}
+
+/// Content words and two-word phrases per line of `text` (no stop words, numbers or
+/// words under three letters). Phrases don't span lines, so pass one segment per line.
+fn keyword_terms(text: &str) -> Vec<String> {
+    let mut terms = Vec::new();
+    for line in text.lines() {
+        let words: Vec<Option<String>> = normalized_words(line)
+            .into_iter()
+            .map(|w| {
+                let content = w.chars().count() >= 3
+                    && !w.chars().all(|c| c.is_numeric())
+                    && !STOP_WORDS.contains(&w.as_str());
+                content.then_some(w)
+            })
+            .collect();
+
+        terms.extend(words.iter().flatten().cloned());
+        for pair in words.windows(2) {
+            if let [Some(first), Some(second)] = pair {
+                terms.push(format!("{} {}", first, second));
+            }
+        }
+    }
+    terms
+}
+
+/// The `top_n` most salient terms of `document` by TF-IDF, with `corpus` (other meetings'
+/// transcripts) as the background, so words every meeting uses rank low. Phrases need to
+/// occur at least twice to count.
+pub fn extract_keywords(document: &str, corpus: &[String], top_n: usize) -> Vec<Keyword> {
+    let mut counts: BTreeMap<String, usize> = BTreeMap::new();
+    for term in keyword_terms(document) {
+        *counts.entry(term).or_insert(0) += 1;
+    }
+    counts.retain(|term, count| !term.contains(' ') || *count >= 2);
+
+    let corpus_terms: Vec<HashSet<String>> = corpus.iter().map(|text| keyword_terms(text).into_iter().collect()).collect();
+    let documents = corpus_terms.len() as f64 + 1.0;
+
+    let mut keywords: Vec<Keyword> = counts
+        .into_iter()
+        .map(|(term, count)| {
+            let containing = corpus_terms.iter().filter(|terms| terms.contains(&term)).count() as f64 + 1.0;
+            let idf = ((1.0 + documents) / (1.0 + containing)).ln() + 1.0;
+            Keyword { weight: count as f64 * idf, term, count }
+        })
+        .collect();
+
+    keywords.sort_by(|a, b| b.weight.total_cmp(&a.weight).then_with(|| a.term.cmp(&b.term)));
+    keywords.truncate(top_n);
+
+    if let Some(max) = keywords.first().map(|k| k.weight).filter(|w| *w > 0.0) {
+        for keyword in &mut keywords {
+            keyword.weight /= max;
+        }
+    }
+    keywords
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn segment(speaker: &str, text: &str) -> TranscriptSegment {
+        TranscriptSegment {
+            timestamp: "10:00:00".to_string(),
+            speaker: speaker.to_string(),
+            text: text.to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_talk_time_splits_by_speaker() {
+        let segments = vec![
+            segment("You", "one two three"),
+            segment("Participant", "one two three four five six seven eight nine"),
+        ];
+        let stats = talk_time_stats(&segments);
+
+        assert_eq!(stats.len(), 2);
+        assert_eq!(stats["You"].words, 3);
+        assert_eq!(stats["Participant"].words, 9);
+        assert!((stats["You"].percent - 25.0).abs() < 0.01);
+        assert!((stats["Participant"].percent - 75.0).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_talk_time_single_speaker_bucket() {
+        let segments = vec![
+            segment("Speaker", "hello everyone"),
+            segment("Speaker", "let's get started"),
+        ];
+        let stats = talk_time_stats(&segments);
+
+        assert_eq!(stats.len(), 1);
+        assert_eq!(stats["Speaker"].words, 5);
+        assert!((stats["Speaker"].percent - 100.0).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_talk_time_prefers_word_timestamps() {
+        let mut timed = segment("You", "hi");
+        timed.start_ms = Some(1_000);
+        timed.end_ms = Some(4_500);
+        let stats = talk_time_stats(&[timed]);
+
+        assert!((stats["You"].seconds - 3.5).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_speaking_style_counts_fillers_and_wpm() {
+        let mut timed = segment("You", "Um, so we, like, you know, ship it now. Um.");
+        timed.start_ms = Some(0);
+        timed.end_ms = Some(5_000);
+        let segments = vec![timed, segment("Participant", "Sounds likely to work")];
+        let fillers: Vec<String> = ["um", "like", "You know"].iter().map(|f| f.to_string()).collect();
+        let stats = speaking_style(&segments, Some(&fillers));
+
+        let you = &stats["You"];
+        assert_eq!(you.words, 10);
+        assert_eq!(you.filler_count, Some(4));
+        let counts = you.fillers.as_ref().unwrap();
+        assert_eq!(counts["um"], 2);
+        assert_eq!(counts["you know"], 1);
+        assert!((you.filler_rate.unwrap() - 40.0).abs() < 0.01);
+        assert!((you.words_per_minute.unwrap() - 120.0).abs() < 0.01);
+
+        // "likely" isn't "like", and there are no timestamps for a WPM
+        assert_eq!(stats["Participant"].filler_count, Some(0));
+        assert_eq!(stats["Participant"].words_per_minute, None);
+
+        // Cleaned text reports no filler stats rather than zero
+        let cleaned = speaking_style(&segments, None);
+        assert_eq!((cleaned["You"].filler_count, cleaned["You"].filler_rate), (None, None));
+        assert_eq!(cleaned["You"].words, 10);
+    }
+
+    #[test]
+    fn test_extract_keywords_ranks_distinctive_terms() {
+        let document = "The launch plan needs review\nLaunch plan budget is tight\nThe team agreed on the launch plan";
+        let corpus = vec!["Team budget review".to_string(), "Team sync notes".to_string()];
+        let keywords = extract_keywords(document, &corpus, 20);
+        let rank = |term: &str| keywords.iter().position(|k| k.term == term);
+
+        assert_eq!(keywords[0].term, "launch");
+        assert_eq!(keywords[0].weight, 1.0);
+        assert_eq!(keywords[1].term, "launch plan");
+        assert_eq!(keywords[1].count, 3);
+        assert!(rank("budget").unwrap() < rank("team").unwrap());
+        // Stop words, short words and one-off phrases are left out
+        assert_eq!(rank("the"), None);
+        assert_eq!(rank("plan budget"), None);
+        assert_eq!(extract_keywords(document, &corpus, 2).len(), 2);
+    }
+
+    #[test]
+    fn test_talk_time_empty_transcript() {
+        assert!(talk_time_stats(&[]).is_empty());
+    }
+}
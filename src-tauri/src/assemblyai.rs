@@ -1,7 +1,13 @@
 use anyhow::{anyhow, Result};
+use futures_util::TryStreamExt;
 use serde::{Deserialize, Serialize};
+use std::sync::{Mutex, MutexGuard};
+use tokio::io::AsyncReadExt;
+use tokio_util::io::ReaderStream;
 
-const ASSEMBLYAI_API_URL: &str = "https://api.assemblyai.com/v2";
+use crate::endpoints;
+use crate::error::ProviderError;
+use crate::net::{http_retry, RetryPolicy};
 
 #[derive(Debug, Serialize)]
 struct TranscriptRequest {
@@ -16,6 +22,9 @@ pub struct TranscriptResponse {
     pub text: Option<String>,
     pub utterances: Option<Vec<Utterance>>,
     pub error: Option<String>,
+    /// Length of the transcribed audio in seconds
+    #[serde(default)]
+    pub audio_duration: Option<f64>,
 }
 
 #[derive(Debug, Deserialize, Clone)]
@@ -24,6 +33,110 @@ pub struct Utterance {
     pub text: String,
     pub start: u64,
     pub end: u64,
+    #[serde(default)]
+    pub confidence: Option<f32>,
+}
+
+/// Trade-off between latency and speaker labels for file transcription.
+/// Disabling speaker labels and polling more often returns results sooner.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TranscriptionOptions {
+    #[serde(default = "default_speaker_labels")]
+    pub speaker_labels: bool,
+    /// First poll delay; short jobs come back this quickly
+    #[serde(default = "default_poll_interval_ms")]
+    pub poll_interval_ms: u64,
+    /// The delay grows by half each poll up to this, so long jobs aren't polled hundreds of times
+    #[serde(default = "default_max_poll_interval_ms")]
+    pub max_poll_interval_ms: u64,
+}
+
+/// Bounds for the poll interval, so we neither hammer the API nor stall
+pub const MIN_POLL_INTERVAL_MS: u64 = 250;
+pub const MAX_POLL_INTERVAL_MS: u64 = 30_000;
+
+fn default_speaker_labels() -> bool {
+    true
+}
+
+fn default_poll_interval_ms() -> u64 {
+    3000
+}
+
+fn default_max_poll_interval_ms() -> u64 {
+    15_000
+}
+
+impl Default for TranscriptionOptions {
+    fn default() -> Self {
+        Self {
+            speaker_labels: default_speaker_labels(),
+            poll_interval_ms: default_poll_interval_ms(),
+            max_poll_interval_ms: default_max_poll_interval_ms(),
+        }
+    }
+}
+
+impl TranscriptionOptions {
+    pub fn validate(&self) -> Result<(), String> {
+        if !(MIN_POLL_INTERVAL_MS..=MAX_POLL_INTERVAL_MS).contains(&self.poll_interval_ms) {
+            return Err(format!(
+                "Poll interval must be between {} and {} ms",
+                MIN_POLL_INTERVAL_MS, MAX_POLL_INTERVAL_MS
+            ));
+        }
+        if !(self.poll_interval_ms..=MAX_POLL_INTERVAL_MS).contains(&self.max_poll_interval_ms) {
+            return Err(format!(
+                "Max poll interval must be between the poll interval and {} ms",
+                MAX_POLL_INTERVAL_MS
+            ));
+        }
+        Ok(())
+    }
+
+    /// Delay before the poll after one that waited `current_ms`
+    fn next_poll_interval_ms(&self, current_ms: u64) -> u64 {
+        (current_ms + current_ms / 2).min(self.max_poll_interval_ms.max(self.poll_interval_ms))
+    }
+}
+
+const CANCELLED: &str = "Transcription cancelled";
+
+/// Jobs in flight, so a stop can cancel them on AssemblyAI as well as locally
+#[derive(Debug, Default)]
+pub struct ActiveJobs {
+    transcript_ids: Vec<String>,
+    /// Bumped by each cancel; jobs started under an older generation stop polling
+    generation: u64,
+}
+
+impl ActiveJobs {
+    fn is_cancelled(&self, generation: u64) -> bool {
+        self.generation != generation
+    }
+
+    /// Track a started job. Returns false (and doesn't track it) if a cancel came in meanwhile.
+    fn register(&mut self, transcript_id: &str, generation: u64) -> bool {
+        if self.is_cancelled(generation) {
+            return false;
+        }
+        self.transcript_ids.push(transcript_id.to_string());
+        true
+    }
+
+    fn finish(&mut self, transcript_id: &str) {
+        self.transcript_ids.retain(|id| id != transcript_id);
+    }
+
+    /// Mark everything in flight as cancelled, returning the jobs to delete
+    fn cancel(&mut self) -> Vec<String> {
+        self.generation += 1;
+        std::mem::take(&mut self.transcript_ids)
+    }
+}
+
+fn lock(jobs: &Mutex<ActiveJobs>) -> MutexGuard<'_, ActiveJobs> {
+    jobs.lock().unwrap_or_else(|e| e.into_inner())
 }
 
 #[derive(Debug, Serialize)]
@@ -37,9 +150,9 @@ pub async fn check_api_key(api_key: &str) -> Result<bool> {
         return Ok(false);
     }
 
-    let client = reqwest::Client::new();
+    let client = crate::net::client();
     let response = client
-        .get(format!("{}/transcript", ASSEMBLYAI_API_URL))
+        .get(format!("{}/transcript", endpoints::assemblyai()))
         .header("Authorization", api_key)
         .timeout(std::time::Duration::from_secs(10))
         .send()
@@ -51,24 +164,38 @@ pub async fn check_api_key(api_key: &str) -> Result<bool> {
     }
 }
 
+/// Request body that streams the first `len` bytes of the file from disk, so memory stays
+/// flat for long recordings. The file is opened when the body is first polled, so each retry
+/// reads it from the start. Capping at `len` keeps the body matching the declared
+/// Content-Length while the recorder is still appending to the file.
+fn file_body(file_path: &str, len: u64) -> reqwest::Body {
+    let chunks = futures_util::stream::once(tokio::fs::File::open(file_path.to_string()))
+        .map_ok(move |file| ReaderStream::new(file.take(len)))
+        .try_flatten();
+    reqwest::Body::wrap_stream(chunks)
+}
+
 /// Upload audio file to AssemblyAI
 pub async fn upload_audio(api_key: &str, file_path: &str) -> Result<String> {
-    let client = reqwest::Client::new();
-
-    let file_content = std::fs::read(file_path)?;
-
-    let response = client
-        .post(format!("{}/upload", ASSEMBLYAI_API_URL))
-        .header("Authorization", api_key)
-        .header("Content-Type", "application/octet-stream")
-        .body(file_content)
-        .timeout(std::time::Duration::from_secs(300))
-        .send()
-        .await?;
-
-    if !response.status().is_success() {
+    let client = crate::net::client();
+
+    let file_size = tokio::fs::metadata(file_path).await?.len();
+
+    let response = http_retry("AssemblyAI upload", RetryPolicy::TRANSIENT, || {
+        client
+            .post(format!("{}/upload", endpoints::assemblyai()))
+            .header("Authorization", api_key)
+            .header("Content-Type", "application/octet-stream")
+            .header("Content-Length", file_size)
+            .body(file_body(file_path, file_size))
+            .timeout(std::time::Duration::from_secs(300))
+    })
+    .await?;
+
+    let status = response.status();
+    if !status.is_success() {
         let error_text = response.text().await.unwrap_or_default();
-        return Err(anyhow!("Failed to upload audio: {}", error_text));
+        return Err(ProviderError::new(status, format!("Failed to upload audio: {}", error_text)).into());
     }
 
     #[derive(Deserialize)]
@@ -81,26 +208,30 @@ pub async fn upload_audio(api_key: &str, file_path: &str) -> Result<String> {
 }
 
 /// Start transcription job
-pub async fn start_transcription(api_key: &str, audio_url: &str) -> Result<String> {
-    let client = reqwest::Client::new();
+pub async fn start_transcription(api_key: &str, audio_url: &str, speaker_labels: bool) -> Result<String> {
+    let client = crate::net::client();
 
     let request = TranscriptRequest {
         audio_url: audio_url.to_string(),
-        speaker_labels: true,
+        speaker_labels,
     };
 
-    let response = client
-        .post(format!("{}/transcript", ASSEMBLYAI_API_URL))
-        .header("Authorization", api_key)
-        .header("Content-Type", "application/json")
-        .json(&request)
-        .timeout(std::time::Duration::from_secs(30))
-        .send()
-        .await?;
-
-    if !response.status().is_success() {
+    // Creating a job isn't idempotent: a timeout may come after AssemblyAI accepted it, and
+    // a retry would start (and bill) a second one. A 429 means nothing was created.
+    let response = http_retry("AssemblyAI", RetryPolicy::RATE_LIMIT, || {
+        client
+            .post(format!("{}/transcript", endpoints::assemblyai()))
+            .header("Authorization", api_key)
+            .header("Content-Type", "application/json")
+            .json(&request)
+            .timeout(std::time::Duration::from_secs(30))
+    })
+    .await?;
+
+    let status = response.status();
+    if !status.is_success() {
         let error_text = response.text().await.unwrap_or_default();
-        return Err(anyhow!("Failed to start transcription: {}", error_text));
+        return Err(ProviderError::new(status, format!("Failed to start transcription: {}", error_text)).into());
     }
 
     let result: TranscriptResponse = response.json().await?;
@@ -109,10 +240,32 @@ pub async fn start_transcription(api_key: &str, audio_url: &str) -> Result<Strin
 
 /// Get transcription result
 pub async fn get_transcription(api_key: &str, transcript_id: &str) -> Result<TranscriptResponse> {
-    let client = reqwest::Client::new();
+    let client = crate::net::client();
+
+    let response = http_retry("AssemblyAI", RetryPolicy::TRANSIENT, || {
+        client
+            .get(format!("{}/transcript/{}", endpoints::assemblyai(), transcript_id))
+            .header("Authorization", api_key)
+            .timeout(std::time::Duration::from_secs(30))
+    })
+    .await?;
+
+    let status = response.status();
+    if !status.is_success() {
+        let error_text = response.text().await.unwrap_or_default();
+        return Err(ProviderError::new(status, format!("Failed to get transcription: {}", error_text)).into());
+    }
+
+    let result: TranscriptResponse = response.json().await?;
+    Ok(result)
+}
+
+/// Delete a job on AssemblyAI
+pub async fn delete_transcription(api_key: &str, transcript_id: &str) -> Result<()> {
+    let client = crate::net::client();
 
     let response = client
-        .get(format!("{}/transcript/{}", ASSEMBLYAI_API_URL, transcript_id))
+        .delete(format!("{}/transcript/{}", endpoints::assemblyai(), transcript_id))
         .header("Authorization", api_key)
         .timeout(std::time::Duration::from_secs(30))
         .send()
@@ -120,16 +273,26 @@ pub async fn get_transcription(api_key: &str, transcript_id: &str) -> Result<Tra
 
     if !response.status().is_success() {
         let error_text = response.text().await.unwrap_or_default();
-        return Err(anyhow!("Failed to get transcription: {}", error_text));
+        return Err(anyhow!("Failed to delete transcription: {}", error_text));
     }
 
-    let result: TranscriptResponse = response.json().await?;
-    Ok(result)
+    Ok(())
 }
 
-/// Poll for transcription completion
-pub async fn wait_for_transcription(api_key: &str, transcript_id: &str) -> Result<TranscriptResponse> {
+/// Poll for transcription completion with a growing delay between polls,
+/// giving up once `cancelled` returns true
+pub async fn wait_for_transcription(
+    api_key: &str,
+    transcript_id: &str,
+    options: &TranscriptionOptions,
+    cancelled: impl Fn() -> bool,
+) -> Result<TranscriptResponse> {
+    let mut poll_interval_ms = options.poll_interval_ms;
     loop {
+        if cancelled() {
+            return Err(anyhow!(CANCELLED));
+        }
+
         let result = get_transcription(api_key, transcript_id).await?;
 
         match result.status.as_str() {
@@ -142,80 +305,99 @@ pub async fn wait_for_transcription(api_key: &str, transcript_id: &str) -> Resul
             }
             _ => {
                 // Still processing, wait and retry
-                tokio::time::sleep(std::time::Duration::from_secs(3)).await;
+                tokio::time::sleep(std::time::Duration::from_millis(poll_interval_ms)).await;
+                poll_interval_ms = options.next_poll_interval_ms(poll_interval_ms);
             }
         }
     }
 }
 
-/// Transcribe an audio file (upload, start, and wait for result)
-pub async fn transcribe_file(api_key: &str, file_path: &str) -> Result<TranscriptResponse> {
+/// Transcribe an audio file (upload, start, and wait for result).
+/// The job is tracked in `jobs` while it runs so `cancel_jobs` can stop it.
+pub async fn transcribe_file(
+    api_key: &str,
+    file_path: &str,
+    options: &TranscriptionOptions,
+    jobs: &Mutex<ActiveJobs>,
+) -> Result<TranscriptResponse> {
+    let generation = lock(jobs).generation;
+
     // Upload the file
     let upload_url = upload_audio(api_key, file_path).await?;
+    if lock(jobs).is_cancelled(generation) {
+        return Err(anyhow!(CANCELLED));
+    }
 
     // Start transcription
-    let transcript_id = start_transcription(api_key, &upload_url).await?;
+    let transcript_id = start_transcription(api_key, &upload_url, options.speaker_labels).await?;
+    let registered = lock(jobs).register(&transcript_id, generation);
+    if !registered {
+        // Cancelled while the job was being created
+        if let Err(e) = delete_transcription(api_key, &transcript_id).await {
+            tracing::warn!("Failed to delete cancelled AssemblyAI job {}: {}", transcript_id, e);
+        }
+        return Err(anyhow!(CANCELLED));
+    }
 
     // Wait for completion
-    wait_for_transcription(api_key, &transcript_id).await
+    let result = wait_for_transcription(api_key, &transcript_id, options, || {
+        lock(jobs).is_cancelled(generation)
+    })
+    .await;
+    lock(jobs).finish(&transcript_id);
+    if let Ok(response) = &result {
+        crate::usage::record_audio("assemblyai", response.audio_duration.unwrap_or(0.0));
+    }
+    result
 }
 
-/// Poll for transcription completion with configurable poll interval
-async fn wait_for_transcription_fast(api_key: &str, transcript_id: &str, poll_interval_ms: u64) -> Result<TranscriptResponse> {
-    loop {
-        let result = get_transcription(api_key, transcript_id).await?;
-
-        match result.status.as_str() {
-            "completed" => return Ok(result),
-            "error" => {
-                return Err(anyhow!(
-                    "Transcription failed: {}",
-                    result.error.unwrap_or_else(|| "Unknown error".to_string())
-                ));
-            }
-            _ => {
-                // Still processing, wait and retry with configurable interval
-                tokio::time::sleep(std::time::Duration::from_millis(poll_interval_ms)).await;
-            }
+/// Cancel every job in flight: polling stops and the jobs are deleted on AssemblyAI.
+/// Returns how many jobs were running.
+pub async fn cancel_jobs(api_key: &str, jobs: &Mutex<ActiveJobs>) -> usize {
+    let transcript_ids = lock(jobs).cancel();
+    for transcript_id in &transcript_ids {
+        if let Err(e) = delete_transcription(api_key, transcript_id).await {
+            tracing::warn!("Failed to delete AssemblyAI job {}: {}", transcript_id, e);
         }
     }
+    transcript_ids.len()
 }
 
-/// Transcribe an audio file with faster polling for lower latency
-pub async fn transcribe_file_fast(api_key: &str, file_path: &str, poll_interval_ms: u64) -> Result<TranscriptResponse> {
-    // Upload the file
-    let upload_url = upload_audio(api_key, file_path).await?;
-
-    // Start transcription (without speaker labels for faster processing)
-    let client = reqwest::Client::new();
-
-    #[derive(serde::Serialize)]
-    struct FastTranscriptRequest {
-        audio_url: String,
-        speaker_labels: bool,
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cancel_stops_jobs_started_before_it() {
+        let mut jobs = ActiveJobs::default();
+        let before = jobs.generation;
+        assert!(jobs.register("job-1", before));
+
+        assert_eq!(jobs.cancel(), vec!["job-1".to_string()]);
+        assert!(jobs.is_cancelled(before));
+        // A job whose upload was in flight during the cancel is not tracked
+        assert!(!jobs.register("job-2", before));
+
+        let after = jobs.generation;
+        assert!(jobs.register("job-3", after));
+        jobs.finish("job-3");
+        assert!(jobs.cancel().is_empty());
     }
 
-    let request = FastTranscriptRequest {
-        audio_url: upload_url,
-        speaker_labels: false, // Disable for faster processing
-    };
-
-    let response = client
-        .post(format!("{}/transcript", ASSEMBLYAI_API_URL))
-        .header("Authorization", api_key)
-        .header("Content-Type", "application/json")
-        .json(&request)
-        .timeout(std::time::Duration::from_secs(30))
-        .send()
-        .await?;
+    #[test]
+    fn test_poll_interval_grows_to_cap() {
+        let options = TranscriptionOptions::default();
+        let mut interval = options.poll_interval_ms;
+        let mut delays = Vec::new();
+        for _ in 0..6 {
+            delays.push(interval);
+            interval = options.next_poll_interval_ms(interval);
+        }
+        assert_eq!(delays, vec![3000, 4500, 6750, 10_125, 15_000, 15_000]);
 
-    if !response.status().is_success() {
-        let error_text = response.text().await.unwrap_or_default();
-        return Err(anyhow!("Failed to start transcription: {}", error_text));
+        let fixed = TranscriptionOptions { max_poll_interval_ms: 3000, ..options.clone() };
+        assert_eq!(fixed.next_poll_interval_ms(3000), 3000);
+        assert!(fixed.validate().is_ok());
+        assert!(TranscriptionOptions { max_poll_interval_ms: 1000, ..options }.validate().is_err());
     }
-
-    let result: TranscriptResponse = response.json().await?;
-
-    // Wait for completion with faster polling
-    wait_for_transcription_fast(api_key, &result.id, poll_interval_ms).await
 }
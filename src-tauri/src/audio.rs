@@ -1,10 +1,287 @@
 use anyhow::{anyhow, Result};
 use cpal::traits::{DeviceTrait, HostTrait, StreamTrait};
 use hound::{WavSpec, WavWriter};
+use serde::{Deserialize, Serialize};
 use std::sync::{Arc, Mutex};
 use std::thread;
 use std::path::PathBuf;
 
+use crate::system_audio::{downmix, LinearResampler};
+
+/// WAV format for recordings. Unset channels/sample rate follow the input device.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub struct RecordingConfig {
+    /// 16 or 24 (integer PCM), or 32 (float)
+    #[serde(default = "default_recording_bits")]
+    pub bits: u16,
+    /// 1 (mono) or 2 (stereo)
+    #[serde(default)]
+    pub channels: Option<u16>,
+    #[serde(default)]
+    pub sample_rate: Option<u32>,
+}
+
+fn default_recording_bits() -> u16 {
+    16
+}
+
+impl Default for RecordingConfig {
+    fn default() -> Self {
+        Self {
+            bits: default_recording_bits(),
+            channels: None,
+            sample_rate: None,
+        }
+    }
+}
+
+impl RecordingConfig {
+    pub fn validate(&self) -> Result<(), String> {
+        if ![16, 24, 32].contains(&self.bits) {
+            return Err(format!("Bit depth must be 16, 24 or 32 (got {})", self.bits));
+        }
+        if let Some(channels) = self.channels {
+            if !(1..=2).contains(&channels) {
+                return Err(format!("Channels must be 1 or 2 (got {})", channels));
+            }
+        }
+        if let Some(rate) = self.sample_rate {
+            if !(8_000..=192_000).contains(&rate) {
+                return Err(format!("Sample rate must be between 8000 and 192000 Hz (got {})", rate));
+            }
+        }
+        Ok(())
+    }
+
+    fn wav_spec(&self, device_channels: u16, device_rate: u32) -> WavSpec {
+        WavSpec {
+            channels: self.channels.unwrap_or(device_channels),
+            sample_rate: self.sample_rate.unwrap_or(device_rate),
+            bits_per_sample: self.bits,
+            sample_format: if self.bits == 32 { hound::SampleFormat::Float } else { hound::SampleFormat::Int },
+        }
+    }
+}
+
+/// Voice-activated recording: only write audio while the input is above `threshold_db`
+/// (dBFS), so silences are left out of the file. Speech keeps being written for
+/// `hangover_ms` after it drops below the threshold, so pauses between words aren't clipped.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub struct VoiceActivation {
+    #[serde(default)]
+    pub enabled: bool,
+    #[serde(default = "default_voice_threshold_db")]
+    pub threshold_db: f32,
+    #[serde(default = "default_voice_hangover_ms")]
+    pub hangover_ms: u64,
+}
+
+fn default_voice_threshold_db() -> f32 {
+    -45.0
+}
+
+fn default_voice_hangover_ms() -> u64 {
+    500
+}
+
+impl Default for VoiceActivation {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            threshold_db: default_voice_threshold_db(),
+            hangover_ms: default_voice_hangover_ms(),
+        }
+    }
+}
+
+impl VoiceActivation {
+    pub fn validate(&self) -> Result<(), String> {
+        if !(-90.0..=0.0).contains(&self.threshold_db) {
+            return Err(format!("Threshold must be between -90 and 0 dB (got {})", self.threshold_db));
+        }
+        if self.hangover_ms > 5000 {
+            return Err(format!("Hangover must be at most 5000 ms (got {})", self.hangover_ms));
+        }
+        Ok(())
+    }
+}
+
+/// Silences voice activation left out of a recording, so times in the file can be mapped to
+/// times in the meeting and back. Saved beside the recording as `<name>.gaps.json`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct SkippedSpans {
+    /// (file_ms, skipped_ms): at `file_ms` into the file, `skipped_ms` of the meeting is missing
+    spans: Vec<(u64, u64)>,
+}
+
+impl SkippedSpans {
+    fn path_for(recording_path: &str) -> PathBuf {
+        PathBuf::from(recording_path).with_extension("gaps.json")
+    }
+
+    /// The spans saved for a recording; none if it was recorded without voice activation
+    pub fn load(recording_path: &str) -> Self {
+        std::fs::read_to_string(Self::path_for(recording_path))
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default()
+    }
+
+    fn save(&self, recording_path: &str) -> Result<()> {
+        std::fs::write(Self::path_for(recording_path), serde_json::to_string(self)?)?;
+        Ok(())
+    }
+
+    fn push(&mut self, file_ms: u64, skipped_ms: u64) {
+        if skipped_ms > 0 {
+            self.spans.push((file_ms, skipped_ms));
+        }
+    }
+
+    /// Meeting time of a position in the file
+    pub fn to_meeting_ms(&self, file_ms: u64) -> u64 {
+        file_ms + self.spans.iter().filter(|(at, _)| *at <= file_ms).map(|(_, skipped)| skipped).sum::<u64>()
+    }
+
+    /// Position in the file of a meeting time; times inside a skipped silence map to where
+    /// it was cut out
+    pub fn to_file_ms(&self, meeting_ms: u64) -> u64 {
+        let mut skipped_before = 0;
+        for (at, skipped) in &self.spans {
+            let starts = at + skipped_before;
+            if meeting_ms < starts {
+                break;
+            }
+            if meeting_ms < starts + skipped {
+                return *at;
+            }
+            skipped_before += skipped;
+        }
+        meeting_ms - skipped_before
+    }
+}
+
+/// Decides per callback whether converted audio gets written, for voice activation
+struct VoiceGate {
+    /// None when voice activation is off and everything is written
+    threshold_rms: Option<f32>,
+    hangover_samples: u64,
+    /// Samples still written after the level last dropped below the threshold
+    remaining: u64,
+    /// Interleaved samples per second, to turn sample counts into ms
+    samples_per_sec: u64,
+    written: u64,
+    /// Samples left out since audio was last written
+    skipping: u64,
+    skipped: Arc<Mutex<SkippedSpans>>,
+}
+
+impl VoiceGate {
+    fn new(voice: &VoiceActivation, spec: &WavSpec) -> Self {
+        let samples_per_sec = (spec.sample_rate as u64 * spec.channels as u64).max(1);
+        Self {
+            threshold_rms: voice.enabled.then(|| 10f32.powf(voice.threshold_db / 20.0)),
+            hangover_samples: voice.hangover_ms * samples_per_sec / 1000,
+            remaining: 0,
+            samples_per_sec,
+            written: 0,
+            skipping: 0,
+            skipped: Arc::new(Mutex::new(SkippedSpans::default())),
+        }
+    }
+
+    /// Whether `samples` should be written
+    fn admit(&mut self, samples: &[f32]) -> bool {
+        let Some(threshold) = self.threshold_rms else {
+            return true;
+        };
+        if samples.is_empty() {
+            return false;
+        }
+
+        let rms = (samples.iter().map(|s| s * s).sum::<f32>() / samples.len() as f32).sqrt();
+        let admitted = if rms >= threshold {
+            self.remaining = self.hangover_samples;
+            true
+        } else if self.remaining > 0 {
+            self.remaining = self.remaining.saturating_sub(samples.len() as u64);
+            true
+        } else {
+            false
+        };
+
+        if !admitted {
+            self.skipping += samples.len() as u64;
+            return false;
+        }
+        if self.skipping > 0 {
+            if let Ok(mut skipped) = self.skipped.lock() {
+                skipped.push(self.written * 1000 / self.samples_per_sec, self.skipping * 1000 / self.samples_per_sec);
+            }
+            self.skipping = 0;
+        }
+        self.written += samples.len() as u64;
+        true
+    }
+}
+
+/// Remix interleaved audio to another channel count: averaged for mono, otherwise
+/// each output channel takes the matching input channel (the last one when there are fewer)
+fn map_channels(data: &[f32], from: usize, to: usize) -> Vec<f32> {
+    if from == to || from == 0 {
+        return data.to_vec();
+    }
+    if to == 1 {
+        return downmix(data, from);
+    }
+    data.chunks_exact(from)
+        .flat_map(|frame| (0..to).map(move |c| frame[c.min(from - 1)]))
+        .collect()
+}
+
+/// Converts device callbacks (interleaved f32) to the recording's channels and sample rate
+struct FrameConverter {
+    from_channels: usize,
+    to_channels: usize,
+    /// One resampler per output channel, so each keeps its own history across callbacks
+    resamplers: Vec<LinearResampler>,
+}
+
+impl FrameConverter {
+    fn new(from_channels: u16, from_rate: u32, spec: &WavSpec) -> Self {
+        Self {
+            from_channels: from_channels as usize,
+            to_channels: spec.channels as usize,
+            resamplers: (0..spec.channels).map(|_| LinearResampler::new(from_rate, spec.sample_rate)).collect(),
+        }
+    }
+
+    fn process(&mut self, input: &[f32]) -> Vec<f32> {
+        let mapped = map_channels(input, self.from_channels, self.to_channels);
+        if self.to_channels == 1 {
+            return self.resamplers[0].process(&mapped);
+        }
+
+        let channels: Vec<Vec<f32>> = self.resamplers.iter_mut().enumerate()
+            .map(|(c, resampler)| {
+                let channel: Vec<f32> = mapped.iter().skip(c).step_by(self.to_channels).copied().collect();
+                resampler.process(&channel)
+            })
+            .collect();
+        let frames = channels.iter().map(Vec::len).min().unwrap_or(0);
+        (0..frames).flat_map(|i| channels.iter().map(move |channel| channel[i])).collect()
+    }
+}
+
+/// Quantize a -1.0..=1.0 sample to the recording's bit depth
+fn write_sample<W: std::io::Write + std::io::Seek>(writer: &mut WavWriter<W>, bits: u16, sample: f32) -> hound::Result<()> {
+    match bits {
+        16 => writer.write_sample((sample * 32_768.0).clamp(-32_768.0, 32_767.0) as i16),
+        24 => writer.write_sample((sample * 8_388_608.0).clamp(-8_388_608.0, 8_388_607.0) as i32),
+        _ => writer.write_sample(sample),
+    }
+}
+
 // We need to handle the Stream in a separate thread since cpal::Stream is not Send
 pub struct AudioRecorder {
     stop_signal: Arc<Mutex<bool>>,
@@ -12,6 +289,73 @@ pub struct AudioRecorder {
     thread_handle: Option<thread::JoinHandle<Result<()>>>,
 }
 
+/// Sample conversions shared by recording and live capture, so every module maps a
+/// device's format the same way
+pub fn f32_to_i16(s: f32) -> i16 {
+    (s.clamp(-1.0, 1.0) * 32_767.0) as i16
+}
+
+pub fn i16_to_f32(s: i16) -> f32 {
+    s as f32 / 32_768.0
+}
+
+pub fn u16_to_i16(s: u16) -> i16 {
+    (s as i32 - 32_768) as i16
+}
+
+pub fn u16_to_f32(s: u16) -> f32 {
+    i16_to_f32(u16_to_i16(s))
+}
+
+fn build_converted_stream<S: cpal::SizedSample + 'static, T: Send + 'static>(
+    device: &cpal::Device,
+    config: &cpal::StreamConfig,
+    convert: fn(S) -> T,
+    mut on_data: impl FnMut(&[T]) + Send + 'static,
+) -> Result<cpal::Stream> {
+    let stream = device.build_input_stream(
+        config,
+        move |data: &[S], _: &cpal::InputCallbackInfo| {
+            let samples: Vec<T> = data.iter().map(|&s| convert(s)).collect();
+            on_data(&samples)
+        },
+        |err| tracing::warn!("Audio stream error: {}", err),
+        None,
+    )?;
+    Ok(stream)
+}
+
+/// Input stream in the device's own sample format (F32, I16 or U16), delivering f32 samples
+pub fn build_f32_input_stream(
+    device: &cpal::Device,
+    config: &cpal::SupportedStreamConfig,
+    on_data: impl FnMut(&[f32]) + Send + 'static,
+) -> Result<cpal::Stream> {
+    let stream_config = config.config();
+    match config.sample_format() {
+        cpal::SampleFormat::F32 => build_converted_stream(device, &stream_config, |s: f32| s, on_data),
+        cpal::SampleFormat::I16 => build_converted_stream(device, &stream_config, i16_to_f32, on_data),
+        cpal::SampleFormat::U16 => build_converted_stream(device, &stream_config, u16_to_f32, on_data),
+        format => Err(anyhow!("Unsupported sample format: {}", format)),
+    }
+}
+
+/// Input stream in the device's own sample format (F32, I16 or U16), delivering 16-bit PCM
+/// as sent to the live transcription providers
+pub fn build_pcm16_input_stream(
+    device: &cpal::Device,
+    config: &cpal::SupportedStreamConfig,
+    on_data: impl FnMut(&[i16]) + Send + 'static,
+) -> Result<cpal::Stream> {
+    let stream_config = config.config();
+    match config.sample_format() {
+        cpal::SampleFormat::F32 => build_converted_stream(device, &stream_config, f32_to_i16, on_data),
+        cpal::SampleFormat::I16 => build_converted_stream(device, &stream_config, |s: i16| s, on_data),
+        cpal::SampleFormat::U16 => build_converted_stream(device, &stream_config, u16_to_i16, on_data),
+        format => Err(anyhow!("Unsupported sample format: {}", format)),
+    }
+}
+
 /// Get the recordings folder path (Documents/MeetingRecordings)
 pub fn get_recordings_folder() -> Result<PathBuf> {
     let home = std::env::var("HOME").map_err(|_| anyhow!("Could not find HOME directory"))?;
@@ -26,7 +370,22 @@ pub fn get_recordings_folder() -> Result<PathBuf> {
 }
 
 impl AudioRecorder {
-    pub fn new() -> Result<Self> {
+    /// Start recording to a new timestamped file in the recordings folder
+    pub fn new(recording: RecordingConfig, voice: VoiceActivation) -> Result<Self> {
+        let recordings_folder = get_recordings_folder()?;
+        let timestamp = chrono::Local::now().format("%Y%m%d_%H%M%S");
+        let output_path = recordings_folder
+            .join(format!("meeting_{}.wav", timestamp))
+            .to_string_lossy()
+            .to_string();
+
+        Self::start_at(output_path, recording, voice)
+    }
+
+    /// Start recording the default input device to the given WAV path
+    pub fn start_at(output_path: String, recording: RecordingConfig, voice: VoiceActivation) -> Result<Self> {
+        recording.validate().map_err(|e| anyhow!(e))?;
+        voice.validate().map_err(|e| anyhow!(e))?;
         let host = cpal::default_host();
 
         // Try to get the default input device (microphone)
@@ -35,21 +394,10 @@ impl AudioRecorder {
             .ok_or_else(|| anyhow!("No input device available"))?;
 
         let config = device.default_input_config()?;
-
-        // Create output file path in Documents/MeetingRecordings
-        let recordings_folder = get_recordings_folder()?;
-        let timestamp = chrono::Local::now().format("%Y%m%d_%H%M%S");
-        let output_path = recordings_folder
-            .join(format!("meeting_{}.wav", timestamp))
-            .to_string_lossy()
-            .to_string();
-
-        let spec = WavSpec {
-            channels: config.channels(),
-            sample_rate: config.sample_rate().0,
-            bits_per_sample: 16,
-            sample_format: hound::SampleFormat::Int,
-        };
+        let spec = recording.wav_spec(config.channels(), config.sample_rate().0);
+        let mut converter = FrameConverter::new(config.channels(), config.sample_rate().0, &spec);
+        let mut gate = VoiceGate::new(&voice, &spec);
+        let skipped = gate.skipped.clone();
 
         let stop_signal = Arc::new(Mutex::new(false));
         let stop_signal_clone = stop_signal.clone();
@@ -61,56 +409,24 @@ impl AudioRecorder {
             let writer = Arc::new(Mutex::new(Some(writer)));
             let writer_clone = writer.clone();
 
-            let err_fn = |err| eprintln!("Audio stream error: {}", err);
-
-            let stream = match config.sample_format() {
-                cpal::SampleFormat::F32 => device.build_input_stream(
-                    &config.into(),
-                    move |data: &[f32], _: &cpal::InputCallbackInfo| {
-                        if let Ok(mut writer_guard) = writer_clone.lock() {
-                            if let Some(ref mut writer) = *writer_guard {
-                                for &sample in data {
-                                    let sample_i16 = (sample * i16::MAX as f32) as i16;
-                                    let _ = writer.write_sample(sample_i16);
-                                }
-                            }
-                        }
-                    },
-                    err_fn,
-                    None,
-                )?,
-                cpal::SampleFormat::I16 => device.build_input_stream(
-                    &config.into(),
-                    move |data: &[i16], _: &cpal::InputCallbackInfo| {
-                        if let Ok(mut writer_guard) = writer_clone.lock() {
-                            if let Some(ref mut writer) = *writer_guard {
-                                for &sample in data {
-                                    let _ = writer.write_sample(sample);
-                                }
-                            }
-                        }
-                    },
-                    err_fn,
-                    None,
-                )?,
-                cpal::SampleFormat::U16 => device.build_input_stream(
-                    &config.into(),
-                    move |data: &[u16], _: &cpal::InputCallbackInfo| {
-                        if let Ok(mut writer_guard) = writer_clone.lock() {
-                            if let Some(ref mut writer) = *writer_guard {
-                                for &sample in data {
-                                    let sample_i16 = (sample as i32 - 32768) as i16;
-                                    let _ = writer.write_sample(sample_i16);
-                                }
-                            }
+            // Every device format goes through f32, then to the recording's format
+            let bits = spec.bits_per_sample;
+            let write = move |data: &[f32]| {
+                let samples = converter.process(data);
+                if !gate.admit(&samples) {
+                    return;
+                }
+                if let Ok(mut writer_guard) = writer_clone.lock() {
+                    if let Some(ref mut writer) = *writer_guard {
+                        for &sample in &samples {
+                            let _ = write_sample(writer, bits, sample);
                         }
-                    },
-                    err_fn,
-                    None,
-                )?,
-                _ => return Err(anyhow!("Unsupported sample format")),
+                    }
+                }
             };
 
+            let stream = build_f32_input_stream(&device, &config, write)?;
+
             stream.play()?;
 
             // Keep recording until stop signal
@@ -133,6 +449,12 @@ impl AudioRecorder {
                 }
             }
 
+            // Keep what voice activation left out, to map file times back to the meeting
+            let skipped = skipped.lock().map(|s| s.clone()).unwrap_or_default();
+            if skipped != SkippedSpans::default() {
+                skipped.save(&output_path_clone)?;
+            }
+
             Ok(())
         });
 
@@ -162,6 +484,143 @@ impl AudioRecorder {
     }
 }
 
+/// Peak and RMS level of a WAV file, both normalized to 0.0..=1.0
+pub fn measure_levels(path: &str) -> Result<(f32, f32)> {
+    let mut reader = hound::WavReader::open(path)?;
+    let samples: Vec<i16> = reader.samples::<i16>().collect::<std::result::Result<_, _>>()?;
+    Ok(sample_levels(&samples))
+}
+
+fn sample_levels(samples: &[i16]) -> (f32, f32) {
+    if samples.is_empty() {
+        return (0.0, 0.0);
+    }
+
+    let scale = i16::MAX as f32;
+    let peak = samples.iter().map(|&s| (s as f32 / scale).abs()).fold(0.0, f32::max);
+    let mean_square = samples.iter().map(|&s| (s as f32 / scale).powi(2)).sum::<f32>() / samples.len() as f32;
+    (peak.min(1.0), mean_square.sqrt())
+}
+
+/// Where the samples of a WAV file start, and how many bytes make up a second and a frame
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct WavLayout {
+    pub data_offset: usize,
+    pub byte_rate: u32,
+    pub block_align: u16,
+}
+
+/// Walk the RIFF chunks at the start of a WAV to find its format and data chunk. The header
+/// isn't a fixed size: hound writes 44 bytes for 16-bit PCM and 68 for WAVEFORMATEXTENSIBLE.
+/// Works for a recording still being written, since the data size isn't read.
+pub fn wav_layout(bytes: &[u8]) -> Option<WavLayout> {
+    if bytes.get(0..4)? != b"RIFF" || bytes.get(8..12)? != b"WAVE" {
+        return None;
+    }
+
+    let mut format = None;
+    let mut pos = 12;
+    while let Some(chunk) = bytes.get(pos..pos + 8) {
+        let size = u32::from_le_bytes([chunk[4], chunk[5], chunk[6], chunk[7]]) as usize;
+        let body = pos + 8;
+        match &chunk[0..4] {
+            b"fmt " => {
+                let fmt = bytes.get(body..body + 16)?;
+                let byte_rate = u32::from_le_bytes([fmt[8], fmt[9], fmt[10], fmt[11]]);
+                let block_align = u16::from_le_bytes([fmt[12], fmt[13]]);
+                format = Some((byte_rate, block_align));
+            }
+            b"data" => {
+                let (byte_rate, block_align) = format?;
+                if byte_rate == 0 || block_align == 0 {
+                    return None;
+                }
+                return Some(WavLayout { data_offset: body, byte_rate, block_align });
+            }
+            _ => {}
+        }
+        // Chunks are padded to an even length
+        pos = body + size + (size & 1);
+    }
+    None
+}
+
+/// A WAV made of `header` (the original file up to its data, see `wav_layout`) and `data`,
+/// with the RIFF and data chunk sizes rewritten for the new length
+pub fn wav_with_data(header: &[u8], layout: &WavLayout, data: &[u8]) -> Vec<u8> {
+    let mut wav = header[..layout.data_offset].to_vec();
+    let riff_size = (layout.data_offset + data.len() - 8) as u32;
+    wav[4..8].copy_from_slice(&riff_size.to_le_bytes());
+    wav[layout.data_offset - 4..layout.data_offset].copy_from_slice(&(data.len() as u32).to_le_bytes());
+    wav.extend_from_slice(data);
+    wav
+}
+
+/// Sort (start_ms, end_ms) regions and merge the ones that overlap or touch
+fn merge_regions(regions: &[(u64, u64)]) -> Vec<(u64, u64)> {
+    let mut sorted: Vec<(u64, u64)> = regions.iter().copied().filter(|(start, end)| end > start).collect();
+    sorted.sort_unstable();
+
+    let mut merged: Vec<(u64, u64)> = Vec::new();
+    for (start, end) in sorted {
+        match merged.last_mut() {
+            Some(last) if start <= last.1 => last.1 = last.1.max(end),
+            _ => merged.push((start, end)),
+        }
+    }
+    merged
+}
+
+/// Copy the frames inside `regions` from `reader` to `writer`, back to back
+fn copy_regions<S, R, W>(reader: &mut hound::WavReader<R>, writer: &mut WavWriter<W>, regions: &[(u64, u64)]) -> Result<u64>
+where
+    S: hound::Sample,
+    R: std::io::Read,
+    W: std::io::Write + std::io::Seek,
+{
+    let spec = reader.spec();
+    let channels = spec.channels.max(1) as u64;
+    let mut region = 0;
+    let mut written = 0;
+
+    for (index, sample) in reader.samples::<S>().enumerate() {
+        let sample = sample?;
+        let ms = index as u64 / channels * 1000 / spec.sample_rate as u64;
+        while region < regions.len() && ms >= regions[region].1 {
+            region += 1;
+        }
+        if region == regions.len() {
+            break;
+        }
+        if ms >= regions[region].0 {
+            writer.write_sample(sample)?;
+            written += 1;
+        }
+    }
+
+    Ok(written / channels * 1000 / spec.sample_rate as u64)
+}
+
+/// Write the `regions` (start_ms, end_ms) of a WAV back to back into a new WAV with the
+/// same format, e.g. to keep only one speaker. Returns the new file's length in ms.
+pub fn extract_regions(input_path: &str, output_path: &str, regions: &[(u64, u64)]) -> Result<u64> {
+    let regions = merge_regions(regions);
+    let mut reader = hound::WavReader::open(input_path)?;
+    let spec = reader.spec();
+    if spec.sample_rate == 0 {
+        return Err(anyhow!("Invalid sample rate in {}", input_path));
+    }
+
+    let mut writer = WavWriter::create(output_path, spec)?;
+    let duration_ms = match spec.sample_format {
+        hound::SampleFormat::Float => copy_regions::<f32, _, _>(&mut reader, &mut writer, &regions)?,
+        hound::SampleFormat::Int => copy_regions::<i32, _, _>(&mut reader, &mut writer, &regions)?,
+    };
+    writer.finalize()?;
+
+    Ok(duration_ms)
+}
+
 /// List all recordings in the recordings folder
 pub fn list_recordings() -> Result<Vec<String>> {
     let recordings_folder = get_recordings_folder()?;
@@ -184,3 +643,235 @@ pub fn list_recordings() -> Result<Vec<String>> {
 
     Ok(recordings)
 }
+
+/// A sample format and channel count an input device supports, over a range of rates
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct SupportedConfig {
+    /// "f32", "i16", "u16", ...
+    pub sample_format: String,
+    pub channels: u16,
+    pub min_sample_rate: u32,
+    pub max_sample_rate: u32,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DeviceCapabilities {
+    pub name: String,
+    /// What a stream gets when nothing is requested; min and max rate are the same
+    pub default_config: Option<SupportedConfig>,
+    pub configs: Vec<SupportedConfig>,
+}
+
+/// Sorted and de-duplicated (backends can list the same range more than once)
+fn supported_configs(ranges: impl Iterator<Item = cpal::SupportedStreamConfigRange>) -> Vec<SupportedConfig> {
+    let mut configs: Vec<SupportedConfig> = ranges
+        .map(|range| SupportedConfig {
+            sample_format: range.sample_format().to_string(),
+            channels: range.channels(),
+            min_sample_rate: range.min_sample_rate().0,
+            max_sample_rate: range.max_sample_rate().0,
+        })
+        .collect();
+    configs.sort_by(|a, b| {
+        (&a.sample_format, a.channels, a.min_sample_rate, a.max_sample_rate)
+            .cmp(&(&b.sample_format, b.channels, b.min_sample_rate, b.max_sample_rate))
+    });
+    configs.dedup();
+    configs
+}
+
+/// Sample formats, channel counts and rate ranges an input device supports.
+/// `name` of None means the default input device.
+pub fn device_capabilities(name: Option<&str>) -> Result<DeviceCapabilities> {
+    let host = cpal::default_host();
+    let device = match name {
+        Some(name) => host
+            .input_devices()?
+            .find(|d| d.name().map(|n| n == name).unwrap_or(false))
+            .ok_or_else(|| anyhow!("Input device not found: {}", name))?,
+        None => host
+            .default_input_device()
+            .ok_or_else(|| anyhow!("No input device available"))?,
+    };
+
+    let default_config = device.default_input_config().ok().map(|config| SupportedConfig {
+        sample_format: config.sample_format().to_string(),
+        channels: config.channels(),
+        min_sample_rate: config.sample_rate().0,
+        max_sample_rate: config.sample_rate().0,
+    });
+
+    Ok(DeviceCapabilities {
+        name: device.name()?,
+        default_config,
+        configs: supported_configs(device.supported_input_configs()?),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sample_conversions_agree() {
+        assert_eq!(u16_to_i16(32_768), 0);
+        assert_eq!(u16_to_i16(0), i16::MIN);
+        assert_eq!(u16_to_i16(u16::MAX), i16::MAX);
+        assert_eq!(f32_to_i16(1.5), 32_767);
+        assert_eq!(f32_to_i16(-1.0), -32_767);
+        assert_eq!(u16_to_f32(49_152), i16_to_f32(16_384));
+        assert_eq!(i16_to_f32(16_384), 0.5);
+    }
+
+    #[test]
+    fn test_supported_configs_sorted_and_deduped() {
+        let range = |channels, min, max, format| {
+            cpal::SupportedStreamConfigRange::new(
+                channels,
+                cpal::SampleRate(min),
+                cpal::SampleRate(max),
+                cpal::SupportedBufferSize::Unknown,
+                format,
+            )
+        };
+        let configs = supported_configs(
+            vec![
+                range(2, 44_100, 48_000, cpal::SampleFormat::I16),
+                range(1, 44_100, 44_100, cpal::SampleFormat::F32),
+                range(2, 44_100, 48_000, cpal::SampleFormat::I16),
+            ]
+            .into_iter(),
+        );
+
+        assert_eq!(configs.len(), 2);
+        assert_eq!(configs[0].sample_format, "f32");
+        assert_eq!((configs[0].channels, configs[0].min_sample_rate, configs[0].max_sample_rate), (1, 44_100, 44_100));
+        assert_eq!((configs[1].sample_format.as_str(), configs[1].max_sample_rate), ("i16", 48_000));
+    }
+
+    #[test]
+    fn test_recording_conversion() {
+        let stereo_44k = RecordingConfig { bits: 24, channels: Some(2), sample_rate: Some(44_100) };
+        assert!(stereo_44k.validate().is_ok());
+        assert!(RecordingConfig { bits: 8, ..Default::default() }.validate().is_err());
+        assert!(RecordingConfig { channels: Some(6), ..Default::default() }.validate().is_err());
+
+        // Device default: format follows the device, 16-bit PCM
+        let spec = RecordingConfig::default().wav_spec(2, 48_000);
+        assert_eq!((spec.channels, spec.sample_rate, spec.bits_per_sample), (2, 48_000, 16));
+        assert_eq!(RecordingConfig { bits: 32, ..Default::default() }.wav_spec(1, 48_000).sample_format, hound::SampleFormat::Float);
+
+        // Stereo 48kHz forced to mono 16kHz: channels averaged, a third of the frames
+        let spec = RecordingConfig { channels: Some(1), sample_rate: Some(16_000), ..Default::default() }.wav_spec(2, 48_000);
+        let mut converter = FrameConverter::new(2, 48_000, &spec);
+        let input: Vec<f32> = (0..480).flat_map(|_| [0.5, -0.1]).collect();
+        let output = converter.process(&input);
+        assert_eq!(output.len(), 160);
+        assert!(output[1..].iter().all(|&s| (s - 0.2).abs() < 1e-6));
+
+        // Mono to stereo duplicates the channel
+        assert_eq!(map_channels(&[0.1, 0.2], 1, 2), vec![0.1, 0.1, 0.2, 0.2]);
+    }
+
+    #[test]
+    fn test_voice_gate_skips_silence_after_hangover() {
+        let spec = WavSpec { channels: 1, sample_rate: 1000, bits_per_sample: 16, sample_format: hound::SampleFormat::Int };
+        let voice = VoiceActivation { enabled: true, threshold_db: -20.0, hangover_ms: 20 };
+        let mut gate = VoiceGate::new(&voice, &spec);
+        let speech = [0.5f32; 10];
+        let silence = [0.001f32; 10];
+
+        assert!(!gate.admit(&silence));
+        assert!(gate.admit(&speech));
+        // 20 ms hangover at 1 kHz mono = 20 samples of silence still written
+        assert!(gate.admit(&silence));
+        assert!(gate.admit(&silence));
+        assert!(!gate.admit(&silence));
+        assert!(gate.admit(&speech));
+
+        // 10 ms left out before anything was written, and 10 ms before the last speech
+        let skipped = gate.skipped.lock().unwrap().clone();
+        assert_eq!(skipped.spans, vec![(0, 10), (30, 10)]);
+        assert_eq!(skipped.to_meeting_ms(35), 55);
+        assert_eq!(skipped.to_file_ms(55), 35);
+        assert_eq!(skipped.to_file_ms(20), 10);
+        // Inside a skipped silence
+        assert_eq!(skipped.to_file_ms(45), 30);
+
+        let mut off = VoiceGate::new(&VoiceActivation::default(), &spec);
+        assert!(off.admit(&silence));
+        assert!(VoiceActivation { threshold_db: 3.0, ..voice }.validate().is_err());
+    }
+
+    #[test]
+    fn test_extract_regions_keeps_only_given_spans() {
+        let dir = std::env::temp_dir().join(format!("vantage-extract-test-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let input = dir.join("meeting.wav").to_string_lossy().to_string();
+        let output = dir.join("speaker.wav").to_string_lossy().to_string();
+
+        // 1 second at 1 kHz, each sample holding its own index
+        let spec = WavSpec { channels: 1, sample_rate: 1000, bits_per_sample: 16, sample_format: hound::SampleFormat::Int };
+        let mut writer = WavWriter::create(&input, spec).unwrap();
+        for i in 0..1000 {
+            writer.write_sample(i as i16).unwrap();
+        }
+        writer.finalize().unwrap();
+
+        // Overlapping and out-of-order regions are merged
+        let duration = extract_regions(&input, &output, &[(600, 700), (100, 200), (150, 250)]).unwrap();
+        let samples: Vec<i16> = hound::WavReader::open(&output).unwrap().samples::<i16>().map(|s| s.unwrap()).collect();
+        std::fs::remove_dir_all(&dir).ok();
+
+        assert_eq!(duration, 250);
+        assert_eq!(samples.len(), 250);
+        assert_eq!((samples[0], samples[149], samples[150]), (100, 249, 600));
+    }
+
+    #[test]
+    fn test_wav_layout_handles_extensible_headers() {
+        let wav = |bits: u16, channels: u16| {
+            let spec = WavSpec { channels, sample_rate: 8000, bits_per_sample: bits, sample_format: hound::SampleFormat::Int };
+            let mut cursor = std::io::Cursor::new(Vec::new());
+            let mut writer = WavWriter::new(&mut cursor, spec).unwrap();
+            for i in 0..80 * channels as i32 {
+                writer.write_sample(i).unwrap();
+            }
+            writer.finalize().unwrap();
+            cursor.into_inner()
+        };
+
+        let pcm16 = wav(16, 1);
+        let layout = wav_layout(&pcm16).unwrap();
+        assert_eq!(layout, WavLayout { data_offset: 44, byte_rate: 16_000, block_align: 2 });
+
+        let pcm24 = wav(24, 2);
+        let layout = wav_layout(&pcm24).unwrap();
+        assert_eq!(layout, WavLayout { data_offset: 68, byte_rate: 48_000, block_align: 6 });
+
+        // Rebuilt with the last 10 frames, the file reads back as those frames
+        let data = &pcm24[pcm24.len() - 60..];
+        let rebuilt = wav_with_data(&pcm24, &layout, data);
+        let samples: Vec<i32> = hound::WavReader::new(std::io::Cursor::new(rebuilt)).unwrap()
+            .samples::<i32>().map(|s| s.unwrap()).collect();
+        assert_eq!(samples.len(), 20);
+        assert_eq!(samples[0], 140);
+
+        assert!(wav_layout(b"RIFF\0\0\0\0WAVE").is_none());
+        assert!(wav_layout(&[0u8; 10]).is_none());
+    }
+
+    #[test]
+    fn test_sample_levels() {
+        assert_eq!(sample_levels(&[]), (0.0, 0.0));
+        assert_eq!(sample_levels(&[0, 0, 0]), (0.0, 0.0));
+
+        let (peak, rms) = sample_levels(&[i16::MAX, -i16::MAX]);
+        assert!((peak - 1.0).abs() < 1e-6);
+        assert!((rms - 1.0).abs() < 1e-6);
+
+        let (peak, rms) = sample_levels(&[i16::MAX / 2, 0]);
+        assert!((peak - 0.5).abs() < 1e-3);
+        assert!(rms > 0.3 && rms < 0.4);
+    }
+}
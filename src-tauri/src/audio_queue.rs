@@ -0,0 +1,128 @@
+//! Bounded audio chunk queue that never blocks the capture thread
+//!
+//! cpal callbacks must return quickly. With a blocking channel, a stalled WebSocket
+//! send fills the channel and blocks the callback, causing overruns and glitches.
+//! This queue drops the oldest chunk instead and counts the drops.
+
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use tokio::sync::Notify;
+
+struct Inner {
+    chunks: Mutex<VecDeque<Vec<u8>>>,
+    capacity: usize,
+    notify: Notify,
+    senders: AtomicUsize,
+    receiver_alive: AtomicBool,
+    dropped: Arc<AtomicU64>,
+}
+
+/// Capture side of the queue. Cloneable; the queue closes when every sender is dropped.
+pub struct AudioSender {
+    inner: Arc<Inner>,
+}
+
+/// Network side of the queue
+pub struct AudioReceiver {
+    inner: Arc<Inner>,
+}
+
+/// Queue holding at most `capacity` chunks. Dropped chunks are added to `dropped`,
+/// which can be shared (e.g. a counter kept in AppState).
+pub fn channel(capacity: usize, dropped: Arc<AtomicU64>) -> (AudioSender, AudioReceiver) {
+    let inner = Arc::new(Inner {
+        chunks: Mutex::new(VecDeque::with_capacity(capacity)),
+        capacity: capacity.max(1),
+        notify: Notify::new(),
+        senders: AtomicUsize::new(1),
+        receiver_alive: AtomicBool::new(true),
+        dropped,
+    });
+    (AudioSender { inner: inner.clone() }, AudioReceiver { inner })
+}
+
+impl AudioSender {
+    /// Queue a chunk without blocking, dropping the oldest one if the queue is full.
+    /// Returns false once the receiver is gone.
+    pub fn send(&self, chunk: Vec<u8>) -> bool {
+        if !self.inner.receiver_alive.load(Ordering::SeqCst) {
+            return false;
+        }
+
+        if let Ok(mut chunks) = self.inner.chunks.lock() {
+            if chunks.len() >= self.inner.capacity {
+                chunks.pop_front();
+                self.inner.dropped.fetch_add(1, Ordering::Relaxed);
+            }
+            chunks.push_back(chunk);
+        }
+        self.inner.notify.notify_one();
+        true
+    }
+}
+
+impl Clone for AudioSender {
+    fn clone(&self) -> Self {
+        self.inner.senders.fetch_add(1, Ordering::SeqCst);
+        Self { inner: self.inner.clone() }
+    }
+}
+
+impl Drop for AudioSender {
+    fn drop(&mut self) {
+        if self.inner.senders.fetch_sub(1, Ordering::SeqCst) == 1 {
+            // Wake the receiver so it sees the queue is closed
+            self.inner.notify.notify_one();
+        }
+    }
+}
+
+impl AudioReceiver {
+    /// Next chunk, oldest first. Returns None once the queue is empty and all senders are gone.
+    pub async fn recv(&mut self) -> Option<Vec<u8>> {
+        loop {
+            if let Some(chunk) = self.inner.chunks.lock().ok()?.pop_front() {
+                return Some(chunk);
+            }
+            if self.inner.senders.load(Ordering::SeqCst) == 0 {
+                return None;
+            }
+            self.inner.notify.notified().await;
+        }
+    }
+}
+
+impl Drop for AudioReceiver {
+    fn drop(&mut self) {
+        self.inner.receiver_alive.store(false, Ordering::SeqCst);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_full_queue_drops_oldest_chunk() {
+        let dropped = Arc::new(AtomicU64::new(0));
+        let (tx, mut rx) = channel(2, dropped.clone());
+        assert!(tx.send(vec![1]));
+        assert!(tx.send(vec![2]));
+        assert!(tx.send(vec![3]));
+
+        assert_eq!(dropped.load(Ordering::Relaxed), 1);
+        assert_eq!(rx.recv().await, Some(vec![2]));
+        assert_eq!(rx.recv().await, Some(vec![3]));
+
+        drop(tx);
+        assert_eq!(rx.recv().await, None);
+    }
+
+    #[test]
+    fn test_send_fails_after_receiver_dropped() {
+        let (tx, rx) = channel(4, Arc::new(AtomicU64::new(0)));
+        drop(rx);
+        assert!(!tx.send(vec![1]));
+    }
+}
@@ -1,8 +1,14 @@
 use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
 use std::fs;
 use std::path::PathBuf;
 use reqwest::Client;
-use chrono::{DateTime, Utc, Duration};
+use chrono::{DateTime, Duration, Local, NaiveDate, NaiveDateTime, NaiveTime, TimeZone, Utc};
+use chrono_tz::Tz;
+use tauri::{AppHandle, Emitter};
+
+use crate::error::{AppError, ErrorCode};
+use crate::persist;
 
 /// Google OAuth2 configuration
 const GOOGLE_AUTH_URL: &str = "https://accounts.google.com/o/oauth2/v2/auth";
@@ -55,6 +61,51 @@ pub struct EntryPoint {
     pub entry_point_type: Option<String>,
     pub uri: Option<String>,
     pub label: Option<String>,
+    pub passcode: Option<String>,
+    pub pin: Option<String>,
+    #[serde(rename = "accessCode")]
+    pub access_code: Option<String>,
+    #[serde(rename = "meetingCode")]
+    pub meeting_code: Option<String>,
+    pub password: Option<String>,
+}
+
+/// Way to join a meeting: "video", "phone", "sip" or "more" (e.g. a Teams or Webex page)
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct MeetingLink {
+    pub kind: String,
+    pub uri: String,
+    pub label: Option<String>,
+    /// Passcode, PIN or access code needed to join, if any
+    pub passcode: Option<String>,
+}
+
+/// All join options of an event, video first, then other web links, SIP and phone
+fn meeting_links(conference_data: Option<ConferenceData>) -> Vec<MeetingLink> {
+    let rank = |kind: &str| match kind {
+        "video" => 0,
+        "more" => 1,
+        "sip" => 2,
+        "phone" => 3,
+        _ => 4,
+    };
+
+    let mut links: Vec<MeetingLink> = conference_data
+        .and_then(|cd| cd.entry_points)
+        .unwrap_or_default()
+        .into_iter()
+        .filter_map(|ep| {
+            let uri = ep.uri.filter(|u| !u.trim().is_empty())?;
+            Some(MeetingLink {
+                kind: ep.entry_point_type.unwrap_or_else(|| "more".to_string()),
+                uri,
+                label: ep.label,
+                passcode: ep.passcode.or(ep.pin).or(ep.access_code).or(ep.password).or(ep.meeting_code),
+            })
+        })
+        .collect();
+    links.sort_by_key(|l| rank(&l.kind));
+    links
 }
 
 /// Simplified calendar event for frontend
@@ -66,9 +117,41 @@ pub struct SimpleCalendarEvent {
     pub start_time: String,
     pub end_time: String,
     pub attendees: Vec<String>,
+    /// Best link to open to join: the video link, else another web link
     pub meeting_link: Option<String>,
+    /// Every join option (video, phone, SIP, ...) with labels and passcodes
+    #[serde(default)]
+    pub meeting_links: Vec<MeetingLink>,
     pub is_today: bool,
     pub is_past: bool,
+    /// All-day event: start/end times are bare dates (end exclusive)
+    #[serde(default)]
+    pub is_all_day: bool,
+}
+
+/// Calendar in the user's calendar list
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CalendarInfo {
+    pub id: String,
+    pub name: String,
+    pub primary: bool,
+    /// Events are fetched from this calendar
+    pub active: bool,
+}
+
+/// Google Calendar API response for the calendar list
+#[derive(Debug, Deserialize)]
+struct CalendarListResponse {
+    items: Option<Vec<CalendarListEntry>>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CalendarListEntry {
+    id: String,
+    summary: Option<String>,
+    #[serde(rename = "summaryOverride")]
+    summary_override: Option<String>,
+    primary: Option<bool>,
 }
 
 /// Google Calendar API response for events list
@@ -79,6 +162,266 @@ struct EventsListResponse {
     next_page_token: Option<String>,
 }
 
+/// Tokens file from before multiple accounts were supported (account unknown)
+const LEGACY_TOKENS_FILE: &str = "google_tokens.json";
+const TOKENS_FILE_PREFIX: &str = "google_tokens_";
+/// Remembers which connected account calendar requests use
+const ACCOUNTS_FILE: &str = "google_accounts.json";
+
+fn config_file(name: &str) -> Option<PathBuf> {
+    dirs::config_dir().map(|mut path| {
+        path.push("vantage");
+        path.push(name);
+        path
+    })
+}
+
+/// Account email from a tokens file name (`google_tokens_<email>.json`)
+fn account_from_file_name(name: &str) -> Option<String> {
+    name.strip_prefix(TOKENS_FILE_PREFIX)?
+        .strip_suffix(".json")
+        .filter(|email| email.contains('@'))
+        .map(|email| email.to_string())
+}
+
+/// Connected Google account
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GoogleAccount {
+    pub email: String,
+    pub active: bool,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct AccountsFile {
+    active: Option<String>,
+    /// Calendars to fetch events from, per account (none saved = primary only)
+    #[serde(default)]
+    calendars: HashMap<String, Vec<String>>,
+}
+
+impl AccountsFile {
+    fn load() -> Self {
+        config_file(ACCOUNTS_FILE)
+            .and_then(|path| fs::read_to_string(path).ok())
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default()
+    }
+
+    fn save(&self) -> Result<(), String> {
+        let path = config_file(ACCOUNTS_FILE)
+            .ok_or_else(|| "Could not determine config directory".to_string())?;
+        let content = serde_json::to_string_pretty(self).map_err(|e| e.to_string())?;
+        persist::write_atomic(&path, &content)
+    }
+}
+
+/// Emails of all connected accounts, sorted
+pub fn list_accounts() -> Vec<String> {
+    let Some(dir) = config_file("") else {
+        return Vec::new();
+    };
+    let mut accounts: Vec<String> = fs::read_dir(dir)
+        .map(|entries| {
+            entries
+                .filter_map(|e| e.ok())
+                .filter_map(|e| account_from_file_name(&e.file_name().to_string_lossy()))
+                .collect()
+        })
+        .unwrap_or_default();
+    accounts.sort();
+    accounts
+}
+
+/// The account calendar requests use: the one last switched to, else the first connected one
+pub fn active_account() -> Option<String> {
+    let accounts = list_accounts();
+    match AccountsFile::load().active {
+        Some(email) if accounts.contains(&email) => Some(email),
+        _ => accounts.into_iter().next(),
+    }
+}
+
+/// Make `email` the account calendar requests use. It must already be connected.
+pub fn set_active_account(email: &str) -> Result<(), String> {
+    let email = email.trim().to_lowercase();
+    if !list_accounts().contains(&email) {
+        return Err(format!("Google account not connected: {}", email));
+    }
+
+    let mut file = AccountsFile::load();
+    file.active = Some(email);
+    file.save()
+}
+
+/// Calendar IDs the active account's events are fetched from ("primary" by default)
+pub fn active_calendars() -> Vec<String> {
+    active_account()
+        .and_then(|email| AccountsFile::load().calendars.remove(&email))
+        .filter(|ids| !ids.is_empty())
+        .unwrap_or_else(|| vec!["primary".to_string()])
+}
+
+/// Error for calendar requests made before any Google account is connected
+fn not_connected() -> AppError {
+    AppError::new(ErrorCode::SetupRequired, "Not authenticated with Google. Please connect your calendar.")
+}
+
+/// Choose which of the active account's calendars events are fetched from.
+/// An empty list goes back to the primary calendar only. Returns the saved selection.
+pub fn set_active_calendars(ids: Vec<String>) -> Result<Vec<String>, AppError> {
+    let email = active_account().ok_or_else(not_connected)?;
+
+    let mut seen = HashSet::new();
+    let ids: Vec<String> = ids
+        .into_iter()
+        .map(|id| id.trim().to_string())
+        .filter(|id| !id.is_empty() && seen.insert(id.clone()))
+        .collect();
+
+    let mut file = AccountsFile::load();
+    if ids.is_empty() {
+        file.calendars.remove(&email);
+    } else {
+        file.calendars.insert(email, ids);
+    }
+    file.save()?;
+    Ok(active_calendars())
+}
+
+/// Parse an event time: RFC 3339 for timed events, or a bare date ("2024-03-05")
+/// for all-day events, taken as the start of that day in local time
+pub fn parse_event_time(value: &str) -> Option<DateTime<Utc>> {
+    parse_event_time_in(value, None)
+}
+
+/// Like `parse_event_time`, but a time without an offset is read in the event's IANA
+/// `time_zone` (e.g. "America/Los_Angeles"), falling back to local time. A bare date is
+/// an all-day event's local date, whatever its time zone.
+fn parse_event_time_in(value: &str, time_zone: Option<&str>) -> Option<DateTime<Utc>> {
+    if let Ok(dt) = DateTime::parse_from_rfc3339(value) {
+        return Some(dt.with_timezone(&Utc));
+    }
+    if let Ok(date) = NaiveDate::parse_from_str(value, "%Y-%m-%d") {
+        return Local.from_local_datetime(&date.and_time(NaiveTime::MIN)).earliest().map(|dt| dt.with_timezone(&Utc));
+    }
+    let naive = NaiveDateTime::parse_from_str(value, "%Y-%m-%dT%H:%M:%S").ok()?;
+
+    match time_zone.and_then(|tz| tz.parse::<Tz>().ok()) {
+        Some(tz) => tz.from_local_datetime(&naive).earliest().map(|dt| dt.with_timezone(&Utc)),
+        None => Local.from_local_datetime(&naive).earliest().map(|dt| dt.with_timezone(&Utc)),
+    }
+}
+
+/// Convert an API event for the frontend. Times are read in the event's time zone and
+/// compared in local time. All-day events span their days: they are "today" while now is
+/// within them and past once the last day is over.
+fn simplify_event(e: CalendarEvent, now: DateTime<Utc>) -> SimpleCalendarEvent {
+    let is_all_day = e.start.date_time.is_none() && e.start.date.is_some();
+    let mut start_time = e.start.date_time.clone()
+        .or(e.start.date.clone())
+        .unwrap_or_default();
+    let mut end_time = e.end.date_time.clone()
+        .or(e.end.date.clone())
+        .unwrap_or_default();
+
+    let start = parse_event_time_in(&start_time, e.start.time_zone.as_deref());
+    let end = parse_event_time_in(&end_time, e.end.time_zone.as_deref().or(e.start.time_zone.as_deref()));
+
+    let (is_today, is_past) = if is_all_day {
+        // The end date is exclusive; a missing end means a single day
+        let end = end.or_else(|| start.map(|s| s + Duration::days(1)));
+        (
+            start.map_or(false, |s| s <= now) && end.map_or(false, |e| now < e),
+            end.map_or(false, |e| e <= now),
+        )
+    } else {
+        // Give times sent without an offset one, so they parse the same everywhere else
+        if DateTime::parse_from_rfc3339(&start_time).is_err() {
+            if let Some(start) = start {
+                start_time = start.to_rfc3339();
+            }
+        }
+        if DateTime::parse_from_rfc3339(&end_time).is_err() {
+            if let Some(end) = end {
+                end_time = end.to_rfc3339();
+            }
+        }
+
+        let today = now.with_timezone(&Local).date_naive();
+        (
+            start.map_or(false, |dt| dt.with_timezone(&Local).date_naive() == today),
+            start.map_or(false, |dt| dt < now),
+        )
+    };
+
+    // Extract meeting links
+    let meeting_links = meeting_links(e.conference_data);
+    let meeting_link = meeting_links
+        .iter()
+        .find(|l| l.uri.starts_with("https://") || l.uri.starts_with("http://"))
+        .map(|l| l.uri.clone());
+
+    // Extract attendees
+    let attendees = e.attendees
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|a| a.is_self != Some(true))
+        .filter_map(|a| a.display_name.or(a.email))
+        .collect();
+
+    SimpleCalendarEvent {
+        id: e.id,
+        title: e.summary.unwrap_or_else(|| "(No title)".to_string()),
+        description: e.description,
+        start_time,
+        end_time,
+        attendees,
+        meeting_link,
+        meeting_links,
+        is_today,
+        is_past,
+        is_all_day,
+    }
+}
+
+/// Largest page the events API returns
+const MAX_EVENTS_PAGE_SIZE: u32 = 250;
+
+/// URL of one page of a calendar's events, in start order
+fn events_page_url(
+    calendar_id: &str,
+    time_min: DateTime<Utc>,
+    time_max: DateTime<Utc>,
+    page_size: u32,
+    page_token: Option<&str>,
+) -> String {
+    let mut url = format!(
+        "{}/calendars/{}/events?timeMin={}&timeMax={}&maxResults={}&singleEvents=true&orderBy=startTime",
+        GOOGLE_CALENDAR_API,
+        urlencoding::encode(calendar_id),
+        urlencoding::encode(&time_min.to_rfc3339()),
+        urlencoding::encode(&time_max.to_rfc3339()),
+        page_size
+    );
+    if let Some(token) = page_token {
+        url.push_str(&format!("&pageToken={}", urlencoding::encode(token)));
+    }
+    url
+}
+
+/// Combine events from several calendars: drop duplicates (an event shared between
+/// calendars keeps its ID), order by start time and keep the first `max_results`
+fn merge_calendar_events(events: Vec<SimpleCalendarEvent>, max_results: usize) -> Vec<SimpleCalendarEvent> {
+    let mut seen = HashSet::new();
+    let mut events: Vec<SimpleCalendarEvent> = events
+        .into_iter()
+        .filter(|e| seen.insert(e.id.clone()))
+        .collect();
+    events.sort_by_key(|e| parse_event_time(&e.start_time));
+    events.truncate(max_results);
+    events
+}
+
 /// OAuth2 tokens
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct GoogleTokens {
@@ -88,43 +431,37 @@ pub struct GoogleTokens {
 }
 
 impl GoogleTokens {
-    fn get_tokens_path() -> Option<PathBuf> {
-        dirs::config_dir().map(|mut path| {
-            path.push("vantage");
-            path.push("google_tokens.json");
-            path
-        })
+    fn get_tokens_path(email: &str) -> Option<PathBuf> {
+        config_file(&format!("{}{}.json", TOKENS_FILE_PREFIX, email))
     }
 
-    pub fn load() -> Option<Self> {
-        let path = Self::get_tokens_path()?;
-        if !path.exists() {
-            return None;
-        }
-        let content = fs::read_to_string(&path).ok()?;
+    fn read(path: Option<PathBuf>) -> Option<Self> {
+        let content = fs::read_to_string(path?).ok()?;
         serde_json::from_str(&content).ok()
     }
 
-    pub fn save(&self) -> Result<(), String> {
-        let path = Self::get_tokens_path()
-            .ok_or_else(|| "Could not determine config directory".to_string())?;
+    pub fn load(email: &str) -> Option<Self> {
+        Self::read(Self::get_tokens_path(email))
+    }
 
-        if let Some(parent) = path.parent() {
-            fs::create_dir_all(parent)
-                .map_err(|e| format!("Failed to create config directory: {}", e))?;
+    pub fn save(&self, email: &str) -> Result<(), String> {
+        if email.contains(['/', '\\']) || !email.contains('@') {
+            return Err(format!("Invalid Google account email: {}", email));
         }
+        let path = Self::get_tokens_path(email)
+            .ok_or_else(|| "Could not determine config directory".to_string())?;
 
         let content = serde_json::to_string_pretty(self)
             .map_err(|e| format!("Failed to serialize tokens: {}", e))?;
 
-        fs::write(&path, content)
+        persist::write_atomic(&path, &content)
             .map_err(|e| format!("Failed to write tokens file: {}", e))?;
 
         Ok(())
     }
 
-    pub fn delete() -> Result<(), String> {
-        if let Some(path) = Self::get_tokens_path() {
+    pub fn delete(email: &str) -> Result<(), String> {
+        if let Some(path) = Self::get_tokens_path(email) {
             if path.exists() {
                 fs::remove_file(&path)
                     .map_err(|e| format!("Failed to delete tokens file: {}", e))?;
@@ -143,24 +480,85 @@ impl GoogleTokens {
     }
 }
 
+/// Payload of `calendar-reauth-required`, emitted when Google rejects the saved sign-in
+#[derive(Debug, Clone, Serialize)]
+pub struct ReauthRequiredEvent {
+    /// Account that must be reconnected (None for tokens saved before accounts were tracked)
+    pub email: Option<String>,
+    pub reason: String,
+}
+
+/// Why a token refresh failed
+#[derive(Debug)]
+enum RefreshError {
+    /// Google rejected the refresh token (revoked or expired): the user must reconnect
+    Rejected(String),
+    /// Network or server problem: the saved tokens may still be good
+    Failed(String),
+}
+
+impl From<RefreshError> for String {
+    fn from(err: RefreshError) -> Self {
+        match err {
+            RefreshError::Rejected(e) | RefreshError::Failed(e) => e,
+        }
+    }
+}
+
 /// Google Calendar client
 pub struct GoogleCalendar {
     client: Client,
     client_id: String,
     client_secret: String,
     redirect_uri: String,
+    app: Option<AppHandle>,
 }
 
 impl GoogleCalendar {
     pub fn new(client_id: String, client_secret: String) -> Self {
         Self {
-            client: Client::new(),
+            client: crate::net::client(),
             client_id,
             client_secret,
             redirect_uri: "http://localhost:8765/callback".to_string(),
+            app: None,
         }
     }
 
+    /// Emit `calendar-reauth-required` through this app when the sign-in is rejected
+    pub fn with_app_handle(mut self, app: AppHandle) -> Self {
+        self.app = Some(app);
+        self
+    }
+
+    /// Forget rejected tokens and ask the UI to reconnect. Returns the error to report.
+    fn require_reauth(&self, email: Option<&str>, reason: &str) -> AppError {
+        tracing::warn!("Google sign-in rejected ({}), reconnect required: {}", email.unwrap_or("unknown account"), reason);
+
+        let deleted = match email {
+            Some(email) => GoogleTokens::delete(email),
+            None => config_file(LEGACY_TOKENS_FILE)
+                .filter(|p| p.exists())
+                .map_or(Ok(()), |p| fs::remove_file(p).map_err(|e| e.to_string())),
+        };
+        if let Err(e) = deleted {
+            tracing::warn!("Failed to delete rejected Google tokens: {}", e);
+        }
+
+        if let Some(app) = &self.app {
+            let _ = app.emit("calendar-reauth-required", ReauthRequiredEvent {
+                email: email.map(str::to_string),
+                reason: reason.to_string(),
+            });
+        }
+
+        let message = match email {
+            Some(email) => format!("Google sign-in for {} has expired. Please reconnect your calendar.", email),
+            None => "Google sign-in has expired. Please reconnect your calendar.".to_string(),
+        };
+        AppError::new(ErrorCode::Unauthorized, message)
+    }
+
     /// Generate the OAuth2 authorization URL
     pub fn get_auth_url(&self) -> String {
         format!(
@@ -213,12 +611,75 @@ impl GoogleCalendar {
             expires_at,
         };
 
-        tokens.save()?;
+        // Connecting an account makes it the active one
+        let email = self.fetch_account_email(&tokens.access_token).await?;
+        tokens.save(&email)?;
+        set_active_account(&email)?;
+        tracing::info!("Connected Google account {}", email);
+        Ok(tokens)
+    }
+
+    /// Email of the account a token belongs to (the ID of its primary calendar)
+    async fn fetch_account_email(&self, access_token: &str) -> Result<String, String> {
+        #[derive(Deserialize)]
+        struct PrimaryCalendar {
+            id: String,
+        }
+
+        let response = self.client
+            .get(format!("{}/calendars/primary", GOOGLE_CALENDAR_API))
+            .header("Authorization", format!("Bearer {}", access_token))
+            .send()
+            .await
+            .map_err(|e| format!("Failed to look up Google account: {}", e))?;
+
+        if !response.status().is_success() {
+            let error_text = response.text().await.unwrap_or_default();
+            return Err(format!("Failed to look up Google account: {}", error_text));
+        }
+
+        let calendar: PrimaryCalendar = response.json().await
+            .map_err(|e| format!("Failed to parse calendar response: {}", e))?;
+        Ok(calendar.id.to_lowercase())
+    }
+
+    /// Move tokens saved before multiple accounts were supported to a per-account file
+    pub async fn migrate_legacy_tokens(&self) -> Result<(), AppError> {
+        let legacy_path = config_file(LEGACY_TOKENS_FILE);
+        let Some(mut tokens) = GoogleTokens::read(legacy_path.clone()) else {
+            return Ok(());
+        };
+
+        if tokens.is_expired() {
+            let refreshed = match &tokens.refresh_token {
+                Some(refresh_token) => self.request_refresh(refresh_token).await,
+                None => Err(RefreshError::Rejected("Token expired and no refresh token available".to_string())),
+            };
+            tokens = match refreshed {
+                Ok(tokens) => tokens,
+                Err(RefreshError::Rejected(reason)) => return Err(self.require_reauth(None, &reason)),
+                Err(err) => return Err(String::from(err).into()),
+            };
+        }
+
+        let email = self.fetch_account_email(&tokens.access_token).await?;
+        tokens.save(&email)?;
+        set_active_account(&email)?;
+        if let Some(path) = legacy_path {
+            let _ = fs::remove_file(path);
+        }
+        tracing::info!("Migrated Google tokens to account {}", email);
+        Ok(())
+    }
+
+    /// Refresh an account's access token and save the new tokens
+    async fn refresh_token(&self, email: &str, refresh_token: &str) -> Result<GoogleTokens, RefreshError> {
+        let tokens = self.request_refresh(refresh_token).await?;
+        tokens.save(email).map_err(RefreshError::Failed)?;
         Ok(tokens)
     }
 
-    /// Refresh access token using refresh token
-    pub async fn refresh_token(&self, refresh_token: &str) -> Result<GoogleTokens, String> {
+    async fn request_refresh(&self, refresh_token: &str) -> Result<GoogleTokens, RefreshError> {
         let params = [
             ("client_id", self.client_id.as_str()),
             ("client_secret", self.client_secret.as_str()),
@@ -231,11 +692,17 @@ impl GoogleCalendar {
             .form(&params)
             .send()
             .await
-            .map_err(|e| format!("Failed to refresh token: {}", e))?;
+            .map_err(|e| RefreshError::Failed(format!("Failed to refresh token: {}", e)))?;
 
-        if !response.status().is_success() {
+        let status = response.status();
+        if !status.is_success() {
             let error_text = response.text().await.unwrap_or_default();
-            return Err(format!("Token refresh failed: {}", error_text));
+            let message = format!("Token refresh failed: {}", error_text);
+            // invalid_grant (400) or unauthorized_client (401): the refresh token is no good
+            return Err(match status {
+                reqwest::StatusCode::BAD_REQUEST | reqwest::StatusCode::UNAUTHORIZED => RefreshError::Rejected(message),
+                _ => RefreshError::Failed(message),
+            });
         }
 
         #[derive(Deserialize)]
@@ -245,33 +712,39 @@ impl GoogleCalendar {
         }
 
         let refresh_response: RefreshResponse = response.json().await
-            .map_err(|e| format!("Failed to parse refresh response: {}", e))?;
+            .map_err(|e| RefreshError::Failed(format!("Failed to parse refresh response: {}", e)))?;
 
         let expires_at = refresh_response.expires_in
             .map(|exp| Utc::now().timestamp() + exp);
 
-        let tokens = GoogleTokens {
+        Ok(GoogleTokens {
             access_token: refresh_response.access_token,
             refresh_token: Some(refresh_token.to_string()),
             expires_at,
-        };
-
-        tokens.save()?;
-        Ok(tokens)
+        })
     }
 
-    /// Get valid access token (refresh if needed)
-    pub async fn get_valid_token(&self) -> Result<String, String> {
-        let tokens = GoogleTokens::load()
-            .ok_or_else(|| "Not authenticated with Google. Please connect your calendar.".to_string())?;
+    /// Get a valid access token for the active account (refresh if needed)
+    pub async fn get_valid_token(&self) -> Result<String, AppError> {
+        // A failed migration leaves the legacy file for the next attempt; accounts that are
+        // already migrated keep working meanwhile
+        if let Err(e) = self.migrate_legacy_tokens().await {
+            tracing::warn!("Failed to migrate Google tokens: {}", e);
+        }
+
+        let email = active_account().ok_or_else(not_connected)?;
+        let tokens = GoogleTokens::load(&email).ok_or_else(not_connected)?;
 
         if tokens.is_expired() {
-            if let Some(refresh_token) = &tokens.refresh_token {
-                let new_tokens = self.refresh_token(refresh_token).await?;
-                return Ok(new_tokens.access_token);
-            } else {
-                return Err("Token expired and no refresh token available".to_string());
-            }
+            let refreshed = match &tokens.refresh_token {
+                Some(refresh_token) => self.refresh_token(&email, refresh_token).await,
+                None => Err(RefreshError::Rejected("Token expired and no refresh token available".to_string())),
+            };
+            return match refreshed {
+                Ok(new_tokens) => Ok(new_tokens.access_token),
+                Err(RefreshError::Rejected(reason)) => Err(self.require_reauth(Some(&email), &reason)),
+                Err(err) => Err(String::from(err).into()),
+            };
         }
 
         Ok(tokens.access_token)
@@ -283,7 +756,7 @@ impl GoogleCalendar {
         time_min: Option<DateTime<Utc>>,
         time_max: Option<DateTime<Utc>>,
         max_results: Option<u32>,
-    ) -> Result<Vec<SimpleCalendarEvent>, String> {
+    ) -> Result<Vec<SimpleCalendarEvent>, AppError> {
         let access_token = self.get_valid_token().await?;
 
         let now = Utc::now();
@@ -291,84 +764,158 @@ impl GoogleCalendar {
         let time_max = time_max.unwrap_or_else(|| now + Duration::days(7));
         let max_results = max_results.unwrap_or(50);
 
-        let url = format!(
-            "{}/calendars/primary/events?timeMin={}&timeMax={}&maxResults={}&singleEvents=true&orderBy=startTime",
-            GOOGLE_CALENDAR_API,
-            urlencoding::encode(&time_min.to_rfc3339()),
-            urlencoding::encode(&time_max.to_rfc3339()),
-            max_results
-        );
+        // A calendar that fails (e.g. access was revoked) shouldn't hide the others
+        let mut events = Vec::new();
+        let mut last_error = None;
+        let mut fetched_any = false;
+        for calendar_id in active_calendars() {
+            match self.fetch_calendar_events(&access_token, &calendar_id, time_min, time_max, max_results).await {
+                Ok(items) => {
+                    fetched_any = true;
+                    events.extend(items);
+                }
+                Err(e) => {
+                    tracing::warn!("Failed to fetch events from calendar {}: {}", calendar_id, e);
+                    last_error = Some(e);
+                }
+            }
+        }
+        if let (false, Some(e)) = (fetched_any, last_error) {
+            return Err(e.into());
+        }
+
+        let simple_events: Vec<SimpleCalendarEvent> = events
+            .into_iter()
+            .map(|e| simplify_event(e, now))
+            .collect();
+
+        Ok(merge_calendar_events(simple_events, max_results as usize))
+    }
+
+    /// Fetch up to `max_results` of one calendar's events in a time range,
+    /// following `nextPageToken` across pages
+    async fn fetch_calendar_events(
+        &self,
+        access_token: &str,
+        calendar_id: &str,
+        time_min: DateTime<Utc>,
+        time_max: DateTime<Utc>,
+        max_results: u32,
+    ) -> Result<Vec<CalendarEvent>, String> {
+        let mut events = Vec::new();
+        let mut page_token: Option<String> = None;
+
+        loop {
+            let remaining = max_results.saturating_sub(events.len() as u32);
+            if remaining == 0 {
+                break;
+            }
+
+            let url = events_page_url(
+                calendar_id,
+                time_min,
+                time_max,
+                remaining.min(MAX_EVENTS_PAGE_SIZE),
+                page_token.as_deref(),
+            );
+
+            let response = self.client
+                .get(&url)
+                .header("Authorization", format!("Bearer {}", access_token))
+                .send()
+                .await
+                .map_err(|e| format!("Failed to fetch events: {}", e))?;
+
+            if !response.status().is_success() {
+                let error_text = response.text().await.unwrap_or_default();
+                return Err(format!("Failed to fetch events: {}", error_text));
+            }
+
+            let events_response: EventsListResponse = response.json().await
+                .map_err(|e| format!("Failed to parse events: {}", e))?;
+
+            events.extend(events_response.items.unwrap_or_default());
+            page_token = events_response.next_page_token;
+            if page_token.is_none() {
+                break;
+            }
+        }
+
+        events.truncate(max_results as usize);
+        Ok(events)
+    }
+
+    /// Look up one event by ID in the active calendars
+    pub async fn get_event(&self, event_id: &str) -> Result<SimpleCalendarEvent, AppError> {
+        let access_token = self.get_valid_token().await?;
+
+        for calendar_id in active_calendars() {
+            let url = format!(
+                "{}/calendars/{}/events/{}",
+                GOOGLE_CALENDAR_API,
+                urlencoding::encode(&calendar_id),
+                urlencoding::encode(event_id)
+            );
+
+            let response = self.client
+                .get(&url)
+                .header("Authorization", format!("Bearer {}", access_token))
+                .send()
+                .await
+                .map_err(|e| format!("Failed to fetch event: {}", e))?;
+
+            if response.status() == reqwest::StatusCode::NOT_FOUND {
+                continue;
+            }
+            if !response.status().is_success() {
+                let error_text = response.text().await.unwrap_or_default();
+                return Err(format!("Failed to fetch event: {}", error_text).into());
+            }
+
+            let event: CalendarEvent = response.json().await
+                .map_err(|e| format!("Failed to parse event: {}", e))?;
+            return Ok(simplify_event(event, Utc::now()));
+        }
+
+        Err(AppError::new(ErrorCode::NotFound, format!("Calendar event not found: {}", event_id)))
+    }
+
+    /// The active account's calendars, marking the ones events are fetched from
+    pub async fn list_calendars(&self) -> Result<Vec<CalendarInfo>, AppError> {
+        let access_token = self.get_valid_token().await?;
 
         let response = self.client
-            .get(&url)
+            .get(format!("{}/users/me/calendarList", GOOGLE_CALENDAR_API))
             .header("Authorization", format!("Bearer {}", access_token))
             .send()
             .await
-            .map_err(|e| format!("Failed to fetch events: {}", e))?;
+            .map_err(|e| format!("Failed to fetch calendars: {}", e))?;
 
         if !response.status().is_success() {
             let error_text = response.text().await.unwrap_or_default();
-            return Err(format!("Failed to fetch events: {}", error_text));
+            return Err(format!("Failed to fetch calendars: {}", error_text).into());
         }
 
-        let events_response: EventsListResponse = response.json().await
-            .map_err(|e| format!("Failed to parse events: {}", e))?;
+        let list: CalendarListResponse = response.json().await
+            .map_err(|e| format!("Failed to parse calendars: {}", e))?;
 
-        let events = events_response.items.unwrap_or_default();
-        let today = Utc::now().date_naive();
-
-        let simple_events: Vec<SimpleCalendarEvent> = events
+        let active = active_calendars();
+        Ok(list.items.unwrap_or_default()
             .into_iter()
-            .map(|e| {
-                let start_time = e.start.date_time.clone()
-                    .or(e.start.date.clone())
-                    .unwrap_or_default();
-                let end_time = e.end.date_time.clone()
-                    .or(e.end.date.clone())
-                    .unwrap_or_default();
-
-                // Parse start time to check if today/past
-                let is_today = start_time.parse::<DateTime<Utc>>()
-                    .map(|dt| dt.date_naive() == today)
-                    .unwrap_or(false);
-                let is_past = start_time.parse::<DateTime<Utc>>()
-                    .map(|dt| dt < now)
-                    .unwrap_or(false);
-
-                // Extract meeting link
-                let meeting_link = e.conference_data
-                    .and_then(|cd| cd.entry_points)
-                    .and_then(|eps| eps.into_iter()
-                        .find(|ep| ep.entry_point_type.as_deref() == Some("video"))
-                        .and_then(|ep| ep.uri));
-
-                // Extract attendees
-                let attendees = e.attendees
-                    .unwrap_or_default()
-                    .into_iter()
-                    .filter(|a| a.is_self != Some(true))
-                    .filter_map(|a| a.display_name.or(a.email))
-                    .collect();
-
-                SimpleCalendarEvent {
-                    id: e.id,
-                    title: e.summary.unwrap_or_else(|| "(No title)".to_string()),
-                    description: e.description,
-                    start_time,
-                    end_time,
-                    attendees,
-                    meeting_link,
-                    is_today,
-                    is_past,
+            .map(|c| {
+                let primary = c.primary.unwrap_or(false);
+                CalendarInfo {
+                    active: active.contains(&c.id) || (primary && active.iter().any(|id| id == "primary")),
+                    name: c.summary_override.or(c.summary).unwrap_or_else(|| c.id.clone()),
+                    id: c.id,
+                    primary,
                 }
             })
-            .collect();
-
-        Ok(simple_events)
+            .collect())
     }
 
     /// Get upcoming events (today and future)
-    pub async fn get_upcoming_events(&self, limit: Option<u32>) -> Result<Vec<SimpleCalendarEvent>, String> {
+    pub async fn get_upcoming_events(&self, limit: Option<u32>) -> Result<Vec<SimpleCalendarEvent>, AppError> {
         let now = Utc::now();
         let events = self.get_events(
             Some(now),
@@ -379,7 +926,7 @@ impl GoogleCalendar {
     }
 
     /// Get past events
-    pub async fn get_past_events(&self, days: Option<i64>, limit: Option<u32>) -> Result<Vec<SimpleCalendarEvent>, String> {
+    pub async fn get_past_events(&self, days: Option<i64>, limit: Option<u32>) -> Result<Vec<SimpleCalendarEvent>, AppError> {
         let now = Utc::now();
         let days = days.unwrap_or(30);
         let events = self.get_events(
@@ -397,10 +944,159 @@ impl GoogleCalendar {
 
 /// Check if Google Calendar is connected
 pub fn is_calendar_connected() -> bool {
-    GoogleTokens::load().is_some()
+    active_account().is_some() || config_file(LEGACY_TOKENS_FILE).map_or(false, |p| p.exists())
 }
 
-/// Disconnect Google Calendar
+/// Disconnect the active Google account. Another connected account, if any, becomes active.
 pub fn disconnect_calendar() -> Result<(), String> {
-    GoogleTokens::delete()
+    if let Some(path) = config_file(LEGACY_TOKENS_FILE).filter(|p| p.exists()) {
+        fs::remove_file(&path).map_err(|e| format!("Failed to delete tokens file: {}", e))?;
+    }
+    match active_account() {
+        Some(email) => GoogleTokens::delete(&email),
+        None => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_account_from_file_name() {
+        assert_eq!(account_from_file_name("google_tokens_me@work.com.json").as_deref(), Some("me@work.com"));
+        assert_eq!(account_from_file_name("google_tokens.json"), None);
+        assert_eq!(account_from_file_name("google_accounts.json"), None);
+        assert_eq!(account_from_file_name("google_tokens_me@work.com.json.tmp"), None);
+    }
+
+    fn event(id: &str, start_time: &str) -> SimpleCalendarEvent {
+        SimpleCalendarEvent {
+            id: id.to_string(),
+            title: id.to_string(),
+            description: None,
+            start_time: start_time.to_string(),
+            end_time: start_time.to_string(),
+            attendees: Vec::new(),
+            meeting_link: None,
+            meeting_links: Vec::new(),
+            is_today: false,
+            is_past: false,
+            is_all_day: false,
+        }
+    }
+
+    #[test]
+    fn test_merge_calendar_events_dedupes_and_orders() {
+        let events = vec![
+            event("standup", "2024-03-05T09:00:00-08:00"),
+            event("review", "2024-03-05T15:00:00Z"),
+            event("standup", "2024-03-05T09:00:00-08:00"),
+            event("planning", "2024-03-05T08:00:00-08:00"),
+        ];
+
+        let merged = merge_calendar_events(events, 10);
+        let ids: Vec<&str> = merged.iter().map(|e| e.id.as_str()).collect();
+        assert_eq!(ids, ["review", "planning", "standup"]);
+
+        assert_eq!(merge_calendar_events(merged, 2).len(), 2);
+    }
+
+    #[test]
+    fn test_meeting_links_from_non_video_entry_points() {
+        let conference: ConferenceData = serde_json::from_value(serde_json::json!({
+            "entryPoints": [
+                {"entryPointType": "phone", "uri": "tel:+1-555-0100", "label": "+1 555-0100", "pin": "123456"},
+                {"entryPointType": "more", "uri": "https://teams.microsoft.com/l/meetup-join/abc", "passcode": "xY9z"},
+                {"entryPointType": "sip", "uri": "sip:123@webex.com"}
+            ]
+        }))
+        .unwrap();
+
+        let links = meeting_links(Some(conference));
+        let kinds: Vec<&str> = links.iter().map(|l| l.kind.as_str()).collect();
+        assert_eq!(kinds, ["more", "sip", "phone"]);
+        assert_eq!(links[0].passcode.as_deref(), Some("xY9z"));
+        assert_eq!(links[2].passcode.as_deref(), Some("123456"));
+        assert!(meeting_links(None).is_empty());
+    }
+
+    #[test]
+    fn test_events_page_url_includes_page_token() {
+        let now = Utc::now();
+        let first = events_page_url("team@group.calendar.google.com", now, now, 250, None);
+        assert!(first.contains("/calendars/team%40group.calendar.google.com/events?"));
+        assert!(first.contains("maxResults=250"));
+        assert!(!first.contains("pageToken"));
+
+        let next = events_page_url("primary", now, now, 10, Some("abc+/="));
+        assert!(next.ends_with("&pageToken=abc%2B%2F%3D"));
+    }
+
+    #[test]
+    fn test_simplify_mixed_timed_and_all_day_events() {
+        let today = Local::now().date_naive();
+        let midnight = parse_event_time(&today.format("%Y-%m-%d").to_string()).unwrap();
+        let now = midnight + Duration::hours(12);
+        let day = |offset: i64| (today + Duration::days(offset)).format("%Y-%m-%d").to_string();
+        let at = |hours: i64| (now + Duration::hours(hours)).to_rfc3339();
+
+        let events: Vec<CalendarEvent> = serde_json::from_value(serde_json::json!([
+            {"id": "offsite", "start": {"date": day(0)}, "end": {"date": day(1)}},
+            {"id": "conference", "start": {"date": day(-1)}, "end": {"date": day(2)}},
+            {"id": "holiday", "start": {"date": day(-1)}, "end": {"date": day(0)}},
+            {"id": "standup", "start": {"dateTime": at(-2)}, "end": {"dateTime": at(-1)}},
+            {"id": "review", "start": {"dateTime": at(1)}, "end": {"dateTime": at(2)}},
+        ]))
+        .unwrap();
+
+        let flags: Vec<(String, bool, bool, bool)> = events
+            .into_iter()
+            .map(|e| simplify_event(e, now))
+            .map(|e| (e.id, e.is_all_day, e.is_today, e.is_past))
+            .collect();
+
+        assert_eq!(flags, vec![
+            ("offsite".to_string(), true, true, false),
+            ("conference".to_string(), true, true, false),
+            ("holiday".to_string(), true, false, true),
+            ("standup".to_string(), false, true, true),
+            ("review".to_string(), false, true, false),
+        ]);
+
+        // All-day events sort at the start of their day, before timed ones
+        assert_eq!(parse_event_time(&day(0)), Some(midnight));
+        assert!(parse_event_time(&day(0)) < parse_event_time(&at(-2)));
+    }
+
+    #[test]
+    fn test_event_times_use_event_time_zone() {
+        let at = |s: &str| s.parse::<DateTime<Utc>>().ok();
+
+        assert_eq!(
+            parse_event_time_in("2024-03-05T09:00:00", Some("America/Los_Angeles")),
+            at("2024-03-05T17:00:00Z")
+        );
+        // All-day dates are local dates, not dates in the event's time zone
+        let local_midnight = Local
+            .from_local_datetime(&NaiveDate::from_ymd_opt(2024, 3, 5).unwrap().and_time(NaiveTime::MIN))
+            .earliest()
+            .map(|dt| dt.with_timezone(&Utc));
+        assert_eq!(parse_event_time_in("2024-03-05", Some("Asia/Tokyo")), local_midnight);
+        // An explicit offset wins over the time zone name
+        assert_eq!(
+            parse_event_time_in("2024-03-05T09:00:00+01:00", Some("America/Los_Angeles")),
+            at("2024-03-05T08:00:00Z")
+        );
+
+        let event: CalendarEvent = serde_json::from_value(serde_json::json!({
+            "id": "sync",
+            "start": {"dateTime": "2024-03-05T09:00:00", "timeZone": "America/Los_Angeles"},
+            "end": {"dateTime": "2024-03-05T09:30:00", "timeZone": "America/Los_Angeles"}
+        }))
+        .unwrap();
+        let simple = simplify_event(event, at("2024-03-05T17:10:00Z").unwrap());
+        assert!(simple.is_past);
+        assert_eq!(parse_event_time(&simple.start_time), at("2024-03-05T17:00:00Z"));
+    }
 }
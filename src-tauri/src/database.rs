@@ -1,7 +1,10 @@
+use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
 use std::fs;
 use std::path::PathBuf;
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, Local, NaiveDate, Utc};
+use rusqlite::{params, Connection, Transaction};
 
 /// Stored meeting with transcript and summary
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -17,6 +20,47 @@ pub struct StoredMeeting {
     pub recording_path: Option<String>,
     pub created_at: String,
     pub updated_at: String,
+    #[serde(default)]
+    pub chapters: Vec<Chapter>,
+    /// User labels for grouping meetings (project, client, ...)
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// Private notes the user jotted during the meeting (not part of the transcript)
+    #[serde(default)]
+    pub notes: Vec<Note>,
+    /// Moments the user marked to revisit
+    #[serde(default)]
+    pub bookmarks: Vec<Bookmark>,
+    /// The recording re-transcribed by other providers, keyed by provider, for comparison
+    #[serde(default)]
+    pub alternate_transcripts: BTreeMap<String, Vec<TranscriptSegment>>,
+}
+
+/// Highlighted moment in a meeting, for jumping back to it later
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Bookmark {
+    pub label: String,
+    /// Wall-clock time the bookmark was added (HH:MM:SS)
+    pub timestamp: String,
+    /// Time since the meeting started
+    pub elapsed_ms: u64,
+    /// Transcript segment that was most recent when the bookmark was added
+    #[serde(default)]
+    pub segment_index: Option<usize>,
+}
+
+/// User note taken during a meeting, stamped with the wall-clock time it was written
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Note {
+    pub timestamp: String,
+    pub text: String,
+}
+
+/// Topical section of a meeting, starting at a transcript timestamp
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Chapter {
+    pub title: String,
+    pub start_timestamp: String,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -35,90 +79,395 @@ pub struct MeetingSummary {
     pub raw_summary: Option<String>,
 }
 
-/// Database for storing meetings (JSON file-based for simplicity)
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+impl MeetingSummary {
+    /// Summary as plain text, preferring the raw summary when present
+    pub fn to_text(&self) -> String {
+        if let Some(raw) = self.raw_summary.as_ref().filter(|r| !r.trim().is_empty()) {
+            return raw.clone();
+        }
+
+        let sections = [
+            ("KEY POINTS", &self.key_points),
+            ("ACTION ITEMS", &self.action_items),
+            ("DECISIONS", &self.decisions),
+            ("NOTES", &self.notes),
+        ];
+        sections
+            .iter()
+            .filter(|(_, items)| !items.is_empty())
+            .map(|(heading, items)| {
+                let bullets = items.iter().map(|i| format!("• {}", i)).collect::<Vec<_>>().join("\n");
+                format!("## {}\n{}", heading, bullets)
+            })
+            .collect::<Vec<_>>()
+            .join("\n\n")
+    }
+}
+
+impl StoredMeeting {
+    /// Whether the meeting has a tag (case-insensitive)
+    pub fn has_tag(&self, tag: &str) -> bool {
+        self.tags.iter().any(|t| t.eq_ignore_ascii_case(tag.trim()))
+    }
+
+    /// Local calendar date the meeting took place on
+    pub fn local_date(&self) -> Option<NaiveDate> {
+        DateTime::parse_from_rfc3339(&self.date)
+            .ok()
+            .map(|d| d.with_timezone(&Local).date_naive())
+    }
+
+    /// Transcript converted back to the app's segment type (for summaries, exports, etc.)
+    pub fn app_transcript(&self) -> Vec<crate::TranscriptSegment> {
+        self.transcript
+            .iter()
+            .map(|s| crate::TranscriptSegment {
+                timestamp: s.timestamp.clone(),
+                speaker: s.speaker.clone(),
+                text: s.text.clone(),
+                ..Default::default()
+            })
+            .collect()
+    }
+}
+
+/// Version of the legacy meetings.json format, used when importing it.
+/// Bump this and add a step to `migrate()` whenever `StoredMeeting` changes shape.
+pub const JSON_DB_VERSION: u32 = 1;
+
+/// SQLite schema version, kept in `PRAGMA user_version`
+const SCHEMA_VERSION: i32 = 4;
+
+/// Legacy single-file JSON database, only read when importing
+#[derive(Debug, Deserialize)]
+struct JsonDatabase {
+    meetings: Vec<StoredMeeting>,
+}
+
+/// Upgrade a raw meetings.json document to the current JSON schema version.
+/// Each step only touches the fields that changed in that version.
+pub fn migrate(mut value: serde_json::Value) -> Result<serde_json::Value, String> {
+    let version = value.get("version").and_then(|v| v.as_u64()).unwrap_or(0);
+    if version > JSON_DB_VERSION as u64 {
+        return Err(format!(
+            "Meetings database version {} is newer than supported version {}",
+            version, JSON_DB_VERSION
+        ));
+    }
+
+    // v0 -> v1: meetings gained chapters and tags
+    if version < 1 {
+        if let Some(meetings) = value.get_mut("meetings").and_then(|m| m.as_array_mut()) {
+            for meeting in meetings.iter_mut().filter_map(|m| m.as_object_mut()) {
+                meeting.entry("chapters").or_insert_with(|| serde_json::json!([]));
+                meeting.entry("tags").or_insert_with(|| serde_json::json!([]));
+            }
+        }
+    }
+
+    if let Some(obj) = value.as_object_mut() {
+        obj.insert("version".to_string(), serde_json::json!(JSON_DB_VERSION));
+    }
+    Ok(value)
+}
+
+/// Create or upgrade the SQLite schema
+fn init_schema(conn: &Connection) -> rusqlite::Result<()> {
+    conn.execute_batch("PRAGMA foreign_keys = ON;")?;
+
+    let version: i32 = conn.query_row("PRAGMA user_version", [], |row| row.get(0))?;
+    if version < 1 {
+        conn.execute_batch(
+            "CREATE TABLE IF NOT EXISTS meetings (
+                id TEXT PRIMARY KEY,
+                title TEXT NOT NULL,
+                date TEXT NOT NULL,
+                duration_seconds INTEGER,
+                summary TEXT,
+                attendees TEXT NOT NULL DEFAULT '[]',
+                calendar_event_id TEXT,
+                recording_path TEXT,
+                created_at TEXT NOT NULL,
+                updated_at TEXT NOT NULL,
+                chapters TEXT NOT NULL DEFAULT '[]',
+                tags TEXT NOT NULL DEFAULT '[]'
+            );
+            CREATE TABLE IF NOT EXISTS segments (
+                meeting_id TEXT NOT NULL REFERENCES meetings(id) ON DELETE CASCADE,
+                position INTEGER NOT NULL,
+                timestamp TEXT NOT NULL,
+                speaker TEXT NOT NULL,
+                text TEXT NOT NULL,
+                PRIMARY KEY (meeting_id, position)
+            );
+            CREATE INDEX IF NOT EXISTS meetings_date ON meetings(date);",
+        )?;
+    }
+    // v1 -> v2: user notes
+    if version < 2 {
+        conn.execute_batch("ALTER TABLE meetings ADD COLUMN notes TEXT NOT NULL DEFAULT '[]';")?;
+    }
+    // v2 -> v3: bookmarks
+    if version < 3 {
+        conn.execute_batch("ALTER TABLE meetings ADD COLUMN bookmarks TEXT NOT NULL DEFAULT '[]';")?;
+    }
+    // v3 -> v4: transcripts from other providers
+    if version < 4 {
+        conn.execute_batch("ALTER TABLE meetings ADD COLUMN alternate_transcripts TEXT NOT NULL DEFAULT '{}';")?;
+    }
+    if version < SCHEMA_VERSION {
+        conn.pragma_update(None, "user_version", SCHEMA_VERSION)?;
+    }
+
+    Ok(())
+}
+
+/// Read a JSON-encoded TEXT column
+fn json_column<T: DeserializeOwned>(row: &rusqlite::Row, idx: usize) -> rusqlite::Result<T> {
+    let text: String = row.get(idx)?;
+    serde_json::from_str(&text)
+        .map_err(|e| rusqlite::Error::FromSqlConversionFailure(idx, rusqlite::types::Type::Text, Box::new(e)))
+}
+
+fn to_json<T: Serialize>(value: &T) -> Result<String, String> {
+    serde_json::to_string(value).map_err(|e| format!("Failed to serialize meeting: {}", e))
+}
+
+/// Database for storing meetings, backed by SQLite (config_dir/vantage/meetings.db).
+///
+/// All meetings are also kept in memory for listing and search. Each write only
+/// touches the affected meeting's rows, inside a transaction.
+#[derive(Debug)]
 pub struct MeetingsDatabase {
     pub meetings: Vec<StoredMeeting>,
+    conn: Option<Connection>,
+}
+
+impl Default for MeetingsDatabase {
+    /// Empty database in memory (used when the file can't be opened)
+    fn default() -> Self {
+        let conn = Connection::open_in_memory()
+            .and_then(|conn| init_schema(&conn).map(|_| conn))
+            .map_err(|e| tracing::warn!("Failed to open in-memory meetings database: {}", e))
+            .ok();
+        Self {
+            meetings: Vec::new(),
+            conn,
+        }
+    }
 }
 
 impl MeetingsDatabase {
-    fn get_db_path() -> Option<PathBuf> {
+    fn get_config_path(file_name: &str) -> Option<PathBuf> {
         dirs::config_dir().map(|mut path| {
             path.push("vantage");
-            path.push("meetings.json");
+            path.push(file_name);
             path
         })
     }
 
     pub fn load() -> Self {
-        let Some(path) = Self::get_db_path() else {
-            eprintln!("Could not determine config directory for meetings database");
+        let Some(path) = Self::get_config_path("meetings.db") else {
+            tracing::warn!("Could not determine config directory for meetings database");
             return Self::default();
         };
 
+        if let Some(parent) = path.parent() {
+            if let Err(e) = fs::create_dir_all(parent) {
+                tracing::warn!("Failed to create config directory: {}", e);
+            }
+        }
+
+        let conn = match Connection::open(&path).and_then(|conn| init_schema(&conn).map(|_| conn)) {
+            Ok(conn) => conn,
+            Err(e) => {
+                tracing::warn!("Failed to open meetings database: {}", e);
+                return Self::default();
+            }
+        };
+
+        let mut db = Self {
+            meetings: Vec::new(),
+            conn: Some(conn),
+        };
+        db.import_legacy_json();
+
+        match db.read_all() {
+            Ok(meetings) => db.meetings = meetings,
+            Err(e) => tracing::warn!("Failed to read meetings database: {}", e),
+        }
+
+        db
+    }
+
+    /// One-time import of the old meetings.json. On success the file is renamed to
+    /// meetings.imported.json; if it can't be parsed it is left in place untouched.
+    fn import_legacy_json(&mut self) {
+        let Some(path) = Self::get_config_path("meetings.json") else {
+            return;
+        };
         if !path.exists() {
-            return Self::default();
+            return;
         }
 
-        match fs::read_to_string(&path) {
-            Ok(content) => {
-                match serde_json::from_str(&content) {
-                    Ok(db) => db,
-                    Err(e) => {
-                        eprintln!("Failed to parse meetings database: {}", e);
-                        Self::default()
-                    }
+        let result = fs::read_to_string(&path)
+            .map_err(|e| e.to_string())
+            .and_then(|content| self.import_json(&content));
+
+        match result {
+            Ok(count) => {
+                tracing::info!("Imported {} meetings from {:?}", count, path);
+                let imported_path = path.with_extension("imported.json");
+                if let Err(e) = fs::rename(&path, &imported_path) {
+                    tracing::warn!("Failed to rename imported meetings file: {}", e);
                 }
             }
-            Err(e) => {
-                eprintln!("Failed to read meetings database: {}", e);
-                Self::default()
-            }
+            Err(e) => tracing::warn!("Failed to import meetings.json (left in place): {}", e),
         }
     }
 
-    pub fn save(&self) -> Result<(), String> {
-        let path = Self::get_db_path()
-            .ok_or_else(|| "Could not determine config directory".to_string())?;
+    /// Insert every meeting from a meetings.json document. Existing ids are overwritten,
+    /// so an interrupted import can simply run again.
+    fn import_json(&mut self, content: &str) -> Result<usize, String> {
+        let value: serde_json::Value = serde_json::from_str(content)
+            .map_err(|e| format!("Failed to parse meetings database: {}", e))?;
+        let legacy: JsonDatabase = serde_json::from_value(migrate(value)?)
+            .map_err(|e| format!("Failed to parse meetings database: {}", e))?;
 
-        if let Some(parent) = path.parent() {
-            fs::create_dir_all(parent)
-                .map_err(|e| format!("Failed to create config directory: {}", e))?;
+        let conn = self.conn_mut()?;
+        let tx = conn.transaction().map_err(|e| e.to_string())?;
+        for meeting in &legacy.meetings {
+            write_meeting(&tx, meeting)?;
         }
+        tx.commit().map_err(|e| e.to_string())?;
 
-        let content = serde_json::to_string_pretty(self)
-            .map_err(|e| format!("Failed to serialize meetings: {}", e))?;
+        Ok(legacy.meetings.len())
+    }
 
-        fs::write(&path, content)
-            .map_err(|e| format!("Failed to write meetings file: {}", e))?;
+    fn conn_mut(&mut self) -> Result<&mut Connection, String> {
+        self.conn.as_mut().ok_or_else(|| "Meetings database is not available".to_string())
+    }
 
-        Ok(())
+    /// Load every meeting and its transcript from SQLite
+    fn read_all(&self) -> rusqlite::Result<Vec<StoredMeeting>> {
+        let Some(conn) = self.conn.as_ref() else {
+            return Ok(Vec::new());
+        };
+
+        let mut segments: HashMap<String, Vec<TranscriptSegment>> = HashMap::new();
+        let mut stmt = conn.prepare(
+            "SELECT meeting_id, timestamp, speaker, text FROM segments ORDER BY meeting_id, position",
+        )?;
+        let rows = stmt.query_map([], |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                TranscriptSegment {
+                    timestamp: row.get(1)?,
+                    speaker: row.get(2)?,
+                    text: row.get(3)?,
+                },
+            ))
+        })?;
+        for row in rows {
+            let (meeting_id, segment) = row?;
+            segments.entry(meeting_id).or_default().push(segment);
+        }
+
+        let mut stmt = conn.prepare(
+            "SELECT id, title, date, duration_seconds, summary, attendees, calendar_event_id,
+                    recording_path, created_at, updated_at, chapters, tags, notes, bookmarks,
+                    alternate_transcripts
+             FROM meetings",
+        )?;
+        let meetings = stmt.query_map([], |row| {
+            let summary: Option<String> = row.get(4)?;
+            let summary = summary
+                .map(|s| serde_json::from_str(&s))
+                .transpose()
+                .map_err(|e| rusqlite::Error::FromSqlConversionFailure(4, rusqlite::types::Type::Text, Box::new(e)))?;
+            Ok(StoredMeeting {
+                id: row.get(0)?,
+                title: row.get(1)?,
+                date: row.get(2)?,
+                duration_seconds: row.get::<_, Option<i64>>(3)?.map(|d| d as u64),
+                transcript: Vec::new(),
+                summary,
+                attendees: json_column(row, 5)?,
+                calendar_event_id: row.get(6)?,
+                recording_path: row.get(7)?,
+                created_at: row.get(8)?,
+                updated_at: row.get(9)?,
+                chapters: json_column(row, 10)?,
+                tags: json_column(row, 11)?,
+                notes: json_column(row, 12)?,
+                bookmarks: json_column(row, 13)?,
+                alternate_transcripts: json_column(row, 14)?,
+            })
+        })?;
+
+        meetings
+            .map(|m| {
+                m.map(|mut meeting| {
+                    meeting.transcript = segments.remove(&meeting.id).unwrap_or_default();
+                    meeting
+                })
+            })
+            .collect()
+    }
+
+    /// Write one meeting (and its transcript) to SQLite
+    fn persist(&mut self, meeting: &StoredMeeting) -> Result<(), String> {
+        let conn = self.conn_mut()?;
+        crate::persist::blocking(|| {
+            let tx = conn.transaction().map_err(|e| e.to_string())?;
+            write_meeting(&tx, meeting)?;
+            tx.commit().map_err(|e| e.to_string())
+        })
+    }
+
+    fn delete_row(&mut self, id: &str) -> Result<(), String> {
+        let conn = self.conn_mut()?;
+        crate::persist::blocking(|| conn.execute("DELETE FROM meetings WHERE id = ?1", params![id]))
+            .map(|_| ())
+            .map_err(|e| e.to_string())
     }
 
     /// Add a new meeting
     pub fn add_meeting(&mut self, meeting: StoredMeeting) -> Result<(), String> {
+        self.persist(&meeting)?;
         self.meetings.push(meeting);
-        self.save()
+        Ok(())
     }
 
     /// Update an existing meeting
     pub fn update_meeting(&mut self, id: &str, meeting: StoredMeeting) -> Result<(), String> {
-        if let Some(pos) = self.meetings.iter().position(|m| m.id == id) {
-            self.meetings[pos] = meeting;
-            self.save()
+        let pos = self.meetings.iter().position(|m| m.id == id)
+            .ok_or_else(|| format!("Meeting not found: {}", id))?;
+        if meeting.id != id {
+            self.delete_row(id)?;
+        }
+        self.persist(&meeting)?;
+        self.meetings[pos] = meeting;
+        Ok(())
+    }
+
+    /// Replace the meeting with the same ID, or add it if there is none
+    pub fn upsert_meeting(&mut self, meeting: StoredMeeting) -> Result<(), String> {
+        if self.get_meeting(&meeting.id).is_some() {
+            let id = meeting.id.clone();
+            self.update_meeting(&id, meeting)
         } else {
-            Err(format!("Meeting not found: {}", id))
+            self.add_meeting(meeting)
         }
     }
 
     /// Delete a meeting
     pub fn delete_meeting(&mut self, id: &str) -> Result<(), String> {
-        if let Some(pos) = self.meetings.iter().position(|m| m.id == id) {
-            self.meetings.remove(pos);
-            self.save()
-        } else {
-            Err(format!("Meeting not found: {}", id))
-        }
+        let pos = self.meetings.iter().position(|m| m.id == id)
+            .ok_or_else(|| format!("Meeting not found: {}", id))?;
+        self.delete_row(id)?;
+        self.meetings.remove(pos);
+        Ok(())
     }
 
     /// Get a meeting by ID
@@ -147,16 +496,164 @@ impl MeetingsDatabase {
         meetings
     }
 
-    /// Search meetings by title or transcript content
-    pub fn search_meetings(&self, query: &str) -> Vec<&StoredMeeting> {
+    /// Meetings held between two local dates (inclusive), oldest first
+    pub fn meetings_in_range(&self, start: NaiveDate, end: NaiveDate) -> Vec<&StoredMeeting> {
+        let mut meetings: Vec<_> = self.meetings.iter()
+            .filter(|m| m.local_date().is_some_and(|d| d >= start && d <= end))
+            .collect();
+        meetings.sort_by(|a, b| a.date.cmp(&b.date));
+        meetings
+    }
+
+    /// Search meetings by title or transcript content.
+    /// When tags are given, only meetings carrying all of them match.
+    pub fn search_meetings(&self, query: &str, tags: &[String]) -> Vec<&StoredMeeting> {
         let query_lower = query.to_lowercase();
         self.meetings.iter()
+            .filter(|m| tags.iter().all(|tag| m.has_tag(tag)))
             .filter(|m| {
                 m.title.to_lowercase().contains(&query_lower) ||
                 m.transcript.iter().any(|t| t.text.to_lowercase().contains(&query_lower))
             })
             .collect()
     }
+
+    /// Add a tag to a meeting (no-op if it already has it). Returns the meeting's tags.
+    pub fn add_tag(&mut self, id: &str, tag: &str) -> Result<Vec<String>, String> {
+        let tag = tag.trim();
+        if tag.is_empty() {
+            return Err("Tag cannot be empty".to_string());
+        }
+
+        let mut meeting = self.get_meeting(id).cloned()
+            .ok_or_else(|| format!("Meeting not found: {}", id))?;
+        if !meeting.has_tag(tag) {
+            meeting.tags.push(tag.to_string());
+            meeting.updated_at = Utc::now().to_rfc3339();
+        }
+        let tags = meeting.tags.clone();
+
+        self.update_meeting(id, meeting)?;
+        Ok(tags)
+    }
+
+    /// Rename a speaker (e.g. diarization label "A") on every segment of a meeting.
+    /// Returns the updated meeting.
+    pub fn relabel_speaker(&mut self, id: &str, from_label: &str, to_name: &str) -> Result<StoredMeeting, String> {
+        let to_name = to_name.trim();
+        if to_name.is_empty() {
+            return Err("Speaker name cannot be empty".to_string());
+        }
+
+        let mut meeting = self.get_meeting(id).cloned()
+            .ok_or_else(|| format!("Meeting not found: {}", id))?;
+        let mut renamed = 0;
+        for segment in meeting.transcript.iter_mut().filter(|s| s.speaker == from_label) {
+            segment.speaker = to_name.to_string();
+            renamed += 1;
+        }
+        if renamed == 0 {
+            return Err(format!("No segments from speaker: {}", from_label));
+        }
+        meeting.updated_at = Utc::now().to_rfc3339();
+
+        self.update_meeting(id, meeting.clone())?;
+        Ok(meeting)
+    }
+
+    /// Remove a tag from a meeting. Returns the meeting's tags.
+    pub fn remove_tag(&mut self, id: &str, tag: &str) -> Result<Vec<String>, String> {
+        let mut meeting = self.get_meeting(id).cloned()
+            .ok_or_else(|| format!("Meeting not found: {}", id))?;
+        let before = meeting.tags.len();
+        meeting.tags.retain(|t| !t.eq_ignore_ascii_case(tag.trim()));
+        if meeting.tags.len() != before {
+            meeting.updated_at = Utc::now().to_rfc3339();
+        }
+        let tags = meeting.tags.clone();
+
+        self.update_meeting(id, meeting)?;
+        Ok(tags)
+    }
+
+    /// Store a transcript of the meeting's recording from another provider, replacing any
+    /// earlier one from that provider. The main transcript is left as it is.
+    pub fn set_alternate_transcript(
+        &mut self,
+        id: &str,
+        provider: &str,
+        transcript: &[crate::TranscriptSegment],
+    ) -> Result<(), String> {
+        let mut meeting = self.get_meeting(id).cloned()
+            .ok_or_else(|| format!("Meeting not found: {}", id))?;
+        let segments = transcript
+            .iter()
+            .map(|s| TranscriptSegment {
+                timestamp: s.timestamp.clone(),
+                speaker: s.speaker.clone(),
+                text: s.text.clone(),
+            })
+            .collect();
+        meeting.alternate_transcripts.insert(provider.to_string(), segments);
+        meeting.updated_at = Utc::now().to_rfc3339();
+
+        self.update_meeting(id, meeting)
+    }
+
+    /// Meetings with a given tag, newest first
+    pub fn meetings_with_tag(&self, tag: &str) -> Vec<&StoredMeeting> {
+        let mut meetings: Vec<_> = self.meetings.iter().filter(|m| m.has_tag(tag)).collect();
+        meetings.sort_by(|a, b| b.date.cmp(&a.date));
+        meetings
+    }
+}
+
+/// Insert or update a meeting row and replace its transcript segments
+fn write_meeting(tx: &Transaction, meeting: &StoredMeeting) -> Result<(), String> {
+    let summary = meeting.summary.as_ref().map(to_json).transpose()?;
+    tx.execute(
+        "INSERT INTO meetings (id, title, date, duration_seconds, summary, attendees, calendar_event_id,
+                               recording_path, created_at, updated_at, chapters, tags, notes, bookmarks,
+                               alternate_transcripts)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15)
+         ON CONFLICT(id) DO UPDATE SET
+            title = excluded.title, date = excluded.date, duration_seconds = excluded.duration_seconds,
+            summary = excluded.summary, attendees = excluded.attendees,
+            calendar_event_id = excluded.calendar_event_id, recording_path = excluded.recording_path,
+            created_at = excluded.created_at, updated_at = excluded.updated_at,
+            chapters = excluded.chapters, tags = excluded.tags, notes = excluded.notes,
+            bookmarks = excluded.bookmarks, alternate_transcripts = excluded.alternate_transcripts",
+        params![
+            meeting.id,
+            meeting.title,
+            meeting.date,
+            meeting.duration_seconds.map(|d| d as i64),
+            summary,
+            to_json(&meeting.attendees)?,
+            meeting.calendar_event_id,
+            meeting.recording_path,
+            meeting.created_at,
+            meeting.updated_at,
+            to_json(&meeting.chapters)?,
+            to_json(&meeting.tags)?,
+            to_json(&meeting.notes)?,
+            to_json(&meeting.bookmarks)?,
+            to_json(&meeting.alternate_transcripts)?,
+        ],
+    )
+    .map_err(|e| format!("Failed to save meeting: {}", e))?;
+
+    tx.execute("DELETE FROM segments WHERE meeting_id = ?1", params![meeting.id])
+        .map_err(|e| format!("Failed to save transcript: {}", e))?;
+    let mut stmt = tx
+        .prepare("INSERT INTO segments (meeting_id, position, timestamp, speaker, text) VALUES (?1, ?2, ?3, ?4, ?5)")
+        .map_err(|e| format!("Failed to save transcript: {}", e))?;
+    for (position, segment) in meeting.transcript.iter().enumerate() {
+        stmt.execute(params![meeting.id, position as i64, segment.timestamp, segment.speaker, segment.text])
+            .map_err(|e| format!("Failed to save transcript: {}", e))?;
+    }
+
+    Ok(())
 }
 
 /// Generate a unique meeting ID
@@ -165,6 +662,17 @@ pub fn generate_meeting_id() -> String {
     format!("meeting_{}", now.format("%Y%m%d_%H%M%S_%3f"))
 }
 
+/// Convert an app summary into its stored form
+pub fn convert_summary(summary: crate::MeetingSummary) -> MeetingSummary {
+    MeetingSummary {
+        key_points: summary.key_points,
+        action_items: summary.action_items,
+        decisions: summary.decisions,
+        notes: summary.notes,
+        raw_summary: Some(summary.raw_summary),
+    }
+}
+
 /// Create a new meeting from current transcript
 pub fn create_meeting_from_transcript(
     title: String,
@@ -188,13 +696,7 @@ pub fn create_meeting_from_transcript(
         .collect();
 
     // Convert summary
-    let db_summary = summary.map(|s| MeetingSummary {
-        key_points: s.key_points,
-        action_items: s.action_items,
-        decisions: s.decisions,
-        notes: s.notes,
-        raw_summary: Some(s.raw_summary),
-    });
+    let db_summary = summary.map(convert_summary);
 
     StoredMeeting {
         id: generate_meeting_id(),
@@ -208,5 +710,227 @@ pub fn create_meeting_from_transcript(
         recording_path,
         created_at: now.clone(),
         updated_at: now,
+        chapters: Vec::new(),
+        tags: Vec::new(),
+        notes: Vec::new(),
+        bookmarks: Vec::new(),
+        alternate_transcripts: BTreeMap::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn meeting(title: &str, tags: &[&str]) -> StoredMeeting {
+        let mut meeting = create_meeting_from_transcript(
+            title.to_string(),
+            vec![crate::TranscriptSegment {
+                timestamp: "10:00:00".to_string(),
+                speaker: "You".to_string(),
+                text: "Let's review the launch plan".to_string(),
+                ..Default::default()
+            }],
+            None,
+            Vec::new(),
+            None,
+            None,
+            None,
+        );
+        meeting.id = title.to_string();
+        meeting.tags = tags.iter().map(|t| t.to_string()).collect();
+        meeting
+    }
+
+    #[test]
+    fn test_search_filters_by_all_tags() {
+        let db = MeetingsDatabase {
+            meetings: vec![
+                meeting("Acme kickoff", &["acme", "Sales"]),
+                meeting("Acme review", &["acme"]),
+                meeting("Internal sync", &[]),
+            ],
+            ..Default::default()
+        };
+
+        assert_eq!(db.search_meetings("launch", &[]).len(), 3);
+        assert_eq!(db.search_meetings("launch", &["ACME".to_string()]).len(), 2);
+        let both = db.search_meetings("", &["acme".to_string(), "sales".to_string()]);
+        assert_eq!(both.len(), 1);
+        assert_eq!(both[0].title, "Acme kickoff");
+    }
+
+    #[test]
+    fn test_upsert_adds_then_replaces() {
+        let mut db = MeetingsDatabase::default();
+        db.upsert_meeting(meeting("Standup", &[])).unwrap();
+
+        let mut updated = meeting("Standup", &["team"]);
+        updated.title = "Standup (final)".to_string();
+        db.upsert_meeting(updated).unwrap();
+
+        assert_eq!(db.meetings.len(), 1);
+        assert_eq!(db.get_meeting("Standup").unwrap().title, "Standup (final)");
+        assert_eq!(db.read_all().unwrap()[0].tags, ["team"]);
+    }
+
+    #[test]
+    fn test_relabel_speaker_renames_every_segment() {
+        let mut db = MeetingsDatabase::default();
+        let mut standup = meeting("Standup", &[]);
+        standup.transcript = ["A", "B", "A"]
+            .iter()
+            .map(|speaker| TranscriptSegment {
+                timestamp: "10:00:00".to_string(),
+                speaker: speaker.to_string(),
+                text: "Update".to_string(),
+            })
+            .collect();
+        db.add_meeting(standup).unwrap();
+
+        let updated = db.relabel_speaker("Standup", "A", " Priya ").unwrap();
+        let speakers: Vec<&str> = updated.transcript.iter().map(|s| s.speaker.as_str()).collect();
+        assert_eq!(speakers, ["Priya", "B", "Priya"]);
+        assert_eq!(db.read_all().unwrap()[0].transcript[2].speaker, "Priya");
+
+        assert!(db.relabel_speaker("Standup", "C", "Sam").is_err());
+    }
+
+    #[test]
+    fn test_alternate_transcripts_keep_original() {
+        let mut db = MeetingsDatabase::default();
+        db.add_meeting(meeting("Review", &[])).unwrap();
+
+        let retranscribed = vec![crate::TranscriptSegment {
+            timestamp: "00:00:00".to_string(),
+            speaker: "A".to_string(),
+            text: "Let's review the launch plan.".to_string(),
+            ..Default::default()
+        }];
+        db.set_alternate_transcript("Review", "assemblyai", &retranscribed).unwrap();
+
+        let loaded = &db.read_all().unwrap()[0];
+        assert_eq!(loaded.transcript[0].text, "Let's review the launch plan");
+        assert_eq!(loaded.alternate_transcripts["assemblyai"][0].speaker, "A");
+    }
+
+    #[test]
+    fn test_migrate_unversioned_database() {
+        let mut old_meeting = serde_json::to_value(meeting("Old meeting", &[])).unwrap();
+        let obj = old_meeting.as_object_mut().unwrap();
+        obj.remove("tags");
+        obj.remove("chapters");
+        let old_db = serde_json::json!({ "meetings": [old_meeting] });
+
+        let migrated = migrate(old_db).unwrap();
+        assert_eq!(migrated["version"], JSON_DB_VERSION);
+
+        let db: JsonDatabase = serde_json::from_value(migrated).unwrap();
+        assert_eq!(db.meetings.len(), 1);
+        assert!(db.meetings[0].tags.is_empty());
+    }
+
+    #[test]
+    fn test_migrate_rejects_newer_database() {
+        let newer = serde_json::json!({ "version": JSON_DB_VERSION + 1, "meetings": [] });
+        assert!(migrate(newer).unwrap_err().contains("newer"));
+    }
+
+    #[test]
+    fn test_sqlite_round_trip() {
+        let mut db = MeetingsDatabase::default();
+        let mut original = meeting("Acme kickoff", &["acme"]);
+        original.transcript.push(TranscriptSegment {
+            timestamp: "10:00:05".to_string(),
+            speaker: "Participant".to_string(),
+            text: "Sounds good".to_string(),
+        });
+        original.summary = Some(MeetingSummary {
+            key_points: vec!["Launch in May".to_string()],
+            action_items: Vec::new(),
+            decisions: Vec::new(),
+            notes: Vec::new(),
+            raw_summary: None,
+        });
+        original.notes.push(Note {
+            timestamp: "10:02:00".to_string(),
+            text: "Ask legal about the May date".to_string(),
+        });
+        original.bookmarks.push(Bookmark {
+            label: "Pricing question".to_string(),
+            timestamp: "10:03:00".to_string(),
+            elapsed_ms: 180_000,
+            segment_index: Some(1),
+        });
+        db.add_meeting(original.clone()).unwrap();
+        db.add_tag(&original.id, "sales").unwrap();
+
+        let loaded = db.read_all().unwrap();
+        assert_eq!(loaded.len(), 1);
+        assert_eq!(loaded[0].transcript.len(), 2);
+        assert_eq!(loaded[0].transcript[1].text, "Sounds good");
+        assert_eq!(loaded[0].tags, vec!["acme", "sales"]);
+        assert_eq!(loaded[0].summary.as_ref().unwrap().key_points, vec!["Launch in May"]);
+        assert_eq!(loaded[0].notes, original.notes);
+        assert_eq!(loaded[0].bookmarks, original.bookmarks);
+
+        db.delete_meeting(&original.id).unwrap();
+        assert!(db.read_all().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_import_legacy_json() {
+        let mut old_meeting = serde_json::to_value(meeting("Old meeting", &[])).unwrap();
+        old_meeting.as_object_mut().unwrap().remove("tags");
+        let content = serde_json::json!({ "meetings": [old_meeting] }).to_string();
+
+        let mut db = MeetingsDatabase::default();
+        assert_eq!(db.import_json(&content).unwrap(), 1);
+        // Re-running an interrupted import doesn't duplicate meetings
+        assert_eq!(db.import_json(&content).unwrap(), 1);
+
+        let loaded = db.read_all().unwrap();
+        assert_eq!(loaded.len(), 1);
+        assert_eq!(loaded[0].title, "Old meeting");
+        assert_eq!(loaded[0].transcript.len(), 1);
+    }
+
+    #[test]
+    fn test_meetings_without_tags_field_still_load() {
+        let mut value = serde_json::to_value(meeting("Old meeting", &[])).unwrap();
+        value.as_object_mut().unwrap().remove("tags");
+        let loaded: StoredMeeting = serde_json::from_value(value).unwrap();
+        assert!(loaded.tags.is_empty());
+    }
+
+    #[test]
+    fn test_schema_upgrade_adds_new_columns() {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch(
+            "CREATE TABLE meetings (
+                id TEXT PRIMARY KEY, title TEXT NOT NULL, date TEXT NOT NULL, duration_seconds INTEGER,
+                summary TEXT, attendees TEXT NOT NULL DEFAULT '[]', calendar_event_id TEXT,
+                recording_path TEXT, created_at TEXT NOT NULL, updated_at TEXT NOT NULL,
+                chapters TEXT NOT NULL DEFAULT '[]', tags TEXT NOT NULL DEFAULT '[]'
+            );
+            CREATE TABLE segments (
+                meeting_id TEXT NOT NULL REFERENCES meetings(id) ON DELETE CASCADE,
+                position INTEGER NOT NULL, timestamp TEXT NOT NULL, speaker TEXT NOT NULL, text TEXT NOT NULL,
+                PRIMARY KEY (meeting_id, position)
+            );
+            INSERT INTO meetings (id, title, date, created_at, updated_at)
+                VALUES ('m1', 'Old meeting', '2024-05-01T10:00:00Z', '2024-05-01T10:00:00Z', '2024-05-01T10:00:00Z');
+            PRAGMA user_version = 1;",
+        )
+        .unwrap();
+
+        init_schema(&conn).unwrap();
+        let db = MeetingsDatabase { meetings: Vec::new(), conn: Some(conn) };
+
+        let loaded = db.read_all().unwrap();
+        assert_eq!(loaded.len(), 1);
+        assert!(loaded[0].notes.is_empty());
+        assert!(loaded[0].bookmarks.is_empty());
+        assert!(loaded[0].alternate_transcripts.is_empty());
     }
 }
@@ -2,14 +2,19 @@ use anyhow::{anyhow, Result};
 use cpal::traits::{DeviceTrait, HostTrait, StreamTrait};
 use futures_util::{SinkExt, StreamExt};
 use serde::Deserialize;
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
 use std::sync::{
-    atomic::{AtomicBool, Ordering},
+    atomic::{AtomicBool, AtomicU64, Ordering},
     Arc, Mutex,
 };
 use tokio::sync::mpsc;
 use tokio_tungstenite::tungstenite::Message;
 
-use crate::system_audio::{get_system_audio_device, AudioSource};
+use crate::audio_queue;
+use crate::endpoints;
+use crate::metrics::TranscriptionStats;
+use crate::system_audio::{downmix, get_system_audio_device, system_input_config, AudioSource, LinearResampler};
 
 #[derive(Debug, Deserialize)]
 struct DeepgramResponse {
@@ -28,6 +33,9 @@ pub struct TranscriptMessage {
     pub is_final: bool,
     pub speaker: Option<u32>,  // Speaker ID from diarization (0, 1, 2, etc.)
     pub source: AudioSource,   // Which audio source this came from
+    pub start_ms: Option<u64>, // First word start, relative to stream start
+    pub end_ms: Option<u64>,   // Last word end, relative to stream start
+    pub confidence: Option<f32>, // Deepgram's confidence for the alternative (0.0-1.0)
 }
 
 #[derive(Debug, Deserialize)]
@@ -48,11 +56,190 @@ struct Word {
     word: String,
     #[serde(default)]
     speaker: Option<u32>,
+    #[serde(default)]
+    start: Option<f64>,  // Seconds from stream start
+    #[serde(default)]
+    end: Option<f64>,
 }
 
 pub struct DeepgramTranscriber {
     is_running: Arc<AtomicBool>,
     transcript_sender: mpsc::Sender<TranscriptMessage>,
+    /// Speaker ID the user picked as themselves (mono mode), overriding calibration
+    primary_speaker: Arc<Mutex<Option<u32>>>,
+    /// Audio chunks dropped because the WebSocket send fell behind
+    dropped_chunks: Arc<AtomicU64>,
+    /// Latency and throughput for `get_transcription_metrics`
+    stats: Arc<Mutex<TranscriptionStats>>,
+    /// Minimum time between interim results per channel, in milliseconds
+    interim_debounce_ms: Arc<Mutex<u64>>,
+}
+
+/// Coalesces interim results to at most one per interval per channel. An interim held back
+/// is sent once the interval is up, so the latest words show even if the speaker pauses.
+/// Finals always pass and reset the channel, so the next utterance shows immediately.
+#[derive(Debug, Default)]
+struct InterimDebouncer {
+    last_emitted: [Option<Instant>; 2],
+    /// Latest interim held back per channel
+    pending: [Option<TranscriptMessage>; 2],
+}
+
+impl InterimDebouncer {
+    /// The interim to send now, or None if it's held back until `next_due`
+    fn offer(&mut self, channel: usize, message: TranscriptMessage, now: Instant, interval: Duration) -> Option<TranscriptMessage> {
+        let channel = channel.min(1);
+        if self.last_emitted[channel].is_some_and(|last| now.saturating_duration_since(last) < interval) {
+            self.pending[channel] = Some(message);
+            return None;
+        }
+        self.last_emitted[channel] = Some(now);
+        self.pending[channel] = None;
+        Some(message)
+    }
+
+    /// When the earliest held-back interim is due
+    fn next_due(&self, interval: Duration) -> Option<Instant> {
+        (0..2)
+            .filter(|&channel| self.pending[channel].is_some())
+            .filter_map(|channel| self.last_emitted[channel].map(|last| last + interval))
+            .min()
+    }
+
+    /// Held-back interims whose interval is up
+    fn take_due(&mut self, now: Instant, interval: Duration) -> Vec<TranscriptMessage> {
+        let mut due = Vec::new();
+        for channel in 0..2 {
+            let ready = self.last_emitted[channel].map_or(true, |last| now.saturating_duration_since(last) >= interval);
+            if ready {
+                if let Some(message) = self.pending[channel].take() {
+                    self.last_emitted[channel] = Some(now);
+                    due.push(message);
+                }
+            }
+        }
+        due
+    }
+
+    fn reset(&mut self, channel: usize) {
+        self.last_emitted[channel.min(1)] = None;
+        self.pending[channel.min(1)] = None;
+    }
+}
+
+/// Audio chunks (~100ms each) buffered for the WebSocket before the oldest are dropped
+const AUDIO_QUEUE_CHUNKS: usize = 100;
+
+/// Mic loudness is tracked in windows of this length, indexed by stream time
+const LEVEL_WINDOW_MS: u64 = 100;
+
+/// How much of the start of a mono stream is used to work out which speaker is the user
+const CALIBRATION_MS: u64 = 15_000;
+
+/// RMS level of each LEVEL_WINDOW_MS of mic audio sent to Deepgram
+struct LevelMeter {
+    samples_per_window: usize,
+    sum_squares: f32,
+    count: usize,
+    levels: Arc<Mutex<Vec<f32>>>,
+}
+
+impl LevelMeter {
+    fn new(sample_rate: u32, channels: u16, levels: Arc<Mutex<Vec<f32>>>) -> Self {
+        let samples_per_window = (sample_rate as u64 * channels as u64 * LEVEL_WINDOW_MS / 1000).max(1) as usize;
+        Self { samples_per_window, sum_squares: 0.0, count: 0, levels }
+    }
+
+    fn push(&mut self, sample: f32) {
+        self.sum_squares += sample * sample;
+        self.count += 1;
+        if self.count >= self.samples_per_window {
+            let rms = (self.sum_squares / self.count as f32).sqrt();
+            if let Ok(mut levels) = self.levels.lock() {
+                levels.push(rms);
+            }
+            self.sum_squares = 0.0;
+            self.count = 0;
+        }
+    }
+}
+
+/// Works out which diarized speaker is the user in mono mode.
+///
+/// Deepgram's speaker IDs aren't stable, so the user isn't reliably speaker 0. Remote
+/// voices reach the mic through the speakers and are much quieter than the user's own,
+/// so during the first CALIBRATION_MS the speaker whose words are loudest is taken as the user.
+#[derive(Debug, Default)]
+struct SpeakerCalibration {
+    /// Sum of mic levels and number of level windows, per speaker
+    energy: HashMap<u32, (f32, usize)>,
+    detected: Option<u32>,
+}
+
+impl SpeakerCalibration {
+    /// Record the loudness of each word's speaker, locking in the result once
+    /// speech past the calibration window arrives
+    fn observe(&mut self, words: &[Word], levels: &[f32]) {
+        if self.detected.is_some() {
+            return;
+        }
+
+        let mut past_window = false;
+        for word in words {
+            let (Some(speaker), Some(start), Some(end)) = (word.speaker, word.start, word.end) else {
+                continue;
+            };
+            let (start_ms, end_ms) = ((start * 1000.0) as u64, (end * 1000.0) as u64);
+            if start_ms >= CALIBRATION_MS {
+                past_window = true;
+                continue;
+            }
+
+            let first = (start_ms / LEVEL_WINDOW_MS) as usize;
+            let last = (end_ms / LEVEL_WINDOW_MS) as usize;
+            let window = levels.get(first..=last.min(levels.len().saturating_sub(1))).unwrap_or(&[]);
+            let entry = self.energy.entry(speaker).or_insert((0.0, 0));
+            entry.0 += window.iter().sum::<f32>();
+            entry.1 += window.len();
+        }
+
+        if past_window {
+            self.detected = self.loudest();
+            if let Some(speaker) = self.detected {
+                tracing::info!("Deepgram: calibrated speaker {} as the microphone user", speaker);
+            }
+        }
+    }
+
+    fn loudest(&self) -> Option<u32> {
+        self.energy
+            .iter()
+            .filter(|(_, (_, count))| *count > 0)
+            .map(|(speaker, (sum, count))| (*speaker, sum / *count as f32))
+            .max_by(|a, b| a.1.total_cmp(&b.1))
+            .map(|(speaker, _)| speaker)
+    }
+
+    /// Best guess so far; speaker 0 until any speech has been measured
+    fn primary_speaker(&self) -> Option<u32> {
+        self.detected.or_else(|| self.loudest()).or(Some(0))
+    }
+}
+
+/// Check if a Deepgram API key is valid (lists the key's projects)
+pub async fn check_api_key(api_key: &str) -> Result<bool> {
+    if api_key.is_empty() {
+        return Ok(false);
+    }
+
+    let response = crate::net::client()
+        .get(format!("{}/projects", endpoints::deepgram_http()))
+        .header("Authorization", format!("Token {}", api_key))
+        .timeout(std::time::Duration::from_secs(10))
+        .send()
+        .await?;
+
+    Ok(response.status().is_success())
 }
 
 impl DeepgramTranscriber {
@@ -60,15 +247,55 @@ impl DeepgramTranscriber {
         Self {
             is_running: Arc::new(AtomicBool::new(false)),
             transcript_sender,
+            primary_speaker: Arc::new(Mutex::new(None)),
+            dropped_chunks: Arc::new(AtomicU64::new(0)),
+            stats: Arc::new(Mutex::new(TranscriptionStats::default())),
+            interim_debounce_ms: Arc::new(Mutex::new(0)),
         }
     }
 
+    /// Share the interim debounce interval, so changes apply mid-session
+    pub fn with_interim_debounce(mut self, interim_debounce_ms: Arc<Mutex<u64>>) -> Self {
+        self.interim_debounce_ms = interim_debounce_ms;
+        self
+    }
+
+    /// Record latency and throughput into shared stats
+    pub fn with_stats(mut self, stats: Arc<Mutex<TranscriptionStats>>) -> Self {
+        self.stats = stats;
+        self
+    }
+
+    /// Count dropped audio chunks in a shared counter
+    pub fn with_dropped_chunk_counter(mut self, dropped_chunks: Arc<AtomicU64>) -> Self {
+        self.dropped_chunks = dropped_chunks;
+        self
+    }
+
+    /// Share the user's speaker choice (see `set_primary_speaker` in lib.rs)
+    pub fn with_primary_speaker(mut self, primary_speaker: Arc<Mutex<Option<u32>>>) -> Self {
+        self.primary_speaker = primary_speaker;
+        self
+    }
+
     pub async fn start(&self, api_key: &str) -> Result<()> {
         if self.is_running.load(Ordering::SeqCst) {
             return Err(anyhow!("Already running"));
         }
 
         self.is_running.store(true, Ordering::SeqCst);
+        let result = self.connect_and_stream(api_key).await;
+        if result.is_err() {
+            // Allow a retry (or fallback) after a failed connect
+            self.is_running.store(false, Ordering::SeqCst);
+        }
+        result
+    }
+
+    async fn connect_and_stream(&self, api_key: &str) -> Result<()> {
+        if let Ok(mut stats) = self.stats.lock() {
+            stats.stream_restarted();
+        }
 
         // Get audio devices
         let host = cpal::default_host();
@@ -85,7 +312,7 @@ impl DeepgramTranscriber {
         // Determine channels: 2 for stereo (mic + system), 1 for mono (mic only)
         let channels = if has_system_audio { 2 } else { 1 };
 
-        eprintln!(
+        tracing::info!(
             "Deepgram: sample_rate={}, channels={} ({})",
             sample_rate,
             channels,
@@ -93,17 +320,17 @@ impl DeepgramTranscriber {
         );
 
         if has_system_audio {
-            eprintln!("Multichannel mode enabled: Channel 0 = You (mic), Channel 1 = Participants (system audio)");
+            tracing::info!("Multichannel mode enabled: Channel 0 = You (mic), Channel 1 = Participants (system audio)");
         } else {
-            eprintln!("No system audio device found. Install BlackHole for speaker separation.");
-            eprintln!("  Download: https://github.com/ExistentialAudio/BlackHole");
+            tracing::info!("No system audio device found. Install BlackHole for speaker separation.");
+            tracing::info!("  Download: https://github.com/ExistentialAudio/BlackHole");
         }
 
         // Build WebSocket URL with multichannel support
         // multichannel=true tells Deepgram to transcribe each channel separately
         let url = if has_system_audio {
             format!(
-                "wss://api.deepgram.com/v1/listen?\
+                "{}/listen?\
                 encoding=linear16&\
                 sample_rate={}&\
                 channels=2&\
@@ -115,12 +342,13 @@ impl DeepgramTranscriber {
                 smart_format=true&\
                 vad_events=true&\
                 multichannel=true",
+                endpoints::deepgram(),
                 sample_rate
             )
         } else {
             // Fallback to mono with diarization
             format!(
-                "wss://api.deepgram.com/v1/listen?\
+                "{}/listen?\
                 encoding=linear16&\
                 sample_rate={}&\
                 channels=1&\
@@ -132,49 +360,59 @@ impl DeepgramTranscriber {
                 smart_format=true&\
                 vad_events=true&\
                 diarize=true",
+                endpoints::deepgram(),
                 sample_rate
             )
         };
 
-        eprintln!("Connecting to Deepgram...");
+        tracing::info!("Connecting to Deepgram...");
 
         // Log API key info for debugging (first/last few chars only)
         let key_len = api_key.len();
         if key_len > 8 {
-            eprintln!("API key: {}...{} (len={})", &api_key[..4], &api_key[key_len-4..], key_len);
+            tracing::info!("API key: {}...{} (len={})", &api_key[..4], &api_key[key_len-4..], key_len);
         } else {
-            eprintln!("API key seems too short: len={}", key_len);
+            tracing::info!("API key seems too short: len={}", key_len);
         }
 
         // Build WebSocket request with proper Authorization header
+        let host = url::Url::parse(&url)
+            .ok()
+            .and_then(|u| u.host_str().map(|h| match u.port() {
+                Some(port) => format!("{}:{}", h, port),
+                None => h.to_string(),
+            }))
+            .ok_or_else(|| anyhow!("Invalid Deepgram URL: {}", url))?;
         let request = tokio_tungstenite::tungstenite::http::Request::builder()
             .uri(&url)
             .header("Authorization", format!("Token {}", api_key))
-            .header("Host", "api.deepgram.com")
+            .header("Host", host)
             .header("Upgrade", "websocket")
             .header("Connection", "Upgrade")
             .header("Sec-WebSocket-Key", tokio_tungstenite::tungstenite::handshake::client::generate_key())
             .header("Sec-WebSocket-Version", "13")
             .body(())
             .map_err(|e| {
-                eprintln!("Failed to create request: {}", e);
+                tracing::warn!("Failed to create request: {}", e);
                 anyhow!("Failed to create request: {}", e)
             })?;
 
-        let (ws_stream, response) = tokio_tungstenite::connect_async(request).await.map_err(|e| {
-            eprintln!("Deepgram connection failed: {}", e);
+        let (ws_stream, response) = crate::net::connect_websocket(request).await.map_err(|e| {
+            tracing::warn!("Deepgram connection failed: {}", e);
             anyhow!("WebSocket connection failed: {}", e)
         })?;
 
-        eprintln!("WebSocket response status: {:?}", response.status());
+        tracing::info!("WebSocket response status: {:?}", response.status());
 
-        eprintln!("Connected to Deepgram!");
+        tracing::info!("Connected to Deepgram!");
         let (mut write, mut read) = ws_stream.split();
 
-        // Channel for audio data
-        let (audio_tx, mut audio_rx) = mpsc::channel::<Vec<u8>>(100);
+        // Queue for audio data; drops the oldest chunk rather than blocking capture
+        let (audio_tx, mut audio_rx) = audio_queue::channel(AUDIO_QUEUE_CHUNKS, self.dropped_chunks.clone());
         let is_running = self.is_running.clone();
         let transcript_sender = self.transcript_sender.clone();
+        let mic_levels: Arc<Mutex<Vec<f32>>> = Arc::new(Mutex::new(Vec::new()));
+        let mic_levels_capture = mic_levels.clone();
 
         // Audio capture thread
         let is_running_audio = is_running.clone();
@@ -190,68 +428,54 @@ impl DeepgramTranscriber {
                 let mic_buffer: Arc<Mutex<Vec<i16>>> = Arc::new(Mutex::new(Vec::new()));
                 let system_buffer: Arc<Mutex<Vec<i16>>> = Arc::new(Mutex::new(Vec::new()));
 
-                // Build mic stream
+                // Build mic stream in the mic's own config, mixed down to mono
                 let mic_buffer_clone = mic_buffer.clone();
-                let mic_config_stream = cpal::StreamConfig {
-                    channels: 1,
-                    sample_rate: cpal::SampleRate(sample_rate),
-                    buffer_size: cpal::BufferSize::Default,
-                };
-
-                let mic_stream = mic_device.build_input_stream(
-                    &mic_config_stream,
-                    move |data: &[f32], _: &cpal::InputCallbackInfo| {
-                        let samples: Vec<i16> = data
-                            .iter()
-                            .map(|&s| (s.clamp(-1.0, 1.0) * 32767.0) as i16)
-                            .collect();
-                        if let Ok(mut buf) = mic_buffer_clone.lock() {
-                            buf.extend(samples);
-                        }
-                    },
-                    |err| eprintln!("Mic stream error: {}", err),
-                    None,
-                );
+                let mic_channels = mic_config.channels() as usize;
+                let mic_stream = crate::audio::build_f32_input_stream(&mic_device, &mic_config, move |data: &[f32]| {
+                    let samples: Vec<i16> = downmix(data, mic_channels)
+                        .iter()
+                        .map(|&s| crate::audio::f32_to_i16(s))
+                        .collect();
+                    if let Ok(mut buf) = mic_buffer_clone.lock() {
+                        buf.extend(samples);
+                    }
+                });
 
                 // Build system audio stream
                 let system_buffer_clone = system_buffer.clone();
                 let sys_device = system_device.unwrap();
-                let sys_config = cpal::StreamConfig {
-                    channels: 2, // BlackHole is typically stereo
-                    sample_rate: cpal::SampleRate(sample_rate),
-                    buffer_size: cpal::BufferSize::Default,
-                };
-
-                let system_stream = sys_device.build_input_stream(
-                    &sys_config,
-                    move |data: &[f32], _: &cpal::InputCallbackInfo| {
-                        // Mix stereo to mono (average left and right)
-                        let samples: Vec<i16> = data
-                            .chunks(2)
-                            .map(|chunk| {
-                                let left = chunk.get(0).copied().unwrap_or(0.0);
-                                let right = chunk.get(1).copied().unwrap_or(0.0);
-                                let mono = (left + right) / 2.0;
-                                (mono.clamp(-1.0, 1.0) * 32767.0) as i16
-                            })
+                // Query the loopback device separately: it may not support the mic's rate
+                let system_stream = system_input_config(&sys_device, sample_rate).and_then(|sys_config| {
+                    let sys_channels = sys_config.channels() as usize;
+                    let mut resampler = LinearResampler::new(sys_config.sample_rate().0, sample_rate);
+                    crate::audio::build_f32_input_stream(&sys_device, &sys_config, move |data: &[f32]| {
+                        // Mix down to mono and resample to the mic rate before interleaving
+                        let samples: Vec<i16> = resampler
+                            .process(&downmix(data, sys_channels))
+                            .iter()
+                            .map(|&s| crate::audio::f32_to_i16(s))
                             .collect();
                         if let Ok(mut buf) = system_buffer_clone.lock() {
                             buf.extend(samples);
                         }
-                    },
-                    |err| eprintln!("System audio stream error: {}", err),
-                    None,
-                );
+                    })
+                });
 
                 // Start streams
-                if let Ok(ref stream) = mic_stream {
-                    let _ = stream.play();
-                    eprintln!("Microphone capture started (Channel 0 = You)");
+                match mic_stream {
+                    Ok(ref stream) => {
+                        let _ = stream.play();
+                        tracing::info!("Microphone capture started (Channel 0 = You)");
+                    }
+                    Err(ref e) => tracing::warn!("Failed to build mic stream: {}", e),
                 }
 
-                if let Ok(ref stream) = system_stream {
-                    let _ = stream.play();
-                    eprintln!("System audio capture started (Channel 1 = Participants)");
+                match system_stream {
+                    Ok(ref stream) => {
+                        let _ = stream.play();
+                        tracing::info!("System audio capture started (Channel 1 = Participants)");
+                    }
+                    Err(ref e) => tracing::warn!("Failed to build system audio stream, participants will be silent: {}", e),
                 }
 
                 // Main loop: interleave audio and send
@@ -296,7 +520,7 @@ impl DeepgramTranscriber {
                         stereo_bytes.extend_from_slice(&sys_sample.to_le_bytes());
                     }
 
-                    if audio_tx.blocking_send(stereo_bytes).is_err() {
+                    if !audio_tx.send(stereo_bytes) {
                         break;
                     }
                 }
@@ -305,114 +529,106 @@ impl DeepgramTranscriber {
                 let buffer: Arc<Mutex<Vec<u8>>> = Arc::new(Mutex::new(Vec::new()));
                 let buffer_clone = buffer.clone();
 
-                let err_fn = |err| eprintln!("Audio stream error: {}", err);
-
-                let stream_result = match mic_config.sample_format() {
-                    cpal::SampleFormat::F32 => {
-                        let buffer_clone_inner = buffer_clone.clone();
-                        let audio_tx_inner = audio_tx.clone();
-                        mic_device.build_input_stream(
-                            &mic_config.into(),
-                            move |data: &[f32], _: &cpal::InputCallbackInfo| {
-                                let bytes: Vec<u8> = data
-                                    .iter()
-                                    .flat_map(|&s| {
-                                        let sample_i16 = (s.clamp(-1.0, 1.0) * 32767.0) as i16;
-                                        sample_i16.to_le_bytes().to_vec()
-                                    })
-                                    .collect();
-
-                                if let Ok(mut buf) = buffer_clone_inner.lock() {
-                                    buf.extend(bytes);
-                                    if buf.len() >= buffer_size_mono {
-                                        let chunk: Vec<u8> = buf.drain(..).collect();
-                                        let _ = audio_tx_inner.blocking_send(chunk);
-                                    }
-                                }
-                            },
-                            err_fn,
-                            None,
-                        )
-                    }
-                    cpal::SampleFormat::I16 => {
-                        let buffer_clone_inner = buffer_clone.clone();
-                        let audio_tx_inner = audio_tx.clone();
-                        mic_device.build_input_stream(
-                            &mic_config.into(),
-                            move |data: &[i16], _: &cpal::InputCallbackInfo| {
-                                let bytes: Vec<u8> = data
-                                    .iter()
-                                    .flat_map(|&s| s.to_le_bytes().to_vec())
-                                    .collect();
-
-                                if let Ok(mut buf) = buffer_clone_inner.lock() {
-                                    buf.extend(bytes);
-                                    if buf.len() >= buffer_size_mono {
-                                        let chunk: Vec<u8> = buf.drain(..).collect();
-                                        let _ = audio_tx_inner.blocking_send(chunk);
-                                    }
-                                }
-                            },
-                            err_fn,
-                            None,
-                        )
-                    }
-                    _ => {
-                        eprintln!("Unsupported sample format");
-                        return;
-                    }
-                };
+                let mut level_meter = LevelMeter::new(sample_rate, mic_config.channels(), mic_levels_capture);
+
+                let audio_tx_inner = audio_tx.clone();
+                let stream_result = crate::audio::build_pcm16_input_stream(
+                    &mic_device,
+                    &mic_config,
+                    move |data: &[i16]| {
+                        data.iter().for_each(|&s| level_meter.push(crate::audio::i16_to_f32(s)));
+                        let bytes: Vec<u8> = data.iter().flat_map(|&s| s.to_le_bytes()).collect();
+
+                        if let Ok(mut buf) = buffer_clone.lock() {
+                            buf.extend(bytes);
+                            if buf.len() >= buffer_size_mono {
+                                let chunk: Vec<u8> = buf.drain(..).collect();
+                                audio_tx_inner.send(chunk);
+                            }
+                        }
+                    },
+                );
 
                 match stream_result {
                     Ok(stream) => {
                         if let Err(e) = stream.play() {
-                            eprintln!("Failed to play stream: {}", e);
+                            tracing::warn!("Failed to play stream: {}", e);
                             return;
                         }
-                        eprintln!("Audio capture started!");
+                        tracing::info!("Audio capture started!");
                         while is_running_audio.load(Ordering::SeqCst) {
                             std::thread::sleep(std::time::Duration::from_millis(10));
                         }
-                        eprintln!("Audio capture stopped");
+                        tracing::info!("Audio capture stopped");
                     }
                     Err(e) => {
-                        eprintln!("Failed to build audio stream: {}", e);
+                        tracing::warn!("Failed to build audio stream: {}", e);
                     }
                 }
             }
 
-            eprintln!("Audio capture thread ended");
+            tracing::info!("Audio capture thread ended");
         });
 
         // Task to send audio to WebSocket
         let is_running_send = is_running.clone();
+        let stats_send = self.stats.clone();
+        // 16-bit samples across all channels
+        let bytes_per_second = sample_rate as u64 * 2 * channels as u64;
         tokio::spawn(async move {
-            eprintln!("Audio sender task started");
+            tracing::info!("Audio sender task started");
             while is_running_send.load(Ordering::SeqCst) {
                 match audio_rx.recv().await {
                     Some(bytes) => {
+                        let len = bytes.len();
                         if let Err(e) = write.send(Message::Binary(bytes)).await {
-                            eprintln!("Failed to send audio: {}", e);
+                            tracing::warn!("Failed to send audio: {}", e);
                             break;
                         }
+                        if let Ok(mut stats) = stats_send.lock() {
+                            stats.record_audio_sent(len, len as u64 * 1000 / bytes_per_second.max(1));
+                        }
                     }
                     None => break,
                 }
             }
             let _ = write.close().await;
-            eprintln!("Audio sender task ended");
+            tracing::info!("Audio sender task ended");
         });
 
         // Task to receive transcripts
         let is_running_recv = is_running.clone();
         let has_system_audio_recv = has_system_audio;
+        let primary_speaker = self.primary_speaker.clone();
+        let stats_recv = self.stats.clone();
+        let interim_debounce_ms = self.interim_debounce_ms.clone();
         tokio::spawn(async move {
-            eprintln!("Transcript receiver task started");
+            tracing::info!("Transcript receiver task started");
             let mut last_interim_text_ch0 = String::new();
             let mut last_interim_text_ch1 = String::new();
+            let mut calibration = SpeakerCalibration::default();
+            let mut debouncer = InterimDebouncer::default();
 
             while is_running_recv.load(Ordering::SeqCst) {
-                match read.next().await {
+                let interval = Duration::from_millis(interim_debounce_ms.lock().map(|ms| *ms).unwrap_or(0));
+                let next_due = debouncer.next_due(interval);
+                let flush = async move {
+                    match next_due {
+                        Some(at) => tokio::time::sleep_until(tokio::time::Instant::from_std(at)).await,
+                        None => std::future::pending().await,
+                    }
+                };
+                let message = tokio::select! {
+                    message = read.next() => message,
+                    _ = flush => {
+                        for interim in debouncer.take_due(Instant::now(), interval) {
+                            let _ = transcript_sender.send(interim).await;
+                        }
+                        continue;
+                    }
+                };
+
+                match message {
                     Some(Ok(Message::Text(text))) => {
                         match serde_json::from_str::<DeepgramResponse>(&text) {
                             Ok(response) => {
@@ -442,10 +658,18 @@ impl DeepgramTranscriber {
                                                 (AudioSource::SystemAudio, &mut last_interim_text_ch1)
                                             }
                                         } else {
-                                            // Mono mode: use diarization speaker ID
-                                            // Speaker 0 assumed to be you (first detected)
+                                            // Mono mode: use diarization speaker ID, mapped to the user
+                                            // by the user's choice or by calibration
+                                            if response.is_final.unwrap_or(false) {
+                                                if let Ok(levels) = mic_levels.lock() {
+                                                    calibration.observe(&alt.words, &levels);
+                                                }
+                                            }
+                                            let primary = primary_speaker.lock().ok()
+                                                .and_then(|p| *p)
+                                                .or_else(|| calibration.primary_speaker());
                                             let speaker = alt.words.first().and_then(|w| w.speaker);
-                                            if speaker == Some(0) {
+                                            if speaker.is_some() && speaker == primary {
                                                 (AudioSource::Microphone, &mut last_interim_text_ch0)
                                             } else {
                                                 (AudioSource::SystemAudio, &mut last_interim_text_ch1)
@@ -455,6 +679,14 @@ impl DeepgramTranscriber {
                                         // Extract speaker from words for additional context
                                         let speaker = alt.words.first().and_then(|w| w.speaker);
 
+                                        // Word timestamps give the real span of this utterance
+                                        let start_ms = alt.words.first()
+                                            .and_then(|w| w.start)
+                                            .map(|s| (s * 1000.0) as u64);
+                                        let end_ms = alt.words.last()
+                                            .and_then(|w| w.end)
+                                            .map(|s| (s * 1000.0) as u64);
+
                                         let is_final = response.is_final.unwrap_or(false);
                                         let speech_final = response.speech_final.unwrap_or(false);
 
@@ -462,9 +694,16 @@ impl DeepgramTranscriber {
                                             AudioSource::Microphone => "You",
                                             AudioSource::SystemAudio => "Participant",
                                         };
+                                        let channel_slot = match source {
+                                            AudioSource::Microphone => 0,
+                                            AudioSource::SystemAudio => 1,
+                                        };
 
                                         if is_final || speech_final {
-                                            eprintln!("Deepgram [FINAL] {} (ch={:?}): {}",
+                                            if let (Some(end), Ok(mut stats)) = (end_ms, stats_recv.lock()) {
+                                                stats.record_final(end);
+                                            }
+                                            tracing::info!("Deepgram [FINAL] {} (ch={:?}): {}",
                                                 source_label,
                                                 response.channel_index,
                                                 transcript_text
@@ -474,51 +713,63 @@ impl DeepgramTranscriber {
                                                 is_final: true,
                                                 speaker,
                                                 source,
+                                                start_ms,
+                                                end_ms,
+                                                confidence: Some(alt.confidence),
                                             }).await;
                                             last_interim.clear();
+                                            debouncer.reset(channel_slot);
                                         } else if transcript_text != *last_interim {
-                                            eprintln!("Deepgram [interim] {} (ch={:?}): {}",
-                                                source_label,
-                                                response.channel_index,
-                                                transcript_text
-                                            );
-                                            let _ = transcript_sender.send(TranscriptMessage {
+                                            *last_interim = transcript_text.to_string();
+                                            let interim = TranscriptMessage {
                                                 text: transcript_text.to_string(),
                                                 is_final: false,
                                                 speaker,
                                                 source,
-                                            }).await;
-                                            *last_interim = transcript_text.to_string();
+                                                start_ms,
+                                                end_ms,
+                                                confidence: Some(alt.confidence),
+                                            };
+                                            // A held-back interim is sent when its interval is up
+                                            let Some(interim) = debouncer.offer(channel_slot, interim, Instant::now(), interval) else {
+                                                continue;
+                                            };
+                                            tracing::info!("Deepgram [interim] {} (ch={:?}): {}",
+                                                source_label,
+                                                response.channel_index,
+                                                transcript_text
+                                            );
+                                            let _ = transcript_sender.send(interim).await;
                                         }
                                     }
                                 }
                             }
                             Err(e) => {
                                 if !text.contains("Metadata") && !text.contains("SpeechStarted") {
-                                    eprintln!("Parse warning: {}", e);
+                                    tracing::info!("Parse warning: {}", e);
                                 }
                             }
                         }
                     }
                     Some(Ok(Message::Close(_))) => {
-                        eprintln!("WebSocket closed by server");
+                        tracing::info!("WebSocket closed by server");
                         break;
                     }
                     Some(Ok(_)) => {}
                     Some(Err(e)) => {
-                        eprintln!("WebSocket error: {}", e);
+                        tracing::warn!("WebSocket error: {}", e);
                         break;
                     }
                     None => {
-                        eprintln!("WebSocket closed");
+                        tracing::info!("WebSocket closed");
                         break;
                     }
                 }
             }
-            eprintln!("Transcript receiver task ended");
+            tracing::info!("Transcript receiver task ended");
         });
 
-        eprintln!("Deepgram transcriber completed normally");
+        tracing::info!("Deepgram transcriber completed normally");
         Ok(())
     }
 
@@ -542,12 +793,80 @@ mod tests {
             is_final: true,
             speaker: Some(0),
             source: AudioSource::Microphone,
+            start_ms: Some(0),
+            end_ms: Some(1200),
+            confidence: Some(0.98),
         };
         assert!(msg.is_final);
         assert_eq!(msg.text, "Hello world");
         assert_eq!(msg.source, AudioSource::Microphone);
     }
 
+    fn word(speaker: u32, start: f64, end: f64) -> Word {
+        Word {
+            word: "word".to_string(),
+            speaker: Some(speaker),
+            start: Some(start),
+            end: Some(end),
+        }
+    }
+
+    #[test]
+    fn test_calibration_picks_loudest_speaker() {
+        // 0-2s quiet (speaker playback), 2-4s loud (the user at the mic)
+        let levels: Vec<f32> = (0..40).map(|i| if i < 20 { 0.02 } else { 0.3 }).collect();
+        let mut calibration = SpeakerCalibration::default();
+
+        calibration.observe(&[word(0, 0.0, 1.5), word(1, 2.0, 3.5)], &levels);
+        assert_eq!(calibration.primary_speaker(), Some(1));
+        assert_eq!(calibration.detected, None);
+
+        // Speech past the calibration window locks the result in
+        calibration.observe(&[word(0, 20.0, 20.5)], &levels);
+        assert_eq!(calibration.detected, Some(1));
+    }
+
+    #[test]
+    fn test_interim_debounce_per_channel() {
+        let mut debouncer = InterimDebouncer::default();
+        let interval = Duration::from_millis(250);
+        let start = Instant::now();
+        let at = |ms| start + Duration::from_millis(ms);
+        let interim = |text: &str| TranscriptMessage {
+            text: text.to_string(),
+            is_final: false,
+            speaker: None,
+            source: AudioSource::Microphone,
+            start_ms: None,
+            end_ms: None,
+            confidence: None,
+        };
+
+        assert!(debouncer.offer(0, interim("a"), start, interval).is_some());
+        assert!(debouncer.offer(0, interim("a b"), at(100), interval).is_none());
+        assert!(debouncer.offer(0, interim("a b c"), at(150), interval).is_none());
+        // The other channel has its own window
+        assert!(debouncer.offer(1, interim("x"), at(100), interval).is_some());
+
+        // The latest held-back interim is sent once the interval is up
+        assert_eq!(debouncer.next_due(interval), Some(at(250)));
+        assert!(debouncer.take_due(at(200), interval).is_empty());
+        let due = debouncer.take_due(at(250), interval);
+        assert_eq!(due.iter().map(|m| m.text.as_str()).collect::<Vec<_>>(), ["a b c"]);
+        assert_eq!(debouncer.next_due(interval), None);
+
+        // A final resets the channel and drops what was held back
+        assert!(debouncer.offer(0, interim("d"), at(300), interval).is_none());
+        debouncer.reset(0);
+        assert_eq!(debouncer.next_due(interval), None);
+        assert!(debouncer.offer(0, interim("e"), at(310), interval).is_some());
+    }
+
+    #[test]
+    fn test_calibration_defaults_to_speaker_zero() {
+        assert_eq!(SpeakerCalibration::default().primary_speaker(), Some(0));
+    }
+
     #[test]
     fn test_transcript_message_interim() {
         let msg = TranscriptMessage {
@@ -555,6 +874,9 @@ mod tests {
             is_final: false,
             speaker: Some(1),
             source: AudioSource::SystemAudio,
+            start_ms: None,
+            end_ms: None,
+            confidence: None,
         };
         assert!(!msg.is_final);
         assert_eq!(msg.text, "Hello...");
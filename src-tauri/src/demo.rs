@@ -0,0 +1,132 @@
+//! Offline demo mode: a scripted meeting and canned AI output
+//!
+//! Lets new users try the whole flow (live transcript, summary, replies, chapters)
+//! before they have any API keys. Nothing here touches the network.
+
+use crate::{GenerationKind, TranscriptSegment};
+
+/// The scripted meeting: (seconds from start, speaker, text)
+const SCRIPT: &[(u64, &str, &str)] = &[
+    (0, "You", "Thanks for joining, everyone. Let's go over the launch plan for the new onboarding flow."),
+    (6, "Participant", "Sure. Design is done, and engineering thinks the first version can ship in two weeks."),
+    (14, "You", "Two weeks works. What's still open on the engineering side?"),
+    (20, "Participant", "Mostly the email verification step. We haven't decided whether it blocks sign-up or not."),
+    (29, "You", "Let's not block sign-up. Users can verify later from the banner."),
+    (36, "Participant", "Agreed. That also makes the mobile flow simpler."),
+    (42, "You", "Moving on to metrics. How will we know the new flow is working?"),
+    (49, "Participant", "We'll track completion rate and time to first meeting recorded. Priya can set up the dashboard."),
+    (58, "You", "Great. Priya owns the dashboard, and I'll write the announcement for the changelog."),
+    (66, "Participant", "One concern: support needs the new screenshots before launch, or the help articles will be out of date."),
+    (75, "You", "Good catch. Let's get screenshots to support by next Wednesday."),
+    (82, "Participant", "Sounds good. I'll send them once the final build is on staging."),
+];
+
+/// Chapters matching the script's timestamps
+const CHAPTERS: &str = r#"[
+  {"title": "Onboarding launch plan", "start_timestamp": "00:00:00"},
+  {"title": "Email verification", "start_timestamp": "00:00:20"},
+  {"title": "Success metrics", "start_timestamp": "00:00:42"},
+  {"title": "Support readiness", "start_timestamp": "00:01:06"}
+]"#;
+
+const STRUCTURED_SUMMARY: &str = r#"{
+  "key_points": [
+    "The new onboarding flow is designed and can ship in about two weeks.",
+    "Email verification will not block sign-up; users verify later from a banner.",
+    "Success will be measured by completion rate and time to first recorded meeting."
+  ],
+  "action_items": [
+    "Priya: set up the onboarding metrics dashboard.",
+    "You: write the changelog announcement.",
+    "Participant: send updated screenshots to support by next Wednesday."
+  ],
+  "decisions": [
+    "Ship the first version of the new onboarding flow in two weeks.",
+    "Email verification does not block sign-up."
+  ],
+  "notes": [
+    "Help articles need the new screenshots before launch."
+  ]
+}"#;
+
+const DECISIONS: &str = r#"["Ship the first version of the new onboarding flow in two weeks", "Email verification does not block sign-up"]"#;
+
+const TEXT_SUMMARY: &str = "## KEY POINTS
+• The new onboarding flow is designed and can ship in about two weeks.
+• Email verification will not block sign-up; users verify later from a banner.
+• Success will be measured by completion rate and time to first recorded meeting.
+
+## ACTION ITEMS
+• Priya: set up the onboarding metrics dashboard.
+• You: write the changelog announcement.
+• Participant: send updated screenshots to support by next Wednesday.
+
+## DECISIONS
+• Ship the first version of the new onboarding flow in two weeks.
+• Email verification does not block sign-up.
+
+## NOTES
+• Help articles need the new screenshots before launch.";
+
+const LIVE_SUMMARY: &str = "• Reviewing the launch plan for the new onboarding flow
+• Email verification won't block sign-up
+• Metrics: completion rate and time to first meeting";
+
+const REPLIES: &str = "★ PROBE: What could push the launch past two weeks?
+INSIGHT: Support readiness is the hidden dependency
+MIRROR: Out of date help articles?
+CLARIFY: Who reviews the dashboard before launch?
+REFRAME: Could support preview the staging build?
+LABEL: Sounds like everyone is aligned on scope";
+
+/// The scripted transcript, with offsets from the start of the demo as timestamps
+pub fn script() -> Vec<TranscriptSegment> {
+    SCRIPT
+        .iter()
+        .map(|(secs, speaker, text)| TranscriptSegment {
+            timestamp: format!("{:02}:{:02}:{:02}", secs / 3600, (secs / 60) % 60, secs % 60),
+            speaker: speaker.to_string(),
+            text: text.to_string(),
+            is_final: true,
+            start_ms: Some(secs * 1000),
+            ..Default::default()
+        })
+        .collect()
+}
+
+/// Canned LLM output in the shape the caller parses
+pub fn respond(kind: GenerationKind) -> String {
+    let response = match kind {
+        GenerationKind::Text => TEXT_SUMMARY,
+        GenerationKind::StructuredSummary => STRUCTURED_SUMMARY,
+        GenerationKind::Decisions => DECISIONS,
+        GenerationKind::Chapters => CHAPTERS,
+        GenerationKind::Replies => REPLIES,
+        GenerationKind::LiveSummary => LIVE_SUMMARY,
+    };
+    response.to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_canned_responses_match_prompt_shape() {
+        let segments = script();
+        assert_eq!(segments.len(), SCRIPT.len());
+        assert_eq!(segments[0].timestamp, "00:00:00");
+        assert_eq!(segments[9].timestamp, "00:01:06");
+
+        let summary: serde_json::Value = serde_json::from_str(&respond(GenerationKind::StructuredSummary)).unwrap();
+        assert_eq!(summary["decisions"].as_array().unwrap().len(), 2);
+
+        let chapters: Vec<crate::database::Chapter> = serde_json::from_str(&respond(GenerationKind::Chapters)).unwrap();
+        assert!(chapters.iter().all(|c| segments.iter().any(|s| s.timestamp == c.start_timestamp)));
+
+        let decisions: Vec<String> = serde_json::from_str(&respond(GenerationKind::Decisions)).unwrap();
+        assert_eq!(decisions.len(), 2);
+
+        assert!(respond(GenerationKind::Text).starts_with("## KEY POINTS"));
+    }
+}
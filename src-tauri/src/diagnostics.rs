@@ -0,0 +1,94 @@
+//! Environment report for support requests
+//!
+//! Collects everything we usually have to ask users about when triaging an issue
+//! (platform, audio devices, provider keys, folders) into one copyable struct.
+
+use cpal::traits::{DeviceTrait, HostTrait};
+use serde::Serialize;
+use std::path::Path;
+
+use crate::{assemblyai, audio, deepgram, groq, ollama, openai, screen_share, system_audio};
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ProviderStatus {
+    pub key_present: bool,
+    /// None when there's no key to check with
+    pub reachable: Option<bool>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Diagnostics {
+    pub app_version: String,
+    pub os: String,
+    pub arch: String,
+    pub platform_info: String,
+    pub audio_input_device: Option<String>,
+    pub system_audio_device: Option<String>,
+    pub screen_share_exclusion_supported: bool,
+    pub groq: ProviderStatus,
+    pub assemblyai: ProviderStatus,
+    pub deepgram: ProviderStatus,
+    pub openai: ProviderStatus,
+    pub ollama_reachable: bool,
+    pub recordings_folder: Option<String>,
+    pub recordings_folder_writable: bool,
+    pub config_dir: Option<String>,
+}
+
+/// Whether we can create files in a folder
+fn is_writable(folder: &Path) -> bool {
+    let probe = folder.join(".vantage-write-test");
+    let writable = std::fs::write(&probe, b"ok").is_ok();
+    let _ = std::fs::remove_file(&probe);
+    writable
+}
+
+async fn provider_status<F>(api_key: &str, check: F) -> ProviderStatus
+where
+    F: std::future::Future<Output = anyhow::Result<bool>>,
+{
+    if api_key.is_empty() {
+        return ProviderStatus { key_present: false, reachable: None };
+    }
+    ProviderStatus {
+        key_present: true,
+        reachable: Some(check.await.unwrap_or(false)),
+    }
+}
+
+/// Run all checks. Network checks run concurrently and each time out on their own.
+pub async fn run(groq_key: &str, assemblyai_key: &str, deepgram_key: &str, openai_key: &str) -> Diagnostics {
+    let audio_input_device = cpal::default_host()
+        .default_input_device()
+        .and_then(|d| d.name().ok());
+    let system_audio_device = system_audio::get_system_audio_device().and_then(|d| d.name().ok());
+
+    let recordings_folder = audio::get_recordings_folder().ok();
+    let recordings_folder_writable = recordings_folder.as_deref().is_some_and(is_writable);
+
+    let (groq, assemblyai, deepgram, openai, ollama_reachable) = tokio::join!(
+        provider_status(groq_key, async { groq::list_models(groq_key).await.map(|_| true) }),
+        provider_status(assemblyai_key, assemblyai::check_api_key(assemblyai_key)),
+        provider_status(deepgram_key, deepgram::check_api_key(deepgram_key)),
+        provider_status(openai_key, openai::check_api_key(openai_key)),
+        async { ollama::check_connection().await.unwrap_or(false) },
+    );
+
+    Diagnostics {
+        app_version: env!("CARGO_PKG_VERSION").to_string(),
+        os: std::env::consts::OS.to_string(),
+        arch: std::env::consts::ARCH.to_string(),
+        platform_info: screen_share::get_platform_info(),
+        audio_input_device,
+        system_audio_device,
+        screen_share_exclusion_supported: screen_share::is_supported(),
+        groq,
+        assemblyai,
+        deepgram,
+        openai,
+        ollama_reachable,
+        recordings_folder: recordings_folder.map(|p| p.display().to_string()),
+        recordings_folder_writable,
+        config_dir: dirs::config_dir().map(|p| p.join("vantage").display().to_string()),
+    }
+}
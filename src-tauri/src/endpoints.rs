@@ -0,0 +1,119 @@
+//! Overridable API base URLs for corporate proxies and API-compatible gateways
+//!
+//! Provider modules read their base URL from here instead of a hardcoded constant.
+//! An empty override means the provider's public default.
+
+use serde::{Deserialize, Serialize};
+use std::sync::RwLock;
+
+pub const DEFAULT_GROQ: &str = "https://api.groq.com/openai/v1";
+pub const DEFAULT_OPENAI: &str = "https://api.openai.com/v1";
+pub const DEFAULT_ASSEMBLYAI: &str = "https://api.assemblyai.com/v2";
+pub const DEFAULT_OLLAMA: &str = "http://localhost:11434";
+pub const DEFAULT_DEEPGRAM: &str = "wss://api.deepgram.com/v1";
+
+/// Base URL overrides, persisted in settings (empty = default)
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct EndpointSettings {
+    #[serde(default)]
+    pub groq: String,
+    #[serde(default)]
+    pub openai: String,
+    #[serde(default)]
+    pub assemblyai: String,
+    #[serde(default)]
+    pub ollama: String,
+    /// WebSocket base for streaming (ws:// or wss://)
+    #[serde(default)]
+    pub deepgram: String,
+}
+
+static OVERRIDES: RwLock<EndpointSettings> = RwLock::new(EndpointSettings {
+    groq: String::new(),
+    openai: String::new(),
+    assemblyai: String::new(),
+    ollama: String::new(),
+    deepgram: String::new(),
+});
+
+/// Apply overrides (at startup and whenever they change)
+pub fn configure(endpoints: &EndpointSettings) {
+    if let Ok(mut overrides) = OVERRIDES.write() {
+        *overrides = endpoints.clone();
+    }
+}
+
+/// Check an override URL for `service` and normalize it (trimmed, no trailing slash).
+/// Empty input is valid and means "use the default".
+pub fn validate(service: &str, url: &str) -> Result<String, String> {
+    let url = url.trim().trim_end_matches('/');
+    if url.is_empty() {
+        return Ok(String::new());
+    }
+
+    let schemes: &[&str] = if service == "deepgram" { &["ws", "wss"] } else { &["http", "https"] };
+    let parsed = url::Url::parse(url).map_err(|e| format!("Invalid URL '{}': {}", url, e))?;
+    if !schemes.contains(&parsed.scheme()) || parsed.host_str().is_none() {
+        return Err(format!("{} URL must start with {}://", service, schemes.join(":// or ")));
+    }
+    Ok(url.to_string())
+}
+
+fn resolve(pick: impl Fn(&EndpointSettings) -> &String, default: &str) -> String {
+    OVERRIDES
+        .read()
+        .ok()
+        .map(|o| pick(&o).clone())
+        .filter(|url| !url.is_empty())
+        .unwrap_or_else(|| default.to_string())
+}
+
+pub fn groq() -> String {
+    resolve(|o| &o.groq, DEFAULT_GROQ)
+}
+
+pub fn openai() -> String {
+    resolve(|o| &o.openai, DEFAULT_OPENAI)
+}
+
+pub fn assemblyai() -> String {
+    resolve(|o| &o.assemblyai, DEFAULT_ASSEMBLYAI)
+}
+
+pub fn ollama() -> String {
+    resolve(|o| &o.ollama, DEFAULT_OLLAMA)
+}
+
+/// Deepgram streaming base (ws:// or wss://)
+pub fn deepgram() -> String {
+    resolve(|o| &o.deepgram, DEFAULT_DEEPGRAM)
+}
+
+/// Deepgram REST base, derived from the streaming base
+pub fn deepgram_http() -> String {
+    let base = deepgram();
+    if let Some(rest) = base.strip_prefix("wss://") {
+        format!("https://{}", rest)
+    } else if let Some(rest) = base.strip_prefix("ws://") {
+        format!("http://{}", rest)
+    } else {
+        base
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_endpoint_urls() {
+        assert_eq!(validate("groq", "  ").unwrap(), "");
+        assert_eq!(
+            validate("groq", "https://llm-gateway.corp.example/openai/v1/").unwrap(),
+            "https://llm-gateway.corp.example/openai/v1"
+        );
+        assert_eq!(validate("deepgram", "ws://10.0.0.5:8080/v1").unwrap(), "ws://10.0.0.5:8080/v1");
+        assert!(validate("deepgram", "https://api.deepgram.com/v1").is_err());
+        assert!(validate("ollama", "localhost:11434").is_err());
+    }
+}
@@ -0,0 +1,181 @@
+//! Error type returned by commands
+//!
+//! Serialized as `{ "code": "...", "message": "..." }` so the frontend can branch on the
+//! kind of failure (e.g. point to Settings for a missing key) instead of parsing text.
+//! Commands construct the code where they know it (missing keys, recorder state), and
+//! provider clients report error statuses as `ProviderError`. Internal helpers keep
+//! returning `Result<_, String>`; for those, `?` falls back to classifying the message.
+
+use serde::Serialize;
+use std::fmt;
+
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorCode {
+    /// Something has to be set up first: an API key, credentials, a calendar connection, a model
+    SetupRequired,
+    /// The provider rejected the API key or token
+    Unauthorized,
+    /// The provider is throttling requests
+    RateLimited,
+    /// The request never got a response (offline, DNS, timeout, service unreachable)
+    Network,
+    /// No microphone or other input device
+    NoAudioDevice,
+    NotFound,
+    /// Conflicts with what's running (already recording, not recording, ...)
+    InvalidState,
+    /// Nothing to work on yet (no transcript, no summary, no speech)
+    Empty,
+    InvalidInput,
+    Internal,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct AppError {
+    pub code: ErrorCode,
+    pub message: String,
+}
+
+impl AppError {
+    pub fn new(code: ErrorCode, message: impl Into<String>) -> Self {
+        Self { code, message: message.into() }
+    }
+}
+
+/// A provider answered with an error status. Provider clients return it inside their
+/// `anyhow::Error`, so a rejected key or throttling keeps its code on the way to the UI.
+#[derive(Debug)]
+pub struct ProviderError {
+    pub status: reqwest::StatusCode,
+    pub message: String,
+}
+
+impl ProviderError {
+    pub fn new(status: reqwest::StatusCode, message: impl Into<String>) -> Self {
+        Self { status, message: message.into() }
+    }
+
+    fn code(&self) -> Option<ErrorCode> {
+        match self.status {
+            reqwest::StatusCode::UNAUTHORIZED | reqwest::StatusCode::FORBIDDEN => Some(ErrorCode::Unauthorized),
+            reqwest::StatusCode::TOO_MANY_REQUESTS => Some(ErrorCode::RateLimited),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for ProviderError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl std::error::Error for ProviderError {}
+
+/// Fallback code for a message that came without one
+fn classify(message: &str) -> ErrorCode {
+    let m = message.to_lowercase();
+    let has = |needles: &[&str]| needles.iter().any(|n| m.contains(n));
+
+    if has(&["api key not set", "credentials not set", "not connected", "in settings first"])
+        || (m.contains("api key") && has(&["set your", "needs an"]))
+    {
+        ErrorCode::SetupRequired
+    } else if has(&["unauthorized", "forbidden", "invalid api key", "invalid_api_key"]) {
+        ErrorCode::Unauthorized
+    } else if has(&["rate limit", "too many requests"]) {
+        ErrorCode::RateLimited
+    } else if has(&["error sending request", "connection", "timed out", "timeout", "dns", "not reachable", "network"]) {
+        ErrorCode::Network
+    } else if has(&["already", "stop the current recording", "still in progress", "not recording", "no active recorder"]) {
+        ErrorCode::InvalidState
+    } else if has(&["no input device", "no output device"]) {
+        ErrorCode::NoAudioDevice
+    } else if m.contains("not found") {
+        ErrorCode::NotFound
+    } else if m.starts_with("no ") || m.starts_with("nothing ") {
+        ErrorCode::Empty
+    } else if has(&["invalid", "must be", "unknown", "cannot be empty", "out of range", "expected"]) {
+        ErrorCode::InvalidInput
+    } else {
+        ErrorCode::Internal
+    }
+}
+
+impl From<String> for AppError {
+    fn from(message: String) -> Self {
+        Self { code: classify(&message), message }
+    }
+}
+
+impl From<anyhow::Error> for AppError {
+    fn from(error: anyhow::Error) -> Self {
+        let message = error.to_string();
+        if let Some(code) = error.downcast_ref::<ProviderError>().and_then(ProviderError::code) {
+            return Self::new(code, message);
+        }
+        if error.downcast_ref::<reqwest::Error>().is_some_and(|e| e.is_connect() || e.is_timeout()) {
+            return Self::new(ErrorCode::Network, message);
+        }
+        message.into()
+    }
+}
+
+impl From<&str> for AppError {
+    fn from(message: &str) -> Self {
+        message.to_string().into()
+    }
+}
+
+/// Lets helpers that still return `Result<_, String>` call commands with `?`
+impl From<AppError> for String {
+    fn from(error: AppError) -> Self {
+        error.message
+    }
+}
+
+impl fmt::Display for AppError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl std::error::Error for AppError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_classify_common_messages() {
+        let code = |message: &str| AppError::from(message).code;
+        assert_eq!(code("Groq API key not set. Please add it in Settings."), ErrorCode::SetupRequired);
+        assert_eq!(code("Please set your AssemblyAI API key in Settings"), ErrorCode::SetupRequired);
+        assert_eq!(code("Groq API error (401 Unauthorized): Invalid API Key"), ErrorCode::Unauthorized);
+        assert_eq!(code("Groq API error (429 Too Many Requests): slow down"), ErrorCode::RateLimited);
+        assert_eq!(code("Rate limit exceeded after 3 retries"), ErrorCode::RateLimited);
+        assert_eq!(code("error sending request for url (https://api.groq.com)"), ErrorCode::Network);
+        assert_eq!(code("Already recording"), ErrorCode::InvalidState);
+        assert_eq!(code("No input device available"), ErrorCode::NoAudioDevice);
+        assert_eq!(code("Meeting not found: abc"), ErrorCode::NotFound);
+        assert_eq!(code("No transcription to summarize"), ErrorCode::Empty);
+        assert_eq!(code("Segment index out of range: 9"), ErrorCode::InvalidInput);
+        assert_eq!(code("Failed to write meetings CSV: disk full"), ErrorCode::Internal);
+    }
+
+    #[test]
+    fn test_provider_status_sets_code() {
+        let error = |status| AppError::from(anyhow::Error::from(ProviderError::new(status, "Groq API error: details")));
+        assert_eq!(error(reqwest::StatusCode::UNAUTHORIZED).code, ErrorCode::Unauthorized);
+        assert_eq!(error(reqwest::StatusCode::TOO_MANY_REQUESTS).code, ErrorCode::RateLimited);
+        assert_eq!(error(reqwest::StatusCode::BAD_REQUEST).code, ErrorCode::Internal);
+        assert_eq!(error(reqwest::StatusCode::UNAUTHORIZED).message, "Groq API error: details");
+    }
+
+    #[test]
+    fn test_serializes_code_and_message() {
+        let json = serde_json::to_value(AppError::from("Meeting not found: abc")).unwrap();
+        assert_eq!(json, serde_json::json!({ "code": "not_found", "message": "Meeting not found: abc" }));
+    }
+}
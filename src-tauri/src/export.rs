@@ -0,0 +1,416 @@
+//! Transcript export formats (subtitles, per-speaker sections, meeting JSON, meeting list CSV)
+
+use serde::{Deserialize, Serialize};
+
+use crate::analytics::{estimate_speech_seconds, talk_time_stats};
+use crate::database::StoredMeeting;
+use crate::TranscriptSegment;
+
+/// Version of the meeting JSON export format.
+/// Bump when the shape of `MeetingExport` changes in a way parsers need to know about.
+pub const MEETING_EXPORT_SCHEMA_VERSION: u32 = 1;
+
+/// Shortest cue we emit, so one-word segments stay readable on screen
+const MIN_CUE_MS: u64 = 1000;
+
+/// Supported subtitle formats
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SubtitleFormat {
+    Srt,
+    WebVtt,
+}
+
+impl SubtitleFormat {
+    pub fn parse(format: &str) -> Result<Self, String> {
+        match format.to_lowercase().as_str() {
+            "srt" => Ok(SubtitleFormat::Srt),
+            "vtt" | "webvtt" => Ok(SubtitleFormat::WebVtt),
+            _ => Err(format!("Unknown subtitle format: {} (expected srt or vtt)", format)),
+        }
+    }
+}
+
+/// Parse a wall-clock "HH:MM:SS" timestamp into seconds since midnight
+fn parse_clock_seconds(timestamp: &str) -> Option<u64> {
+    let parts: Vec<u64> = timestamp
+        .split(':')
+        .map(|p| p.trim().parse::<u64>().ok())
+        .collect::<Option<Vec<_>>>()?;
+
+    match parts.as_slice() {
+        [h, m, s] => Some(h * 3600 + m * 60 + s),
+        [m, s] => Some(m * 60 + s),
+        _ => None,
+    }
+}
+
+/// Compute (start_ms, end_ms) for each segment relative to the start of the meeting.
+///
+/// Segments with word timestamps use them directly. Otherwise the start comes from the
+/// wall-clock timestamp and the duration is estimated from the word count, clipped so it
+/// doesn't run into the next segment.
+pub fn segment_timings(segments: &[TranscriptSegment]) -> Vec<(u64, u64)> {
+    let first_clock = segments.iter().find_map(|s| parse_clock_seconds(&s.timestamp));
+
+    let mut timings: Vec<(u64, u64, bool)> = segments
+        .iter()
+        .map(|s| {
+            if let (Some(start), Some(end)) = (s.start_ms, s.end_ms) {
+                if end > start {
+                    return (start, end, false);
+                }
+            }
+
+            let start = match (parse_clock_seconds(&s.timestamp), first_clock) {
+                // Handle meetings that run past midnight
+                (Some(clock), Some(first)) if clock >= first => (clock - first) * 1000,
+                (Some(clock), Some(first)) => (clock + 24 * 3600 - first) * 1000,
+                _ => 0,
+            };
+            let words = s.text.split_whitespace().count();
+            let duration = ((estimate_speech_seconds(words) * 1000.0) as u64).max(MIN_CUE_MS);
+            (start, start + duration, true)
+        })
+        .collect();
+
+    // Keep estimated cues from overlapping the cue that follows
+    for i in 0..timings.len().saturating_sub(1) {
+        let next_start = timings[i + 1].0;
+        let (start, end, estimated) = timings[i];
+        if estimated && next_start > start && end > next_start {
+            timings[i].1 = next_start;
+        }
+    }
+
+    timings.into_iter().map(|(start, end, _)| (start, end)).collect()
+}
+
+/// Format milliseconds as a subtitle timestamp (HH:MM:SS,mmm or HH:MM:SS.mmm)
+fn format_cue_time(ms: u64, separator: char) -> String {
+    let hours = ms / 3_600_000;
+    let minutes = (ms / 60_000) % 60;
+    let seconds = (ms / 1000) % 60;
+    format!("{:02}:{:02}:{:02}{}{:03}", hours, minutes, seconds, separator, ms % 1000)
+}
+
+/// Render transcript segments as SRT or WebVTT subtitles
+pub fn to_subtitles(segments: &[TranscriptSegment], format: SubtitleFormat) -> String {
+    let timings = segment_timings(segments);
+    let mut output = String::new();
+
+    if format == SubtitleFormat::WebVtt {
+        output.push_str("WEBVTT\n\n");
+    }
+
+    let cues = segments
+        .iter()
+        .zip(timings)
+        .filter(|(s, _)| !s.text.trim().is_empty());
+
+    for (index, (segment, (start, end))) in cues.enumerate() {
+        match format {
+            SubtitleFormat::Srt => {
+                output.push_str(&format!(
+                    "{}\n{} --> {}\n{}: {}\n\n",
+                    index + 1,
+                    format_cue_time(start, ','),
+                    format_cue_time(end, ','),
+                    segment.speaker,
+                    segment.text.trim()
+                ));
+            }
+            SubtitleFormat::WebVtt => {
+                output.push_str(&format!(
+                    "{} --> {}\n<v {}>{}\n\n",
+                    format_cue_time(start, '.'),
+                    format_cue_time(end, '.'),
+                    segment.speaker,
+                    segment.text.trim()
+                ));
+            }
+        }
+    }
+
+    output
+}
+
+/// Labels assigned by audio source rather than by diarization or the user
+const PLACEHOLDER_SPEAKERS: &[&str] = &["You", "Participant"];
+
+/// Format a talk-time duration as "1h 02m 03s", "4m 05s" or "12s"
+fn format_talk_time(seconds: f64) -> String {
+    let total = seconds.round() as u64;
+    let (hours, minutes, seconds) = (total / 3600, (total / 60) % 60, total % 60);
+    if hours > 0 {
+        format!("{}h {:02}m {:02}s", hours, minutes, seconds)
+    } else if minutes > 0 {
+        format!("{}m {:02}s", minutes, seconds)
+    } else {
+        format!("{}s", seconds)
+    }
+}
+
+/// Regroup a transcript into one Markdown section per speaker, most talk-time first,
+/// with a talk-time table at the top. Needs real speaker labels: a transcript labelled
+/// only "You"/"Participant" is rejected since "Participant" lumps everyone else together.
+pub fn to_speaker_sections(segments: &[TranscriptSegment]) -> Result<String, String> {
+    let spoken: Vec<&TranscriptSegment> = segments
+        .iter()
+        .filter(|s| !s.text.trim().is_empty() && !s.speaker.trim().is_empty())
+        .collect();
+
+    if !spoken.iter().any(|s| !PLACEHOLDER_SPEAKERS.contains(&s.speaker.as_str())) {
+        return Err("No speaker labels to group by. Transcribe with speaker labels or rename speakers first".to_string());
+    }
+
+    let stats = talk_time_stats(segments);
+    let mut speakers: Vec<(&String, _)> = stats.iter().filter(|(speaker, _)| !speaker.trim().is_empty()).collect();
+    speakers.sort_by(|a, b| b.1.seconds.total_cmp(&a.1.seconds).then_with(|| a.0.cmp(b.0)));
+
+    let mut output = String::from("# Transcript by speaker\n\n## Talk time\n\n");
+    for (speaker, talk) in &speakers {
+        output.push_str(&format!(
+            "- {}: {} ({:.0}%, {} words)\n",
+            speaker,
+            format_talk_time(talk.seconds),
+            talk.percent,
+            talk.words
+        ));
+    }
+
+    for (speaker, _) in &speakers {
+        output.push_str(&format!("\n## Everything {} said\n\n", speaker));
+        for segment in spoken.iter().filter(|s| &&s.speaker == speaker) {
+            if segment.timestamp.is_empty() {
+                output.push_str(&format!("{}\n", segment.text.trim()));
+            } else {
+                output.push_str(&format!("[{}] {}\n", segment.timestamp, segment.text.trim()));
+            }
+        }
+    }
+
+    Ok(output)
+}
+
+/// Stored meeting wrapped with a schema version for programmatic use
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MeetingExport {
+    pub schema_version: u32,
+    pub meeting: StoredMeeting,
+}
+
+/// Serialize a stored meeting to pretty JSON
+pub fn meeting_to_json(meeting: &StoredMeeting) -> Result<String, String> {
+    let export = MeetingExport {
+        schema_version: MEETING_EXPORT_SCHEMA_VERSION,
+        meeting: meeting.clone(),
+    };
+    serde_json::to_string_pretty(&export).map_err(|e| format!("Failed to serialize meeting: {}", e))
+}
+
+/// Parse a meeting JSON export, rejecting versions newer than we understand
+pub fn meeting_from_json(content: &str) -> Result<StoredMeeting, String> {
+    let value: serde_json::Value = serde_json::from_str(content)
+        .map_err(|e| format!("Failed to parse meeting export: {}", e))?;
+
+    // Check the version before the shape, so newer exports get a clear error
+    let schema_version = value
+        .get("schema_version")
+        .and_then(|v| v.as_u64())
+        .ok_or_else(|| "Meeting export is missing schema_version".to_string())?;
+    if schema_version > MEETING_EXPORT_SCHEMA_VERSION as u64 {
+        return Err(format!(
+            "Meeting export schema version {} is newer than supported version {}",
+            schema_version, MEETING_EXPORT_SCHEMA_VERSION
+        ));
+    }
+
+    let export: MeetingExport = serde_json::from_value(value)
+        .map_err(|e| format!("Failed to parse meeting export: {}", e))?;
+    Ok(export.meeting)
+}
+
+/// One CSV row per meeting (date, title, duration, attendees, action items, tags),
+/// for reporting in a spreadsheet
+pub fn meetings_to_csv(meetings: &[&StoredMeeting]) -> Result<String, String> {
+    let mut writer = csv::Writer::from_writer(Vec::new());
+    writer
+        .write_record(["date", "title", "duration_minutes", "attendees", "action_items", "tags"])
+        .map_err(|e| format!("Failed to write CSV: {}", e))?;
+
+    for meeting in meetings {
+        let date = meeting.local_date().map(|d| d.to_string()).unwrap_or_else(|| meeting.date.clone());
+        let duration = meeting
+            .duration_seconds
+            .map(|secs| ((secs + 30) / 60).to_string())
+            .unwrap_or_default();
+        let action_items = meeting.summary.as_ref().map_or(0, |s| s.action_items.len());
+
+        writer
+            .write_record([
+                date,
+                meeting.title.clone(),
+                duration,
+                meeting.attendees.join("; "),
+                action_items.to_string(),
+                meeting.tags.join("; "),
+            ])
+            .map_err(|e| format!("Failed to write CSV: {}", e))?;
+    }
+
+    let bytes = writer.into_inner().map_err(|e| format!("Failed to write CSV: {}", e))?;
+    String::from_utf8(bytes).map_err(|e| e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn segment(timestamp: &str, speaker: &str, text: &str) -> TranscriptSegment {
+        TranscriptSegment {
+            timestamp: timestamp.to_string(),
+            speaker: speaker.to_string(),
+            text: text.to_string(),
+            ..Default::default()
+        }
+    }
+
+    /// Minimal SRT parser: index line, "start --> end" line, then text until a blank line
+    fn parse_srt(srt: &str) -> Vec<(u64, u64, String)> {
+        fn parse_time(t: &str) -> u64 {
+            let (hms, ms) = t.split_once(',').expect("missing millisecond separator");
+            parse_clock_seconds(hms).expect("bad timestamp") * 1000 + ms.parse::<u64>().unwrap()
+        }
+
+        srt.trim()
+            .split("\n\n")
+            .enumerate()
+            .map(|(i, block)| {
+                let mut lines = block.lines();
+                assert_eq!(lines.next().unwrap().parse::<usize>().unwrap(), i + 1);
+                let (start, end) = lines.next().unwrap().split_once(" --> ").unwrap();
+                let text = lines.collect::<Vec<_>>().join("\n");
+                assert!(!text.is_empty());
+                (parse_time(start), parse_time(end), text)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_srt_output_parses_from_wall_clock_timestamps() {
+        let segments = vec![
+            segment("10:00:00", "You", "Let's review the roadmap for next quarter"),
+            segment("10:00:02", "Participant", "Sounds good"),
+            segment("10:00:30", "You", "Great"),
+        ];
+        let cues = parse_srt(&to_subtitles(&segments, SubtitleFormat::Srt));
+
+        assert_eq!(cues.len(), 3);
+        assert_eq!(cues[0].0, 0);
+        // Estimated duration is clipped at the next cue's start
+        assert_eq!(cues[0].1, 2000);
+        assert_eq!(cues[1].0, 2000);
+        assert_eq!(cues[2].0, 30_000);
+        assert!(cues.iter().all(|(start, end, _)| end > start));
+        assert_eq!(cues[1].2, "Participant: Sounds good");
+    }
+
+    #[test]
+    fn test_subtitles_use_word_timestamps() {
+        let mut timed = segment("10:00:00", "You", "Hello there");
+        timed.start_ms = Some(1_250);
+        timed.end_ms = Some(2_500);
+        let vtt = to_subtitles(&[timed], SubtitleFormat::WebVtt);
+
+        assert!(vtt.starts_with("WEBVTT\n\n"));
+        assert!(vtt.contains("00:00:01.250 --> 00:00:02.500\n<v You>Hello there"));
+    }
+
+    #[test]
+    fn test_speaker_sections_group_by_speaker_with_talk_time() {
+        let segments = vec![
+            segment("10:00:00", "Alice", "Let's review the roadmap for next quarter"),
+            segment("10:00:05", "Bob", "Sounds good"),
+            segment("10:00:07", "Alice", "First item is the launch"),
+        ];
+        let output = to_speaker_sections(&segments).unwrap();
+
+        let alice = output.find("## Everything Alice said").unwrap();
+        let bob = output.find("## Everything Bob said").unwrap();
+        assert!(output.find("## Talk time").unwrap() < alice);
+        assert!(alice < bob);
+        assert!(output.contains("- Alice: 5s (86%, 12 words)"));
+        assert!(output.contains("[10:00:00] Let's review the roadmap for next quarter\n[10:00:07] First item is the launch\n"));
+        assert!(output[bob..].contains("[10:00:05] Sounds good"));
+
+        let placeholders = vec![segment("10:00:00", "You", "Hi"), segment("10:00:01", "Participant", "Hello")];
+        assert!(to_speaker_sections(&placeholders).is_err());
+    }
+
+    #[test]
+    fn test_subtitle_format_parse() {
+        assert_eq!(SubtitleFormat::parse("SRT").unwrap(), SubtitleFormat::Srt);
+        assert_eq!(SubtitleFormat::parse("webvtt").unwrap(), SubtitleFormat::WebVtt);
+        assert!(SubtitleFormat::parse("ass").is_err());
+    }
+
+    #[test]
+    fn test_meeting_json_round_trip() {
+        let mut meeting = crate::database::create_meeting_from_transcript(
+            "Weekly sync".to_string(),
+            vec![segment("10:00:00", "You", "Hello")],
+            None,
+            vec!["alex@example.com".to_string()],
+            None,
+            None,
+            Some(1800),
+        );
+        meeting.notes.push(crate::database::Note {
+            timestamp: "10:05:00".to_string(),
+            text: "Follow up on pricing".to_string(),
+        });
+        meeting.bookmarks.push(crate::database::Bookmark {
+            label: "Revisit budget".to_string(),
+            timestamp: "10:06:00".to_string(),
+            elapsed_ms: 360_000,
+            segment_index: Some(0),
+        });
+        let json = meeting_to_json(&meeting).unwrap();
+        assert!(json.contains("\"schema_version\": 1"));
+
+        let imported = meeting_from_json(&json).unwrap();
+        assert_eq!(imported.id, meeting.id);
+        assert_eq!(imported.title, "Weekly sync");
+        assert_eq!(imported.duration_seconds, Some(1800));
+        assert_eq!(imported.transcript.len(), 1);
+        assert_eq!(imported.notes, meeting.notes);
+        assert_eq!(imported.bookmarks, meeting.bookmarks);
+    }
+
+    #[test]
+    fn test_meetings_csv_escapes_titles() {
+        let mut meeting = crate::database::create_meeting_from_transcript(
+            "Budget, \"Q3\" review".to_string(),
+            vec![segment("10:00:00", "You", "Hello")],
+            None,
+            vec!["alex@example.com".to_string(), "sam@example.com".to_string()],
+            None,
+            None,
+            Some(1790),
+        );
+        meeting.tags = vec!["finance".to_string()];
+
+        let csv = meetings_to_csv(&[&meeting]).unwrap();
+        let mut lines = csv.lines();
+        assert_eq!(lines.next(), Some("date,title,duration_minutes,attendees,action_items,tags"));
+        let row = lines.next().unwrap();
+        assert!(row.contains(",\"Budget, \"\"Q3\"\" review\",30,alex@example.com; sam@example.com,0,finance"));
+        assert_eq!(lines.next(), None);
+    }
+
+    #[test]
+    fn test_meeting_json_rejects_newer_schema() {
+        let json = r#"{"schema_version": 99, "meeting": {}}"#;
+        assert!(meeting_from_json(json).unwrap_err().contains("newer"));
+    }
+}
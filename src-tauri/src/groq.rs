@@ -2,15 +2,18 @@ use anyhow::{anyhow, Result};
 use serde::{Deserialize, Serialize};
 use std::path::Path;
 
-const GROQ_API_URL: &str = "https://api.groq.com/openai/v1/chat/completions";
-const GROQ_WHISPER_URL: &str = "https://api.groq.com/openai/v1/audio/transcriptions";
+use crate::endpoints;
+use crate::error::ProviderError;
+use crate::net::{http_retry, RetryPolicy, StreamedReply};
+use crate::settings::DEFAULT_ASSISTANT_PERSONA;
 
+/// OpenAI-compatible chat completion request (also used by the OpenAI provider)
 #[derive(Debug, Serialize)]
-struct ChatRequest {
-    model: String,
-    messages: Vec<ChatMessage>,
-    temperature: f32,
-    max_tokens: u32,
+pub(crate) struct ChatRequest {
+    pub model: String,
+    pub messages: Vec<ChatMessage>,
+    pub temperature: f32,
+    pub max_tokens: u32,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -20,39 +23,195 @@ pub struct ChatMessage {
 }
 
 #[derive(Debug, Deserialize)]
-struct ChatResponse {
-    choices: Vec<Choice>,
+pub(crate) struct ChatResponse {
+    pub choices: Vec<Choice>,
 }
 
 #[derive(Debug, Deserialize)]
-struct Choice {
-    message: ChatMessage,
+pub(crate) struct Choice {
+    pub message: ChatMessage,
 }
 
-/// Available Groq models (all open-source)
+/// Known-good chat model, used when the selected one has been retired
+pub const DEFAULT_MODEL: &str = "llama-3.1-8b-instant";
+
+/// Fallback Groq chat models, used when the live model list can't be fetched
 pub fn get_available_models() -> Vec<(&'static str, &'static str)> {
     vec![
-        ("llama-3.2-90b-vision-preview", "Llama 3.2 90B (Best)"),
-        ("llama-3.2-11b-vision-preview", "Llama 3.2 11B"),
-        ("llama-3.1-70b-versatile", "Llama 3.1 70B"),
+        ("llama-3.3-70b-versatile", "Llama 3.3 70B (Best)"),
         ("llama-3.1-8b-instant", "Llama 3.1 8B (Fast)"),
-        ("mixtral-8x7b-32768", "Mixtral 8x7B"),
-        ("gemma2-9b-it", "Gemma 2 9B"),
+        ("openai/gpt-oss-120b", "GPT-OSS 120B"),
+        ("openai/gpt-oss-20b", "GPT-OSS 20B"),
     ]
 }
 
-/// Generate a response using Groq API with automatic rate limit retry
+/// Model entry from Groq's `/models` endpoint
+#[derive(Debug, Deserialize, Clone)]
+pub struct GroqModel {
+    pub id: String,
+    #[serde(default)]
+    pub owned_by: Option<String>,
+    #[serde(default)]
+    pub context_window: Option<u32>,
+    #[serde(default = "default_model_active")]
+    pub active: bool,
+}
+
+fn default_model_active() -> bool {
+    true
+}
+
+#[derive(Debug, Deserialize)]
+struct ModelsResponse {
+    data: Vec<GroqModel>,
+}
+
+/// Fetch the live list of chat models from Groq.
+/// Speech models (Whisper, TTS) are filtered out since they can't be used for generation.
+pub async fn list_models(api_key: &str) -> Result<Vec<GroqModel>> {
+    if api_key.is_empty() {
+        return Err(anyhow!("Groq API key not set"));
+    }
+
+    let client = crate::net::client();
+    let response = client
+        .get(format!("{}/models", endpoints::groq()))
+        .header("Authorization", format!("Bearer {}", api_key))
+        .timeout(std::time::Duration::from_secs(10))
+        .send()
+        .await?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let error_text = response.text().await.unwrap_or_default();
+        return Err(ProviderError::new(status, format!("Groq models API error ({}): {}", status, error_text)).into());
+    }
+
+    let result: ModelsResponse = response.json().await?;
+    let mut models: Vec<GroqModel> = result
+        .data
+        .into_iter()
+        .filter(|m| {
+            let id = m.id.to_lowercase();
+            !id.contains("whisper") && !id.contains("tts")
+        })
+        .collect();
+    models.sort_by(|a, b| a.id.cmp(&b.id));
+
+    Ok(models)
+}
+
+/// Groq could not be reached (network error, timeout or 5xx).
+/// Callers can downcast to this to decide whether a fallback provider is worth trying.
+#[derive(Debug)]
+pub struct ServiceUnavailable(pub String);
+
+impl std::fmt::Display for ServiceUnavailable {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Groq unavailable: {}", self.0)
+    }
+}
+
+impl std::error::Error for ServiceUnavailable {}
+
+/// Check whether an error means Groq itself was unreachable (vs. a bad request or key)
+pub fn is_unavailable(err: &anyhow::Error) -> bool {
+    err.downcast_ref::<ServiceUnavailable>().is_some()
+}
+
+/// The requested model was decommissioned or doesn't exist (anymore)
+#[derive(Debug)]
+pub struct ModelUnavailable(pub String);
+
+impl std::fmt::Display for ModelUnavailable {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl std::error::Error for ModelUnavailable {}
+
+/// Check whether an error means the model is gone, so another model is worth trying
+pub fn is_model_unavailable(err: &anyhow::Error) -> bool {
+    err.downcast_ref::<ModelUnavailable>().is_some()
+}
+
+/// Whether an error response says the model was retired or doesn't exist
+fn is_retired_model_error(status: reqwest::StatusCode, body: &str) -> bool {
+    let body = body.to_lowercase();
+    matches!(status.as_u16(), 400 | 404)
+        && ["model_decommissioned", "decommissioned", "model_not_found", "does not exist"]
+            .iter()
+            .any(|needle| body.contains(needle))
+}
+
+/// Sampling parameters for a generation request
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub struct GenerationParams {
+    pub temperature: f32,
+    pub max_tokens: u32,
+}
+
+impl Default for GenerationParams {
+    fn default() -> Self {
+        Self {
+            temperature: 0.7,
+            max_tokens: 1024,
+        }
+    }
+}
+
+impl GenerationParams {
+    /// Defaults for structured extraction (summaries, JSON): deterministic, long output
+    pub fn extraction() -> Self {
+        Self {
+            temperature: 0.2,
+            max_tokens: 4096,
+        }
+    }
+
+    /// Defaults for reply suggestions: more varied, short output
+    pub fn replies() -> Self {
+        Self {
+            temperature: 0.7,
+            max_tokens: 512,
+        }
+    }
+
+    pub fn validate(&self) -> std::result::Result<(), String> {
+        if !(0.0..=2.0).contains(&self.temperature) {
+            return Err(format!("Temperature must be between 0 and 2 (got {})", self.temperature));
+        }
+        if self.max_tokens == 0 || self.max_tokens > 32_768 {
+            return Err(format!("max_tokens must be between 1 and 32768 (got {})", self.max_tokens));
+        }
+        Ok(())
+    }
+}
+
+/// Generate a response using Groq API with default sampling parameters and persona
 pub async fn generate(api_key: &str, model: &str, prompt: &str) -> Result<String> {
+    generate_with_params(api_key, model, DEFAULT_ASSISTANT_PERSONA, prompt, &GenerationParams::default()).await
+}
+
+/// Generate a response using Groq API with automatic rate limit retry
+pub async fn generate_with_params(
+    api_key: &str,
+    model: &str,
+    system_prompt: &str,
+    prompt: &str,
+    params: &GenerationParams,
+) -> Result<String> {
     if api_key.is_empty() {
         return Err(anyhow!("Groq API key not set. Get one free at console.groq.com"));
     }
 
-    let client = reqwest::Client::new();
+    let client = crate::net::client();
 
     let messages = vec![
         ChatMessage {
             role: "system".to_string(),
-            content: "You are a helpful meeting assistant. Be concise and professional.".to_string(),
+            content: system_prompt.to_string(),
         },
         ChatMessage {
             role: "user".to_string(),
@@ -63,62 +222,107 @@ pub async fn generate(api_key: &str, model: &str, prompt: &str) -> Result<String
     let request = ChatRequest {
         model: model.to_string(),
         messages,
-        temperature: 0.7,
-        max_tokens: 1024,
+        temperature: params.temperature,
+        max_tokens: params.max_tokens,
     };
 
-    // Retry with exponential backoff for rate limits
-    const MAX_RETRIES: u32 = 5;
-    let mut retry_delay_ms: u64 = 1000;  // Start with 1 second
-
-    for attempt in 0..MAX_RETRIES {
-        let response = client
-            .post(GROQ_API_URL)
+    // Rate limits are retried with backoff; outages fail fast so the Ollama fallback can step in
+    let response = http_retry("Groq", RetryPolicy::RATE_LIMIT, || {
+        client
+            .post(format!("{}/chat/completions", endpoints::groq()))
             .header("Authorization", format!("Bearer {}", api_key))
             .header("Content-Type", "application/json")
             .json(&request)
             .timeout(std::time::Duration::from_secs(60))
-            .send()
-            .await?;
+    })
+    .await
+    .map_err(|e| ServiceUnavailable(e.to_string()))?;
 
-        let status = response.status();
+    let status = response.status();
+    if status.is_server_error() {
+        let error_text = response.text().await.unwrap_or_default();
+        return Err(ServiceUnavailable(format!("{}: {}", status, error_text)).into());
+    }
 
-        // Handle rate limit (429) with exponential backoff
-        if status == reqwest::StatusCode::TOO_MANY_REQUESTS {
-            // Try to get retry-after header
-            let retry_after = response
-                .headers()
-                .get("retry-after")
-                .and_then(|h| h.to_str().ok())
-                .and_then(|s| s.parse::<u64>().ok())
-                .map(|s| s * 1000)  // Convert seconds to ms
-                .unwrap_or(retry_delay_ms);
-
-            let wait_time = retry_after.max(retry_delay_ms);
-            eprintln!(
-                "Rate limited (attempt {}/{}), waiting {}ms before retry...",
-                attempt + 1, MAX_RETRIES, wait_time
-            );
-
-            tokio::time::sleep(std::time::Duration::from_millis(wait_time)).await;
-            retry_delay_ms = (retry_delay_ms * 2).min(30000);  // Double up to 30s max
-            continue;
+    if !status.is_success() {
+        let error_text = response.text().await.unwrap_or_default();
+        let message = format!("Groq API error ({}): {}", status, error_text);
+        if is_retired_model_error(status, &error_text) {
+            return Err(ModelUnavailable(message).into());
         }
+        return Err(ProviderError::new(status, message).into());
+    }
 
-        if !status.is_success() {
-            let error_text = response.text().await.unwrap_or_default();
-            return Err(anyhow!("Groq API error ({}): {}", status, error_text));
-        }
+    let result: ChatResponse = response.json().await?;
+    result
+        .choices
+        .first()
+        .map(|c| c.message.content.clone())
+        .ok_or_else(|| anyhow!("No response from Groq"))
+}
+
+/// Chat request with streamed (SSE) output
+#[derive(Debug, Serialize)]
+struct StreamingChatRequest<'a> {
+    #[serde(flatten)]
+    request: &'a ChatRequest,
+    stream: bool,
+}
+
+/// Text delta of one `data:` line of a streamed chat completion (OpenAI-compatible SSE)
+pub(crate) fn sse_delta(line: &str) -> Option<String> {
+    let data = line.strip_prefix("data:")?.trim();
+    if data == "[DONE]" {
+        return None;
+    }
+    let chunk: serde_json::Value = serde_json::from_str(data).ok()?;
+    chunk["choices"][0]["delta"]["content"].as_str().map(str::to_string)
+}
+
+/// Stream one short completion from an OpenAI-compatible API at `base_url`, timing the
+/// first token. No retries, so the timing reflects a single request.
+pub(crate) async fn stream_chat_at(
+    label: &str,
+    base_url: &str,
+    api_key: &str,
+    model: &str,
+    prompt: &str,
+    max_tokens: u32,
+) -> Result<StreamedReply> {
+    let request = ChatRequest {
+        model: model.to_string(),
+        messages: vec![ChatMessage {
+            role: "user".to_string(),
+            content: prompt.to_string(),
+        }],
+        temperature: 0.0,
+        max_tokens,
+    };
 
-        let result: ChatResponse = response.json().await?;
-        return result
-            .choices
-            .first()
-            .map(|c| c.message.content.clone())
-            .ok_or_else(|| anyhow!("No response from Groq"));
+    let started = std::time::Instant::now();
+    let response = crate::net::client()
+        .post(format!("{}/chat/completions", base_url))
+        .header("Authorization", format!("Bearer {}", api_key))
+        .json(&StreamingChatRequest { request: &request, stream: true })
+        .timeout(std::time::Duration::from_secs(30))
+        .send()
+        .await?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let error_text = response.text().await.unwrap_or_default();
+        return Err(ProviderError::new(status, format!("{} API error ({}): {}", label, status, error_text)).into());
     }
 
-    Err(anyhow!("Rate limit exceeded after {} retries", MAX_RETRIES))
+    crate::net::read_streamed_text(response, started, sse_delta).await
+}
+
+/// Stream one short completion with `model`, timing the first token
+pub async fn stream_chat(api_key: &str, model: &str, prompt: &str, max_tokens: u32) -> Result<StreamedReply> {
+    if api_key.is_empty() {
+        return Err(anyhow!("Groq API key not set. Get one free at console.groq.com"));
+    }
+    stream_chat_at("Groq", &endpoints::groq(), api_key, model, prompt, max_tokens).await
 }
 
 /// Check if API key is valid
@@ -127,10 +331,10 @@ pub async fn check_api_key(api_key: &str) -> Result<bool> {
         return Ok(false);
     }
 
-    let client = reqwest::Client::new();
+    let client = crate::net::client();
 
     let request = ChatRequest {
-        model: "llama-3.1-8b-instant".to_string(),
+        model: DEFAULT_MODEL.to_string(),
         messages: vec![ChatMessage {
             role: "user".to_string(),
             content: "Hi".to_string(),
@@ -140,7 +344,7 @@ pub async fn check_api_key(api_key: &str) -> Result<bool> {
     };
 
     let response = client
-        .post(GROQ_API_URL)
+        .post(format!("{}/chat/completions", endpoints::groq()))
         .header("Authorization", format!("Bearer {}", api_key))
         .header("Content-Type", "application/json")
         .json(&request)
@@ -158,61 +362,167 @@ pub async fn check_api_key(api_key: &str) -> Result<bool> {
 #[derive(Debug, Deserialize)]
 pub struct WhisperResponse {
     pub text: String,
+    /// Per-segment detail, only present with `verbose_json`
+    #[serde(default)]
+    pub segments: Vec<WhisperSegment>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct WhisperSegment {
+    pub avg_logprob: f64,
+    /// Offsets in seconds from the start of the uploaded audio
+    #[serde(default)]
+    pub start: f64,
+    #[serde(default)]
+    pub end: f64,
+}
+
+/// Average token probability (0.0-1.0) across the segments that reach past `from_secs`,
+/// as a confidence score for that part of the audio.
+/// Whisper reports log probabilities rather than word confidences.
+pub fn segment_confidence(segments: &[WhisperSegment], from_secs: f64) -> Option<f32> {
+    let probabilities: Vec<f64> = segments
+        .iter()
+        .filter(|s| s.end > from_secs)
+        .map(|s| s.avg_logprob.exp())
+        .collect();
+    if probabilities.is_empty() {
+        return None;
+    }
+    Some((probabilities.iter().sum::<f64>() / probabilities.len() as f64) as f32)
 }
 
 /// Maximum file size for Groq Whisper API (25MB, use 15MB to be safe)
 const MAX_WHISPER_FILE_SIZE: u64 = 15_000_000;
 
-/// WAV file header size (44 bytes standard)
-const WAV_HEADER_SIZE: usize = 44;
+/// Enough of the file to hold the RIFF header and any chunks before the audio data
+pub(crate) const WAV_HEADER_PROBE: usize = 4096;
 
-/// Extract the most recent portion of a WAV file for transcription
-/// Creates a new valid WAV with proper headers containing only the last `max_size` bytes of audio
-async fn extract_recent_audio(file_path: &str, max_size: usize) -> Result<Vec<u8>> {
-    use tokio::io::{AsyncReadExt, AsyncSeekExt};
-    use std::io::SeekFrom;
+/// Open a WAV and read its header, returning the file size too
+async fn open_wav(file_path: &str) -> Result<(tokio::fs::File, Vec<u8>, crate::audio::WavLayout, u64)> {
+    use tokio::io::AsyncReadExt;
 
     let mut file = tokio::fs::File::open(file_path).await?;
-    let file_size = file.metadata().await?.len() as usize;
+    let file_size = file.metadata().await?.len();
 
-    // Read the original WAV header (first 44 bytes)
-    let mut header = vec![0u8; WAV_HEADER_SIZE];
+    // Read the original header; its length depends on the sample format
+    let mut header = vec![0u8; WAV_HEADER_PROBE.min(file_size as usize)];
     file.read_exact(&mut header).await?;
+    let layout = crate::audio::wav_layout(&header)
+        .ok_or_else(|| anyhow!("Not a valid WAV file: {}", file_path))?;
+    Ok((file, header, layout, file_size))
+}
 
-    // Calculate how much audio data to read (excluding header)
-    let total_audio_data = file_size - WAV_HEADER_SIZE;
-    let audio_to_read = (max_size - WAV_HEADER_SIZE).min(total_audio_data);
-
-    // Seek to the position where we want to start reading
-    let start_pos = file_size - audio_to_read;
-    file.seek(SeekFrom::Start(start_pos as u64)).await?;
+/// Read the whole frames between file offsets `start` and `end` and wrap them in the
+/// original header
+async fn read_frames(
+    file: &mut tokio::fs::File,
+    header: &[u8],
+    layout: &crate::audio::WavLayout,
+    start: u64,
+    end: u64,
+) -> Result<Vec<u8>> {
+    use tokio::io::{AsyncReadExt, AsyncSeekExt};
+    use std::io::SeekFrom;
 
-    // Read the audio data
-    let mut audio_data = vec![0u8; audio_to_read];
+    file.seek(SeekFrom::Start(start)).await?;
+    let mut audio_data = vec![0u8; end.saturating_sub(start) as usize];
     file.read_exact(&mut audio_data).await?;
+    Ok(crate::audio::wav_with_data(header, layout, &audio_data))
+}
 
-    // Update the WAV header with correct sizes
-    // Bytes 4-7: File size - 8 (little endian)
-    let new_file_size = (WAV_HEADER_SIZE + audio_to_read - 8) as u32;
-    header[4..8].copy_from_slice(&new_file_size.to_le_bytes());
-
-    // Bytes 40-43: Data chunk size (little endian)
-    let data_size = audio_to_read as u32;
-    header[40..44].copy_from_slice(&data_size.to_le_bytes());
-
-    // Combine header and audio data
-    let mut result = header;
-    result.extend(audio_data);
+/// Round a file offset down to a frame boundary inside the data chunk
+fn frame_offset(layout: &crate::audio::WavLayout, offset: u64) -> u64 {
+    let data_offset = layout.data_offset as u64;
+    let block_align = layout.block_align as u64;
+    data_offset + offset.saturating_sub(data_offset) / block_align * block_align
+}
 
-    eprintln!("Extracted {}MB of recent audio from {}MB file",
+/// Extract the most recent portion of a WAV file for transcription
+/// Creates a new valid WAV with the original format header and as many of the last whole
+/// frames as fit in `max_size` bytes
+pub(crate) async fn extract_recent_audio(file_path: &str, max_size: usize) -> Result<Vec<u8>> {
+    let (mut file, header, layout, file_size) = open_wav(file_path).await?;
+
+    // Keep whole frames only, so samples and channels don't shift
+    let end = frame_offset(&layout, file_size);
+    let budget = max_size.saturating_sub(layout.data_offset) as u64;
+    let start = end - (end - layout.data_offset as u64).min(budget) / layout.block_align as u64 * layout.block_align as u64;
+    let result = read_frames(&mut file, &header, &layout, start, end).await?;
+
+    tracing::info!("Extracted {}MB of recent audio from {}MB file",
         result.len() / 1_000_000, file_size / 1_000_000);
 
     Ok(result)
 }
 
+/// The audio between file offsets `from` and `to` as a standalone WAV, cut on whole frames.
+/// Offsets inside the header mean the start of the data. Also returns the file offset the
+/// WAV ends at, to continue from there.
+pub(crate) async fn extract_audio_range(file_path: &str, from: u64, to: u64) -> Result<(Vec<u8>, u64)> {
+    let (mut file, header, layout, file_size) = open_wav(file_path).await?;
+
+    let start = frame_offset(&layout, from);
+    let end = frame_offset(&layout, to.min(file_size)).max(start);
+    let wav = read_frames(&mut file, &header, &layout, start, end).await?;
+    Ok((wav, end))
+}
+
+/// Reads a recording as consecutive standalone WAVs of at most `chunk_bytes` of audio
+/// each, for providers with an upload size limit
+pub(crate) struct WavChunks {
+    file_path: String,
+    chunk_bytes: u64,
+    next: u64,
+}
+
+impl WavChunks {
+    pub(crate) fn new(file_path: &str, chunk_bytes: u64) -> Self {
+        Self { file_path: file_path.to_string(), chunk_bytes, next: 0 }
+    }
+
+    /// The next chunk, or None once the whole recording has been read
+    pub(crate) async fn next(&mut self) -> Result<Option<Vec<u8>>> {
+        let (chunk, end) = extract_audio_range(&self.file_path, self.next, self.next + self.chunk_bytes).await?;
+        if crate::usage::wav_seconds(&chunk) == 0.0 {
+            return Ok(None);
+        }
+        self.next = end;
+        Ok(Some(chunk))
+    }
+}
+
+/// Audio bytes per chunk when a recording is sent in pieces, leaving room for the header
+const MAX_CHUNK_AUDIO_BYTES: u64 = MAX_WHISPER_FILE_SIZE - WAV_HEADER_PROBE as u64;
+
 /// Transcribe audio file using Groq's Whisper API
 /// For files larger than MAX_WHISPER_FILE_SIZE, only transcribes the last portion
 pub async fn transcribe_audio(api_key: &str, file_path: &str) -> Result<String> {
+    Ok(request_transcription(api_key, file_path, "json").await?.text)
+}
+
+/// Like `transcribe_audio`, also returning a confidence score when Groq reports one.
+/// The score only covers the audio from file offset `since` on, i.e. what was recorded
+/// since the last check, not the earlier audio sent along for context.
+pub async fn transcribe_audio_with_confidence(api_key: &str, file_path: &str, since: u64) -> Result<(String, Option<f32>)> {
+    let (file_bytes, end, file_name) = read_recent_audio(api_key, file_path).await?;
+    let new_seconds = crate::audio::wav_layout(&file_bytes)
+        .map_or(0.0, |layout| end.saturating_sub(since) as f64 / layout.byte_rate as f64);
+    let new_from = (crate::usage::wav_seconds(&file_bytes) - new_seconds).max(0.0);
+
+    let response = post_transcription(api_key, file_bytes, file_name, "verbose_json").await?;
+    let confidence = segment_confidence(&response.segments, new_from);
+    Ok((response.text, confidence))
+}
+
+async fn request_transcription(api_key: &str, file_path: &str, response_format: &str) -> Result<WhisperResponse> {
+    let (file_bytes, _, file_name) = read_recent_audio(api_key, file_path).await?;
+    post_transcription(api_key, file_bytes, file_name, response_format).await
+}
+
+/// The recording, or its last `MAX_WHISPER_FILE_SIZE` bytes when it's larger, as a WAV to
+/// upload. Also returns the file offset the audio ends at, and the file name.
+async fn read_recent_audio(api_key: &str, file_path: &str) -> Result<(Vec<u8>, u64, String)> {
     if api_key.is_empty() {
         return Err(anyhow!("Groq API key not set"));
     }
@@ -226,13 +536,15 @@ pub async fn transcribe_audio(api_key: &str, file_path: &str) -> Result<String>
     let file_size = metadata.len();
 
     // If file is small enough, read the whole thing
-    let file_bytes = if file_size <= MAX_WHISPER_FILE_SIZE {
-        tokio::fs::read(file_path).await?
+    let (file_bytes, end) = if file_size <= MAX_WHISPER_FILE_SIZE {
+        let bytes = tokio::fs::read(file_path).await?;
+        let end = bytes.len() as u64;
+        (bytes, end)
     } else {
         // File too large - extract only the last portion
-        eprintln!("Large file detected ({}MB), extracting last {}MB for transcription",
+        tracing::info!("Large file detected ({}MB), extracting last {}MB for transcription",
             file_size / 1_000_000, MAX_WHISPER_FILE_SIZE / 1_000_000);
-        extract_recent_audio(file_path, MAX_WHISPER_FILE_SIZE as usize).await?
+        (extract_recent_audio(file_path, MAX_WHISPER_FILE_SIZE as usize).await?, file_size)
     };
 
     let file_name = path.file_name()
@@ -240,7 +552,39 @@ pub async fn transcribe_audio(api_key: &str, file_path: &str) -> Result<String>
         .unwrap_or("audio.wav")
         .to_string();
 
-    let client = reqwest::Client::new();
+    Ok((file_bytes, end, file_name))
+}
+
+/// Transcribe a whole recording; large files are sent in chunks and the texts joined,
+/// rather than keeping only the last part like `transcribe_audio`
+pub async fn transcribe_recording(api_key: &str, file_path: &str) -> Result<String> {
+    if api_key.is_empty() {
+        return Err(anyhow!("Groq API key not set"));
+    }
+
+    let path = Path::new(file_path);
+    if !path.exists() {
+        return Err(anyhow!("Audio file not found: {}", file_path));
+    }
+
+    let file_name = path.file_name()
+        .and_then(|n| n.to_str())
+        .unwrap_or("audio.wav")
+        .to_string();
+
+    let mut texts = Vec::new();
+    let mut reader = WavChunks::new(file_path, MAX_CHUNK_AUDIO_BYTES);
+    while let Some(chunk) = reader.next().await? {
+        let text = post_transcription(api_key, chunk, file_name.clone(), "json").await?.text;
+        texts.push(text.trim().to_string());
+    }
+    Ok(texts.join(" ").trim().to_string())
+}
+
+/// Upload one WAV to the transcription endpoint
+async fn post_transcription(api_key: &str, file_bytes: Vec<u8>, file_name: String, response_format: &str) -> Result<WhisperResponse> {
+    let client = crate::net::client();
+    let audio_seconds = crate::usage::wav_seconds(&file_bytes);
 
     // Create multipart form
     let file_part = reqwest::multipart::Part::bytes(file_bytes)
@@ -250,11 +594,11 @@ pub async fn transcribe_audio(api_key: &str, file_path: &str) -> Result<String>
     let form = reqwest::multipart::Form::new()
         .part("file", file_part)
         .text("model", "whisper-large-v3-turbo")
-        .text("response_format", "json")
+        .text("response_format", response_format.to_string())
         .text("language", "en");
 
     let response = client
-        .post(GROQ_WHISPER_URL)
+        .post(format!("{}/audio/transcriptions", endpoints::groq()))
         .header("Authorization", format!("Bearer {}", api_key))
         .multipart(form)
         .timeout(std::time::Duration::from_secs(120))
@@ -264,11 +608,99 @@ pub async fn transcribe_audio(api_key: &str, file_path: &str) -> Result<String>
     if !response.status().is_success() {
         let status = response.status();
         let error_text = response.text().await.unwrap_or_default();
-        return Err(anyhow!("Groq Whisper API error ({}): {}", status, error_text));
+        return Err(ProviderError::new(status, format!("Groq Whisper API error ({}): {}", status, error_text)).into());
     }
 
-    let result: WhisperResponse = response.json().await?;
-    Ok(result.text)
+    let result = response.json().await?;
+    crate::usage::record_audio("groq", audio_seconds);
+    Ok(result)
+}
+
+/// Word with offsets in seconds from the start of the audio
+#[derive(Debug, Deserialize)]
+pub struct WhisperWord {
+    pub word: String,
+    pub start: f64,
+    pub end: f64,
+}
+
+#[derive(Debug, Deserialize)]
+struct VerboseWhisperResponse {
+    #[serde(default)]
+    words: Vec<WhisperWord>,
+}
+
+/// Transcribe a whole recording with word timestamps (for realigning a transcript).
+/// Unlike `transcribe_audio`, large files aren't truncated: they're sent in chunks, and
+/// word times are offset to the start of the recording.
+pub async fn transcribe_words(api_key: &str, file_path: &str) -> Result<Vec<WhisperWord>> {
+    if api_key.is_empty() {
+        return Err(anyhow!("Groq API key not set"));
+    }
+
+    let path = Path::new(file_path);
+    if !path.exists() {
+        return Err(anyhow!("Audio file not found: {}", file_path));
+    }
+
+    let file_name = path.file_name()
+        .and_then(|n| n.to_str())
+        .unwrap_or("audio.wav")
+        .to_string();
+
+    let mut words = Vec::new();
+    let mut offset_secs = 0.0;
+    let mut chunks = 0;
+    let mut reader = WavChunks::new(file_path, MAX_CHUNK_AUDIO_BYTES);
+    while let Some(chunk) = reader.next().await? {
+        let chunk_secs = crate::usage::wav_seconds(&chunk);
+        words.extend(request_words(api_key, chunk, &file_name).await?.into_iter().map(|w| WhisperWord {
+            start: w.start + offset_secs,
+            end: w.end + offset_secs,
+            ..w
+        }));
+        offset_secs += chunk_secs;
+        chunks += 1;
+    }
+
+    if chunks > 1 {
+        tracing::info!("Transcribed {} with word timestamps in {} chunks", file_path, chunks);
+    }
+    Ok(words)
+}
+
+/// One verbose_json request with word timestamps
+async fn request_words(api_key: &str, file_bytes: Vec<u8>, file_name: &str) -> Result<Vec<WhisperWord>> {
+    let audio_seconds = crate::usage::wav_seconds(&file_bytes);
+
+    let file_part = reqwest::multipart::Part::bytes(file_bytes)
+        .file_name(file_name.to_string())
+        .mime_str("audio/wav")?;
+
+    let form = reqwest::multipart::Form::new()
+        .part("file", file_part)
+        .text("model", "whisper-large-v3-turbo")
+        .text("response_format", "verbose_json")
+        .text("timestamp_granularities[]", "word")
+        .text("language", "en");
+
+    let response = crate::net::client()
+        .post(format!("{}/audio/transcriptions", endpoints::groq()))
+        .header("Authorization", format!("Bearer {}", api_key))
+        .multipart(form)
+        .timeout(std::time::Duration::from_secs(300))
+        .send()
+        .await?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let error_text = response.text().await.unwrap_or_default();
+        return Err(ProviderError::new(status, format!("Groq Whisper API error ({}): {}", status, error_text)).into());
+    }
+
+    let result: VerboseWhisperResponse = response.json().await?;
+    crate::usage::record_audio("groq", audio_seconds);
+    Ok(result.words)
 }
 
 /// Transcribe audio bytes directly (for real-time chunks)
@@ -281,7 +713,8 @@ pub async fn transcribe_audio_bytes(api_key: &str, audio_bytes: Vec<u8>, file_na
         return Err(anyhow!("Audio too short for transcription"));
     }
 
-    let client = reqwest::Client::new();
+    let client = crate::net::client();
+    let audio_seconds = crate::usage::wav_seconds(&audio_bytes);
 
     // Create multipart form
     let file_part = reqwest::multipart::Part::bytes(audio_bytes)
@@ -295,7 +728,7 @@ pub async fn transcribe_audio_bytes(api_key: &str, audio_bytes: Vec<u8>, file_na
         .text("language", "en");
 
     let response = client
-        .post(GROQ_WHISPER_URL)
+        .post(format!("{}/audio/transcriptions", endpoints::groq()))
         .header("Authorization", format!("Bearer {}", api_key))
         .multipart(form)
         .timeout(std::time::Duration::from_secs(60))
@@ -305,9 +738,26 @@ pub async fn transcribe_audio_bytes(api_key: &str, audio_bytes: Vec<u8>, file_na
     if !response.status().is_success() {
         let status = response.status();
         let error_text = response.text().await.unwrap_or_default();
-        return Err(anyhow!("Groq Whisper API error ({}): {}", status, error_text));
+        return Err(ProviderError::new(status, format!("Groq Whisper API error ({}): {}", status, error_text)).into());
     }
 
     let result: WhisperResponse = response.json().await?;
+    crate::usage::record_audio("groq", audio_seconds);
     Ok(result.text)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_retired_model_errors() {
+        let decommissioned = r#"{"error":{"message":"The model `mixtral-8x7b-32768` has been decommissioned","type":"invalid_request_error","code":"model_decommissioned"}}"#;
+        let missing = r#"{"error":{"message":"The model `llama-3.2-90b-vision-preview` does not exist or you do not have access to it.","code":"model_not_found"}}"#;
+
+        assert!(is_retired_model_error(reqwest::StatusCode::BAD_REQUEST, decommissioned));
+        assert!(is_retired_model_error(reqwest::StatusCode::NOT_FOUND, missing));
+        assert!(!is_retired_model_error(reqwest::StatusCode::BAD_REQUEST, r#"{"error":{"message":"max_tokens too large"}}"#));
+        assert!(!is_retired_model_error(reqwest::StatusCode::UNAUTHORIZED, missing));
+    }
+}
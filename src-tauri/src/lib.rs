@@ -1,23 +1,48 @@
 use serde::{Deserialize, Serialize};
 use std::sync::{
-    atomic::{AtomicBool, Ordering},
+    atomic::{AtomicBool, AtomicU64, Ordering},
     Arc, Mutex,
 };
 use tauri::{AppHandle, Emitter, Manager, State};
+use tauri_plugin_clipboard_manager::ClipboardExt;
+use tauri_plugin_opener::OpenerExt;
 use tokio::sync::{mpsc, watch};
 
+use error::{AppError, ErrorCode};
+
+mod alignment;
+mod analytics;
 mod assemblyai;
 mod audio;
+mod audio_queue;
 mod calendar;
 mod database;
 mod deepgram;
+mod demo;
+mod diagnostics;
+mod endpoints;
+mod error;
+mod export;
 pub mod groq;  // Public for mock_test binary
+mod logs;
 mod meeting_monitor;
+mod net;
+mod metrics;
 mod mock;
+mod ollama;
+mod openai;
+mod persist;
 mod realtime;
 mod screen_share;
 mod settings;
 mod system_audio;
+mod templates;
+mod tokens;
+mod transcript_archive;
+mod transcript_history;
+mod transcript_import;
+mod usage;
+mod whisper_local;
 
 use settings::AppSettings;
 
@@ -29,6 +54,23 @@ pub enum TranscriptionProvider {
     Groq,       // Recommended - uses Whisper, good quality
     Deepgram,   // Real-time streaming, fast
     AssemblyAI, // High accuracy, batch processing
+    OpenAI,     // whisper-1, batch processing
+    WhisperLocal, // Offline whisper.cpp, no audio leaves the machine
+}
+
+impl TranscriptionProvider {
+    /// Parse a provider name as used in settings ("groq", "deepgram", ...)
+    fn parse(name: &str) -> Result<Self, String> {
+        match name.to_lowercase().as_str() {
+            "groq" => Ok(TranscriptionProvider::Groq),
+            "deepgram" => Ok(TranscriptionProvider::Deepgram),
+            "assemblyai" => Ok(TranscriptionProvider::AssemblyAI),
+            "openai" => Ok(TranscriptionProvider::OpenAI),
+            "whisperlocal" if cfg!(feature = "local-whisper") => Ok(TranscriptionProvider::WhisperLocal),
+            "whisperlocal" => Err("Local Whisper isn't available in this build (enable the local-whisper feature)".to_string()),
+            _ => Err(format!("Unknown provider: {}", name)),
+        }
+    }
 }
 
 impl Default for TranscriptionProvider {
@@ -37,24 +79,107 @@ impl Default for TranscriptionProvider {
     }
 }
 
+/// Chat completion provider for summaries, replies and other generation
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Default)]
+pub enum LlmProvider {
+    #[default]
+    Groq,
+    OpenAI,
+    /// Canned output while demo mode is on; never persisted
+    Demo,
+}
+
+impl LlmProvider {
+    fn name(&self) -> &'static str {
+        match self {
+            LlmProvider::Groq => "Groq",
+            LlmProvider::OpenAI => "OpenAI",
+            LlmProvider::Demo => "Demo",
+        }
+    }
+}
+
+/// What a generation is for, i.e. the shape of output the caller parses.
+/// Demo mode answers with the canned output of that shape.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GenerationKind {
+    /// Free-form markdown (summaries, rollups, comparisons)
+    Text,
+    /// JSON with key points, action items, decisions and notes
+    StructuredSummary,
+    /// JSON array of decisions
+    Decisions,
+    /// JSON array of chapters
+    Chapters,
+    /// Reply suggestions, one per line
+    Replies,
+    /// Short bullet list of the meeting so far
+    LiveSummary,
+}
+
+/// The active LLM provider and its API key
+#[derive(Debug, Clone)]
+struct LlmClient {
+    provider: LlmProvider,
+    api_key: String,
+    /// Assistant persona sent as the system message
+    system_prompt: String,
+    /// For events such as `model-fallback`; None before the app is running
+    app: Option<AppHandle>,
+}
+
+/// What started the active audio recorder, so each stop only finalizes its own recording
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RecorderOwner {
+    /// `start_recording`
+    Recording,
+    /// Batch live transcription, which transcribes the growing file
+    LiveTranscription,
+}
+
+/// Who excluded the window from screen capture
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ExclusionSource {
+    /// `set_screen_share_exclusion`
+    Manual,
+    /// The auto-hide task, while a meeting app was running
+    AutoHide,
+}
+
 // Application state
 pub struct AppState {
     pub is_recording: Arc<Mutex<bool>>,
     pub is_live_transcribing: Arc<Mutex<bool>>,
     pub transcription: Arc<Mutex<Vec<TranscriptSegment>>>,
     pub summary: Arc<Mutex<String>>,
+    // Key of the last `generate_summary` input and its result, to skip identical re-runs
+    pub summary_cache: Arc<Mutex<Option<(u64, String)>>>,
+    pub live_summary: Arc<Mutex<String>>,
     pub suggested_replies: Arc<Mutex<Vec<String>>>,
     pub selected_model: Arc<Mutex<String>>,
     pub transcription_provider: Arc<Mutex<TranscriptionProvider>>,
     pub groq_api_key: Arc<Mutex<String>>,
     pub assemblyai_api_key: Arc<Mutex<String>>,
     pub deepgram_api_key: Arc<Mutex<String>>,
+    pub openai_api_key: Arc<Mutex<String>>,
+    pub llm_provider: Arc<Mutex<LlmProvider>>,
     pub audio_recorder: Arc<Mutex<Option<audio::AudioRecorder>>>,
+    pub recorder_owner: Arc<Mutex<Option<RecorderOwner>>>,
     pub current_recording_path: Arc<Mutex<Option<String>>>,
     pub is_transcribing: Arc<Mutex<bool>>,
+    // AssemblyAI jobs being uploaded or polled, for cancel_assemblyai_job
+    pub assemblyai_jobs: Arc<Mutex<assemblyai::ActiveJobs>>,
+    // Jobs of batch live transcription, cancelled on its own when it stops
+    pub live_assemblyai_jobs: Arc<Mutex<assemblyai::ActiveJobs>>,
     pub live_stop_signal: Arc<Mutex<Option<mpsc::Sender<()>>>>,
     pub deepgram_transcriber: Arc<Mutex<Option<DeepgramTranscriber>>>,
     pub deepgram_stop_flag: Arc<AtomicBool>,
+    // Diarized speaker ID the user marked as themselves (Deepgram mono mode)
+    pub primary_speaker: Arc<Mutex<Option<u32>>>,
+    // Live audio chunks dropped because the network send fell behind
+    pub dropped_audio_chunks: Arc<AtomicU64>,
+    // Latency/throughput of the current live transcription session
+    pub transcription_stats: Arc<Mutex<metrics::TranscriptionStats>>,
     pub settings: Arc<Mutex<AppSettings>>,
     pub meeting_context: Arc<Mutex<String>>,
     // Mock transcription state
@@ -67,28 +192,98 @@ pub struct AppState {
     pub meetings_db: Arc<Mutex<database::MeetingsDatabase>>,
     // Meeting monitor for auto-start
     pub meeting_monitor: Arc<meeting_monitor::MeetingMonitor>,
+    // Undo/redo snapshots for manual transcript edits
+    pub transcript_history: Arc<Mutex<transcript_history::TranscriptHistory>>,
+    // Live Groq model list with the time it was fetched
+    pub model_cache: Arc<Mutex<Option<(std::time::Instant, Vec<ModelInfo>)>>>,
+    // Meeting templates, plus what the active template seeded for this meeting
+    pub templates: Arc<Mutex<templates::TemplateStore>>,
+    pub summary_template: Arc<Mutex<String>>,
+    pub meeting_attendees: Arc<Mutex<Vec<String>>>,
+    // Calendar event the current meeting was started from (title/ID for saving)
+    pub calendar_event: Arc<Mutex<Option<calendar::SimpleCalendarEvent>>>,
+    // Words/phrases that trigger a keyword-alert when spoken
+    pub alert_keywords: Arc<Mutex<Vec<String>>>,
+    // Shortest live segment (in characters) that gets added to the transcript
+    // (None = each live path's default)
+    pub min_segment_length: Arc<Mutex<Option<usize>>>,
+    // Minimum milliseconds between interim updates per channel (Deepgram)
+    pub interim_debounce_ms: Arc<Mutex<u64>>,
+    // Also commit Deepgram interim results to the transcript (replaced by the final)
+    pub save_interim: Arc<Mutex<bool>>,
+    // Silent seconds before batch transcription emits `no-speech-detected` (0 = never)
+    pub no_speech_notice_secs: Arc<Mutex<u64>>,
+    // Live segments kept in memory (0 = no cap); older ones are moved to the archive
+    pub max_live_segments: Arc<Mutex<usize>>,
+    pub transcript_archive: Arc<Mutex<transcript_archive::TranscriptArchive>>,
+    // Private notes the user takes during the meeting
+    pub notes: Arc<Mutex<Vec<database::Note>>>,
+    // Highlighted moments, measured from when recording/transcription started
+    pub bookmarks: Arc<Mutex<Vec<database::Bookmark>>>,
+    pub meeting_started_at: Arc<Mutex<Option<std::time::Instant>>>,
+    // Auto-stop limit for recording/transcription, and the session its watchdog belongs to
+    pub max_recording_minutes: Arc<Mutex<Option<u32>>>,
+    pub recording_limit_session: Arc<AtomicU64>,
+    // Bumped per live transcription session, so background tasks of an earlier one exit
+    pub live_session: Arc<AtomicU64>,
+    // Set when recording or transcription starts; the post-meeting steps run once, when all
+    // of the session has stopped
+    pub post_meeting_pending: Arc<AtomicBool>,
+    // Saved-meeting ID the in-progress meeting is auto-saved under (replaced by save_meeting)
+    pub autosave_meeting_id: Arc<Mutex<Option<String>>>,
+    // Seconds between automatic reply refreshes while transcribing (0 = off)
+    pub auto_refresh_replies_secs: Arc<Mutex<u64>>,
+    // Generate replies always, or only after a participant final while the mic is quiet
+    pub reply_trigger: Arc<Mutex<settings::ReplyTrigger>>,
+    // Last time the microphone channel produced speech (Deepgram)
+    pub last_mic_speech: Arc<Mutex<Option<std::time::Instant>>>,
+    // A participant finished speaking since replies were last generated
+    pub participant_final_pending: Arc<AtomicBool>,
+    // Offline demo: scripted transcript and canned AI output, never saved
+    pub demo_mode: Arc<AtomicBool>,
+    // Set while the window is excluded from screen capture, to whoever excluded it
+    pub screen_share_exclusion: Arc<Mutex<Option<ExclusionSource>>>,
+    // Bumped each time demo mode is turned on or off, so an older script stops playing
+    pub demo_generation: Arc<AtomicU64>,
+    // Recent log lines for in-app diagnostics
+    pub log_buffer: logs::LogBuffer,
+    // Set once the app is running, for events from helpers that only get the state
+    pub app_handle: Arc<std::sync::OnceLock<AppHandle>>,
 }
 
 impl Default for AppState {
     fn default() -> Self {
         // Load persisted settings from disk
         let saved_settings = AppSettings::load();
+        endpoints::configure(&saved_settings.endpoints);
+        if !saved_settings.proxy_url.is_empty() {
+            if let Err(e) = net::set_proxy(Some(&saved_settings.proxy_url)) {
+                tracing::warn!("Ignoring saved proxy: {}", e);
+            }
+        }
 
         // Parse transcription provider from saved settings (default to Deepgram for real-time)
         let provider = match saved_settings.transcription_provider.to_lowercase().as_str() {
             "groq" => TranscriptionProvider::Groq,
             "assemblyai" => TranscriptionProvider::AssemblyAI,
+            "openai" => TranscriptionProvider::OpenAI,
+            // A build without local Whisper falls back to the default
+            "whisperlocal" if cfg!(feature = "local-whisper") => TranscriptionProvider::WhisperLocal,
             _ => TranscriptionProvider::Deepgram,
         };
+        let llm_provider = match saved_settings.llm_provider.to_lowercase().as_str() {
+            "openai" => LlmProvider::OpenAI,
+            _ => LlmProvider::Groq,
+        };
 
         // Use saved model or default
         let model = if saved_settings.selected_model.is_empty() {
-            "llama-3.1-8b-instant".to_string()
+            groq::DEFAULT_MODEL.to_string()
         } else {
             saved_settings.selected_model.clone()
         };
 
-        eprintln!("Loaded settings - Groq key present: {}, Model: {}",
+        tracing::info!("Loaded settings - Groq key present: {}, Model: {}",
             !saved_settings.groq_api_key.is_empty(), model);
 
         Self {
@@ -96,18 +291,28 @@ impl Default for AppState {
             is_live_transcribing: Arc::new(Mutex::new(false)),
             transcription: Arc::new(Mutex::new(Vec::new())),
             summary: Arc::new(Mutex::new(String::new())),
+            summary_cache: Arc::new(Mutex::new(None)),
+            live_summary: Arc::new(Mutex::new(String::new())),
             suggested_replies: Arc::new(Mutex::new(Vec::new())),
             selected_model: Arc::new(Mutex::new(model)),
             transcription_provider: Arc::new(Mutex::new(provider)),
             groq_api_key: Arc::new(Mutex::new(saved_settings.groq_api_key.clone())),
             assemblyai_api_key: Arc::new(Mutex::new(saved_settings.assemblyai_api_key.clone())),
             deepgram_api_key: Arc::new(Mutex::new(saved_settings.deepgram_api_key.clone())),
+            openai_api_key: Arc::new(Mutex::new(saved_settings.openai_api_key.clone())),
+            llm_provider: Arc::new(Mutex::new(llm_provider)),
             audio_recorder: Arc::new(Mutex::new(None)),
+            recorder_owner: Arc::new(Mutex::new(None)),
             current_recording_path: Arc::new(Mutex::new(None)),
             is_transcribing: Arc::new(Mutex::new(false)),
+            assemblyai_jobs: Arc::new(Mutex::new(assemblyai::ActiveJobs::default())),
+            live_assemblyai_jobs: Arc::new(Mutex::new(assemblyai::ActiveJobs::default())),
             live_stop_signal: Arc::new(Mutex::new(None)),
             deepgram_transcriber: Arc::new(Mutex::new(None)),
             deepgram_stop_flag: Arc::new(AtomicBool::new(false)),
+            primary_speaker: Arc::new(Mutex::new(None)),
+            dropped_audio_chunks: Arc::new(AtomicU64::new(0)),
+            transcription_stats: Arc::new(Mutex::new(metrics::TranscriptionStats::default())),
             settings: Arc::new(Mutex::new(saved_settings.clone())),
             meeting_context: Arc::new(Mutex::new(saved_settings.meeting_context.clone())),
             // Mock transcription state
@@ -120,11 +325,43 @@ impl Default for AppState {
             meetings_db: Arc::new(Mutex::new(database::MeetingsDatabase::load())),
             // Meeting monitor
             meeting_monitor: Arc::new(meeting_monitor::MeetingMonitor::new()),
+            // Transcript edit history
+            transcript_history: Arc::new(Mutex::new(transcript_history::TranscriptHistory::default())),
+            model_cache: Arc::new(Mutex::new(None)),
+            // Meeting templates
+            templates: Arc::new(Mutex::new(templates::TemplateStore::load())),
+            summary_template: Arc::new(Mutex::new(String::new())),
+            meeting_attendees: Arc::new(Mutex::new(Vec::new())),
+            calendar_event: Arc::new(Mutex::new(None)),
+            alert_keywords: Arc::new(Mutex::new(saved_settings.alert_keywords.clone())),
+            min_segment_length: Arc::new(Mutex::new(saved_settings.min_segment_length)),
+            interim_debounce_ms: Arc::new(Mutex::new(saved_settings.interim_debounce_ms())),
+            no_speech_notice_secs: Arc::new(Mutex::new(saved_settings.no_speech_notice_secs())),
+            save_interim: Arc::new(Mutex::new(saved_settings.save_interim)),
+            max_live_segments: Arc::new(Mutex::new(saved_settings.max_live_segments)),
+            transcript_archive: Arc::new(Mutex::new(transcript_archive::TranscriptArchive::new())),
+            notes: Arc::new(Mutex::new(Vec::new())),
+            bookmarks: Arc::new(Mutex::new(Vec::new())),
+            meeting_started_at: Arc::new(Mutex::new(None)),
+            max_recording_minutes: Arc::new(Mutex::new(saved_settings.max_recording_minutes)),
+            recording_limit_session: Arc::new(AtomicU64::new(0)),
+            live_session: Arc::new(AtomicU64::new(0)),
+            post_meeting_pending: Arc::new(AtomicBool::new(false)),
+            auto_refresh_replies_secs: Arc::new(Mutex::new(saved_settings.auto_refresh_replies_secs)),
+            reply_trigger: Arc::new(Mutex::new(saved_settings.reply_trigger)),
+            last_mic_speech: Arc::new(Mutex::new(None)),
+            participant_final_pending: Arc::new(AtomicBool::new(false)),
+            autosave_meeting_id: Arc::new(Mutex::new(None)),
+            demo_mode: Arc::new(AtomicBool::new(false)),
+            screen_share_exclusion: Arc::new(Mutex::new(None)),
+            demo_generation: Arc::new(AtomicU64::new(0)),
+            log_buffer: logs::LogBuffer::global().clone(),
+            app_handle: Arc::new(std::sync::OnceLock::new()),
         }
     }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
 #[serde(default)]
 pub struct TranscriptSegment {
     pub timestamp: String,
@@ -132,6 +369,57 @@ pub struct TranscriptSegment {
     pub text: String,
     #[serde(default, skip_serializing)]
     pub is_final: bool,
+    /// Start/end offsets in milliseconds from word timestamps (when the provider supplies them)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub start_ms: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub end_ms: Option<u64>,
+    /// Diarization speaker ID (Deepgram mono mode), used to relabel "You"/"Participant"
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub speaker_id: Option<u32>,
+    /// Provider confidence from 0.0 to 1.0 (Deepgram and AssemblyAI supply it)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub confidence: Option<f32>,
+}
+
+/// Whether a live segment is long enough to keep (filters fragments like "Mm." or "Yeah").
+/// `default` applies until the user sets a minimum.
+fn meets_min_length(text: &str, min_segment_length: &Arc<Mutex<Option<usize>>>, default: usize) -> bool {
+    let min_chars = min_segment_length.lock().map(|m| m.unwrap_or(default)).unwrap_or(default);
+    text.trim().chars().count() >= min_chars
+}
+
+/// Tracks silence during batch live transcription so a quiet stretch is reported once,
+/// rather than looking like the app has stalled
+struct SilenceTracker {
+    last_speech: std::time::Instant,
+    notified: bool,
+}
+
+impl SilenceTracker {
+    fn new(now: std::time::Instant) -> Self {
+        Self { last_speech: now, notified: false }
+    }
+
+    fn speech(&mut self, now: std::time::Instant) {
+        self.last_speech = now;
+        self.notified = false;
+    }
+
+    /// Seconds of silence, the first time they reach `notice_secs` (0 disables the notice)
+    fn check(&mut self, now: std::time::Instant, notice_secs: u64) -> Option<u64> {
+        let silent_secs = now.saturating_duration_since(self.last_speech).as_secs();
+        if notice_secs == 0 || self.notified || silent_secs < notice_secs {
+            return None;
+        }
+        self.notified = true;
+        Some(silent_secs)
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct NoSpeechEvent {
+    silent_secs: u64,
 }
 
 /// Filler words to remove from transcripts for cleaner output
@@ -171,22 +459,57 @@ pub struct MeetingState {
     pub is_transcribing: bool,
     pub transcription: Vec<TranscriptSegment>,
     pub summary: String,
+    pub live_summary: String,
     pub suggested_replies: Vec<String>,
     pub selected_model: String,
     pub transcription_provider: TranscriptionProvider,
     pub has_groq_key: bool,
     pub has_assemblyai_key: bool,
     pub has_deepgram_key: bool,
+    pub has_openai_key: bool,
+    pub llm_provider: LlmProvider,
+    /// Demo mode is on: the transcript and AI output are canned and can't be saved
+    pub is_demo_mode: bool,
     pub current_recording_path: Option<String>,
     pub meeting_context: String,
+    pub summary_template: String,
+    pub attendees: Vec<String>,
+    pub calendar_event: Option<calendar::SimpleCalendarEvent>,
+    pub notes: Vec<database::Note>,
+    pub bookmarks: Vec<database::Bookmark>,
+}
+
+/// Lightweight subset of `MeetingState` for frequent polling (no transcript or summary)
+#[derive(Debug, Clone, Serialize)]
+pub struct MeetingStatus {
+    pub is_recording: bool,
+    pub is_live_transcribing: bool,
+    pub is_transcribing: bool,
+    pub selected_model: String,
+    pub transcription_provider: TranscriptionProvider,
+    pub has_groq_key: bool,
+    pub has_assemblyai_key: bool,
+    pub has_deepgram_key: bool,
+    pub has_openai_key: bool,
+    pub llm_provider: LlmProvider,
+    pub is_demo_mode: bool,
+    /// Number of transcript segments, to pass to `get_latest_segments`
+    pub segment_count: usize,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ModelInfo {
     pub id: String,
     pub name: String,
+    pub context_window: Option<u32>,
+    pub owned_by: Option<String>,
+    /// false when Groq reports the model as inactive/deprecated
+    pub available: bool,
 }
 
+/// How long the live Groq model list is cached before refetching
+const MODEL_CACHE_TTL_SECS: u64 = 3600;
+
 #[derive(Clone, Serialize)]
 struct TranscriptEvent {
     text: String,
@@ -195,51 +518,136 @@ struct TranscriptEvent {
     is_final: bool,  // true = finalized transcript, false = interim (still being transcribed)
 }
 
+/// Remember when the meeting started, for bookmark offsets. Keeps the first start
+/// when recording and live transcription are started separately.
+fn mark_meeting_started(state: &AppState) -> Result<(), String> {
+    state
+        .meeting_started_at
+        .lock()
+        .map_err(|e| e.to_string())?
+        .get_or_insert_with(std::time::Instant::now);
+    Ok(())
+}
+
+/// Start a recorder for `owner` and make it the current recording. Only one recorder runs
+/// at a time, so a second session can't take over the first one's path or file.
+fn start_recorder(state: &AppState, owner: RecorderOwner) -> Result<String, String> {
+    let mut recorder = state.audio_recorder.lock().map_err(|e| e.to_string())?;
+    let mut current_owner = state.recorder_owner.lock().map_err(|e| e.to_string())?;
+    if let Some(active) = recorder.as_ref() {
+        return Err(match *current_owner {
+            Some(RecorderOwner::LiveTranscription) => format!(
+                "Live transcription is already recording to {}. Stop it before starting a separate recording.",
+                active.get_output_path()
+            ),
+            _ => format!("Already recording to {}", active.get_output_path()),
+        });
+    }
+
+    let (recording_config, voice_activation) = {
+        let settings = state.settings.lock().map_err(|e| e.to_string())?;
+        (settings.recording, settings.voice_activation)
+    };
+    let new_recorder = audio::AudioRecorder::new(recording_config, voice_activation).map_err(|e| e.to_string())?;
+    let output_path = new_recorder.get_output_path().to_string();
+
+    *recorder = Some(new_recorder);
+    *current_owner = Some(owner);
+    *state.current_recording_path.lock().map_err(|e| e.to_string())? = Some(output_path.clone());
+    Ok(output_path)
+}
+
+/// Stop and finalize the recorder if `owner` started it. Returns the recording's path.
+fn stop_recorder(state: &AppState, owner: RecorderOwner) -> Result<Option<String>, String> {
+    let mut recorder = state.audio_recorder.lock().map_err(|e| e.to_string())?;
+    let mut current_owner = state.recorder_owner.lock().map_err(|e| e.to_string())?;
+    if *current_owner != Some(owner) {
+        return Ok(None);
+    }
+
+    *current_owner = None;
+    recorder.take().map(|r| r.stop().map_err(|e| e.to_string())).transpose()
+}
+
 // Commands
 
 #[tauri::command]
-async fn start_recording(state: State<'_, AppState>) -> Result<String, String> {
+async fn start_recording(app: AppHandle, state: State<'_, AppState>) -> Result<String, AppError> {
+    ensure_demo_off(&state)?;
     let mut is_recording = state.is_recording.lock().map_err(|e| e.to_string())?;
     if *is_recording {
-        return Err("Already recording".to_string());
+        return Err(AppError::new(ErrorCode::InvalidState, "Already recording"));
     }
 
-    let recorder = audio::AudioRecorder::new().map_err(|e| e.to_string())?;
-    let output_path = recorder.get_output_path().to_string();
-
-    *state.current_recording_path.lock().map_err(|e| e.to_string())? = Some(output_path.clone());
-    *state.audio_recorder.lock().map_err(|e| e.to_string())? = Some(recorder);
+    let output_path = start_recorder(&state, RecorderOwner::Recording)?;
     *is_recording = true;
+    drop(is_recording);
+    mark_meeting_started(&state)?;
+    state.post_meeting_pending.store(true, Ordering::SeqCst);
+    spawn_recording_limit_task(app);
 
     Ok(output_path)
 }
 
 #[tauri::command]
-async fn stop_recording(state: State<'_, AppState>) -> Result<String, String> {
-    let mut is_recording = state.is_recording.lock().map_err(|e| e.to_string())?;
-    if !*is_recording {
-        return Err("Not recording".to_string());
-    }
+async fn stop_recording(app: AppHandle, state: State<'_, AppState>) -> Result<String, AppError> {
+    let audio_path = {
+        let mut is_recording = state.is_recording.lock().map_err(|e| e.to_string())?;
+        if !*is_recording {
+            return Err(AppError::new(ErrorCode::InvalidState, "Not recording"));
+        }
 
-    let audio_path = if let Some(recorder) = state.audio_recorder.lock().map_err(|e| e.to_string())?.take() {
-        recorder.stop().map_err(|e| e.to_string())?
-    } else {
-        return Err("No active recorder".to_string());
+        let Some(audio_path) = stop_recorder(&state, RecorderOwner::Recording)? else {
+            return Err(AppError::new(ErrorCode::InvalidState, "No active recorder"));
+        };
+
+        *is_recording = false;
+        audio_path
     };
 
-    *is_recording = false;
+    finish_session_if_idle(&app, &state);
+    stop_watching_meeting_if_idle(&state).await;
     Ok(audio_path)
 }
 
+/// Once nothing is recording or transcribing, save the session's usage and run the
+/// post-meeting steps for the session that just ended. Runs once per session, whichever
+/// of recording and transcription stopped last and however it was stopped.
+fn finish_session_if_idle(app: &AppHandle, state: &AppState) {
+    let idle = !state.is_recording.lock().map(|v| *v).unwrap_or(false)
+        && !state.is_live_transcribing.lock().map(|v| *v).unwrap_or(false);
+    if !idle || !state.post_meeting_pending.swap(false, Ordering::SeqCst) {
+        return;
+    }
+    usage::save();
+    if state.settings.lock().map(|s| s.post_meeting.enabled).unwrap_or(false) {
+        let recording_path = state.current_recording_path.lock().ok().and_then(|p| p.clone());
+        tokio::spawn(run_post_meeting_actions(app.clone(), recording_path));
+    }
+}
+
+/// Once nothing is recording or transcribing, stop watching the started calendar event, so
+/// the end of a meeting that was stopped by hand doesn't stop a later session
+async fn stop_watching_meeting_if_idle(state: &AppState) {
+    let idle = !state.is_recording.lock().map(|v| *v).unwrap_or(false)
+        && !state.is_live_transcribing.lock().map(|v| *v).unwrap_or(false);
+    if idle {
+        state.meeting_monitor.clear_active_event().await;
+    }
+}
+
 #[tauri::command]
 async fn start_live_transcription(
     app: AppHandle,
     state: State<'_, AppState>,
-) -> Result<(), String> {
+) -> Result<(), AppError> {
+    ensure_demo_off(&state)?;
     let provider = state.transcription_provider.lock().map_err(|e| e.to_string())?.clone();
     let groq_key = state.groq_api_key.lock().map_err(|e| e.to_string())?.clone();
     let deepgram_key = state.deepgram_api_key.lock().map_err(|e| e.to_string())?.clone();
     let assemblyai_key = state.assemblyai_api_key.lock().map_err(|e| e.to_string())?.clone();
+    let openai_key = state.openai_api_key.lock().map_err(|e| e.to_string())?.clone();
+    let whisper_model = state.settings.lock().map_err(|e| e.to_string())?.whisper_model();
 
     // Auto-select provider: prefer Deepgram (real-time) if available, else Groq (batch)
     let effective_provider = match provider {
@@ -247,10 +655,10 @@ async fn start_live_transcription(
             if deepgram_key.is_empty() {
                 // Fallback to Groq if Deepgram key not set
                 if !groq_key.is_empty() {
-                    eprintln!("Deepgram key not set, falling back to Groq Whisper");
+                    tracing::info!("Deepgram key not set, falling back to Groq Whisper");
                     TranscriptionProvider::Groq
                 } else {
-                    return Err("Please set your Deepgram or Groq API key in Settings".to_string());
+                    return Err(AppError::new(ErrorCode::SetupRequired, "Please set your Deepgram or Groq API key in Settings"));
                 }
             } else {
                 TranscriptionProvider::Deepgram
@@ -260,10 +668,10 @@ async fn start_live_transcription(
             if groq_key.is_empty() {
                 // Fallback to Deepgram if Groq key not set
                 if !deepgram_key.is_empty() {
-                    eprintln!("Groq key not set, falling back to Deepgram streaming");
+                    tracing::info!("Groq key not set, falling back to Deepgram streaming");
                     TranscriptionProvider::Deepgram
                 } else {
-                    return Err("Please set your Groq or Deepgram API key in Settings".to_string());
+                    return Err(AppError::new(ErrorCode::SetupRequired, "Please set your Groq or Deepgram API key in Settings"));
                 }
             } else {
                 TranscriptionProvider::Groq
@@ -271,37 +679,74 @@ async fn start_live_transcription(
         }
         TranscriptionProvider::AssemblyAI => {
             if assemblyai_key.is_empty() {
-                return Err("Please set your AssemblyAI API key in Settings".to_string());
+                return Err(AppError::new(ErrorCode::SetupRequired, "Please set your AssemblyAI API key in Settings"));
             }
             TranscriptionProvider::AssemblyAI
         }
+        TranscriptionProvider::OpenAI => {
+            if openai_key.is_empty() {
+                return Err(AppError::new(ErrorCode::SetupRequired, "Please set your OpenAI API key in Settings"));
+            }
+            TranscriptionProvider::OpenAI
+        }
+        TranscriptionProvider::WhisperLocal => {
+            if !whisper_local::is_downloaded(&whisper_model) {
+                return Err(AppError::new(ErrorCode::SetupRequired, format!("Download the Whisper '{}' model in Settings first", whisper_model)));
+            }
+            TranscriptionProvider::WhisperLocal
+        }
     };
 
     {
         let mut is_live = state.is_live_transcribing.lock().map_err(|e| e.to_string())?;
         if *is_live {
-            return Err("Already transcribing".to_string());
+            return Err(AppError::new(ErrorCode::InvalidState, "Already transcribing"));
         }
         *is_live = true;
     }
+    mark_meeting_started(&state)?;
+    state.post_meeting_pending.store(true, Ordering::SeqCst);
+
+    // A quick stop and restart would otherwise leave the previous session's tasks running too
+    let session = state.live_session.fetch_add(1, Ordering::SeqCst) + 1;
+    spawn_live_summary_task(app.clone(), session);
+    spawn_transcript_cap_task(app.clone(), session);
+    spawn_autosave_task(app.clone(), session);
+    spawn_recording_limit_task(app.clone());
+    spawn_reply_refresh_task(app.clone(), session);
 
     match effective_provider {
         TranscriptionProvider::Deepgram => {
             // Use Deepgram real-time streaming with optimized parameters
-            eprintln!("Using Deepgram for real-time transcription (nova-2, 100ms endpointing)...");
+            tracing::info!("Using Deepgram for real-time transcription (nova-2, 100ms endpointing)...");
             state.deepgram_stop_flag.store(false, Ordering::SeqCst);
 
             // Create channel for receiving transcripts (now includes is_final flag)
             let (tx, mut rx) = mpsc::channel::<TranscriptMessage>(100);
-            let transcriber = DeepgramTranscriber::new(tx);
+            // Speaker IDs are per stream, so a previous meeting's choice doesn't carry over
+            *state.primary_speaker.lock().map_err(|e| e.to_string())? = None;
+            // Metrics cover the current session only
+            *state.transcription_stats.lock().map_err(|e| e.to_string())? = metrics::TranscriptionStats::default();
+            state.dropped_audio_chunks.store(0, Ordering::Relaxed);
+            let transcriber = DeepgramTranscriber::new(tx)
+                .with_primary_speaker(state.primary_speaker.clone())
+                .with_dropped_chunk_counter(state.dropped_audio_chunks.clone())
+                .with_stats(state.transcription_stats.clone())
+                .with_interim_debounce(state.interim_debounce_ms.clone());
+            spawn_audio_drop_monitor(app.clone(), session);
 
             let app_clone = app.clone();
             let transcription_state = state.transcription.clone();
+            let alert_keywords = state.alert_keywords.clone();
+            let min_segment_length = state.min_segment_length.clone();
+            let save_interim = state.save_interim.clone();
+            let last_mic_speech = state.last_mic_speech.clone();
+            let participant_final_pending = state.participant_final_pending.clone();
 
             // Spawn task to handle incoming transcripts
             tokio::spawn(async move {
-                // Track interim text to replace with final
-                let mut current_interim_index: Option<usize> = None;
+                // Interim text saved in the transcript per speaker, replaced when the final arrives
+                let mut pending_interims: std::collections::HashMap<String, String> = std::collections::HashMap::new();
                 // Track last few final transcripts to deduplicate
                 let mut recent_finals: Vec<String> = Vec::new();
                 const MAX_RECENT: usize = 5;
@@ -320,12 +765,30 @@ async fn start_live_transcription(
                         system_audio::AudioSource::Microphone => "You".to_string(),
                         system_audio::AudioSource::SystemAudio => "Participant".to_string(),
                     };
+                    if msg.source == system_audio::AudioSource::Microphone {
+                        if let Ok(mut last) = last_mic_speech.lock() {
+                            *last = Some(std::time::Instant::now());
+                        }
+                    }
 
                     if msg.is_final {
+                        // The final supersedes any interim we saved for this speaker
+                        if let Some(interim_text) = pending_interims.remove(&speaker_label) {
+                            if let Ok(mut trans) = transcription_state.lock() {
+                                if let Some(idx) = interim_position(&trans, &speaker_label, &interim_text) {
+                                    trans.remove(idx);
+                                }
+                            }
+                        }
+
                         // Check if this is a duplicate final transcript
                         let cleaned_text = clean_transcript(&msg.text);
+                        if !meets_min_length(&cleaned_text, &min_segment_length, settings::DEFAULT_STREAMING_MIN_SEGMENT_LENGTH) {
+                            tracing::info!("Skipping short final transcript: {}", cleaned_text);
+                            continue;
+                        }
                         if recent_finals.contains(&cleaned_text) {
-                            eprintln!("Skipping duplicate final transcript: {}", cleaned_text);
+                            tracing::info!("Skipping duplicate final transcript: {}", cleaned_text);
                             continue;
                         }
 
@@ -336,20 +799,23 @@ async fn start_live_transcription(
                         }
 
                         // Final transcript - add to transcription history
+                        let segment = TranscriptSegment {
+                            timestamp: timestamp.clone(),
+                            speaker: speaker_label.clone(),
+                            text: cleaned_text.clone(),
+                            start_ms: msg.start_ms,
+                            end_ms: msg.end_ms,
+                            speaker_id: msg.speaker,
+                            confidence: msg.confidence,
+                            is_final: true,
+                        };
                         if let Ok(mut trans) = transcription_state.lock() {
-                            // If we had an interim result, remove it
-                            if let Some(idx) = current_interim_index.take() {
-                                if idx < trans.len() {
-                                    trans.remove(idx);
-                                }
-                            }
-                            trans.push(TranscriptSegment {
-                                timestamp: timestamp.clone(),
-                                speaker: speaker_label.clone(),
-                                text: cleaned_text.clone(),
-                                ..Default::default()
-                            });
+                            trans.push(segment.clone());
+                        }
+                        if msg.source == system_audio::AudioSource::SystemAudio {
+                            participant_final_pending.store(true, Ordering::SeqCst);
                         }
+                        emit_keyword_alerts(&app_clone, &alert_keywords, &segment);
 
                         let _ = app_clone.emit("transcript-update", TranscriptEvent {
                             text: cleaned_text,
@@ -358,8 +824,30 @@ async fn start_live_transcription(
                             is_final: true,
                         });
                     } else {
-                        // Interim result - emit for real-time UI feedback
-                        // Don't add to permanent transcription yet
+                        // Interim result - emit for real-time UI feedback.
+                        // Only kept in the transcript when the user asked for interims.
+                        if save_interim.lock().map(|s| *s).unwrap_or(false) {
+                            let segment = TranscriptSegment {
+                                timestamp: timestamp.clone(),
+                                speaker: speaker_label.clone(),
+                                text: msg.text.clone(),
+                                start_ms: msg.start_ms,
+                                end_ms: msg.end_ms,
+                                speaker_id: msg.speaker,
+                                confidence: msg.confidence,
+                                is_final: false,
+                            };
+                            if let Ok(mut trans) = transcription_state.lock() {
+                                let previous = pending_interims.get(&speaker_label)
+                                    .and_then(|text| interim_position(&trans, &speaker_label, text));
+                                match previous {
+                                    Some(idx) => trans[idx] = segment,
+                                    None => trans.push(segment),
+                                }
+                            }
+                            pending_interims.insert(speaker_label.clone(), msg.text.clone());
+                        }
+
                         let _ = app_clone.emit("transcript-update", TranscriptEvent {
                             text: msg.text,
                             timestamp,
@@ -372,6 +860,7 @@ async fn start_live_transcription(
 
             // Start the transcriber with auto-retry on connection failures
             let api_key = deepgram_key.clone();
+            let app = app.clone();
             tokio::spawn(async move {
                 let mut retry_delay_ms: u64 = 1000;
                 let mut consecutive_failures: u32 = 0;
@@ -381,20 +870,45 @@ async fn start_live_transcription(
                 loop {
                     match transcriber.start(&api_key).await {
                         Ok(()) => {
-                            eprintln!("Deepgram transcriber completed normally");
+                            tracing::info!("Deepgram transcriber completed normally");
                             break;
                         }
                         Err(e) => {
                             consecutive_failures += 1;
-                            eprintln!("Deepgram transcriber error (attempt {}): {}", consecutive_failures, e);
+                            tracing::warn!("Deepgram transcriber error (attempt {}): {}", consecutive_failures, e);
+
+                            let state = app.state::<AppState>();
+                            if !state.is_live_transcribing.lock().map(|v| *v).unwrap_or(false) {
+                                break;
+                            }
+
+                            // Couldn't connect at all: keep the session alive with Groq batch transcription
+                            if consecutive_failures == 1 && !groq_key.is_empty() {
+                                tracing::warn!("Deepgram unavailable, falling back to Groq Whisper");
+                                let _ = app.emit("provider-fallback", ProviderFallbackEvent {
+                                    from: TranscriptionProvider::Deepgram,
+                                    to: TranscriptionProvider::Groq,
+                                    reason: e.to_string(),
+                                });
+                                if let Err(e) = start_batch_live_transcription(app.clone(), &state, TranscriptionProvider::Groq, groq_key) {
+                                    tracing::warn!("Groq fallback failed to start: {}", e);
+                                    if let Ok(mut is_live) = state.is_live_transcribing.lock() {
+                                        *is_live = false;
+                                    }
+                                }
+                                break;
+                            }
 
                             if consecutive_failures >= MAX_RETRIES {
-                                eprintln!("Deepgram: Max retries ({}) reached, giving up", MAX_RETRIES);
+                                tracing::warn!("Deepgram: Max retries ({}) reached, giving up", MAX_RETRIES);
+                                if let Ok(mut is_live) = state.is_live_transcribing.lock() {
+                                    *is_live = false;
+                                }
                                 break;
                             }
 
                             // Exponential backoff
-                            eprintln!("Deepgram: Retrying in {}ms...", retry_delay_ms);
+                            tracing::info!("Deepgram: Retrying in {}ms...", retry_delay_ms);
                             tokio::time::sleep(std::time::Duration::from_millis(retry_delay_ms)).await;
 
                             retry_delay_ms = std::cmp::min(retry_delay_ms * 2, MAX_RETRY_DELAY_MS);
@@ -403,460 +917,2997 @@ async fn start_live_transcription(
                 }
             });
         }
-        TranscriptionProvider::Groq | TranscriptionProvider::AssemblyAI => {
-            // Use batch transcription (Groq Whisper or AssemblyAI)
-            let provider_name = match effective_provider {
-                TranscriptionProvider::Groq => "Groq Whisper",
-                TranscriptionProvider::AssemblyAI => "AssemblyAI",
-                _ => "Unknown",
+        TranscriptionProvider::Groq
+        | TranscriptionProvider::AssemblyAI
+        | TranscriptionProvider::OpenAI
+        | TranscriptionProvider::WhisperLocal => {
+            let api_key = match effective_provider {
+                TranscriptionProvider::Groq => groq_key,
+                TranscriptionProvider::OpenAI => openai_key,
+                TranscriptionProvider::WhisperLocal => String::new(),
+                _ => assemblyai_key,
             };
-            eprintln!("Using {} for transcription...", provider_name);
+            start_batch_live_transcription(app, &state, effective_provider, api_key)?;
+        }
+    }
+
+    Ok(())
+}
 
-            let (stop_tx, mut stop_rx) = mpsc::channel::<()>(1);
-            *state.live_stop_signal.lock().map_err(|e| e.to_string())? = Some(stop_tx);
+/// Copy the audio between file offsets `from` and `to` of a recording into a temporary WAV
+/// named after `purpose`, for providers that read a file
+async fn write_audio_range(output_path: &str, from: u64, to: u64, purpose: &str) -> anyhow::Result<std::path::PathBuf> {
+    let (wav, _) = groq::extract_audio_range(output_path, from, to).await?;
+    let chunk_path = std::env::temp_dir().join(format!("vantage-{}-{}.wav", purpose, std::process::id()));
+    tokio::fs::write(&chunk_path, wav).await?;
+    Ok(chunk_path)
+}
 
-            let recorder = audio::AudioRecorder::new().map_err(|e| e.to_string())?;
-            let output_path = recorder.get_output_path().to_string();
-            *state.audio_recorder.lock().map_err(|e| e.to_string())? = Some(recorder);
-            *state.current_recording_path.lock().map_err(|e| e.to_string())? = Some(output_path.clone());
+/// Transcribe the audio between file offsets `from` and `to` with AssemblyAI, for a
+/// low-confidence Groq result
+async fn escalate_new_audio(
+    api_key: &str,
+    output_path: &str,
+    from: u64,
+    to: u64,
+    options: &assemblyai::TranscriptionOptions,
+    jobs: &Mutex<assemblyai::ActiveJobs>,
+) -> Result<String, String> {
+    let chunk_path = write_audio_range(output_path, from, to, "escalation").await.map_err(|e| e.to_string())?;
+    let result = assemblyai::transcribe_file(api_key, &chunk_path.to_string_lossy(), options, jobs).await;
+    let _ = tokio::fs::remove_file(&chunk_path).await;
+    Ok(result.map_err(|e| e.to_string())?.text.unwrap_or_default())
+}
 
-            let transcription_state = state.transcription.clone();
-            let is_live_transcribing = state.is_live_transcribing.clone();
-            let api_key = if effective_provider == TranscriptionProvider::Groq { groq_key } else { assemblyai_key };
-            let use_groq = effective_provider == TranscriptionProvider::Groq;
+/// Transcribe the audio between file offsets `from` and `to` with local Whisper, so each
+/// check only runs the model on what was recorded since the last one
+async fn transcribe_new_audio_locally(model: &str, output_path: &str, from: u64, to: u64) -> anyhow::Result<String> {
+    let chunk_path = write_audio_range(output_path, from, to, "local-whisper").await?;
+    let result = whisper_local::transcribe_file(model, &chunk_path.to_string_lossy()).await;
+    let _ = tokio::fs::remove_file(&chunk_path).await;
+    result
+}
 
-            tokio::spawn(async move {
-                eprintln!("Starting {} transcription...", provider_name);
+/// Live transcription by periodically sending the growing recording to a batch provider
+/// (Groq Whisper, OpenAI Whisper, AssemblyAI or local Whisper)
+fn start_batch_live_transcription(
+    app: AppHandle,
+    state: &AppState,
+    provider: TranscriptionProvider,
+    api_key: String,
+) -> Result<(), String> {
+    let provider_name = match provider {
+        TranscriptionProvider::Groq => "Groq Whisper",
+        TranscriptionProvider::AssemblyAI => "AssemblyAI",
+        TranscriptionProvider::OpenAI => "OpenAI Whisper",
+        TranscriptionProvider::WhisperLocal => "Local Whisper",
+        _ => "Unknown",
+    };
+    tracing::info!("Using {} for transcription...", provider_name);
+
+    let (stop_tx, mut stop_rx) = mpsc::channel::<()>(1);
+    *state.live_stop_signal.lock().map_err(|e| e.to_string())? = Some(stop_tx);
+
+    // A recording started with start_recording is transcribed as it grows, rather than
+    // capturing the mic a second time into another file
+    let owner = *state.recorder_owner.lock().map_err(|e| e.to_string())?;
+    let output_path = match owner {
+        Some(RecorderOwner::Recording) => state.audio_recorder.lock().map_err(|e| e.to_string())?
+            .as_ref()
+            .map(|recorder| recorder.get_output_path().to_string())
+            .ok_or("No active recorder")?,
+        _ => start_recorder(state, RecorderOwner::LiveTranscription)?,
+    };
 
-                const CHECK_INTERVAL_MS: u64 = 4000;  // Check every 4 seconds
-                const MIN_AUDIO_BYTES: u64 = 48_000;
+    let transcription_state = state.transcription.clone();
+    let is_live_transcribing = state.is_live_transcribing.clone();
+    let batch_provider = provider;
+    let assemblyai_options = state.settings.lock().map_err(|e| e.to_string())?.assemblyai.clone();
+    let alert_keywords = state.alert_keywords.clone();
+    let min_segment_length = state.min_segment_length.clone();
+    let batch_options = state.settings.lock().map_err(|e| e.to_string())?.batch_live;
+    let whisper_model = state.settings.lock().map_err(|e| e.to_string())?.whisper_model();
+    let hybrid = state.settings.lock().map_err(|e| e.to_string())?.hybrid_transcription;
+    let assemblyai_key = state.assemblyai_api_key.lock().map_err(|e| e.to_string())?.clone();
+    let assemblyai_jobs = state.live_assemblyai_jobs.clone();
+    let no_speech_notice_secs = state.no_speech_notice_secs.clone();
 
-                let mut last_transcribed_size: u64 = 0;
-                let mut last_full_text = String::new();  // Track last transcription to extract new text
+    tokio::spawn(async move {
+        tracing::info!("Starting {} transcription...", provider_name);
 
-                // Retry state for resilient error handling
-                let mut consecutive_errors: u32 = 0;
-                let mut retry_delay_ms: u64 = 1000;
+        let mut last_transcribed_size: u64 = 0;
+        let mut last_full_text = String::new();  // Track last transcription to extract new text
+        let mut silence = SilenceTracker::new(std::time::Instant::now());
 
-                loop {
-                    tokio::select! {
-                        _ = stop_rx.recv() => {
-                            eprintln!("Received stop signal");
-                            break;
-                        }
-                        _ = tokio::time::sleep(std::time::Duration::from_millis(CHECK_INTERVAL_MS)) => {
-                            if let Ok(metadata) = tokio::fs::metadata(&output_path).await {
-                                let current_size = metadata.len();
-                                let new_audio = current_size.saturating_sub(last_transcribed_size);
-
-                                if new_audio >= MIN_AUDIO_BYTES {
-                                    eprintln!("New audio detected: {} bytes (total: {}MB), transcribing...",
-                                        new_audio, current_size / 1_000_000);
-
-                                    let result = if use_groq {
-                                        groq::transcribe_audio(&api_key, &output_path).await
-                                    } else {
-                                        // AssemblyAI transcription
-                                        assemblyai::transcribe_file(&api_key, &output_path).await
-                                            .map(|r| r.text.unwrap_or_default())
-                                    };
-
-                                    match result {
-                                        Ok(full_text) => {
-                                            // Reset retry state on success
-                                            consecutive_errors = 0;
-                                            retry_delay_ms = 1000;
-
-                                            if !full_text.is_empty() {
-                                                // Extract only the NEW text (what's different from last transcription)
-                                                let new_text = if last_full_text.is_empty() {
-                                                    full_text.clone()
-                                                } else if full_text.len() > last_full_text.len() && full_text.starts_with(&last_full_text) {
-                                                    // New text is appended at the end
-                                                    full_text[last_full_text.len()..].trim().to_string()
-                                                } else if full_text != last_full_text {
-                                                    // Text changed completely, use the full new text
-                                                    full_text.clone()
-                                                } else {
-                                                    // Same text, nothing new
-                                                    String::new()
-                                                };
-
-                                                if !new_text.is_empty() && new_text.len() > 5 {
-                                                    let timestamp = chrono::Local::now().format("%H:%M:%S").to_string();
-
-                                                    if let Ok(mut trans) = transcription_state.lock() {
-                                                        trans.push(TranscriptSegment {
-                                                            timestamp: timestamp.clone(),
-                                                            speaker: "Speaker".to_string(),
-                                                            text: clean_transcript(&new_text),
-                                                            ..Default::default()
-                                                        });
-                                                    }
-
-                                                    let _ = app.emit("transcript-update", TranscriptEvent {
-                                                        text: new_text,
-                                                        timestamp,
-                                                        speaker: "Speaker".to_string(),
-                                                        is_final: true,
-                                                    });
-
-                                                    eprintln!("New transcript segment emitted");
-                                                } else {
-                                                    eprintln!("No new speech detected");
-                                                }
-
-                                                last_full_text = full_text;
+        // Retry state for resilient error handling
+        let mut consecutive_errors: u32 = 0;
+        let mut retry_delay_ms: u64 = 1000;
+
+        loop {
+            tokio::select! {
+                _ = stop_rx.recv() => {
+                    tracing::info!("Received stop signal");
+                    break;
+                }
+                _ = tokio::time::sleep(std::time::Duration::from_millis(batch_options.check_interval_ms)) => {
+                    if let Ok(metadata) = tokio::fs::metadata(&output_path).await {
+                        let current_size = metadata.len();
+                        let new_audio = current_size.saturating_sub(last_transcribed_size);
+
+                        if new_audio >= batch_options.min_audio_bytes {
+                            tracing::info!("New audio detected: {} bytes (total: {}MB), transcribing...",
+                                new_audio, current_size / 1_000_000);
+
+                            let mut new_audio_text: Option<String> = None;
+                            let result = match batch_provider {
+                                TranscriptionProvider::Groq if hybrid.enabled && !assemblyai_key.is_empty() => {
+                                    match groq::transcribe_audio_with_confidence(&api_key, &output_path, last_transcribed_size).await {
+                                        Ok((text, Some(confidence))) if confidence < hybrid.threshold => {
+                                            tracing::info!("Groq confidence {:.2} below {:.2}, escalating to AssemblyAI", confidence, hybrid.threshold);
+                                            // Only the new audio is re-transcribed; Groq's full text stays the
+                                            // baseline for working out what's new next time
+                                            match escalate_new_audio(&assemblyai_key, &output_path, last_transcribed_size, current_size, &assemblyai_options, &assemblyai_jobs).await {
+                                                Ok(new_text) => new_audio_text = Some(new_text),
+                                                Err(e) => tracing::warn!("AssemblyAI escalation failed, keeping Groq result: {}", e),
                                             }
-                                            last_transcribed_size = current_size;
+                                            Ok(text)
                                         }
-                                        Err(e) => {
-                                            consecutive_errors += 1;
-                                            let error_msg = e.to_string();
-                                            eprintln!("Transcription error (attempt {}): {}", consecutive_errors, error_msg);
-
-                                            // Emit retry status to frontend
-                                            let _ = app.emit("transcription-status", serde_json::json!({
-                                                "status": "retrying",
-                                                "error": error_msg,
-                                                "attempt": consecutive_errors,
-                                                "next_retry_ms": retry_delay_ms
-                                            }));
-
-                                            // Exponential backoff with max delay of 30 seconds
-                                            if retry_delay_ms < 30000 {
-                                                retry_delay_ms = std::cmp::min(retry_delay_ms * 2, 30000);
+                                        other => other.map(|(text, _)| text),
+                                    }
+                                }
+                                TranscriptionProvider::Groq => groq::transcribe_audio(&api_key, &output_path).await,
+                                TranscriptionProvider::OpenAI => openai::transcribe_audio(&api_key, &output_path).await,
+                                TranscriptionProvider::WhisperLocal => {
+                                    let text = transcribe_new_audio_locally(&whisper_model, &output_path, last_transcribed_size, current_size).await;
+                                    if let Ok(text) = &text {
+                                        new_audio_text = Some(text.clone());
+                                    }
+                                    text
+                                }
+                                // AssemblyAI transcription
+                                _ => assemblyai::transcribe_file(&api_key, &output_path, &assemblyai_options, &assemblyai_jobs).await
+                                    .map(|r| r.text.unwrap_or_default()),
+                            };
+
+                            match result {
+                                Ok(full_text) => {
+                                    // Reset retry state on success
+                                    consecutive_errors = 0;
+                                    retry_delay_ms = 1000;
+
+                                    if !full_text.is_empty() {
+                                        // Extract only the NEW text (what's different from last transcription)
+                                        let new_text = if let Some(text) = new_audio_text.take() {
+                                            // Only the new audio was transcribed
+                                            text
+                                        } else if last_full_text.is_empty() {
+                                            full_text.clone()
+                                        } else if full_text.len() > last_full_text.len() && full_text.starts_with(&last_full_text) {
+                                            // New text is appended at the end
+                                            full_text[last_full_text.len()..].trim().to_string()
+                                        } else if full_text != last_full_text {
+                                            // Text changed completely, use the full new text
+                                            full_text.clone()
+                                        } else {
+                                            // Same text, nothing new
+                                            String::new()
+                                        };
+
+                                        if !new_text.trim().is_empty() && meets_min_length(&new_text, &min_segment_length, settings::DEFAULT_BATCH_MIN_SEGMENT_LENGTH) {
+                                            let timestamp = chrono::Local::now().format("%H:%M:%S").to_string();
+
+                                            let segment = TranscriptSegment {
+                                                timestamp: timestamp.clone(),
+                                                speaker: "Speaker".to_string(),
+                                                text: clean_transcript(&new_text),
+                                                ..Default::default()
+                                            };
+                                            if let Ok(mut trans) = transcription_state.lock() {
+                                                trans.push(segment.clone());
                                             }
-
-                                            // Wait before next attempt (but still check for stop signal)
-                                            tokio::time::sleep(std::time::Duration::from_millis(retry_delay_ms)).await;
-
-                                            // Continue trying - the loop will automatically retry
+                                            emit_keyword_alerts(&app, &alert_keywords, &segment);
+
+                                            let _ = app.emit("transcript-update", TranscriptEvent {
+                                                text: new_text,
+                                                timestamp,
+                                                speaker: "Speaker".to_string(),
+                                                is_final: true,
+                                            });
+
+                                            tracing::info!("New transcript segment emitted");
+                                            silence.speech(std::time::Instant::now());
+                                        } else {
+                                            tracing::info!("No new speech detected");
                                         }
+
+                                        last_full_text = full_text;
                                     }
+                                    last_transcribed_size = current_size;
+                                }
+                                Err(e) => {
+                                    consecutive_errors += 1;
+                                    let error_msg = e.to_string();
+                                    tracing::warn!("Transcription error (attempt {}): {}", consecutive_errors, error_msg);
+
+                                    // Emit retry status to frontend
+                                    let _ = app.emit("transcription-status", serde_json::json!({
+                                        "status": "retrying",
+                                        "error": error_msg,
+                                        "attempt": consecutive_errors,
+                                        "next_retry_ms": retry_delay_ms
+                                    }));
+
+                                    // Exponential backoff with max delay of 30 seconds
+                                    if retry_delay_ms < 30000 {
+                                        retry_delay_ms = std::cmp::min(retry_delay_ms * 2, 30000);
+                                    }
+
+                                    // Wait before next attempt (but still check for stop signal)
+                                    tokio::time::sleep(std::time::Duration::from_millis(retry_delay_ms)).await;
+
+                                    // Continue trying - the loop will automatically retry
                                 }
                             }
                         }
                     }
-                }
 
-                if let Ok(mut is_live) = is_live_transcribing.lock() {
-                    *is_live = false;
+                    // Checked every tick: with voice activation on, silence adds no audio at all
+                    let notice_secs = no_speech_notice_secs.lock().map(|s| *s).unwrap_or(0);
+                    if let Some(silent_secs) = silence.check(std::time::Instant::now(), notice_secs) {
+                        tracing::info!("No speech for {}s", silent_secs);
+                        let _ = app.emit("no-speech-detected", NoSpeechEvent { silent_secs });
+                    }
                 }
-                eprintln!("{} transcription stopped", provider_name);
-            });
+            }
         }
-    }
+
+        if let Ok(mut is_live) = is_live_transcribing.lock() {
+            *is_live = false;
+        }
+        tracing::info!("{} transcription stopped", provider_name);
+    });
 
     Ok(())
 }
 
-#[tauri::command]
-async fn stop_live_transcription(state: State<'_, AppState>) -> Result<String, String> {
-    // Stop Deepgram if running
-    state.deepgram_stop_flag.store(true, Ordering::SeqCst);
-    if let Some(transcriber) = state.deepgram_transcriber.lock().map_err(|e| e.to_string())?.take() {
-        transcriber.stop();
-        eprintln!("Deepgram transcriber stopped");
-    }
-
-    // Stop AssemblyAI batch mode if running
-    let stop_tx = state.live_stop_signal.lock().map_err(|e| e.to_string())?.take();
-    if let Some(tx) = stop_tx {
-        let _ = tx.send(()).await;
-    }
-
-    // Stop the audio recorder (only used for AssemblyAI batch mode)
-    let audio_path = if let Some(recorder) = state.audio_recorder.lock().map_err(|e| e.to_string())?.take() {
-        recorder.stop().map_err(|e| e.to_string())?
-    } else {
-        String::new()
-    };
+/// Where a speaker's saved interim segment is, found by content since the transcript cap
+/// can shift indices between updates
+fn interim_position(transcript: &[TranscriptSegment], speaker: &str, text: &str) -> Option<usize> {
+    transcript.iter().rposition(|s| !s.is_final && s.speaker == speaker && s.text == text)
+}
 
-    *state.is_live_transcribing.lock().map_err(|e| e.to_string())? = false;
+/// Emitted when live transcription switches provider because the chosen one failed
+#[derive(Debug, Clone, Serialize)]
+struct ProviderFallbackEvent {
+    from: TranscriptionProvider,
+    to: TranscriptionProvider,
+    reason: String,
+}
 
-    Ok(audio_path)
+#[derive(Debug, Clone, Serialize)]
+struct KeywordAlertEvent {
+    keywords: Vec<String>,
+    segment: TranscriptSegment,
 }
 
-#[tauri::command]
-async fn get_meeting_state(state: State<'_, AppState>) -> Result<MeetingState, String> {
-    let has_groq_key = !state.groq_api_key.lock().map_err(|e| e.to_string())?.is_empty();
-    let has_assemblyai_key = !state.assemblyai_api_key.lock().map_err(|e| e.to_string())?.is_empty();
-    let has_deepgram_key = !state.deepgram_api_key.lock().map_err(|e| e.to_string())?.is_empty();
-    let transcription_provider = state.transcription_provider.lock().map_err(|e| e.to_string())?.clone();
+/// Lowercased words of a text, split on anything that isn't part of a word
+fn alert_words(text: &str) -> Vec<String> {
+    text.to_lowercase()
+        .split(|c: char| !c.is_alphanumeric() && c != '\'')
+        .filter(|w| !w.is_empty())
+        .map(|w| w.to_string())
+        .collect()
+}
 
-    Ok(MeetingState {
-        is_recording: *state.is_recording.lock().map_err(|e| e.to_string())?,
-        is_live_transcribing: *state.is_live_transcribing.lock().map_err(|e| e.to_string())?,
-        is_transcribing: *state.is_transcribing.lock().map_err(|e| e.to_string())?,
-        transcription: state.transcription.lock().map_err(|e| e.to_string())?.clone(),
-        summary: state.summary.lock().map_err(|e| e.to_string())?.clone(),
-        suggested_replies: state.suggested_replies.lock().map_err(|e| e.to_string())?.clone(),
-        selected_model: state.selected_model.lock().map_err(|e| e.to_string())?.clone(),
-        transcription_provider,
-        has_groq_key,
-        has_assemblyai_key,
-        has_deepgram_key,
-        current_recording_path: state.current_recording_path.lock().map_err(|e| e.to_string())?.clone(),
-        meeting_context: state.meeting_context.lock().map_err(|e| e.to_string())?.clone(),
-    })
+/// Keywords that appear in the text as whole words (case-insensitive).
+/// Multi-word keywords like "action item" must appear as consecutive words.
+fn match_alert_keywords(text: &str, keywords: &[String]) -> Vec<String> {
+    let words = alert_words(text);
+    keywords
+        .iter()
+        .filter(|keyword| {
+            let phrase = alert_words(keyword);
+            !phrase.is_empty() && words.windows(phrase.len()).any(|w| w == phrase.as_slice())
+        })
+        .cloned()
+        .collect()
 }
 
-#[tauri::command]
-async fn set_groq_api_key(state: State<'_, AppState>, key: String) -> Result<bool, String> {
-    // Basic validation - Groq API keys start with "gsk_"
-    if key.is_empty() {
-        return Ok(false);
+/// Emit `keyword-alert` if a final live segment mentions any alert keyword
+fn emit_keyword_alerts(app: &AppHandle, alert_keywords: &Arc<Mutex<Vec<String>>>, segment: &TranscriptSegment) {
+    let matched = match alert_keywords.lock() {
+        Ok(keywords) if !keywords.is_empty() => match_alert_keywords(&segment.text, &keywords),
+        _ => return,
+    };
+    if matched.is_empty() {
+        return;
     }
 
-    // Save the key to memory
-    *state.groq_api_key.lock().map_err(|e| e.to_string())? = key.clone();
+    tracing::info!("Keyword alert: {:?}", matched);
+    let _ = app.emit("keyword-alert", KeywordAlertEvent {
+        keywords: matched,
+        segment: segment.clone(),
+    });
+}
 
-    // Persist to disk
-    {
-        let mut settings = state.settings.lock().map_err(|e| e.to_string())?;
-        settings.groq_api_key = key.clone();
-        if let Err(e) = settings.save() {
-            eprintln!("Failed to persist settings: {}", e);
-        }
+/// Ollama model to fall back to when Groq is unreachable, if fallback is enabled
+fn llm_fallback_model(state: &AppState) -> Option<String> {
+    let settings = state.settings.lock().ok()?;
+    if settings.llm_fallback_enabled {
+        Some(settings.ollama_model.clone())
+    } else {
+        None
     }
+}
 
-    // Optionally verify with API (but don't block on failure)
-    match groq::check_api_key(&key).await {
-        Ok(true) => {
-            eprintln!("Groq API key verified and saved successfully");
-            Ok(true)
-        }
-        Ok(false) => {
-            eprintln!("Groq API key verification failed, but key saved anyway");
-            // Still return true since we saved it - user can try it
-            Ok(true)
-        }
-        Err(e) => {
-            eprintln!("Groq API key verification error: {}, but key saved anyway", e);
-            // Still return true since we saved it
-            Ok(true)
-        }
+/// The selected LLM provider with its key
+fn llm_client(state: &AppState) -> Result<LlmClient, String> {
+    if state.demo_mode.load(Ordering::SeqCst) {
+        return Ok(LlmClient {
+            provider: LlmProvider::Demo,
+            api_key: "demo".to_string(),
+            system_prompt: String::new(),
+            app: None,
+        });
     }
+
+    let provider = *state.llm_provider.lock().map_err(|e| e.to_string())?;
+    let api_key = match provider {
+        LlmProvider::Groq => state.groq_api_key.lock().map_err(|e| e.to_string())?.clone(),
+        LlmProvider::OpenAI => state.openai_api_key.lock().map_err(|e| e.to_string())?.clone(),
+        LlmProvider::Demo => String::new(),
+    };
+    let system_prompt = state.settings.lock().map_err(|e| e.to_string())?.assistant_persona().to_string();
+    let app = state.app_handle.get().cloned();
+    Ok(LlmClient { provider, api_key, system_prompt, app })
 }
 
-#[tauri::command]
-async fn set_assemblyai_api_key(state: State<'_, AppState>, key: String) -> Result<bool, String> {
-    if !key.is_empty() {
-        *state.assemblyai_api_key.lock().map_err(|e| e.to_string())? = key.clone();
+/// Emitted when a retired Groq model is replaced by the default for one request,
+/// so the UI can ask the user to pick a current model
+#[derive(Debug, Clone, Serialize)]
+struct ModelFallbackEvent {
+    model: String,
+    fallback_model: String,
+    reason: String,
+}
 
-        // Persist to disk
-        let mut settings = state.settings.lock().map_err(|e| e.to_string())?;
-        settings.assemblyai_api_key = key;
-        if let Err(e) = settings.save() {
-            eprintln!("Failed to persist settings: {}", e);
+/// Generate text with the selected provider, warning first if the prompt looks too large
+/// for the model. A retired Groq model is retried once with the default model. If the
+/// provider is unreachable (network error or 5xx) and a fallback is configured, retry once
+/// against the local Ollama server.
+async fn generate_llm(
+    llm: &LlmClient,
+    kind: GenerationKind,
+    model: &str,
+    prompt: &str,
+    params: &groq::GenerationParams,
+    fallback_model: Option<String>,
+) -> Result<String, AppError> {
+    if llm.provider != LlmProvider::Demo {
+        check_prompt_size(llm, model, prompt, params);
+    }
+    let (result, provider) = match llm.provider {
+        LlmProvider::Groq => {
+            let result = groq::generate_with_params(&llm.api_key, model, &llm.system_prompt, prompt, params).await;
+            match result {
+                Err(e) if groq::is_model_unavailable(&e) && model != groq::DEFAULT_MODEL => {
+                    tracing::warn!("Groq model {} is unavailable, retrying with {}: {}", model, groq::DEFAULT_MODEL, e);
+                    if let Some(app) = &llm.app {
+                        let _ = app.emit("model-fallback", ModelFallbackEvent {
+                            model: model.to_string(),
+                            fallback_model: groq::DEFAULT_MODEL.to_string(),
+                            reason: e.to_string(),
+                        });
+                    }
+                    (groq::generate_with_params(&llm.api_key, groq::DEFAULT_MODEL, &llm.system_prompt, prompt, params).await, "groq")
+                }
+                result => (result, "groq"),
+            }
         }
+        LlmProvider::OpenAI => (openai::generate_with_params(&llm.api_key, model, &llm.system_prompt, prompt, params).await, "openai"),
+        LlmProvider::Demo => return Ok(demo::respond(kind)),
+    };
+    let prompt_tokens = tokens::estimate_tokens(&llm.system_prompt) + tokens::estimate_tokens(prompt);
+    let err = match result {
+        Ok(text) => {
+            usage::record_tokens(provider, prompt_tokens, tokens::estimate_tokens(&text));
+            return Ok(text);
+        }
+        Err(e) => e,
+    };
 
-        Ok(true)
-    } else {
-        Ok(false)
+    let Some(mut ollama_model) = fallback_model else {
+        return Err(err.into());
+    };
+    if !groq::is_unavailable(&err) && !openai::is_unavailable(&err) {
+        return Err(err.into());
+    }
+
+    tracing::info!("{}. Trying local Ollama fallback...", err);
+    if !ollama::check_connection().await.unwrap_or(false) {
+        return Err(format!("{} (Ollama fallback not reachable)", err).into());
+    }
+
+    if ollama_model.is_empty() {
+        ollama_model = ollama::list_models().await
+            .ok()
+            .and_then(|models| models.into_iter().next())
+            .ok_or_else(|| format!("{} (no Ollama models installed for fallback)", err))?;
+    }
+
+    tracing::info!("Generating with Ollama model {}", ollama_model);
+    let text = ollama::generate(&ollama_model, &llm.system_prompt, prompt, params.temperature, params.max_tokens)
+        .await
+        .map_err(|e| format!("{} (Ollama fallback failed: {})", err, e))?;
+    usage::record_tokens("ollama", prompt_tokens, tokens::estimate_tokens(&text));
+    Ok(text)
+}
+
+/// Regenerate the rolling summary after this many new transcript segments
+const LIVE_SUMMARY_EVERY_SEGMENTS: usize = 10;
+/// Minimum time between rolling summaries (keeps us well inside Groq rate limits)
+const LIVE_SUMMARY_MIN_INTERVAL_SECS: u64 = 60;
+/// Only the most recent segments feed the rolling summary
+const LIVE_SUMMARY_CONTEXT_SEGMENTS: usize = 40;
+
+#[derive(Clone, Serialize)]
+struct LiveSummaryEvent {
+    summary: String,
+    word_count: usize,
+    segment_count: usize,
+}
+
+/// How often the dropped-audio counter is checked during live transcription
+const AUDIO_DROP_CHECK_INTERVAL_SECS: u64 = 2;
+
+#[derive(Clone, Serialize)]
+struct AudioDropEvent {
+    /// Chunks dropped since the last event
+    dropped: u64,
+    total_dropped: u64,
+}
+
+/// Whether a background task of live session `session` should keep running: transcription
+/// is on and hasn't been restarted since the task was spawned
+fn live_session_active(state: &AppState, session: u64) -> bool {
+    state.live_session.load(Ordering::SeqCst) == session
+        && state.is_live_transcribing.lock().map(|v| *v).unwrap_or(false)
+}
+
+/// Background task that emits `audio-chunks-dropped` when the live audio queue
+/// had to drop chunks because the network couldn't keep up
+fn spawn_audio_drop_monitor(app: AppHandle, session: u64) {
+    tokio::spawn(async move {
+        let mut last_total = app.state::<AppState>().dropped_audio_chunks.load(Ordering::Relaxed);
+
+        loop {
+            tokio::time::sleep(std::time::Duration::from_secs(AUDIO_DROP_CHECK_INTERVAL_SECS)).await;
+
+            let state = app.state::<AppState>();
+            if !live_session_active(&state, session) {
+                break;
+            }
+
+            let total = state.dropped_audio_chunks.load(Ordering::Relaxed);
+            if total > last_total {
+                tracing::warn!("Dropped {} audio chunks (network send is falling behind)", total - last_total);
+                let _ = app.emit("audio-chunks-dropped", AudioDropEvent {
+                    dropped: total - last_total,
+                    total_dropped: total,
+                });
+                last_total = total;
+            }
+        }
+    });
+}
+
+/// How often the live transcript is checked against `max_live_segments`
+const TRANSCRIPT_CAP_CHECK_SECS: u64 = 5;
+
+/// Move the oldest live segments to the on-disk archive until at most `max` remain.
+/// Returns how many were moved.
+fn flush_old_segments(state: &AppState, max: usize) -> Result<usize, String> {
+    let mut transcription = state.transcription.lock().map_err(|e| e.to_string())?;
+    if max == 0 || transcription.len() <= max {
+        return Ok(0);
+    }
+
+    let excess = transcription.len() - max;
+    state.transcript_archive.lock().map_err(|e| e.to_string())?.append(&transcription[..excess])?;
+    // Undo snapshots would otherwise bring the archived segments back into memory
+    state.transcript_history.lock().map_err(|e| e.to_string())?.archive_front(&transcription[..excess]);
+    transcription.drain(..excess);
+    Ok(excess)
+}
+
+/// The whole current transcript: archived segments followed by the in-memory window
+fn full_transcript(state: &AppState) -> Result<Vec<TranscriptSegment>, String> {
+    let transcription = state.transcription.lock().map_err(|e| e.to_string())?;
+    with_archived(state, &transcription)
+}
+
+/// `window` (the in-memory segments) preceded by the archived ones. Call with the
+/// transcription lock held.
+fn with_archived(state: &AppState, window: &[TranscriptSegment]) -> Result<Vec<TranscriptSegment>, String> {
+    let mut segments = state.transcript_archive.lock().map_err(|e| e.to_string())?.load()?;
+    segments.extend(window.iter().cloned());
+    Ok(segments)
+}
+
+/// Number of segments moved out of memory, to turn window positions into transcript indices
+fn archived_segment_count(state: &AppState) -> Result<usize, String> {
+    Ok(state.transcript_archive.lock().map_err(|e| e.to_string())?.len())
+}
+
+/// Position in the in-memory window of transcript segment `index`. Call with the
+/// transcription lock held, so a flush can't shift the window in between.
+fn window_index(state: &AppState, index: usize) -> Result<usize, String> {
+    index
+        .checked_sub(archived_segment_count(state)?)
+        .ok_or_else(|| format!("Segment {} has been moved to disk and can no longer be edited", index))
+}
+
+/// Background task that enforces `max_live_segments` while live transcription is running
+fn spawn_transcript_cap_task(app: AppHandle, session: u64) {
+    tokio::spawn(async move {
+        loop {
+            tokio::time::sleep(std::time::Duration::from_secs(TRANSCRIPT_CAP_CHECK_SECS)).await;
+
+            let state = app.state::<AppState>();
+            if !live_session_active(&state, session) {
+                break;
+            }
+
+            let max = state.max_live_segments.lock().map(|m| *m).unwrap_or(0);
+            match flush_old_segments(&state, max) {
+                Ok(0) => {}
+                Ok(moved) => tracing::info!("Moved {} old transcript segments to disk", moved),
+                Err(e) => tracing::warn!("Failed to archive old transcript segments: {}", e),
+            }
+        }
+    });
+}
+
+/// Where a session stands relative to the recording limit
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum RecordingLimitStage {
+    Running,
+    /// Less than `RECORDING_LIMIT_WARNING_SECS` left
+    Warning,
+    Reached,
+}
+
+/// The `recording-limit-warning` event is sent this long before the cutoff
+const RECORDING_LIMIT_WARNING_SECS: u64 = 60;
+
+fn recording_limit_stage(elapsed: std::time::Duration, max_minutes: u32) -> RecordingLimitStage {
+    let limit_secs = u64::from(max_minutes) * 60;
+    let elapsed_secs = elapsed.as_secs();
+    if elapsed_secs >= limit_secs {
+        RecordingLimitStage::Reached
+    } else if elapsed_secs + RECORDING_LIMIT_WARNING_SECS >= limit_secs {
+        RecordingLimitStage::Warning
+    } else {
+        RecordingLimitStage::Running
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct RecordingLimitEvent {
+    max_minutes: u32,
+    seconds_remaining: u64,
+}
+
+/// Watchdog that stops recording and live transcription once the session has run for
+/// `max_recording_minutes`, warning a minute before. One per session, however it was started:
+/// call it after setting the flag of what was just started.
+fn spawn_recording_limit_task(app: AppHandle) {
+    let state = app.state::<AppState>();
+    let is_recording = state.is_recording.lock().map(|v| *v).unwrap_or(false);
+    let is_live = state.is_live_transcribing.lock().map(|v| *v).unwrap_or(false);
+    if is_recording && is_live {
+        // Joined a session that is already watched
+        return;
+    }
+
+    // A new session; a watchdog of an earlier one that hasn't noticed its stop yet exits
+    let session = state.recording_limit_session.fetch_add(1, Ordering::SeqCst) + 1;
+
+    tokio::spawn(async move {
+        let started = std::time::Instant::now();
+        let mut warned = false;
+
+        loop {
+            tokio::time::sleep(std::time::Duration::from_secs(5)).await;
+
+            let state = app.state::<AppState>();
+            if state.recording_limit_session.load(Ordering::SeqCst) != session {
+                break;
+            }
+            let is_recording = state.is_recording.lock().map(|v| *v).unwrap_or(false);
+            let is_live = state.is_live_transcribing.lock().map(|v| *v).unwrap_or(false);
+            if !is_recording && !is_live {
+                break;
+            }
+
+            let Some(max_minutes) = state.max_recording_minutes.lock().ok().and_then(|m| *m) else {
+                continue;
+            };
+            let elapsed = started.elapsed();
+            match recording_limit_stage(elapsed, max_minutes) {
+                RecordingLimitStage::Running => {}
+                RecordingLimitStage::Warning => {
+                    if !warned {
+                        warned = true;
+                        let _ = app.emit("recording-limit-warning", RecordingLimitEvent {
+                            max_minutes,
+                            seconds_remaining: (u64::from(max_minutes) * 60).saturating_sub(elapsed.as_secs()),
+                        });
+                    }
+                }
+                RecordingLimitStage::Reached => {
+                    tracing::info!("Recording limit of {} minutes reached, stopping", max_minutes);
+                    if is_recording {
+                        if let Err(e) = stop_recording(app.clone(), app.state()).await {
+                            tracing::warn!("Failed to auto-stop recording: {}", e);
+                        }
+                    }
+                    if is_live {
+                        if let Err(e) = stop_live_transcription(app.clone(), app.state()).await {
+                            tracing::warn!("Failed to auto-stop live transcription: {}", e);
+                        }
+                    }
+                    let _ = app.emit("recording-auto-stopped", RecordingLimitEvent {
+                        max_minutes,
+                        seconds_remaining: 0,
+                    });
+                    break;
+                }
+            }
+        }
+    });
+}
+
+/// How often the in-progress meeting is auto-saved while transcribing
+const AUTOSAVE_INTERVAL_SECS: u64 = 60;
+
+/// Title for a meeting saved without one: the calendar event's, or the date and time
+fn current_meeting_title(state: &AppState) -> Result<String, String> {
+    Ok(match state.calendar_event.lock().map_err(|e| e.to_string())?.as_ref() {
+        Some(event) => event.title.clone(),
+        None => format!("Meeting {}", chrono::Local::now().format("%Y-%m-%d %H:%M")),
+    })
+}
+
+/// Seconds since recording or live transcription started
+fn current_meeting_duration(state: &AppState) -> Result<Option<u64>, String> {
+    Ok(state.meeting_started_at.lock().map_err(|e| e.to_string())?
+        .map(|started| started.elapsed().as_secs()))
+}
+
+/// Result of the post-meeting pipeline, emitted as `meeting-processed` and sent to the webhook
+#[derive(Debug, Clone, Serialize)]
+struct MeetingProcessedEvent {
+    meeting_id: Option<String>,
+    title: String,
+    summary: Option<MeetingSummary>,
+    /// Steps that failed; the others still ran
+    errors: Vec<String>,
+}
+
+/// Run the configured post-meeting steps (summary, save, webhook) after a meeting stops.
+/// A session that only recorded has no transcript yet, so `recording_path` is transcribed first.
+async fn run_post_meeting_actions(app: AppHandle, recording_path: Option<String>) {
+    let state = app.state::<AppState>();
+    let Ok(actions) = state.settings.lock().map(|s| s.post_meeting.clone()) else {
+        return;
+    };
+    let mut errors = Vec::new();
+    let mut has_transcript = !full_transcript(&state).map_or(true, |t| t.is_empty());
+    if !has_transcript {
+        let Some(path) = recording_path else {
+            return;
+        };
+        // Deepgram only streams, so recordings go to Groq instead
+        let provider = match state.transcription_provider.lock().map(|p| p.clone()) {
+            Ok(TranscriptionProvider::Deepgram) | Err(_) => TranscriptionProvider::Groq,
+            Ok(provider) => provider,
+        };
+        match transcribe_with_provider(&state, &provider, &path).await {
+            Ok(segments) if !segments.is_empty() => {
+                if let Ok(mut transcription) = state.transcription.lock() {
+                    // Undoable like any other replacement of the transcript
+                    if let Ok(mut history) = state.transcript_history.lock() {
+                        history.record(transcription.clone(), segments.len());
+                    }
+                    *transcription = segments;
+                    has_transcript = true;
+                }
+            }
+            Ok(_) => errors.push("Transcription: No speech found in the recording".to_string()),
+            Err(e) => {
+                tracing::warn!("Post-meeting transcription of {} failed: {}", path, e);
+                errors.push(format!("Transcription: {}", e));
+            }
+        }
+    }
+    tracing::info!("Running post-meeting actions");
+
+    let title = current_meeting_title(&state).unwrap_or_default();
+
+    // Without a transcript there is nothing to summarize or save, but the failure is still
+    // reported below
+    let summary = if actions.generate_summary && has_transcript {
+        generate_structured_summary(app.state())
+            .await
+            .map_err(|e| errors.push(format!("Summary: {}", e)))
+            .ok()
+    } else {
+        None
+    };
+
+    let meeting_id = if actions.save_meeting && has_transcript {
+        let duration_seconds = current_meeting_duration(&state).ok().flatten();
+        save_meeting(app.state(), title.clone(), Vec::new(), None, duration_seconds, None, summary.clone())
+            .await
+            .map_err(|e| errors.push(format!("Save: {}", e)))
+            .ok()
+    } else {
+        None
+    };
+
+    let processed = MeetingProcessedEvent { meeting_id, title, summary, errors };
+
+    let webhook_url = actions.webhook_url.trim();
+    if !webhook_url.is_empty() {
+        let result = net::client()
+            .post(webhook_url)
+            .json(&processed)
+            .timeout(std::time::Duration::from_secs(30))
+            .send()
+            .await
+            .and_then(|r| r.error_for_status());
+        if let Err(e) = result {
+            tracing::warn!("Post-meeting webhook failed: {}", e);
+        }
+    }
+
+    let _ = app.emit("meeting-processed", processed);
+}
+
+/// Write the in-progress meeting to the meetings database under a stable ID, so a crash
+/// or a forgotten save doesn't lose it. Each write is a single SQLite transaction.
+/// Returns the meeting ID, or None when there is nothing to save yet.
+fn autosave_meeting(state: &AppState) -> Result<Option<String>, String> {
+    if state.demo_mode.load(Ordering::SeqCst) {
+        return Ok(None);
+    }
+    let transcription = full_transcript(state)?;
+    if transcription.is_empty() {
+        return Ok(None);
+    }
+
+    let summary_text = state.summary.lock().map_err(|e| e.to_string())?.clone();
+    let summary = (!summary_text.is_empty()).then(|| parse_text_summary(&summary_text));
+    let event = state.calendar_event.lock().map_err(|e| e.to_string())?.clone();
+    let title = current_meeting_title(state)?;
+    let duration_seconds = current_meeting_duration(state)?;
+
+    let mut meeting = database::create_meeting_from_transcript(
+        title,
+        transcription,
+        summary,
+        state.meeting_attendees.lock().map_err(|e| e.to_string())?.clone(),
+        event.map(|e| e.id),
+        state.current_recording_path.lock().map_err(|e| e.to_string())?.clone(),
+        duration_seconds,
+    );
+    meeting.notes = state.notes.lock().map_err(|e| e.to_string())?.clone();
+    meeting.bookmarks = state.bookmarks.lock().map_err(|e| e.to_string())?.clone();
+
+    let mut autosave_id = state.autosave_meeting_id.lock().map_err(|e| e.to_string())?;
+    let mut db = state.meetings_db.lock().map_err(|e| e.to_string())?;
+    if let Some(existing) = autosave_id.as_deref().and_then(|id| db.get_meeting(id)) {
+        // Keep anything the user changed in the meeting history since the last auto-save
+        meeting.id = existing.id.clone();
+        meeting.title = existing.title.clone();
+        meeting.date = existing.date.clone();
+        meeting.created_at = existing.created_at.clone();
+        meeting.tags = existing.tags.clone();
+        meeting.chapters = existing.chapters.clone();
+        meeting.alternate_transcripts = existing.alternate_transcripts.clone();
+    }
+
+    let id = meeting.id.clone();
+    db.upsert_meeting(meeting)?;
+    *autosave_id = Some(id.clone());
+    Ok(Some(id))
+}
+
+/// Background task that auto-saves the meeting every `AUTOSAVE_INTERVAL_SECS` while transcribing
+fn spawn_autosave_task(app: AppHandle, session: u64) {
+    tokio::spawn(async move {
+        loop {
+            tokio::time::sleep(std::time::Duration::from_secs(AUTOSAVE_INTERVAL_SECS)).await;
+
+            let state = app.state::<AppState>();
+            if !live_session_active(&state, session) {
+                break;
+            }
+
+            match autosave_meeting(&state) {
+                Ok(Some(id)) => tracing::info!("Auto-saved meeting {}", id),
+                Ok(None) => {}
+                Err(e) => tracing::warn!("Failed to auto-save meeting: {}", e),
+            }
+        }
+    });
+}
+
+/// Background task that keeps a short "notes so far" summary up to date
+/// while live transcription is running. Emits `live-summary-update`.
+fn spawn_live_summary_task(app: AppHandle, session: u64) {
+    tokio::spawn(async move {
+        let mut last_summarized_count: usize = 0;
+        let mut last_run: Option<std::time::Instant> = None;
+
+        loop {
+            tokio::time::sleep(std::time::Duration::from_secs(15)).await;
+
+            let state = app.state::<AppState>();
+            if !live_session_active(&state, session) {
+                break;
+            }
+
+            let transcription = match state.transcription.lock() {
+                Ok(t) => t.clone(),
+                Err(_) => continue,
+            };
+            // Transcript was cleared mid-session
+            if transcription.len() < last_summarized_count {
+                last_summarized_count = 0;
+            }
+            if transcription.len() - last_summarized_count < LIVE_SUMMARY_EVERY_SEGMENTS {
+                continue;
+            }
+            if last_run.map_or(false, |t| t.elapsed().as_secs() < LIVE_SUMMARY_MIN_INTERVAL_SECS) {
+                continue;
+            }
+
+            let Ok(llm) = llm_client(&state) else {
+                continue;
+            };
+            let model = state.selected_model.lock().map(|m| m.clone()).unwrap_or_default();
+            let params = state.settings.lock().map(|s| s.generation.extraction).unwrap_or_else(|_| groq::GenerationParams::extraction());
+            if llm.api_key.is_empty() {
+                continue;
+            }
+
+            let recent: String = transcription
+                .iter()
+                .rev()
+                .take(LIVE_SUMMARY_CONTEXT_SEGMENTS)
+                .rev()
+                .map(|s| format!("{}: {}", s.speaker, s.text))
+                .collect::<Vec<_>>()
+                .join("\n");
+
+            let prompt = format!(
+                "Summarize this ongoing meeting so far in 3-5 short bullet points (start each with \"• \"). Focus on topics, decisions and open questions. No preamble.\n\nRECENT TRANSCRIPT:\n{}",
+                recent
+            );
+
+            last_run = Some(std::time::Instant::now());
+            let fallback = llm_fallback_model(&state);
+            match generate_llm(&llm, GenerationKind::LiveSummary, &model, &prompt, &params, fallback).await {
+                Ok(summary) => {
+                    last_summarized_count = transcription.len();
+                    let word_count = transcription.iter().map(|s| s.text.split_whitespace().count()).sum();
+
+                    if let Ok(mut live_summary) = state.live_summary.lock() {
+                        *live_summary = summary.clone();
+                    }
+                    let _ = app.emit("live-summary-update", LiveSummaryEvent {
+                        summary,
+                        word_count,
+                        segment_count: transcription.len(),
+                    });
+                }
+                Err(e) => tracing::warn!("Live summary generation failed: {}", e),
+            }
+        }
+
+        tracing::info!("Live summary task stopped");
+    });
+}
+
+#[tauri::command]
+async fn stop_live_transcription(app: AppHandle, state: State<'_, AppState>) -> Result<String, AppError> {
+    // Stop Deepgram if running
+    state.deepgram_stop_flag.store(true, Ordering::SeqCst);
+    if let Some(transcriber) = state.deepgram_transcriber.lock().map_err(|e| e.to_string())?.take() {
+        transcriber.stop();
+        tracing::info!("Deepgram transcriber stopped");
+        let audio_seconds = state.transcription_stats.lock().map_err(|e| e.to_string())?.snapshot(0).audio_seconds;
+        usage::record_audio("deepgram", audio_seconds);
+    }
+
+    // Stop AssemblyAI batch mode if running
+    let stop_tx = state.live_stop_signal.lock().map_err(|e| e.to_string())?.take();
+    if let Some(tx) = stop_tx {
+        let _ = tx.send(()).await;
+
+        // A job still uploading or polling would otherwise run (and bill) to completion.
+        // Only the live session's jobs; a manual transcription keeps going.
+        let api_key = state.assemblyai_api_key.lock().map_err(|e| e.to_string())?.clone();
+        let cancelled = assemblyai::cancel_jobs(&api_key, &state.live_assemblyai_jobs).await;
+        if cancelled > 0 {
+            tracing::info!("Cancelled {} live AssemblyAI job(s)", cancelled);
+        }
+    }
+
+    // Stop the audio recorder if batch mode started it. A recording started with
+    // start_recording keeps going until stop_recording.
+    let audio_path = stop_recorder(&state, RecorderOwner::LiveTranscription)?.unwrap_or_default();
+
+    *state.is_live_transcribing.lock().map_err(|e| e.to_string())? = false;
+
+    // Final auto-save, so the finished meeting is kept even if it's never saved explicitly
+    match autosave_meeting(&state) {
+        Ok(Some(id)) => tracing::info!("Auto-saved finished meeting {}", id),
+        Ok(None) => {}
+        Err(e) => tracing::warn!("Failed to auto-save meeting: {}", e),
+    }
+
+    finish_session_if_idle(&app, &state);
+    stop_watching_meeting_if_idle(&state).await;
+
+    Ok(audio_path)
+}
+
+/// Recording flags, provider, model and key presence, without cloning the transcript
+#[tauri::command]
+async fn get_status(state: State<'_, AppState>) -> Result<MeetingStatus, AppError> {
+    Ok(MeetingStatus {
+        is_recording: *state.is_recording.lock().map_err(|e| e.to_string())?,
+        is_live_transcribing: *state.is_live_transcribing.lock().map_err(|e| e.to_string())?,
+        is_transcribing: *state.is_transcribing.lock().map_err(|e| e.to_string())?,
+        selected_model: state.selected_model.lock().map_err(|e| e.to_string())?.clone(),
+        transcription_provider: state.transcription_provider.lock().map_err(|e| e.to_string())?.clone(),
+        has_groq_key: !state.groq_api_key.lock().map_err(|e| e.to_string())?.is_empty(),
+        has_assemblyai_key: !state.assemblyai_api_key.lock().map_err(|e| e.to_string())?.is_empty(),
+        has_deepgram_key: !state.deepgram_api_key.lock().map_err(|e| e.to_string())?.is_empty(),
+        has_openai_key: !state.openai_api_key.lock().map_err(|e| e.to_string())?.is_empty(),
+        llm_provider: *state.llm_provider.lock().map_err(|e| e.to_string())?,
+        is_demo_mode: state.demo_mode.load(Ordering::SeqCst),
+        segment_count: archived_segment_count(&state)? + state.transcription.lock().map_err(|e| e.to_string())?.len(),
+    })
+}
+
+/// Full transcript of the current meeting
+#[tauri::command]
+async fn get_transcription(state: State<'_, AppState>) -> Result<Vec<TranscriptSegment>, AppError> {
+    Ok(full_transcript(&state)?)
+}
+
+/// Current meeting summary (empty until one is generated)
+#[tauri::command]
+async fn get_summary(state: State<'_, AppState>) -> Result<String, AppError> {
+    Ok(state.summary.lock().map_err(|e| e.to_string())?.clone())
+}
+
+/// Copy the current summary to the clipboard as markdown. Returns the copied text.
+#[tauri::command]
+async fn copy_summary_to_clipboard(app: AppHandle, state: State<'_, AppState>) -> Result<String, AppError> {
+    let summary = state.summary.lock().map_err(|e| e.to_string())?.clone();
+    if summary.trim().is_empty() {
+        return Err("No summary to copy. Generate one first.".into());
+    }
+
+    // Fall back to the text as generated if it doesn't have recognizable sections
+    let markdown = summary_to_markdown(&parse_text_summary(&summary));
+    let text = if markdown.is_empty() { summary } else { markdown };
+
+    app.clipboard().write_text(text.clone()).map_err(|e| e.to_string())?;
+    Ok(text)
+}
+
+/// Copy the transcript to the clipboard as `[time] Speaker: text` lines. Returns the copied text.
+#[tauri::command]
+async fn copy_transcript_to_clipboard(app: AppHandle, state: State<'_, AppState>) -> Result<String, AppError> {
+    let transcription = full_transcript(&state)?;
+    if transcription.is_empty() {
+        return Err("No transcript to copy".into());
+    }
+
+    let text = format_transcript(&transcription);
+    app.clipboard().write_text(text.clone()).map_err(|e| e.to_string())?;
+    Ok(text)
+}
+
+/// Latency, audio processed and dropped chunks for the current (or last) live session.
+/// Only Deepgram streaming records latency and throughput; the AssemblyAI realtime client
+/// only counts dropped chunks, and batch providers leave the metrics empty.
+#[tauri::command]
+async fn get_transcription_metrics(state: State<'_, AppState>) -> Result<metrics::TranscriptionMetrics, AppError> {
+    let dropped = state.dropped_audio_chunks.load(Ordering::Relaxed);
+    Ok(state.transcription_stats.lock().map_err(|e| e.to_string())?.snapshot(dropped))
+}
+
+/// Audio minutes transcribed and LLM tokens used per provider since the last reset,
+/// to keep an eye on free-tier limits
+#[tauri::command]
+async fn get_usage_stats() -> Result<usage::UsageStats, AppError> {
+    Ok(usage::snapshot())
+}
+
+/// Background task that writes changed usage counters to disk every `usage::SAVE_INTERVAL_SECS`
+fn spawn_usage_save_task() {
+    tauri::async_runtime::spawn(async {
+        loop {
+            tokio::time::sleep(std::time::Duration::from_secs(usage::SAVE_INTERVAL_SECS)).await;
+            usage::save();
+        }
+    });
+}
+
+/// Clear the usage counters and start counting from now
+#[tauri::command]
+async fn reset_usage_stats() -> Result<usage::UsageStats, AppError> {
+    let stats = usage::reset()?;
+    tracing::info!("Usage stats reset");
+    Ok(stats)
+}
+
+/// Transcript segments from `since_index` on, for UIs that poll instead of listening
+/// for events. Pass the number of segments already fetched.
+#[tauri::command]
+async fn get_latest_segments(state: State<'_, AppState>, since_index: usize) -> Result<Vec<TranscriptSegment>, AppError> {
+    // Segments already moved to disk were fetched long ago; indices count them
+    let transcription = state.transcription.lock().map_err(|e| e.to_string())?;
+    let window_start = since_index.saturating_sub(archived_segment_count(&state)?);
+    Ok(transcription.get(window_start..).map(|s| s.to_vec()).unwrap_or_default())
+}
+
+#[tauri::command]
+async fn get_meeting_state(state: State<'_, AppState>) -> Result<MeetingState, AppError> {
+    let has_groq_key = !state.groq_api_key.lock().map_err(|e| e.to_string())?.is_empty();
+    let has_assemblyai_key = !state.assemblyai_api_key.lock().map_err(|e| e.to_string())?.is_empty();
+    let has_deepgram_key = !state.deepgram_api_key.lock().map_err(|e| e.to_string())?.is_empty();
+    let has_openai_key = !state.openai_api_key.lock().map_err(|e| e.to_string())?.is_empty();
+    let llm_provider = *state.llm_provider.lock().map_err(|e| e.to_string())?;
+    let transcription_provider = state.transcription_provider.lock().map_err(|e| e.to_string())?.clone();
+
+    Ok(MeetingState {
+        is_recording: *state.is_recording.lock().map_err(|e| e.to_string())?,
+        is_live_transcribing: *state.is_live_transcribing.lock().map_err(|e| e.to_string())?,
+        is_transcribing: *state.is_transcribing.lock().map_err(|e| e.to_string())?,
+        transcription: state.transcription.lock().map_err(|e| e.to_string())?.clone(),
+        summary: state.summary.lock().map_err(|e| e.to_string())?.clone(),
+        live_summary: state.live_summary.lock().map_err(|e| e.to_string())?.clone(),
+        suggested_replies: state.suggested_replies.lock().map_err(|e| e.to_string())?.clone(),
+        selected_model: state.selected_model.lock().map_err(|e| e.to_string())?.clone(),
+        transcription_provider,
+        has_groq_key,
+        has_assemblyai_key,
+        has_deepgram_key,
+        has_openai_key,
+        llm_provider,
+        is_demo_mode: state.demo_mode.load(Ordering::SeqCst),
+        current_recording_path: state.current_recording_path.lock().map_err(|e| e.to_string())?.clone(),
+        meeting_context: state.meeting_context.lock().map_err(|e| e.to_string())?.clone(),
+        summary_template: state.summary_template.lock().map_err(|e| e.to_string())?.clone(),
+        attendees: state.meeting_attendees.lock().map_err(|e| e.to_string())?.clone(),
+        calendar_event: state.calendar_event.lock().map_err(|e| e.to_string())?.clone(),
+        notes: state.notes.lock().map_err(|e| e.to_string())?.clone(),
+        bookmarks: state.bookmarks.lock().map_err(|e| e.to_string())?.clone(),
+    })
+}
+
+#[tauri::command]
+async fn set_groq_api_key(state: State<'_, AppState>, key: String) -> Result<bool, AppError> {
+    // Basic validation - Groq API keys start with "gsk_"
+    if key.is_empty() {
+        return Ok(false);
+    }
+
+    // Save the key to memory
+    *state.groq_api_key.lock().map_err(|e| e.to_string())? = key.clone();
+    *state.model_cache.lock().map_err(|e| e.to_string())? = None;
+
+    // Persist to disk
+    {
+        let mut settings = state.settings.lock().map_err(|e| e.to_string())?;
+        settings.groq_api_key = key.clone();
+        if let Err(e) = settings.save() {
+            tracing::warn!("Failed to persist settings: {}", e);
+        }
+    }
+
+    // Optionally verify with API (but don't block on failure)
+    match groq::check_api_key(&key).await {
+        Ok(true) => {
+            tracing::info!("Groq API key verified and saved successfully");
+            Ok(true)
+        }
+        Ok(false) => {
+            tracing::warn!("Groq API key verification failed, but key saved anyway");
+            // Still return true since we saved it - user can try it
+            Ok(true)
+        }
+        Err(e) => {
+            tracing::warn!("Groq API key verification error: {}, but key saved anyway", e);
+            // Still return true since we saved it
+            Ok(true)
+        }
+    }
+}
+
+#[tauri::command]
+async fn set_assemblyai_api_key(state: State<'_, AppState>, key: String) -> Result<bool, AppError> {
+    if !key.is_empty() {
+        *state.assemblyai_api_key.lock().map_err(|e| e.to_string())? = key.clone();
+
+        // Persist to disk
+        let mut settings = state.settings.lock().map_err(|e| e.to_string())?;
+        settings.assemblyai_api_key = key;
+        if let Err(e) = settings.save() {
+            tracing::warn!("Failed to persist settings: {}", e);
+        }
+
+        Ok(true)
+    } else {
+        Ok(false)
+    }
+}
+
+#[tauri::command]
+async fn set_deepgram_api_key(state: State<'_, AppState>, key: String) -> Result<bool, AppError> {
+    if !key.is_empty() {
+        *state.deepgram_api_key.lock().map_err(|e| e.to_string())? = key.clone();
+
+        // Persist to disk
+        let mut settings = state.settings.lock().map_err(|e| e.to_string())?;
+        settings.deepgram_api_key = key;
+        if let Err(e) = settings.save() {
+            tracing::warn!("Failed to persist settings: {}", e);
+        }
+
+        Ok(true)
+    } else {
+        Ok(false)
+    }
+}
+
+#[tauri::command]
+async fn set_openai_api_key(state: State<'_, AppState>, key: String) -> Result<bool, AppError> {
+    if key.is_empty() {
+        return Ok(false);
+    }
+
+    *state.openai_api_key.lock().map_err(|e| e.to_string())? = key.clone();
+
+    // Persist to disk
+    {
+        let mut settings = state.settings.lock().map_err(|e| e.to_string())?;
+        settings.openai_api_key = key.clone();
+        if let Err(e) = settings.save() {
+            tracing::warn!("Failed to persist settings: {}", e);
+        }
+    }
+
+    // Verify, but keep the key either way
+    match openai::check_api_key(&key).await {
+        Ok(true) => tracing::info!("OpenAI API key verified and saved successfully"),
+        _ => tracing::warn!("OpenAI API key verification failed, but key saved anyway"),
+    }
+    Ok(true)
+}
+
+/// Switch the chat provider used for summaries and replies ("groq" or "openai").
+/// The selected model is reset to the provider's default if it belongs to the other one.
+/// Returns the selected model.
+#[tauri::command]
+async fn set_llm_provider(state: State<'_, AppState>, provider: String) -> Result<String, AppError> {
+    let provider_enum = match provider.to_lowercase().as_str() {
+        "groq" => LlmProvider::Groq,
+        "openai" => LlmProvider::OpenAI,
+        _ => return Err(format!("Unknown LLM provider: {}", provider).into()),
+    };
+    *state.llm_provider.lock().map_err(|e| e.to_string())? = provider_enum;
+
+    let model = {
+        let mut model = state.selected_model.lock().map_err(|e| e.to_string())?;
+        let is_openai_model = openai::is_openai_model(&model);
+        match provider_enum {
+            LlmProvider::OpenAI if !is_openai_model => *model = openai::DEFAULT_MODEL.to_string(),
+            LlmProvider::Groq if is_openai_model => *model = groq::DEFAULT_MODEL.to_string(),
+            _ => {}
+        }
+        model.clone()
+    };
+
+    // Persist to disk
+    let mut settings = state.settings.lock().map_err(|e| e.to_string())?;
+    settings.llm_provider = provider.to_lowercase();
+    settings.selected_model = model.clone();
+    if let Err(e) = settings.save() {
+        tracing::warn!("Failed to persist settings: {}", e);
+    }
+
+    Ok(model)
+}
+
+#[tauri::command]
+async fn set_model(state: State<'_, AppState>, model: String) -> Result<(), AppError> {
+    *state.selected_model.lock().map_err(|e| e.to_string())? = model.clone();
+
+    // Persist to disk
+    let mut settings = state.settings.lock().map_err(|e| e.to_string())?;
+    settings.selected_model = model;
+    if let Err(e) = settings.save() {
+        tracing::warn!("Failed to persist settings: {}", e);
+    }
+
+    Ok(())
+}
+
+#[tauri::command]
+async fn set_transcription_provider(state: State<'_, AppState>, provider: String) -> Result<(), AppError> {
+    let provider_enum = TranscriptionProvider::parse(&provider)?;
+    *state.transcription_provider.lock().map_err(|e| e.to_string())? = provider_enum;
+
+    // Persist to disk
+    let mut settings = state.settings.lock().map_err(|e| e.to_string())?;
+    settings.transcription_provider = provider;
+    if let Err(e) = settings.save() {
+        tracing::warn!("Failed to persist settings: {}", e);
+    }
+
+    Ok(())
+}
+
+#[tauri::command]
+async fn set_meeting_context(state: State<'_, AppState>, context: String) -> Result<(), AppError> {
+    *state.meeting_context.lock().map_err(|e| e.to_string())? = context.clone();
+
+    // Persist to disk
+    let mut settings = state.settings.lock().map_err(|e| e.to_string())?;
+    settings.meeting_context = context;
+    if let Err(e) = settings.save() {
+        tracing::warn!("Failed to persist settings: {}", e);
+    }
+
+    tracing::info!("Meeting context updated");
+    Ok(())
+}
+
+/// Set LLM temperature and max_tokens for a task type ("extraction" or "replies")
+#[tauri::command]
+async fn set_generation_params(
+    state: State<'_, AppState>,
+    task: String,
+    temperature: f32,
+    max_tokens: u32,
+) -> Result<(), AppError> {
+    let params = groq::GenerationParams { temperature, max_tokens };
+    params.validate()?;
+
+    let mut settings = state.settings.lock().map_err(|e| e.to_string())?;
+    match task.to_lowercase().as_str() {
+        "extraction" | "summary" => settings.generation.extraction = params,
+        "replies" => settings.generation.replies = params,
+        _ => return Err(format!("Unknown generation task: {}", task).into()),
+    }
+    if let Err(e) = settings.save() {
+        tracing::warn!("Failed to persist settings: {}", e);
+    }
+
+    Ok(())
+}
+
+/// Enable/disable falling back to a local Ollama model when Groq is unreachable
+#[tauri::command]
+async fn set_llm_fallback(
+    state: State<'_, AppState>,
+    enabled: bool,
+    ollama_model: Option<String>,
+) -> Result<bool, AppError> {
+    {
+        let mut settings = state.settings.lock().map_err(|e| e.to_string())?;
+        settings.llm_fallback_enabled = enabled;
+        if let Some(model) = ollama_model {
+            settings.ollama_model = model;
+        }
+        if let Err(e) = settings.save() {
+            tracing::warn!("Failed to persist settings: {}", e);
+        }
+    }
+
+    // Report whether Ollama is reachable right now so the UI can warn
+    let reachable = enabled && ollama::check_connection().await.unwrap_or(false);
+    if enabled && !reachable {
+        tracing::info!("LLM fallback enabled but Ollama is not reachable at the moment");
+    }
+    Ok(reachable)
+}
+
+/// Get the LLM generation parameters for each task type
+#[tauri::command]
+async fn get_generation_params(state: State<'_, AppState>) -> Result<settings::GenerationSettings, AppError> {
+    Ok(state.settings.lock().map_err(|e| e.to_string())?.generation.clone())
+}
+
+/// Set words/phrases that trigger a `keyword-alert` event when spoken (e.g. your name, "deadline")
+#[tauri::command]
+async fn set_alert_keywords(state: State<'_, AppState>, words: Vec<String>) -> Result<Vec<String>, AppError> {
+    let mut keywords: Vec<String> = Vec::new();
+    for word in words {
+        let word = word.trim().to_string();
+        if !word.is_empty() && !keywords.iter().any(|k| k.eq_ignore_ascii_case(&word)) {
+            keywords.push(word);
+        }
+    }
+
+    *state.alert_keywords.lock().map_err(|e| e.to_string())? = keywords.clone();
+
+    let mut settings = state.settings.lock().map_err(|e| e.to_string())?;
+    settings.alert_keywords = keywords.clone();
+    if let Err(e) = settings.save() {
+        tracing::warn!("Failed to persist settings: {}", e);
+    }
+
+    Ok(keywords)
+}
+
+/// Get the current alert keywords
+#[tauri::command]
+async fn get_alert_keywords(state: State<'_, AppState>) -> Result<Vec<String>, AppError> {
+    Ok(state.alert_keywords.lock().map_err(|e| e.to_string())?.clone())
+}
+
+/// Set the minimum length (in characters) of live segments added to the transcript.
+/// 0 keeps every utterance.
+#[tauri::command]
+async fn set_min_segment_length(state: State<'_, AppState>, chars: usize) -> Result<usize, AppError> {
+    *state.min_segment_length.lock().map_err(|e| e.to_string())? = Some(chars);
+
+    let mut settings = state.settings.lock().map_err(|e| e.to_string())?;
+    settings.min_segment_length = Some(chars);
+    if let Err(e) = settings.save() {
+        tracing::warn!("Failed to persist settings: {}", e);
+    }
+
+    Ok(chars)
+}
+
+/// Set the minimum time between interim transcript updates per channel.
+/// Finals are never delayed; 0 passes every interim result through.
+#[tauri::command]
+async fn set_interim_debounce_ms(state: State<'_, AppState>, ms: u64) -> Result<u64, AppError> {
+    *state.interim_debounce_ms.lock().map_err(|e| e.to_string())? = ms;
+
+    let mut settings = state.settings.lock().map_err(|e| e.to_string())?;
+    settings.interim_debounce_ms = Some(ms);
+    if let Err(e) = settings.save() {
+        tracing::warn!("Failed to persist settings: {}", e);
+    }
+
+    Ok(ms)
+}
+
+/// Choose whether interim results are kept in the transcript (replaced once final)
+/// or only finals are saved. Interims are emitted for display either way.
+#[tauri::command]
+async fn set_save_interim(state: State<'_, AppState>, enabled: bool) -> Result<bool, AppError> {
+    *state.save_interim.lock().map_err(|e| e.to_string())? = enabled;
+
+    let mut settings = state.settings.lock().map_err(|e| e.to_string())?;
+    settings.save_interim = enabled;
+    if let Err(e) = settings.save() {
+        tracing::warn!("Failed to persist settings: {}", e);
+    }
+
+    Ok(enabled)
+}
+
+/// Whether interim results are kept in the transcript
+#[tauri::command]
+async fn get_save_interim(state: State<'_, AppState>) -> Result<bool, AppError> {
+    Ok(*state.save_interim.lock().map_err(|e| e.to_string())?)
+}
+
+/// Set how many silent seconds pass before batch live transcription emits
+/// `no-speech-detected`. 0 turns the notice off.
+#[tauri::command]
+async fn set_no_speech_notice_secs(state: State<'_, AppState>, secs: u64) -> Result<u64, AppError> {
+    *state.no_speech_notice_secs.lock().map_err(|e| e.to_string())? = secs;
+
+    let mut settings = state.settings.lock().map_err(|e| e.to_string())?;
+    settings.no_speech_notice_secs = Some(secs);
+    if let Err(e) = settings.save() {
+        tracing::warn!("Failed to persist settings: {}", e);
+    }
+
+    Ok(secs)
+}
+
+/// Get the no-speech notice threshold in seconds
+#[tauri::command]
+async fn get_no_speech_notice_secs(state: State<'_, AppState>) -> Result<u64, AppError> {
+    Ok(*state.no_speech_notice_secs.lock().map_err(|e| e.to_string())?)
+}
+
+/// Get the interim debounce interval in milliseconds
+#[tauri::command]
+async fn get_interim_debounce_ms(state: State<'_, AppState>) -> Result<u64, AppError> {
+    Ok(*state.interim_debounce_ms.lock().map_err(|e| e.to_string())?)
+}
+
+/// Cap how many live segments stay in memory; older ones are moved to a file on disk
+/// and still included in summaries, exports and saved meetings. 0 removes the cap.
+#[tauri::command]
+async fn set_max_live_segments(state: State<'_, AppState>, n: usize) -> Result<usize, AppError> {
+    *state.max_live_segments.lock().map_err(|e| e.to_string())? = n;
+
+    let mut settings = state.settings.lock().map_err(|e| e.to_string())?;
+    settings.max_live_segments = n;
+    if let Err(e) = settings.save() {
+        tracing::warn!("Failed to persist settings: {}", e);
+    }
+
+    Ok(n)
+}
+
+/// Stop recording and live transcription automatically after `minutes`, with a
+/// `recording-limit-warning` event a minute before. None removes the limit.
+#[tauri::command]
+async fn set_max_recording_minutes(state: State<'_, AppState>, minutes: Option<u32>) -> Result<Option<u32>, AppError> {
+    if minutes == Some(0) {
+        return Err("Recording limit must be at least 1 minute".into());
+    }
+    *state.max_recording_minutes.lock().map_err(|e| e.to_string())? = minutes;
+
+    let mut settings = state.settings.lock().map_err(|e| e.to_string())?;
+    settings.max_recording_minutes = minutes;
+    if let Err(e) = settings.save() {
+        tracing::warn!("Failed to persist settings: {}", e);
+    }
+
+    Ok(minutes)
+}
+
+/// Configure what happens automatically when live transcription stops
+/// (summary, save, webhook). `meeting-processed` is emitted when the steps finish.
+#[tauri::command]
+async fn set_post_meeting_actions(state: State<'_, AppState>, config: settings::PostMeetingActions) -> Result<(), AppError> {
+    config.validate()?;
+
+    let mut settings = state.settings.lock().map_err(|e| e.to_string())?;
+    settings.post_meeting = config;
+    if let Err(e) = settings.save() {
+        tracing::warn!("Failed to persist settings: {}", e);
+    }
+
+    Ok(())
+}
+
+/// Get the post-meeting pipeline configuration
+#[tauri::command]
+async fn get_post_meeting_actions(state: State<'_, AppState>) -> Result<settings::PostMeetingActions, AppError> {
+    Ok(state.settings.lock().map_err(|e| e.to_string())?.post_meeting.clone())
+}
+
+/// Get the recording limit in minutes (None = unlimited)
+#[tauri::command]
+async fn get_max_recording_minutes(state: State<'_, AppState>) -> Result<Option<u32>, AppError> {
+    Ok(*state.max_recording_minutes.lock().map_err(|e| e.to_string())?)
+}
+
+/// Set the system prompt for summaries and replies, e.g. to make replies more technical
+/// or answer in another language. An empty prompt restores the default persona.
+#[tauri::command]
+async fn set_assistant_persona(state: State<'_, AppState>, prompt: String) -> Result<String, AppError> {
+    let mut settings = state.settings.lock().map_err(|e| e.to_string())?;
+    settings.assistant_persona = prompt.trim().to_string();
+    if let Err(e) = settings.save() {
+        tracing::warn!("Failed to persist settings: {}", e);
+    }
+
+    Ok(settings.assistant_persona().to_string())
+}
+
+/// Get the system prompt used for generation
+#[tauri::command]
+async fn get_assistant_persona(state: State<'_, AppState>) -> Result<String, AppError> {
+    Ok(state.settings.lock().map_err(|e| e.to_string())?.assistant_persona().to_string())
+}
+
+/// Get the live segment cap (0 = no cap)
+#[tauri::command]
+async fn get_max_live_segments(state: State<'_, AppState>) -> Result<usize, AppError> {
+    Ok(*state.max_live_segments.lock().map_err(|e| e.to_string())?)
+}
+
+/// Get the minimum live segment length in characters (None = not set, so streaming keeps
+/// every utterance and batch transcription drops fragments under 6 characters)
+#[tauri::command]
+async fn get_min_segment_length(state: State<'_, AppState>) -> Result<Option<usize>, AppError> {
+    Ok(*state.min_segment_length.lock().map_err(|e| e.to_string())?)
+}
+
+/// Set AssemblyAI speaker labels and poll interval (lower latency vs. speaker labels).
+/// Polling starts at `poll_interval_ms` and backs off to `max_poll_interval_ms`
+/// (kept if omitted; set it equal to the poll interval for fixed polling).
+#[tauri::command]
+async fn set_assemblyai_options(
+    state: State<'_, AppState>,
+    speaker_labels: bool,
+    poll_interval_ms: u64,
+    max_poll_interval_ms: Option<u64>,
+) -> Result<(), AppError> {
+    let mut settings = state.settings.lock().map_err(|e| e.to_string())?;
+    let options = assemblyai::TranscriptionOptions {
+        speaker_labels,
+        poll_interval_ms,
+        max_poll_interval_ms: max_poll_interval_ms
+            .unwrap_or(settings.assemblyai.max_poll_interval_ms.max(poll_interval_ms)),
+    };
+    options.validate()?;
+
+    settings.assemblyai = options;
+    if let Err(e) = settings.save() {
+        tracing::warn!("Failed to persist settings: {}", e);
+    }
+
+    Ok(())
+}
+
+/// Get the AssemblyAI transcription options
+#[tauri::command]
+async fn get_assemblyai_options(state: State<'_, AppState>) -> Result<assemblyai::TranscriptionOptions, AppError> {
+    Ok(state.settings.lock().map_err(|e| e.to_string())?.assemblyai.clone())
+}
+
+/// Cancel AssemblyAI transcriptions in flight: polling stops and the jobs are deleted on
+/// AssemblyAI so they don't keep running (and billing). Returns how many jobs were cancelled.
+/// Live transcription's jobs are cancelled by `stop_live_transcription` instead.
+#[tauri::command]
+async fn cancel_assemblyai_job(state: State<'_, AppState>) -> Result<usize, AppError> {
+    let api_key = state.assemblyai_api_key.lock().map_err(|e| e.to_string())?.clone();
+    let cancelled = assemblyai::cancel_jobs(&api_key, &state.assemblyai_jobs).await;
+    tracing::info!("Cancelled {} AssemblyAI job(s)", cancelled);
+    Ok(cancelled)
+}
+
+/// Set how often batch live transcription (Groq, OpenAI, AssemblyAI, local Whisper) checks
+/// for new audio and how much it needs before transcribing. Applies from the next start.
+#[tauri::command]
+async fn set_batch_live_options(
+    state: State<'_, AppState>,
+    check_interval_ms: u64,
+    min_audio_bytes: u64,
+) -> Result<(), AppError> {
+    let options = settings::BatchLiveOptions { check_interval_ms, min_audio_bytes };
+    options.validate()?;
+
+    let mut settings = state.settings.lock().map_err(|e| e.to_string())?;
+    settings.batch_live = options;
+    if let Err(e) = settings.save() {
+        tracing::warn!("Failed to persist settings: {}", e);
+    }
+
+    Ok(())
+}
+
+/// Turn hybrid transcription on or off: Groq results with confidence below `threshold`
+/// are transcribed again with AssemblyAI (needs an AssemblyAI key). Applies from the next start.
+#[tauri::command]
+async fn set_hybrid_transcription(state: State<'_, AppState>, enabled: bool, threshold: f32) -> Result<(), AppError> {
+    let hybrid = settings::HybridTranscription { enabled, threshold };
+    hybrid.validate()?;
+    if enabled && state.assemblyai_api_key.lock().map_err(|e| e.to_string())?.is_empty() {
+        return Err(AppError::new(ErrorCode::SetupRequired, "Hybrid transcription needs an AssemblyAI API key"));
+    }
+
+    let mut settings = state.settings.lock().map_err(|e| e.to_string())?;
+    settings.hybrid_transcription = hybrid;
+    if let Err(e) = settings.save() {
+        tracing::warn!("Failed to persist settings: {}", e);
+    }
+
+    Ok(())
+}
+
+/// Get the hybrid transcription settings
+#[tauri::command]
+async fn get_hybrid_transcription(state: State<'_, AppState>) -> Result<settings::HybridTranscription, AppError> {
+    Ok(state.settings.lock().map_err(|e| e.to_string())?.hybrid_transcription)
+}
+
+/// Get the batch live transcription timing
+#[tauri::command]
+async fn get_batch_live_options(state: State<'_, AppState>) -> Result<settings::BatchLiveOptions, AppError> {
+    Ok(state.settings.lock().map_err(|e| e.to_string())?.batch_live)
+}
+
+/// Get recent log lines (oldest first) so users can diagnose problems without a terminal
+#[tauri::command]
+async fn get_recent_logs(state: State<'_, AppState>, limit: Option<usize>) -> Result<Vec<logs::LogLine>, AppError> {
+    Ok(state.log_buffer.recent(limit.unwrap_or(200)))
+}
+
+#[tauri::command]
+async fn get_transcription_providers() -> Result<Vec<serde_json::Value>, AppError> {
+    let mut providers = vec![
+        serde_json::json!({
+            "id": "Deepgram",
+            "name": "Deepgram (Recommended)",
+            "description": "Real-time streaming with speaker diarization. Words appear as spoken.",
+            "recommended": true,
+            "requires_key": "deepgram"
+        }),
+        serde_json::json!({
+            "id": "Groq",
+            "name": "Groq Whisper",
+            "description": "Batch transcription every 4 seconds. Good fallback option.",
+            "recommended": false,
+            "requires_key": "groq"
+        }),
+        serde_json::json!({
+            "id": "AssemblyAI",
+            "name": "AssemblyAI",
+            "description": "High accuracy batch transcription.",
+            "recommended": false,
+            "requires_key": "assemblyai"
+        }),
+        serde_json::json!({
+            "id": "OpenAI",
+            "name": "OpenAI Whisper",
+            "description": "Batch transcription with whisper-1 using your OpenAI key.",
+            "recommended": false,
+            "requires_key": "openai"
+        }),
+    ];
+    // Only offered when whisper.cpp is compiled in
+    if cfg!(feature = "local-whisper") {
+        providers.push(serde_json::json!({
+            "id": "WhisperLocal",
+            "name": "Local Whisper",
+            "description": "Offline transcription on this computer. Audio never leaves the machine.",
+            "recommended": false,
+            "requires_key": null
+        }));
+    }
+    Ok(providers)
+}
+
+#[derive(Clone, Serialize)]
+struct WhisperDownloadProgress {
+    size: String,
+    downloaded_bytes: u64,
+    total_bytes: Option<u64>,
+}
+
+/// Local Whisper models and whether each has been downloaded
+#[tauri::command]
+async fn list_whisper_models() -> Result<Vec<whisper_local::WhisperModelInfo>, AppError> {
+    Ok(whisper_local::list_models())
+}
+
+/// Download a GGML Whisper model ("tiny", "base", "small" or "medium") for offline
+/// transcription. Emits `whisper-download-progress` events and returns the saved path.
+#[tauri::command]
+async fn download_whisper_model(app: AppHandle, size: String) -> Result<String, AppError> {
+    let mut last_emitted_mb = u64::MAX;
+    let path = whisper_local::download_model(&size, |downloaded_bytes, total_bytes| {
+        // One event per MB is plenty for a progress bar
+        let mb = downloaded_bytes / 1_000_000;
+        if mb != last_emitted_mb {
+            last_emitted_mb = mb;
+            let _ = app.emit("whisper-download-progress", WhisperDownloadProgress {
+                size: size.clone(),
+                downloaded_bytes,
+                total_bytes,
+            });
+        }
+    })
+    .await
+    .map_err(|e| e.to_string())?;
+
+    Ok(path.display().to_string())
+}
+
+/// Current API base URL overrides (empty = provider default)
+#[tauri::command]
+async fn get_endpoint_settings(state: State<'_, AppState>) -> Result<endpoints::EndpointSettings, AppError> {
+    Ok(state.settings.lock().map_err(|e| e.to_string())?.endpoints.clone())
+}
+
+/// Override the base URL for one provider ("groq", "openai", "assemblyai", "ollama" or
+/// "deepgram"), e.g. to go through a corporate proxy or a LiteLLM gateway.
+/// An empty URL restores the default.
+#[tauri::command]
+async fn set_endpoint_url(state: State<'_, AppState>, service: String, url: String) -> Result<endpoints::EndpointSettings, AppError> {
+    let service = service.to_lowercase();
+    let url = endpoints::validate(&service, &url)?;
+
+    let mut settings = state.settings.lock().map_err(|e| e.to_string())?;
+    match service.as_str() {
+        "groq" => settings.endpoints.groq = url,
+        "openai" => settings.endpoints.openai = url,
+        "assemblyai" => settings.endpoints.assemblyai = url,
+        "ollama" => settings.endpoints.ollama = url,
+        "deepgram" => settings.endpoints.deepgram = url,
+        _ => return Err(format!("Unknown service: {}", service).into()),
+    }
+    endpoints::configure(&settings.endpoints);
+    if let Err(e) = settings.save() {
+        tracing::warn!("Failed to persist settings: {}", e);
+    }
+
+    // Cached Groq models came from the previous endpoint
+    *state.model_cache.lock().map_err(|e| e.to_string())? = None;
+
+    Ok(settings.endpoints.clone())
+}
+
+/// Send all outbound traffic (HTTP and WebSockets) through a proxy, e.g.
+/// "http://proxy.corp:3128" or "socks5://127.0.0.1:1080". An empty URL goes back to
+/// the HTTP(S)_PROXY / ALL_PROXY environment variables.
+#[tauri::command]
+async fn set_proxy(state: State<'_, AppState>, url: String) -> Result<(), AppError> {
+    let url = url.trim();
+    net::set_proxy((!url.is_empty()).then_some(url))?;
+
+    let mut settings = state.settings.lock().map_err(|e| e.to_string())?;
+    settings.proxy_url = url.to_string();
+    if let Err(e) = settings.save() {
+        tracing::warn!("Failed to persist settings: {}", e);
+    }
+    Ok(())
+}
+
+/// The configured proxy URL (empty when using the environment)
+#[tauri::command]
+async fn get_proxy(state: State<'_, AppState>) -> Result<String, AppError> {
+    Ok(state.settings.lock().map_err(|e| e.to_string())?.proxy_url.clone())
+}
+
+/// Choose the local Whisper model used by the WhisperLocal provider
+#[tauri::command]
+async fn set_whisper_model(state: State<'_, AppState>, size: String) -> Result<(), AppError> {
+    whisper_local::model_path(&size).map_err(|e| e.to_string())?;
+
+    let mut settings = state.settings.lock().map_err(|e| e.to_string())?;
+    settings.whisper_model = size;
+    if let Err(e) = settings.save() {
+        tracing::warn!("Failed to persist settings: {}", e);
+    }
+    Ok(())
+}
+
+/// Get chat models with metadata. Fetches the live list from Groq (cached for an hour)
+/// and falls back to the built-in list when offline or no key is set.
+#[tauri::command]
+async fn get_available_models(state: State<'_, AppState>) -> Result<Vec<ModelInfo>, AppError> {
+    if *state.llm_provider.lock().map_err(|e| e.to_string())? == LlmProvider::OpenAI {
+        return Ok(openai::get_available_models()
+            .into_iter()
+            .map(|(id, name)| ModelInfo {
+                id: id.to_string(),
+                name: name.to_string(),
+                context_window: openai::context_window(id),
+                owned_by: Some("openai".to_string()),
+                available: true,
+            })
+            .collect());
+    }
+
+    if let Some((fetched_at, models)) = state.model_cache.lock().map_err(|e| e.to_string())?.as_ref() {
+        if fetched_at.elapsed().as_secs() < MODEL_CACHE_TTL_SECS {
+            return Ok(models.clone());
+        }
+    }
+
+    let api_key = state.groq_api_key.lock().map_err(|e| e.to_string())?.clone();
+    match groq::list_models(&api_key).await {
+        Ok(live_models) => {
+            let known_names = groq::get_available_models();
+            let models: Vec<ModelInfo> = live_models
+                .into_iter()
+                .map(|m| ModelInfo {
+                    name: known_names
+                        .iter()
+                        .find(|(id, _)| *id == m.id)
+                        .map(|(_, name)| name.to_string())
+                        .unwrap_or_else(|| m.id.clone()),
+                    id: m.id,
+                    context_window: m.context_window,
+                    owned_by: m.owned_by,
+                    available: m.active,
+                })
+                .collect();
+
+            *state.model_cache.lock().map_err(|e| e.to_string())? = Some((std::time::Instant::now(), models.clone()));
+            Ok(models)
+        }
+        Err(e) => {
+            tracing::warn!("Could not fetch live Groq models, using built-in list: {}", e);
+            Ok(groq::get_available_models()
+                .into_iter()
+                .map(|(id, name)| ModelInfo {
+                    id: id.to_string(),
+                    name: name.to_string(),
+                    context_window: None,
+                    owned_by: None,
+                    available: true,
+                })
+                .collect())
+        }
+    }
+}
+
+#[tauri::command]
+async fn add_transcription(
+    state: State<'_, AppState>,
+    text: String,
+    speaker: String,
+) -> Result<(), AppError> {
+    let segment = TranscriptSegment {
+        timestamp: chrono::Local::now().format("%H:%M:%S").to_string(),
+        speaker,
+        text: clean_transcript(&text),
+        ..Default::default()
+    };
+    state.transcription.lock().map_err(|e| e.to_string())?.push(segment);
+    Ok(())
+}
+
+#[tauri::command]
+async fn add_manual_transcript(
+    state: State<'_, AppState>,
+    text: String,
+    timestamp: String,
+    speaker: String,
+) -> Result<(), AppError> {
+    let segment = TranscriptSegment {
+        timestamp,
+        speaker,
+        text: clean_transcript(&text),
+        ..Default::default()
+    };
+    state.transcription.lock().map_err(|e| e.to_string())?.push(segment);
+    Ok(())
+}
+
+/// Merge two adjacent segments from the same speaker into the earlier one
+fn merge_transcript_segments(
+    transcription: &mut Vec<TranscriptSegment>,
+    index_a: usize,
+    index_b: usize,
+) -> Result<(), String> {
+    let (first, second) = (index_a.min(index_b), index_a.max(index_b));
+
+    if second >= transcription.len() {
+        return Err(format!("Segment index out of range: {}", second));
+    }
+    if second - first != 1 {
+        return Err("Only adjacent segments can be merged".to_string());
+    }
+    if transcription[first].speaker != transcription[second].speaker {
+        return Err(format!(
+            "Cannot merge segments from different speakers ({} and {})",
+            transcription[first].speaker, transcription[second].speaker
+        ));
+    }
+
+    let removed = transcription.remove(second);
+    append_segment(&mut transcription[first], &removed);
+
+    Ok(())
+}
+
+/// Append `next`'s text to `merged`, widening its time range to cover both
+fn append_segment(merged: &mut TranscriptSegment, next: &TranscriptSegment) {
+    merged.text = format!("{} {}", merged.text.trim(), next.text.trim()).trim().to_string();
+    merged.start_ms = match (merged.start_ms, next.start_ms) {
+        (Some(a), Some(b)) => Some(a.min(b)),
+        (a, b) => a.or(b),
+    };
+    merged.end_ms = match (merged.end_ms, next.end_ms) {
+        (Some(a), Some(b)) => Some(a.max(b)),
+        (a, b) => a.or(b),
+    };
+    // The merged paragraph is only as certain as its least certain part
+    merged.confidence = match (merged.confidence, next.confidence) {
+        (Some(a), Some(b)) => Some(a.min(b)),
+        (a, b) => a.or(b),
+    };
+}
+
+/// Merge each run of consecutive segments from the same speaker into one paragraph,
+/// keeping the run's first timestamp
+fn coalesce_segments(transcription: &[TranscriptSegment]) -> Vec<TranscriptSegment> {
+    let mut coalesced: Vec<TranscriptSegment> = Vec::with_capacity(transcription.len());
+    for segment in transcription {
+        match coalesced.last_mut() {
+            Some(last) if last.speaker == segment.speaker => append_segment(last, segment),
+            _ => coalesced.push(segment.clone()),
+        }
+    }
+    coalesced
+}
+
+/// Replace the text (and optionally speaker) of a transcript segment and return the whole
+/// transcript. Indices count archived segments too, but those can't be edited.
+#[tauri::command]
+async fn edit_segment(
+    state: State<'_, AppState>,
+    index: usize,
+    text: String,
+    speaker: Option<String>,
+) -> Result<Vec<TranscriptSegment>, AppError> {
+    let mut transcription = state.transcription.lock().map_err(|e| e.to_string())?;
+    let position = window_index(&state, index)?;
+    if position >= transcription.len() {
+        return Err(format!("Segment index out of range: {}", index).into());
+    }
+    state.transcript_history.lock().map_err(|e| e.to_string())?.record(transcription.clone(), transcription.len());
+
+    let segment = &mut transcription[position];
+
+    segment.text = text.trim().to_string();
+    if let Some(speaker) = speaker {
+        segment.speaker = speaker;
+    }
+
+    Ok(with_archived(&state, &transcription)?)
+}
+
+/// Delete a transcript segment and return the whole transcript
+#[tauri::command]
+async fn delete_segment(state: State<'_, AppState>, index: usize) -> Result<Vec<TranscriptSegment>, AppError> {
+    let mut transcription = state.transcription.lock().map_err(|e| e.to_string())?;
+    let position = window_index(&state, index)?;
+    if position >= transcription.len() {
+        return Err(format!("Segment index out of range: {}", index).into());
+    }
+    state.transcript_history.lock().map_err(|e| e.to_string())?.record(transcription.clone(), transcription.len() - 1);
+    transcription.remove(position);
+    Ok(with_archived(&state, &transcription)?)
+}
+
+/// Merge two adjacent segments (e.g. a sentence Whisper split across batch cycles) and
+/// return the whole transcript
+#[tauri::command]
+async fn merge_segments(
+    state: State<'_, AppState>,
+    index_a: usize,
+    index_b: usize,
+) -> Result<Vec<TranscriptSegment>, AppError> {
+    let mut transcription = state.transcription.lock().map_err(|e| e.to_string())?;
+    let (position_a, position_b) = (window_index(&state, index_a)?, window_index(&state, index_b)?);
+    let snapshot = transcription.clone();
+    merge_transcript_segments(&mut transcription, position_a, position_b)?;
+    state.transcript_history.lock().map_err(|e| e.to_string())?.record(snapshot, transcription.len());
+    Ok(with_archived(&state, &transcription)?)
+}
+
+/// Merge back-to-back segments from the same speaker into paragraphs, for readability and
+/// better summaries. Returns a merged view of the whole transcript; with `persist`, the
+/// transcript is replaced instead and returned. Undoable while none of it has been moved to
+/// disk yet.
+#[tauri::command]
+async fn coalesce_same_speaker(state: State<'_, AppState>, persist: bool) -> Result<Vec<TranscriptSegment>, AppError> {
+    if !persist {
+        return Ok(coalesce_segments(&full_transcript(&state)?));
+    }
+
+    let mut transcription = state.transcription.lock().map_err(|e| e.to_string())?;
+    let segments = with_archived(&state, &transcription)?;
+    let coalesced = coalesce_segments(&segments);
+    if coalesced.len() == segments.len() {
+        return Ok(segments);
+    }
+
+    let mut archive = state.transcript_archive.lock().map_err(|e| e.to_string())?;
+    let mut history = state.transcript_history.lock().map_err(|e| e.to_string())?;
+    if archive.is_empty() {
+        history.record(transcription.clone(), coalesced.len());
+        *transcription = coalesced.clone();
+    } else {
+        // Paragraphs can reach into the archived part, which undo snapshots don't cover.
+        // Keep at most as many segments in memory as before.
+        let archived = coalesced.len().saturating_sub(transcription.len());
+        archive.clear();
+        archive.append(&coalesced[..archived])?;
+        history.clear();
+        *transcription = coalesced[archived..].to_vec();
+    }
+    Ok(coalesced)
+}
+
+/// Undo the last manual transcript edit and return the restored transcript
+#[tauri::command]
+async fn undo_transcript(state: State<'_, AppState>) -> Result<Vec<TranscriptSegment>, AppError> {
+    let mut transcription = state.transcription.lock().map_err(|e| e.to_string())?;
+    let mut history = state.transcript_history.lock().map_err(|e| e.to_string())?;
+    if let Some(previous) = history.undo(transcription.clone()) {
+        *transcription = previous;
+    }
+    drop(history);
+    Ok(with_archived(&state, &transcription)?)
+}
+
+/// Redo the last undone transcript edit and return the restored transcript
+#[tauri::command]
+async fn redo_transcript(state: State<'_, AppState>) -> Result<Vec<TranscriptSegment>, AppError> {
+    let mut transcription = state.transcription.lock().map_err(|e| e.to_string())?;
+    let mut history = state.transcript_history.lock().map_err(|e| e.to_string())?;
+    if let Some(next) = history.redo(transcription.clone()) {
+        *transcription = next;
+    }
+    drop(history);
+    Ok(with_archived(&state, &transcription)?)
+}
+
+#[tauri::command]
+async fn clear_transcription(state: State<'_, AppState>) -> Result<(), AppError> {
+    state.transcription.lock().map_err(|e| e.to_string())?.clear();
+    state.transcript_archive.lock().map_err(|e| e.to_string())?.clear();
+    *state.summary.lock().map_err(|e| e.to_string())? = String::new();
+    *state.summary_cache.lock().map_err(|e| e.to_string())? = None;
+    *state.live_summary.lock().map_err(|e| e.to_string())? = String::new();
+    state.summary_template.lock().map_err(|e| e.to_string())?.clear();
+    state.meeting_attendees.lock().map_err(|e| e.to_string())?.clear();
+    state.suggested_replies.lock().map_err(|e| e.to_string())?.clear();
+    state.transcript_history.lock().map_err(|e| e.to_string())?.clear();
+    state.notes.lock().map_err(|e| e.to_string())?.clear();
+    state.bookmarks.lock().map_err(|e| e.to_string())?.clear();
+    *state.meeting_started_at.lock().map_err(|e| e.to_string())? = None;
+    *state.autosave_meeting_id.lock().map_err(|e| e.to_string())? = None;
+    *state.calendar_event.lock().map_err(|e| e.to_string())? = None;
+    state.meeting_monitor.clear_active_event().await;
+    Ok(())
+}
+
+/// Load a WebVTT or SRT transcript (e.g. exported from Zoom or Teams) as the current meeting,
+/// so summaries and replies can be generated for it. Replaces the current transcript.
+#[tauri::command]
+async fn import_transcript_file(state: State<'_, AppState>, path: String) -> Result<Vec<TranscriptSegment>, AppError> {
+    if *state.is_recording.lock().map_err(|e| e.to_string())?
+        || *state.is_live_transcribing.lock().map_err(|e| e.to_string())?
+    {
+        return Err(AppError::new(ErrorCode::InvalidState, "Stop the current recording before importing a transcript"));
+    }
+
+    let content = tokio::fs::read_to_string(&path).await.map_err(|e| format!("Failed to read {}: {}", path, e))?;
+    let segments = transcript_import::parse_subtitles(&content);
+    if segments.is_empty() {
+        return Err("No transcript cues found. Expected a WebVTT (.vtt) or SRT (.srt) file.".into());
+    }
+
+    clear_transcription(state.clone()).await?;
+    *state.transcription.lock().map_err(|e| e.to_string())? = segments.clone();
+
+    tracing::info!("Imported {} transcript segments from {}", segments.len(), path);
+    Ok(segments)
+}
+
+/// Add a private note to the current meeting. Unlike `add_manual_transcript`,
+/// notes are kept out of the transcript. Returns all notes.
+#[tauri::command]
+async fn add_note(state: State<'_, AppState>, text: String) -> Result<Vec<database::Note>, AppError> {
+    let text = text.trim();
+    if text.is_empty() {
+        return Err("Note cannot be empty".into());
+    }
+
+    let mut notes = state.notes.lock().map_err(|e| e.to_string())?;
+    notes.push(database::Note {
+        timestamp: chrono::Local::now().format("%H:%M:%S").to_string(),
+        text: text.to_string(),
+    });
+    Ok(notes.clone())
+}
+
+/// Delete a note from the current meeting. Returns the remaining notes.
+#[tauri::command]
+async fn delete_note(state: State<'_, AppState>, index: usize) -> Result<Vec<database::Note>, AppError> {
+    let mut notes = state.notes.lock().map_err(|e| e.to_string())?;
+    if index >= notes.len() {
+        return Err(format!("Note index out of range: {}", index).into());
+    }
+    notes.remove(index);
+    Ok(notes.clone())
+}
+
+/// Mark the current moment of the meeting, linked to the most recent transcript segment
+#[tauri::command]
+async fn add_bookmark(state: State<'_, AppState>, label: String) -> Result<database::Bookmark, AppError> {
+    let started_at = state.meeting_started_at.lock().map_err(|e| e.to_string())?
+        .ok_or_else(|| "Start recording or transcription before adding a bookmark".to_string())?;
+    let segment_count = archived_segment_count(&state)? + state.transcription.lock().map_err(|e| e.to_string())?.len();
+
+    let label = label.trim();
+    let bookmark = database::Bookmark {
+        label: if label.is_empty() { "Bookmark".to_string() } else { label.to_string() },
+        timestamp: chrono::Local::now().format("%H:%M:%S").to_string(),
+        elapsed_ms: started_at.elapsed().as_millis() as u64,
+        segment_index: segment_count.checked_sub(1),
+    };
+
+    state.bookmarks.lock().map_err(|e| e.to_string())?.push(bookmark.clone());
+    tracing::info!("Added bookmark '{}' at {} ms", bookmark.label, bookmark.elapsed_ms);
+    Ok(bookmark)
+}
+
+/// Bookmarks for the current meeting, in the order they were added
+#[tauri::command]
+async fn list_bookmarks(state: State<'_, AppState>) -> Result<Vec<database::Bookmark>, AppError> {
+    Ok(state.bookmarks.lock().map_err(|e| e.to_string())?.clone())
+}
+
+/// Relabel diarized segments: the primary speaker is "You", everyone else "Participant"
+fn relabel_primary_speaker(transcription: &mut [TranscriptSegment], primary: u32) {
+    for segment in transcription.iter_mut() {
+        if let Some(id) = segment.speaker_id {
+            segment.speaker = if id == primary { "You" } else { "Participant" }.to_string();
+        }
+    }
+}
+
+/// Mark a diarized speaker ID as the user (Deepgram mono mode), overriding the automatic
+/// calibration. Existing segments are relabeled; returns the updated transcript.
+#[tauri::command]
+async fn set_primary_speaker(state: State<'_, AppState>, id: u32) -> Result<Vec<TranscriptSegment>, AppError> {
+    *state.primary_speaker.lock().map_err(|e| e.to_string())? = Some(id);
+
+    let mut transcription = state.transcription.lock().map_err(|e| e.to_string())?;
+    relabel_primary_speaker(&mut transcription, id);
+    state.transcript_archive.lock().map_err(|e| e.to_string())?
+        .update(|segments| relabel_primary_speaker(segments, id))?;
+
+    tracing::info!("Primary speaker set to {}", id);
+    Ok(transcription.clone())
+}
+
+/// A segment the provider was unsure about, with its position for `edit_segment`
+#[derive(Debug, Clone, Serialize)]
+struct LowConfidenceSegment {
+    index: usize,
+    segment: TranscriptSegment,
+}
+
+/// Segments whose confidence is below the threshold; segments without a score are skipped
+/// `first_index` is the transcript index of `transcription[0]`.
+fn low_confidence_segments(transcription: &[TranscriptSegment], first_index: usize, threshold: f32) -> Vec<LowConfidenceSegment> {
+    transcription
+        .iter()
+        .enumerate()
+        .filter(|(_, s)| s.confidence.is_some_and(|c| c < threshold))
+        .map(|(index, s)| LowConfidenceSegment { index: first_index + index, segment: s.clone() })
+        .collect()
+}
+
+/// Find shaky transcriptions to review, e.g. `threshold = 0.7`. Only segments still in
+/// memory (and so editable) are returned; indices count archived ones, like `edit_segment`.
+#[tauri::command]
+async fn get_low_confidence_segments(state: State<'_, AppState>, threshold: f32) -> Result<Vec<LowConfidenceSegment>, AppError> {
+    if !(0.0..=1.0).contains(&threshold) {
+        return Err("Confidence threshold must be between 0 and 1".into());
+    }
+    let transcription = state.transcription.lock().map_err(|e| e.to_string())?;
+    Ok(low_confidence_segments(&transcription, archived_segment_count(&state)?, threshold))
+}
+
+/// Get per-speaker talk-time and word counts for the current transcript,
+/// or for a saved meeting when `meeting_id` is given
+#[tauri::command]
+async fn get_talk_time_stats(
+    state: State<'_, AppState>,
+    meeting_id: Option<String>,
+) -> Result<std::collections::BTreeMap<String, analytics::SpeakerTalkTime>, AppError> {
+    let transcription = match &meeting_id {
+        Some(id) => state.meetings_db.lock().map_err(|e| e.to_string())?
+            .get_meeting(id)
+            .map(|m| m.app_transcript())
+            .ok_or_else(|| format!("Meeting not found: {}", id))?,
+        None => full_transcript(&state)?,
+    };
+    Ok(analytics::talk_time_stats(&transcription))
+}
+
+/// Most salient terms and phrases of the current transcript or a saved meeting
+/// (`meeting_id`), weighted by TF-IDF against the other saved meetings, for a word cloud
+#[tauri::command]
+async fn extract_keywords(
+    state: State<'_, AppState>,
+    top_n: Option<usize>,
+    meeting_id: Option<String>,
+) -> Result<Vec<analytics::Keyword>, AppError> {
+    let live = match meeting_id {
+        Some(_) => Vec::new(),
+        None => full_transcript(&state)?,
+    };
+    // The meeting itself (or the live one's auto-save) isn't part of the background
+    let own_id = match &meeting_id {
+        Some(id) => Some(id.clone()),
+        None => state.autosave_meeting_id.lock().map_err(|e| e.to_string())?.clone(),
+    };
+
+    let db = state.meetings_db.lock().map_err(|e| e.to_string())?;
+    let document = match &meeting_id {
+        Some(id) => db.get_meeting(id)
+            .map(|m| m.transcript.iter().map(|s| s.text.as_str()).collect::<Vec<_>>().join("\n"))
+            .ok_or_else(|| format!("Meeting not found: {}", id))?,
+        None => live.iter().map(|s| s.text.as_str()).collect::<Vec<_>>().join("\n"),
+    };
+    if document.trim().is_empty() {
+        return Err("No transcript to extract keywords from".into());
+    }
+
+    let corpus: Vec<String> = db.meetings.iter()
+        .filter(|m| Some(&m.id) != own_id.as_ref())
+        .map(|m| m.transcript.iter().map(|s| s.text.as_str()).collect::<Vec<_>>().join("\n"))
+        .collect();
+    Ok(analytics::extract_keywords(&document, &corpus, top_n.unwrap_or(30)))
+}
+
+/// Words per minute and filler-word counts per speaker, for presentation practice.
+/// Uses the current transcript, a saved meeting (`meeting_id`), or a recording
+/// (`recording_path`). Live and saved transcripts have common fillers cleaned out, so only
+/// a recording (re-transcribed with word timestamps, as one speaker) reports filler stats.
+/// `speaker` limits the report to one speaker.
+#[tauri::command]
+async fn analyze_speaking_style(
+    state: State<'_, AppState>,
+    speaker: Option<String>,
+    meeting_id: Option<String>,
+    recording_path: Option<String>,
+) -> Result<std::collections::BTreeMap<String, analytics::SpeakingStyle>, AppError> {
+    let mut transcription = match (&recording_path, &meeting_id) {
+        (Some(path), _) => {
+            ensure_recording_inactive(&state, path)?;
+            let api_key = state.groq_api_key.lock().map_err(|e| e.to_string())?.clone();
+            if api_key.is_empty() {
+                return Err(AppError::new(ErrorCode::SetupRequired, "Groq API key not set. Please add it in Settings."));
+            }
+
+            *state.is_transcribing.lock().map_err(|e| e.to_string())? = true;
+            let result = groq::transcribe_words(&api_key, path).await;
+            *state.is_transcribing.lock().map_err(|e| e.to_string())? = false;
+
+            let words = result?;
+            let (first, last) = match (words.first(), words.last()) {
+                (Some(first), Some(last)) => (first, last),
+                _ => return Err("No speech found in the recording".into()),
+            };
+            // Silences left out by voice activation still count towards the speaking time
+            let gaps = audio::SkippedSpans::load(path);
+            vec![TranscriptSegment {
+                speaker: speaker.clone().unwrap_or_else(|| "You".to_string()),
+                text: words.iter().map(|w| w.word.trim()).collect::<Vec<_>>().join(" "),
+                is_final: true,
+                start_ms: Some(gaps.to_meeting_ms((first.start * 1000.0) as u64)),
+                end_ms: Some(gaps.to_meeting_ms((last.end * 1000.0) as u64)),
+                ..Default::default()
+            }]
+        }
+        (None, Some(id)) => state.meetings_db.lock().map_err(|e| e.to_string())?
+            .get_meeting(id)
+            .map(|m| m.app_transcript())
+            .ok_or_else(|| format!("Meeting not found: {}", id))?,
+        (None, None) => full_transcript(&state)?,
+    };
+    if let Some(speaker) = &speaker {
+        transcription.retain(|s| s.speaker == *speaker);
+        if transcription.is_empty() {
+            return Err(format!("No speech from {}", speaker).into());
+        }
+    }
+
+    let filler_words = state.settings.lock().map_err(|e| e.to_string())?.filler_words();
+    // Only the raw recording transcription still has its fillers
+    let counted = recording_path.is_some().then_some(filler_words.as_slice());
+    Ok(analytics::speaking_style(&transcription, counted))
+}
+
+/// Set the filler words/phrases counted by `analyze_speaking_style`
+#[tauri::command]
+async fn set_filler_words(state: State<'_, AppState>, words: Vec<String>) -> Result<Vec<String>, AppError> {
+    let mut fillers: Vec<String> = Vec::new();
+    for word in words {
+        let word = word.trim().to_lowercase();
+        if !word.is_empty() && !fillers.contains(&word) {
+            fillers.push(word);
+        }
+    }
+
+    let mut settings = state.settings.lock().map_err(|e| e.to_string())?;
+    settings.filler_words = Some(fillers.clone());
+    if let Err(e) = settings.save() {
+        tracing::warn!("Failed to persist settings: {}", e);
+    }
+
+    Ok(fillers)
+}
+
+/// Get the filler words/phrases counted by `analyze_speaking_style`
+#[tauri::command]
+async fn get_filler_words(state: State<'_, AppState>) -> Result<Vec<String>, AppError> {
+    Ok(state.settings.lock().map_err(|e| e.to_string())?.filler_words())
+}
+
+/// Whether two paths point at the same file (tolerates relative vs absolute forms)
+fn same_file(a: &str, b: &str) -> bool {
+    match (std::fs::canonicalize(a), std::fs::canonicalize(b)) {
+        (Ok(a), Ok(b)) => a == b,
+        _ => a == b,
+    }
+}
+
+/// Whether `path` is the recording currently being written
+fn recording_in_progress(state: &AppState, path: &str) -> Result<bool, String> {
+    Ok(state.audio_recorder.lock().map_err(|e| e.to_string())?
+        .as_ref()
+        .is_some_and(|recorder| same_file(recorder.get_output_path(), path)))
+}
+
+/// Refuse file operations on the recording that's still being written
+fn ensure_recording_inactive(state: &AppState, path: &str) -> Result<(), AppError> {
+    if recording_in_progress(state, path)? {
+        return Err(AppError::new(ErrorCode::InvalidState, "This recording is still in progress. Stop recording first."));
+    }
+    Ok(())
+}
+
+/// Whether a file from `list_recordings` is the one currently being recorded
+#[tauri::command]
+async fn is_recording_active(state: State<'_, AppState>, path: String) -> Result<bool, AppError> {
+    Ok(recording_in_progress(&state, &path)?)
+}
+
+#[tauri::command]
+async fn transcribe_recording(state: State<'_, AppState>, file_path: String) -> Result<Vec<TranscriptSegment>, AppError> {
+    ensure_recording_inactive(&state, &file_path)?;
+    let api_key = state.groq_api_key.lock().map_err(|e| e.to_string())?.clone();
+
+    if api_key.is_empty() {
+        return Err(AppError::new(ErrorCode::SetupRequired, "Groq API key not set. Please add it in Settings."));
+    }
+
+    *state.is_transcribing.lock().map_err(|e| e.to_string())? = true;
+
+    let result = groq::transcribe_audio(&api_key, &file_path).await;
+
+    *state.is_transcribing.lock().map_err(|e| e.to_string())? = false;
+
+    match result {
+        Ok(text) => {
+            let mut segments = Vec::new();
+
+            if !text.is_empty() {
+                let cleaned_text = clean_transcript(&text);
+                let segment = TranscriptSegment {
+                    timestamp: chrono::Local::now().format("%H:%M:%S").to_string(),
+                    speaker: "Speaker".to_string(),
+                    text: cleaned_text,
+                    ..Default::default()
+                };
+                segments.push(segment.clone());
+                state.transcription.lock().map_err(|e| e.to_string())?.push(segment);
+            }
+
+            Ok(segments)
+        }
+        Err(e) => Err(e.to_string().into()),
     }
 }
 
+/// Give the current transcript accurate start/end offsets into a recording, by
+/// re-transcribing it with word timestamps (Groq Whisper) and matching the words back.
+/// Returns the updated transcript.
 #[tauri::command]
-async fn set_deepgram_api_key(state: State<'_, AppState>, key: String) -> Result<bool, String> {
-    if !key.is_empty() {
-        *state.deepgram_api_key.lock().map_err(|e| e.to_string())? = key.clone();
+async fn realign_transcript(state: State<'_, AppState>, recording_path: String) -> Result<Vec<TranscriptSegment>, AppError> {
+    ensure_recording_inactive(&state, &recording_path)?;
+    let api_key = state.groq_api_key.lock().map_err(|e| e.to_string())?.clone();
+    if api_key.is_empty() {
+        return Err(AppError::new(ErrorCode::SetupRequired, "Groq API key not set. Please add it in Settings."));
+    }
 
-        // Persist to disk
-        let mut settings = state.settings.lock().map_err(|e| e.to_string())?;
-        settings.deepgram_api_key = key;
-        if let Err(e) = settings.save() {
-            eprintln!("Failed to persist settings: {}", e);
+    *state.is_transcribing.lock().map_err(|e| e.to_string())? = true;
+    let result = groq::transcribe_words(&api_key, &recording_path).await;
+    *state.is_transcribing.lock().map_err(|e| e.to_string())? = false;
+
+    // Word times are positions in the file; segments are timed from the meeting start
+    let gaps = audio::SkippedSpans::load(&recording_path);
+    let words: Vec<alignment::TimedWord> = result?
+        .into_iter()
+        .map(|w| alignment::TimedWord {
+            text: w.word,
+            start_ms: gaps.to_meeting_ms((w.start * 1000.0) as u64),
+            end_ms: gaps.to_meeting_ms((w.end * 1000.0) as u64),
+        })
+        .collect();
+    if words.is_empty() {
+        return Err("No speech found in the recording".into());
+    }
+
+    // The words cover the whole recording, so align the whole transcript, archive included
+    let mut transcription = state.transcription.lock().map_err(|e| e.to_string())?;
+    let mut segments = with_archived(&state, &transcription)?;
+    let aligned = alignment::align_segments(&mut segments, &words);
+    tracing::info!("Realigned {} of {} segments to {}", aligned, segments.len(), recording_path);
+
+    let archived = archived_segment_count(&state)?;
+    state.transcript_archive.lock().map_err(|e| e.to_string())?
+        .update(|old| old.clone_from_slice(&segments[..archived]))?;
+    state.transcript_history.lock().map_err(|e| e.to_string())?.record(transcription.clone(), segments.len() - archived);
+    *transcription = segments[archived..].to_vec();
+
+    Ok(segments)
+}
+
+/// Transcribe a whole recording with one specific provider (no fallback to another)
+async fn transcribe_with_provider(
+    state: &AppState,
+    provider: &TranscriptionProvider,
+    path: &str,
+) -> Result<Vec<TranscriptSegment>, AppError> {
+    let missing_key = |name: &str| AppError::new(ErrorCode::SetupRequired, format!("Please set your {} API key in Settings", name));
+    let text = match provider {
+        TranscriptionProvider::Groq => {
+            let api_key = state.groq_api_key.lock().map_err(|e| e.to_string())?.clone();
+            if api_key.is_empty() {
+                return Err(missing_key("Groq"));
+            }
+            groq::transcribe_recording(&api_key, path).await
+        }
+        TranscriptionProvider::OpenAI => {
+            let api_key = state.openai_api_key.lock().map_err(|e| e.to_string())?.clone();
+            if api_key.is_empty() {
+                return Err(missing_key("OpenAI"));
+            }
+            openai::transcribe_recording(&api_key, path).await
+        }
+        TranscriptionProvider::AssemblyAI => {
+            let api_key = state.assemblyai_api_key.lock().map_err(|e| e.to_string())?.clone();
+            if api_key.is_empty() {
+                return Err(missing_key("AssemblyAI"));
+            }
+            let options = state.settings.lock().map_err(|e| e.to_string())?.assemblyai.clone();
+            let response = assemblyai::transcribe_file(&api_key, path, &options, &state.assemblyai_jobs).await?;
+            match response.utterances.as_deref() {
+                Some(utterances) if !utterances.is_empty() => {
+                    return Ok(utterances_to_segments(utterances, &audio::SkippedSpans::load(path)));
+                }
+                _ => Ok(response.text.unwrap_or_default()),
+            }
+        }
+        TranscriptionProvider::WhisperLocal => {
+            let model = state.settings.lock().map_err(|e| e.to_string())?.whisper_model();
+            whisper_local::transcribe_file(&model, path).await
+        }
+        TranscriptionProvider::Deepgram => {
+            return Err("Deepgram only supports live transcription; pick a batch provider".into());
         }
+    }?;
 
-        Ok(true)
-    } else {
-        Ok(false)
+    if text.trim().is_empty() {
+        return Ok(Vec::new());
     }
+    Ok(vec![TranscriptSegment {
+        timestamp: format_milliseconds(0),
+        speaker: "Speaker".to_string(),
+        text: clean_transcript(&text),
+        is_final: true,
+        ..Default::default()
+    }])
 }
 
+/// Re-run transcription of a recording with another provider, e.g. to compare Groq and
+/// AssemblyAI on the same audio. The current transcript is left alone; if a saved meeting
+/// uses this recording, the result is stored on it under the provider's name.
 #[tauri::command]
-async fn set_model(state: State<'_, AppState>, model: String) -> Result<(), String> {
-    *state.selected_model.lock().map_err(|e| e.to_string())? = model.clone();
+async fn retranscribe(
+    state: State<'_, AppState>,
+    recording_path: String,
+    provider: String,
+) -> Result<Vec<TranscriptSegment>, AppError> {
+    let provider_enum = TranscriptionProvider::parse(&provider)?;
+    ensure_recording_inactive(&state, &recording_path)?;
 
-    // Persist to disk
-    let mut settings = state.settings.lock().map_err(|e| e.to_string())?;
-    settings.selected_model = model;
-    if let Err(e) = settings.save() {
-        eprintln!("Failed to persist settings: {}", e);
+    *state.is_transcribing.lock().map_err(|e| e.to_string())? = true;
+    let result = transcribe_with_provider(&state, &provider_enum, &recording_path).await;
+    *state.is_transcribing.lock().map_err(|e| e.to_string())? = false;
+    let segments = result?;
+
+    let mut db = state.meetings_db.lock().map_err(|e| e.to_string())?;
+    let meeting_id = db.meetings.iter()
+        .find(|m| m.recording_path.as_deref() == Some(recording_path.as_str()))
+        .map(|m| m.id.clone());
+    if let Some(id) = meeting_id {
+        db.set_alternate_transcript(&id, &provider.to_lowercase(), &segments)?;
+        tracing::info!("Stored {} transcript on meeting {}", provider, id);
     }
 
-    Ok(())
+    Ok(segments)
+}
+
+fn format_milliseconds(ms: u64) -> String {
+    let seconds = ms / 1000;
+    let minutes = seconds / 60;
+    let hours = minutes / 60;
+    format!("{:02}:{:02}:{:02}", hours, minutes % 60, seconds % 60)
+}
+
+/// Convert AssemblyAI utterances to transcript segments, keeping speaker labels and timings.
+/// Timestamps are offsets from the start of the meeting: `gaps` adds back the silences
+/// voice activation left out of the recording.
+fn utterances_to_segments(utterances: &[assemblyai::Utterance], gaps: &audio::SkippedSpans) -> Vec<TranscriptSegment> {
+    utterances
+        .iter()
+        .filter(|u| !u.text.trim().is_empty())
+        .map(|u| TranscriptSegment {
+            timestamp: format_milliseconds(gaps.to_meeting_ms(u.start)),
+            speaker: u.speaker.clone(),
+            text: clean_transcript(&u.text),
+            is_final: true,
+            start_ms: Some(gaps.to_meeting_ms(u.start)),
+            end_ms: Some(gaps.to_meeting_ms(u.end)),
+            confidence: u.confidence,
+            ..Default::default()
+        })
+        .collect()
 }
 
+/// Transcribe a recording with AssemblyAI speaker diarization.
+/// Each utterance becomes its own segment labelled with the speaker ("A", "B", ...).
 #[tauri::command]
-async fn set_transcription_provider(state: State<'_, AppState>, provider: String) -> Result<(), String> {
-    let provider_enum = match provider.to_lowercase().as_str() {
-        "groq" => TranscriptionProvider::Groq,
-        "deepgram" => TranscriptionProvider::Deepgram,
-        "assemblyai" => TranscriptionProvider::AssemblyAI,
-        _ => return Err(format!("Unknown provider: {}", provider)),
+async fn transcribe_with_speakers(state: State<'_, AppState>, path: String) -> Result<Vec<TranscriptSegment>, AppError> {
+    ensure_recording_inactive(&state, &path)?;
+    let api_key = state.assemblyai_api_key.lock().map_err(|e| e.to_string())?.clone();
+    if api_key.is_empty() {
+        return Err(AppError::new(ErrorCode::SetupRequired, "Please set your AssemblyAI API key in Settings"));
+    }
+
+    // Speaker labels are the point of this command, whatever the latency setting says
+    let options = assemblyai::TranscriptionOptions {
+        speaker_labels: true,
+        ..state.settings.lock().map_err(|e| e.to_string())?.assemblyai.clone()
     };
-    *state.transcription_provider.lock().map_err(|e| e.to_string())? = provider_enum;
 
-    // Persist to disk
-    let mut settings = state.settings.lock().map_err(|e| e.to_string())?;
-    settings.transcription_provider = provider;
-    if let Err(e) = settings.save() {
-        eprintln!("Failed to persist settings: {}", e);
+    *state.is_transcribing.lock().map_err(|e| e.to_string())? = true;
+    let result = assemblyai::transcribe_file(&api_key, &path, &options, &state.assemblyai_jobs).await;
+    *state.is_transcribing.lock().map_err(|e| e.to_string())? = false;
+
+    let response = result?;
+    let segments = match response.utterances.as_deref() {
+        Some(utterances) if !utterances.is_empty() => utterances_to_segments(utterances, &audio::SkippedSpans::load(&path)),
+        _ => {
+            // No diarization returned (e.g. silence or a single short clip); keep the plain text
+            let text = response.text.unwrap_or_default();
+            if text.trim().is_empty() {
+                Vec::new()
+            } else {
+                vec![TranscriptSegment {
+                    timestamp: format_milliseconds(0),
+                    speaker: "Speaker".to_string(),
+                    text: clean_transcript(&text),
+                    ..Default::default()
+                }]
+            }
+        }
+    };
+
+    tracing::info!("AssemblyAI returned {} speaker segments", segments.len());
+    let mut transcription = state.transcription.lock().map_err(|e| e.to_string())?;
+    // Transcribing into an empty meeting makes this recording the meeting's own
+    if transcription.is_empty() && archived_segment_count(&state)? == 0 {
+        *state.current_recording_path.lock().map_err(|e| e.to_string())? = Some(path.clone());
     }
+    transcription.extend(segments.iter().cloned());
 
-    Ok(())
+    Ok(segments)
+}
+
+/// Folder next to the recordings for per-speaker clips, so `list_recordings` doesn't pick
+/// them up as meetings
+const SPEAKER_CLIPS_FOLDER: &str = "speakers";
+
+/// Path for one speaker's clip in the speakers folder beside the recording:
+/// speakers/meeting_..._alice.wav
+fn speaker_clip_path(recording_path: &str, speaker: &str) -> String {
+    let path = std::path::Path::new(recording_path);
+    let stem = path.file_stem().map(|s| s.to_string_lossy().to_string()).unwrap_or_default();
+    let label: String = speaker
+        .trim()
+        .chars()
+        .map(|c| if c.is_alphanumeric() { c.to_ascii_lowercase() } else { '_' })
+        .collect();
+    path.with_file_name(SPEAKER_CLIPS_FOLDER)
+        .join(format!("{}_{}.wav", stem, label))
+        .to_string_lossy()
+        .to_string()
+}
+
+/// The transcript of a recording: from the saved meeting it belongs to, or the current
+/// transcript if that is the current meeting's recording
+fn recording_transcript(state: &AppState, recording_path: &str) -> Result<Vec<TranscriptSegment>, String> {
+    let saved = state.meetings_db.lock().map_err(|e| e.to_string())?
+        .meetings
+        .iter()
+        .find(|m| m.recording_path.as_deref() == Some(recording_path))
+        .map(|m| m.app_transcript());
+    if let Some(transcript) = saved {
+        return Ok(transcript);
+    }
+
+    let current = state.current_recording_path.lock().map_err(|e| e.to_string())?.clone();
+    if current.as_deref() == Some(recording_path) {
+        return full_transcript(state);
+    }
+    Err("No transcript for this recording. Transcribe it with speaker labels first.".to_string())
 }
 
+/// Cut one speaker's spoken regions out of a recording and join them into a new WAV in the
+/// speakers folder. Needs a transcript of that recording with speaker timings (from
+/// `transcribe_with_speakers` or Deepgram). Returns the new file's path.
 #[tauri::command]
-async fn set_meeting_context(state: State<'_, AppState>, context: String) -> Result<(), String> {
-    *state.meeting_context.lock().map_err(|e| e.to_string())? = context.clone();
+async fn extract_speaker_audio(
+    state: State<'_, AppState>,
+    recording_path: String,
+    speaker: String,
+) -> Result<String, AppError> {
+    ensure_recording_inactive(&state, &recording_path)?;
 
-    // Persist to disk
-    let mut settings = state.settings.lock().map_err(|e| e.to_string())?;
-    settings.meeting_context = context;
-    if let Err(e) = settings.save() {
-        eprintln!("Failed to persist settings: {}", e);
+    // Segments are timed from the meeting start; the regions are positions in the file
+    let gaps = audio::SkippedSpans::load(&recording_path);
+    let regions: Vec<(u64, u64)> = recording_transcript(&state, &recording_path)?
+        .iter()
+        .filter(|s| s.speaker == speaker)
+        .filter_map(|s| Some((gaps.to_file_ms(s.start_ms?), gaps.to_file_ms(s.end_ms?))))
+        .filter(|(start, end)| end > start)
+        .collect();
+    if regions.is_empty() {
+        return Err(format!(
+            "No timed segments for {}. Transcribe the recording with speaker labels first.",
+            speaker
+        )
+        .into());
     }
 
-    eprintln!("Meeting context updated");
-    Ok(())
+    let output_path = speaker_clip_path(&recording_path, &speaker);
+    let duration_ms = persist::blocking(|| {
+        if let Some(dir) = std::path::Path::new(&output_path).parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        audio::extract_regions(&recording_path, &output_path, &regions)
+    })
+    .map_err(|e| format!("Failed to extract audio for {}: {}", speaker, e))?;
+
+    tracing::info!("Extracted {}s of {} from {} to {}", duration_ms / 1000, speaker, recording_path, output_path);
+    Ok(output_path)
+}
+
+/// RMS level (0..1) above which a mic test counts as having picked up sound (~ -40 dBFS)
+const MIC_TEST_MIN_RMS: f32 = 0.01;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct MicTestResult {
+    pub device: Option<String>,
+    pub peak: f32,
+    pub rms: f32,
+    pub has_audio: bool,
+    pub clip_path: String,
 }
 
+/// Record a short clip from the input device and report its level, so users can
+/// check their mic works before a meeting. The clip is kept in the temp folder for playback.
 #[tauri::command]
-async fn get_transcription_providers() -> Result<Vec<serde_json::Value>, String> {
-    Ok(vec![
-        serde_json::json!({
-            "id": "Deepgram",
-            "name": "Deepgram (Recommended)",
-            "description": "Real-time streaming with speaker diarization. Words appear as spoken.",
-            "recommended": true,
-            "requires_key": "deepgram"
-        }),
-        serde_json::json!({
-            "id": "Groq",
-            "name": "Groq Whisper",
-            "description": "Batch transcription every 4 seconds. Good fallback option.",
-            "recommended": false,
-            "requires_key": "groq"
-        }),
-        serde_json::json!({
-            "id": "AssemblyAI",
-            "name": "AssemblyAI",
-            "description": "High accuracy batch transcription.",
-            "recommended": false,
-            "requires_key": "assemblyai"
-        }),
-    ])
+async fn test_microphone(state: State<'_, AppState>, duration_ms: u64) -> Result<MicTestResult, AppError> {
+    if *state.is_recording.lock().map_err(|e| e.to_string())?
+        || *state.is_live_transcribing.lock().map_err(|e| e.to_string())?
+    {
+        return Err(AppError::new(ErrorCode::InvalidState, "Stop the current recording before testing the microphone"));
+    }
+
+    let duration_ms = duration_ms.clamp(500, 10_000);
+    let clip_path = std::env::temp_dir()
+        .join("vantage_mic_test.wav")
+        .to_string_lossy()
+        .to_string();
+
+    let device = {
+        use cpal::traits::{DeviceTrait, HostTrait};
+        cpal::default_host().default_input_device().and_then(|d| d.name().ok())
+    };
+
+    // Levels are measured on a 16-bit clip, whatever format recordings use, with nothing gated out
+    let recorder = audio::AudioRecorder::start_at(clip_path.clone(), audio::RecordingConfig::default(), audio::VoiceActivation::default())
+        .map_err(|e| e.to_string())?;
+    tokio::time::sleep(std::time::Duration::from_millis(duration_ms)).await;
+    let clip_path = recorder.stop().map_err(|e| e.to_string())?;
+
+    let (peak, rms) = audio::measure_levels(&clip_path).map_err(|e| e.to_string())?;
+    let has_audio = rms >= MIC_TEST_MIN_RMS;
+    tracing::info!("Mic test on {:?}: peak {:.3}, rms {:.4}, has_audio {}", device, peak, rms, has_audio);
+
+    Ok(MicTestResult { device, peak, rms, has_audio, clip_path })
 }
 
+/// Supported sample formats, channel counts and sample rate ranges of an input device
+/// (the default one if `name` is omitted), to explain stream errors and pick a valid config
 #[tauri::command]
-async fn get_available_models() -> Result<Vec<ModelInfo>, String> {
-    Ok(groq::get_available_models()
-        .into_iter()
-        .map(|(id, name)| ModelInfo {
-            id: id.to_string(),
-            name: name.to_string(),
-        })
-        .collect())
+async fn get_device_capabilities(name: Option<String>) -> Result<audio::DeviceCapabilities, AppError> {
+    Ok(audio::device_capabilities(name.as_deref()).map_err(|e| e.to_string())?)
 }
 
+/// Set the WAV format for new recordings: bit depth (16, 24 or 32-bit float), and
+/// optionally channels (1 = mono, half the size) and sample rate; unset ones follow the device
 #[tauri::command]
-async fn add_transcription(
+async fn set_recording_config(
     state: State<'_, AppState>,
-    text: String,
-    speaker: String,
-) -> Result<(), String> {
-    let segment = TranscriptSegment {
-        timestamp: chrono::Local::now().format("%H:%M:%S").to_string(),
-        speaker,
-        text: clean_transcript(&text),
-        ..Default::default()
-    };
-    state.transcription.lock().map_err(|e| e.to_string())?.push(segment);
-    Ok(())
+    config: audio::RecordingConfig,
+) -> Result<audio::RecordingConfig, AppError> {
+    config.validate()?;
+
+    let mut settings = state.settings.lock().map_err(|e| e.to_string())?;
+    settings.recording = config;
+    if let Err(e) = settings.save() {
+        tracing::warn!("Failed to persist settings: {}", e);
+    }
+
+    Ok(config)
 }
 
+/// Get the WAV format used for new recordings
 #[tauri::command]
-async fn add_manual_transcript(
+async fn get_recording_config(state: State<'_, AppState>) -> Result<audio::RecordingConfig, AppError> {
+    Ok(state.settings.lock().map_err(|e| e.to_string())?.recording)
+}
+
+/// Turn voice-activated recording on or off for new recordings: audio below `threshold_db`
+/// (dBFS, e.g. -45) is left out after a short hangover, so files hold just the spoken parts.
+#[tauri::command]
+async fn set_voice_activation(
     state: State<'_, AppState>,
-    text: String,
-    timestamp: String,
-    speaker: String,
-) -> Result<(), String> {
-    let segment = TranscriptSegment {
-        timestamp,
-        speaker,
-        text: clean_transcript(&text),
-        ..Default::default()
+    enabled: bool,
+    threshold_db: f32,
+    hangover_ms: Option<u64>,
+) -> Result<audio::VoiceActivation, AppError> {
+    let mut settings = state.settings.lock().map_err(|e| e.to_string())?;
+    let voice = audio::VoiceActivation {
+        enabled,
+        threshold_db,
+        hangover_ms: hangover_ms.unwrap_or(settings.voice_activation.hangover_ms),
     };
-    state.transcription.lock().map_err(|e| e.to_string())?.push(segment);
-    Ok(())
+    voice.validate()?;
+
+    settings.voice_activation = voice;
+    if let Err(e) = settings.save() {
+        tracing::warn!("Failed to persist settings: {}", e);
+    }
+
+    tracing::info!("Voice activation {} at {} dB", if enabled { "enabled" } else { "disabled" }, threshold_db);
+    Ok(voice)
 }
 
+/// Get the voice activation settings used for new recordings
 #[tauri::command]
-async fn clear_transcription(state: State<'_, AppState>) -> Result<(), String> {
-    state.transcription.lock().map_err(|e| e.to_string())?.clear();
-    *state.summary.lock().map_err(|e| e.to_string())? = String::new();
-    state.suggested_replies.lock().map_err(|e| e.to_string())?.clear();
-    Ok(())
+async fn get_voice_activation(state: State<'_, AppState>) -> Result<audio::VoiceActivation, AppError> {
+    Ok(state.settings.lock().map_err(|e| e.to_string())?.voice_activation)
 }
 
 #[tauri::command]
-async fn transcribe_recording(state: State<'_, AppState>, file_path: String) -> Result<Vec<TranscriptSegment>, String> {
-    let api_key = state.groq_api_key.lock().map_err(|e| e.to_string())?.clone();
+async fn list_recordings() -> Result<Vec<String>, AppError> {
+    Ok(audio::list_recordings().map_err(|e| e.to_string())?)
+}
 
-    if api_key.is_empty() {
-        return Err("Groq API key not set. Please add it in Settings.".to_string());
+#[tauri::command]
+async fn get_recordings_folder() -> Result<String, AppError> {
+    let folder = audio::get_recordings_folder().map_err(|e| e.to_string())?;
+    Ok(folder.to_string_lossy().to_string())
+}
+
+/// Pause between files in a batch transcription, to stay under provider rate limits
+const BATCH_TRANSCRIBE_DELAY_MS: u64 = 2000;
+
+#[derive(Debug, Clone, Serialize)]
+struct BatchTranscriptionProgress {
+    index: usize,
+    total: usize,
+    file: String,
+    status: String,
+    error: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BatchTranscriptionFailure {
+    pub file: String,
+    pub error: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BatchTranscriptionResult {
+    pub meeting_ids: Vec<String>,
+    pub failed: Vec<BatchTranscriptionFailure>,
+    pub skipped: usize,
+}
+
+/// Title and start time ("HH:MM:SS") for a recording named meeting_YYYYmmdd_HHMMSS.wav
+fn recording_title_and_time(path: &str) -> (String, Option<String>) {
+    let stem = std::path::Path::new(path)
+        .file_stem()
+        .map(|s| s.to_string_lossy().to_string())
+        .unwrap_or_default();
+
+    match chrono::NaiveDateTime::parse_from_str(stem.trim_start_matches("meeting_"), "%Y%m%d_%H%M%S") {
+        Ok(dt) => (
+            format!("Recording {}", dt.format("%Y-%m-%d %H:%M")),
+            Some(dt.format("%H:%M:%S").to_string()),
+        ),
+        Err(_) => (stem, None),
     }
+}
 
-    *state.is_transcribing.lock().map_err(|e| e.to_string())? = true;
+/// Recording length in seconds, read from the WAV header
+fn recording_duration_seconds(path: &str) -> Option<u64> {
+    let reader = hound::WavReader::open(path).ok()?;
+    let spec = reader.spec();
+    if spec.sample_rate == 0 {
+        return None;
+    }
+    Some(reader.duration() as u64 / spec.sample_rate as u64)
+}
 
-    let result = groq::transcribe_audio(&api_key, &file_path).await;
+/// Transcribe every recording that isn't linked to a saved meeting yet, saving each as a meeting.
+/// Files are processed one at a time; a failure is recorded and the batch moves on.
+/// Emits `batch-transcription-progress` events.
+#[tauri::command]
+async fn transcribe_all_untranscribed(
+    app: AppHandle,
+    state: State<'_, AppState>,
+) -> Result<BatchTranscriptionResult, AppError> {
+    let groq_key = state.groq_api_key.lock().map_err(|e| e.to_string())?.clone();
+    let assemblyai_key = state.assemblyai_api_key.lock().map_err(|e| e.to_string())?.clone();
+    if groq_key.is_empty() && assemblyai_key.is_empty() {
+        return Err(AppError::new(ErrorCode::SetupRequired, "Please set your Groq or AssemblyAI API key in Settings"));
+    }
 
-    *state.is_transcribing.lock().map_err(|e| e.to_string())? = false;
+    let recordings = audio::list_recordings().map_err(|e| e.to_string())?;
+    let linked: std::collections::HashSet<String> = state.meetings_db.lock().map_err(|e| e.to_string())?
+        .meetings
+        .iter()
+        .filter_map(|m| m.recording_path.clone())
+        .collect();
+    let active_recording = state.current_recording_path.lock().map_err(|e| e.to_string())?.clone();
+    let assemblyai_options = state.settings.lock().map_err(|e| e.to_string())?.assemblyai.clone();
 
-    match result {
-        Ok(text) => {
-            let mut segments = Vec::new();
+    let pending: Vec<String> = recordings
+        .iter()
+        .filter(|path| !linked.contains(*path) && active_recording.as_ref() != Some(*path))
+        .cloned()
+        .collect();
+    let skipped = recordings.len() - pending.len();
 
-            if !text.is_empty() {
-                let cleaned_text = clean_transcript(&text);
+    tracing::info!("Batch transcription: {} recordings to transcribe, {} skipped", pending.len(), skipped);
+
+    let mut result = BatchTranscriptionResult {
+        meeting_ids: Vec::new(),
+        failed: Vec::new(),
+        skipped,
+    };
+
+    for (index, path) in pending.iter().enumerate() {
+        let _ = app.emit("batch-transcription-progress", BatchTranscriptionProgress {
+            index,
+            total: pending.len(),
+            file: path.clone(),
+            status: "transcribing".to_string(),
+            error: None,
+        });
+
+        // AssemblyAI handles long files whole; Groq only takes the last 15MB of large files
+        let transcription = if !assemblyai_key.is_empty() {
+            assemblyai::transcribe_file(&assemblyai_key, path, &assemblyai_options, &state.assemblyai_jobs).await
+                .map(|r| r.text.unwrap_or_default())
+        } else {
+            groq::transcribe_audio(&groq_key, path).await
+        };
+
+        let outcome = match transcription {
+            Ok(text) if text.trim().is_empty() => Err("No speech detected".into()),
+            Ok(text) => {
+                let (title, time) = recording_title_and_time(path);
                 let segment = TranscriptSegment {
-                    timestamp: chrono::Local::now().format("%H:%M:%S").to_string(),
+                    timestamp: time.unwrap_or_else(|| chrono::Local::now().format("%H:%M:%S").to_string()),
                     speaker: "Speaker".to_string(),
-                    text: cleaned_text,
+                    text: clean_transcript(&text),
                     ..Default::default()
                 };
-                segments.push(segment.clone());
-                state.transcription.lock().map_err(|e| e.to_string())?.push(segment);
+                let meeting = database::create_meeting_from_transcript(
+                    title,
+                    vec![segment],
+                    None,
+                    Vec::new(),
+                    None,
+                    Some(path.clone()),
+                    recording_duration_seconds(path),
+                );
+                let meeting_id = meeting.id.clone();
+                // A failure here is this recording's, not the whole batch's
+                state.meetings_db.lock()
+                    .map_err(|e| e.to_string())
+                    .and_then(|mut db| db.add_meeting(meeting))
+                    .map(|_| meeting_id)
             }
+            Err(e) => Err(e.to_string()),
+        };
 
-            Ok(segments)
+        let (status, error) = match outcome {
+            Ok(meeting_id) => {
+                result.meeting_ids.push(meeting_id);
+                ("done".to_string(), None)
+            }
+            Err(e) => {
+                tracing::warn!("Batch transcription failed for {}: {}", path, e);
+                result.failed.push(BatchTranscriptionFailure { file: path.clone(), error: e.clone() });
+                ("failed".to_string(), Some(e))
+            }
+        };
+
+        let _ = app.emit("batch-transcription-progress", BatchTranscriptionProgress {
+            index,
+            total: pending.len(),
+            file: path.clone(),
+            status,
+            error,
+        });
+
+        if index + 1 < pending.len() {
+            tokio::time::sleep(std::time::Duration::from_millis(BATCH_TRANSCRIBE_DELAY_MS)).await;
         }
-        Err(e) => Err(e.to_string()),
     }
-}
 
-fn format_milliseconds(ms: u64) -> String {
-    let seconds = ms / 1000;
-    let minutes = seconds / 60;
-    let hours = minutes / 60;
-    format!("{:02}:{:02}:{:02}", hours, minutes % 60, seconds % 60)
+    tracing::info!("Batch transcription finished: {} saved, {} failed", result.meeting_ids.len(), result.failed.len());
+    Ok(result)
 }
 
+/// Export the current transcript as SRT or WebVTT subtitles
 #[tauri::command]
-async fn list_recordings() -> Result<Vec<String>, String> {
-    audio::list_recordings().map_err(|e| e.to_string())
+async fn export_subtitles(state: State<'_, AppState>, format: String, path: String) -> Result<String, AppError> {
+    let format = export::SubtitleFormat::parse(&format)?;
+    let transcription = full_transcript(&state)?;
+
+    if transcription.is_empty() {
+        return Err("No transcription to export".into());
+    }
+
+    let content = export::to_subtitles(&transcription, format);
+    tokio::fs::write(&path, content).await.map_err(|e| format!("Failed to write subtitles: {}", e))?;
+
+    tracing::info!("Exported {} segments as subtitles to {}", transcription.len(), path);
+    Ok(path)
 }
 
+/// Export the transcript grouped into per-speaker sections, with talk-time per speaker at the top.
+/// Needs real speaker labels (diarization or renamed speakers).
 #[tauri::command]
-async fn get_recordings_folder() -> Result<String, String> {
-    audio::get_recordings_folder()
-        .map(|p| p.to_string_lossy().to_string())
-        .map_err(|e| e.to_string())
+async fn export_by_speaker(state: State<'_, AppState>, path: String) -> Result<String, AppError> {
+    let transcription = full_transcript(&state)?;
+
+    if transcription.is_empty() {
+        return Err("No transcription to export".into());
+    }
+
+    let content = export::to_speaker_sections(&transcription)?;
+    tokio::fs::write(&path, content).await.map_err(|e| format!("Failed to write speaker export: {}", e))?;
+
+    tracing::info!("Exported {} segments by speaker to {}", transcription.len(), path);
+    Ok(path)
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
@@ -873,23 +3924,49 @@ pub struct MeetingSummary {
     pub raw_summary: String,
 }
 
-#[tauri::command]
-async fn generate_summary(state: State<'_, AppState>) -> Result<String, String> {
-    let transcription = state.transcription.lock().map_err(|e| e.to_string())?.clone();
-    let model = state.selected_model.lock().map_err(|e| e.to_string())?.clone();
-    let api_key = state.groq_api_key.lock().map_err(|e| e.to_string())?.clone();
-
-    if transcription.is_empty() {
-        return Err("No transcription to summarize".to_string());
+/// Render segments as "[timestamp] speaker: text" lines for LLM prompts
+fn format_transcript(segments: &[TranscriptSegment]) -> String {
+    segments
+        .iter()
+        .map(|s| format!("[{}] {}: {}", s.timestamp, s.speaker, s.text))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Prompt section carrying the notes the user wrote during the meeting
+fn user_notes_section(notes: &[database::Note]) -> String {
+    if notes.is_empty() {
+        return String::new();
     }
-
-    let transcript_text: String = transcription
+    let lines = notes
         .iter()
-        .map(|s| format!("[{}] {}: {}", s.timestamp, s.speaker, s.text))
+        .map(|n| format!("- [{}] {}", n.timestamp, n.text))
         .collect::<Vec<_>>()
         .join("\n");
+    format!("USER'S NOTES (written by the user during the meeting; treat them as important context):\n{}\n\n", lines)
+}
 
-    let prompt = format!(
+/// Prompt section carrying the active meeting template's summary instructions
+fn summary_template_section(summary_template: &str) -> String {
+    if summary_template.trim().is_empty() {
+        String::new()
+    } else {
+        format!("MEETING TEMPLATE (follow these instructions for the summary):\n{}\n\n", summary_template.trim())
+    }
+}
+
+/// Prompt section carrying one-off instructions for a custom summary
+fn custom_instructions_section(instructions: &str) -> String {
+    if instructions.trim().is_empty() {
+        String::new()
+    } else {
+        format!("USER INSTRUCTIONS (follow these for this summary; they take priority over the format above):\n{}\n\n", instructions.trim())
+    }
+}
+
+/// Text-format summary prompt shared by `generate_summary` and `generate_custom_summary`
+fn text_summary_prompt(summary_template: &str, instructions: &str, notes: &[database::Note], transcript_text: &str) -> String {
+    format!(
         r#"Analyze this meeting transcript and provide a structured summary. Format your response EXACTLY as follows:
 
 ## KEY POINTS
@@ -912,16 +3989,205 @@ async fn generate_summary(state: State<'_, AppState>) -> Result<String, String>
 
 Be concise but comprehensive. Each bullet point should be a complete thought.
 
-MEETING TRANSCRIPT:
+{}{}{}MEETING TRANSCRIPT:
 {}"#,
+        summary_template_section(summary_template),
+        custom_instructions_section(instructions),
+        user_notes_section(notes),
         transcript_text
+    )
+}
+
+/// Context window of `model`: OpenAI's published sizes, the cached Groq model list, or a
+/// conservative default
+fn context_window(llm: &LlmClient, model: &str) -> u32 {
+    let known = match llm.provider {
+        LlmProvider::OpenAI => openai::context_window(model),
+        _ => llm.app.as_ref().and_then(|app| {
+            app.state::<AppState>().model_cache.lock().ok()?
+                .as_ref()
+                .and_then(|(_, models)| models.iter().find(|m| m.id == model))
+                .and_then(|m| m.context_window)
+        }),
+    };
+    known.unwrap_or(tokens::DEFAULT_CONTEXT_WINDOW)
+}
+
+/// Emitted when a prompt probably won't fit the model's context window with room for the
+/// reply, since the provider will then reject or cut it
+#[derive(Debug, Clone, Serialize)]
+struct PromptTooLargeEvent {
+    model: String,
+    prompt_tokens: usize,
+    context_window: u32,
+    max_tokens: u32,
+}
+
+/// Warn (and emit `prompt-too-large`) when a prompt probably won't fit the model's context window
+fn check_prompt_size(llm: &LlmClient, model: &str, prompt: &str, params: &groq::GenerationParams) {
+    let prompt_tokens = tokens::estimate_tokens(&llm.system_prompt) + tokens::estimate_tokens(prompt);
+    let context_window = context_window(llm, model);
+    if prompt_tokens <= tokens::prompt_budget(context_window, params.max_tokens) {
+        return;
+    }
+    tracing::warn!(
+        "Prompt is ~{} tokens; {} has a {}-token context window and {} are reserved for the reply",
+        prompt_tokens, model, context_window, params.max_tokens
     );
+    if let Some(app) = &llm.app {
+        let _ = app.emit("prompt-too-large", PromptTooLargeEvent {
+            model: model.to_string(),
+            prompt_tokens,
+            context_window,
+            max_tokens: params.max_tokens,
+        });
+    }
+}
+
+/// Approximate token count of `text`, e.g. to show how much of a model's context a transcript uses
+#[tauri::command]
+async fn estimate_tokens(text: String) -> Result<usize, AppError> {
+    Ok(tokens::estimate_tokens(&text))
+}
+
+/// Summarize the live transcript. If nothing changed since the last summary, that summary
+/// is returned without calling the LLM again, unless `force` is set.
+#[tauri::command]
+async fn generate_summary(state: State<'_, AppState>, force: bool) -> Result<String, AppError> {
+    let transcription = full_transcript(&state)?;
+    let model = state.selected_model.lock().map_err(|e| e.to_string())?.clone();
+    let llm = llm_client(&state)?;
+    let params = state.settings.lock().map_err(|e| e.to_string())?.generation.extraction;
+    let summary_template = state.summary_template.lock().map_err(|e| e.to_string())?.clone();
+    let notes = state.notes.lock().map_err(|e| e.to_string())?.clone();
+
+    if transcription.is_empty() {
+        return Err("No transcription to summarize".into());
+    }
+
+    let prompt = text_summary_prompt(&summary_template, "", &notes, &format_transcript(&transcription));
+
+    let cache_key = summary_cache_key(&model, &prompt);
+    if !force {
+        let cached = state.summary_cache.lock().map_err(|e| e.to_string())?
+            .as_ref()
+            .filter(|(key, _)| *key == cache_key)
+            .map(|(_, summary)| summary.clone());
+        if let Some(summary) = cached {
+            tracing::info!("Transcript unchanged since last summary, reusing it");
+            *state.summary.lock().map_err(|e| e.to_string())? = summary.clone();
+            return Ok(summary);
+        }
+    }
 
-    let summary = groq::generate(&api_key, &model, &prompt).await.map_err(|e| e.to_string())?;
+    let summary = generate_llm(&llm, GenerationKind::Text, &model, &prompt, &params, llm_fallback_model(&state)).await?;
     *state.summary.lock().map_err(|e| e.to_string())? = summary.clone();
+    *state.summary_cache.lock().map_err(|e| e.to_string())? = Some((cache_key, summary.clone()));
+    Ok(summary)
+}
+
+/// Hash of the model and full prompt (transcript, notes, template), so a summary is only
+/// reused when nothing that feeds it has changed
+fn summary_cache_key(model: &str, prompt: &str) -> u64 {
+    use std::hash::{Hash, Hasher};
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    model.hash(&mut hasher);
+    prompt.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// One-off summary following free-form instructions (e.g. "focus on blockers and owners").
+/// With `meeting_id`, summarizes that saved meeting; otherwise the live transcript.
+/// The result is only returned: the current and saved summaries are left untouched.
+#[tauri::command]
+async fn generate_custom_summary(
+    state: State<'_, AppState>,
+    instructions: String,
+    meeting_id: Option<String>,
+) -> Result<String, AppError> {
+    if instructions.trim().is_empty() {
+        return Err("Describe what the summary should focus on".into());
+    }
+
+    let model = state.selected_model.lock().map_err(|e| e.to_string())?.clone();
+    let llm = llm_client(&state)?;
+    let params = state.settings.lock().map_err(|e| e.to_string())?.generation.extraction;
+
+    let (transcription, notes, summary_template) = match &meeting_id {
+        Some(id) => {
+            let meeting = state.meetings_db.lock().map_err(|e| e.to_string())?
+                .get_meeting(id)
+                .cloned()
+                .ok_or_else(|| format!("Meeting not found: {}", id))?;
+            (meeting.app_transcript(), meeting.notes, String::new())
+        }
+        None => (
+            full_transcript(&state)?,
+            state.notes.lock().map_err(|e| e.to_string())?.clone(),
+            state.summary_template.lock().map_err(|e| e.to_string())?.clone(),
+        ),
+    };
+
+    if transcription.is_empty() {
+        return Err("No transcription to summarize".into());
+    }
+
+    let prompt = text_summary_prompt(&summary_template, &instructions, &notes, &format_transcript(&transcription));
+    let summary = generate_llm(&llm, GenerationKind::Text, &model, &prompt, &params, llm_fallback_model(&state)).await?;
+
+    tracing::info!("Generated custom summary ({} chars)", summary.len());
     Ok(summary)
 }
 
+/// Whether generated text appears to stop mid-sentence (e.g. it hit max_tokens)
+fn ends_mid_sentence(text: &str) -> bool {
+    match text.trim_end().chars().last() {
+        Some(c) => !matches!(c, '.' | '!' | '?' | ')' | ']' | '}' | '"' | '\'' | '*'),
+        None => false,
+    }
+}
+
+/// Continue a truncated summary, or add detail to a complete one.
+/// The model's output is appended to the current summary, which is returned.
+#[tauri::command]
+async fn expand_summary(state: State<'_, AppState>) -> Result<String, AppError> {
+    let transcription = full_transcript(&state)?;
+    let summary = state.summary.lock().map_err(|e| e.to_string())?.clone();
+    let model = state.selected_model.lock().map_err(|e| e.to_string())?.clone();
+    let llm = llm_client(&state)?;
+    let params = state.settings.lock().map_err(|e| e.to_string())?.generation.extraction;
+
+    if summary.trim().is_empty() {
+        return Err("No summary to expand. Generate a summary first.".into());
+    }
+    if transcription.is_empty() {
+        return Err("No transcription available".into());
+    }
+
+    let transcript_text = format_transcript(&transcription);
+
+    let truncated = ends_mid_sentence(&summary);
+    let instruction = if truncated {
+        "The summary below was cut off. Continue it EXACTLY where it stops, in the same format. Output only the continuation, without repeating anything."
+    } else {
+        "Add more detail to the summary below: points it missed, owners and deadlines for action items, and open questions. Use the same section format. Output only the new material, without repeating existing points."
+    };
+
+    let prompt = format!(
+        "{}\n\nCURRENT SUMMARY:\n{}\n\nMEETING TRANSCRIPT:\n{}",
+        instruction, summary, transcript_text
+    );
+
+    let addition = generate_llm(&llm, GenerationKind::Text, &model, &prompt, &params, llm_fallback_model(&state)).await?;
+
+    let separator = if truncated { "" } else { "\n\n" };
+    let expanded = format!("{}{}{}", summary.trim_end(), separator, addition.trim());
+    *state.summary.lock().map_err(|e| e.to_string())? = expanded.clone();
+
+    tracing::info!("Expanded summary ({})", if truncated { "continued truncated text" } else { "added detail" });
+    Ok(expanded)
+}
+
 /// Parse a text-format summary into structured MeetingSummary
 /// Handles formats like:
 /// ## KEY POINTS
@@ -956,7 +4222,9 @@ fn parse_text_summary(text: &str) -> MeetingSummary {
 
         // Extract bullet points
         if line.starts_with('•') || line.starts_with('-') || line.starts_with('*') {
-            let content = line[1..].trim().to_string();
+            // '•' is multi-byte, so skip the bullet by its UTF-8 length
+            let bullet_len = line.chars().next().map_or(0, char::len_utf8);
+            let content = line[bullet_len..].trim().to_string();
             if !content.is_empty() && content.to_lowercase() != "none" && !content.to_lowercase().contains("none identified") {
                 match current_section {
                     Some("key_points") => key_points.push(content),
@@ -972,7 +4240,7 @@ fn parse_text_summary(text: &str) -> MeetingSummary {
         }
     }
 
-    eprintln!("Parsed text summary: {} key points, {} action items, {} decisions, {} notes",
+    tracing::info!("Parsed text summary: {} key points, {} action items, {} decisions, {} notes",
         key_points.len(), action_items.len(), decisions.len(), notes.len());
 
     MeetingSummary {
@@ -984,21 +4252,43 @@ fn parse_text_summary(text: &str) -> MeetingSummary {
     }
 }
 
-#[tauri::command]
-async fn generate_structured_summary(state: State<'_, AppState>) -> Result<MeetingSummary, String> {
-    let transcription = state.transcription.lock().map_err(|e| e.to_string())?.clone();
-    let model = state.selected_model.lock().map_err(|e| e.to_string())?.clone();
-    let api_key = state.groq_api_key.lock().map_err(|e| e.to_string())?.clone();
+/// Render a summary as markdown for pasting into docs or chat. Empty sections are left out;
+/// action items become task-list checkboxes.
+fn summary_to_markdown(summary: &MeetingSummary) -> String {
+    let sections = [
+        ("Key Points", &summary.key_points, "- "),
+        ("Action Items", &summary.action_items, "- [ ] "),
+        ("Decisions", &summary.decisions, "- "),
+        ("Notes", &summary.notes, "- "),
+    ];
+
+    sections
+        .iter()
+        .filter(|(_, items, _)| !items.is_empty())
+        .map(|(title, items, bullet)| {
+            let lines: Vec<String> = items.iter().map(|item| format!("{}{}", bullet, item)).collect();
+            format!("## {}\n{}", title, lines.join("\n"))
+        })
+        .collect::<Vec<_>>()
+        .join("\n\n")
+}
 
+/// Run the structured summary pipeline over a transcript.
+/// Returns the parsed summary with `raw_summary` rendered in the text format.
+async fn summarize_transcript(
+    llm: &LlmClient,
+    model: &str,
+    params: &groq::GenerationParams,
+    fallback_model: Option<String>,
+    summary_template: &str,
+    notes: &[database::Note],
+    transcription: &[TranscriptSegment],
+) -> Result<MeetingSummary, String> {
     if transcription.is_empty() {
         return Err("No transcription to summarize".to_string());
     }
 
-    let transcript_text: String = transcription
-        .iter()
-        .map(|s| format!("[{}] {}: {}", s.timestamp, s.speaker, s.text))
-        .collect::<Vec<_>>()
-        .join("\n");
+    let transcript_text = format_transcript(&transcription);
 
     let prompt = format!(
         r#"Analyze this meeting transcript and provide a structured summary in JSON format.
@@ -1010,71 +4300,421 @@ Return ONLY valid JSON with this exact structure (no markdown, no explanation):
   "notes": ["note 1", "follow-up needed", "question raised"]
 }}
 
-If a category has no items, use an empty array [].
-Each item should be a concise but complete sentence.
+If a category has no items, use an empty array [].
+Each item should be a concise but complete sentence.
+
+{}{}MEETING TRANSCRIPT:
+{}"#,
+        summary_template_section(summary_template),
+        user_notes_section(notes),
+        transcript_text
+    );
+
+    let response = generate_llm(llm, GenerationKind::StructuredSummary, model, &prompt, params, fallback_model).await?;
+    tracing::info!("Summary response from AI (first 500 chars): {}", &response.chars().take(500).collect::<String>());
+
+    // Try to parse JSON response
+    let summary: MeetingSummary = match serde_json::from_str(&response) {
+        Ok(s) => {
+            tracing::info!("Successfully parsed JSON summary");
+            s
+        },
+        Err(e1) => {
+            tracing::warn!("Direct JSON parse failed: {}", e1);
+            // If JSON parsing fails, try to extract JSON from the response
+            let json_start = response.find('{').unwrap_or(0);
+            let json_end = response.rfind('}').map(|i| i + 1).unwrap_or(response.len());
+            let json_str = &response[json_start..json_end];
+
+            match serde_json::from_str(json_str) {
+                Ok(s) => {
+                    tracing::info!("Successfully parsed extracted JSON");
+                    s
+                },
+                Err(e2) => {
+                    tracing::warn!("Extracted JSON parse failed: {}. Falling back to text parsing.", e2);
+                    // Fallback: parse the text format into structured data
+                    parse_text_summary(&response)
+                }
+            }
+        }
+    };
+
+    // Store raw summary for backward compatibility
+    let raw = format!(
+        "## KEY POINTS\n{}\n\n## ACTION ITEMS\n{}\n\n## DECISIONS\n{}\n\n## NOTES\n{}",
+        summary.key_points.iter().map(|p| format!("• {}", p)).collect::<Vec<_>>().join("\n"),
+        if summary.action_items.is_empty() { "• None identified".to_string() } else { summary.action_items.iter().map(|p| format!("• {}", p)).collect::<Vec<_>>().join("\n") },
+        if summary.decisions.is_empty() { "• None identified".to_string() } else { summary.decisions.iter().map(|p| format!("• {}", p)).collect::<Vec<_>>().join("\n") },
+        if summary.notes.is_empty() { "• None".to_string() } else { summary.notes.iter().map(|p| format!("• {}", p)).collect::<Vec<_>>().join("\n") }
+    );
+
+    // Return summary with raw_summary populated
+    Ok(MeetingSummary {
+        key_points: summary.key_points,
+        action_items: summary.action_items,
+        decisions: summary.decisions,
+        notes: summary.notes,
+        raw_summary: raw,
+    })
+}
+
+#[tauri::command]
+async fn generate_structured_summary(state: State<'_, AppState>) -> Result<MeetingSummary, AppError> {
+    let transcription = full_transcript(&state)?;
+    let model = state.selected_model.lock().map_err(|e| e.to_string())?.clone();
+    let llm = llm_client(&state)?;
+
+    let params = state.settings.lock().map_err(|e| e.to_string())?.generation.extraction;
+    let summary_template = state.summary_template.lock().map_err(|e| e.to_string())?.clone();
+    let notes = state.notes.lock().map_err(|e| e.to_string())?.clone();
+
+    let summary = summarize_transcript(&llm, &model, &params, llm_fallback_model(&state), &summary_template, &notes, &transcription).await?;
+    *state.summary.lock().map_err(|e| e.to_string())? = summary.raw_summary.clone();
+
+    Ok(summary)
+}
+
+/// Sampling for decision extraction: near-deterministic and short, so it's quick to run mid-meeting
+const DECISIONS_PARAMS: groq::GenerationParams = groq::GenerationParams { temperature: 0.1, max_tokens: 512 };
+
+/// Parse the decisions list from the model's response (JSON array, or bullet lines as a fallback)
+fn parse_decisions(response: &str) -> Vec<String> {
+    let json = match (response.find('['), response.rfind(']')) {
+        (Some(start), Some(end)) if end > start => &response[start..=end],
+        _ => response,
+    };
+    if let Ok(decisions) = serde_json::from_str::<Vec<String>>(json) {
+        return decisions.into_iter().map(|d| d.trim().to_string()).filter(|d| !d.is_empty()).collect();
+    }
+
+    response
+        .lines()
+        .map(|l| l.trim().trim_start_matches(['-', '*', '•']).trim())
+        .filter(|l| !l.is_empty() && !l.eq_ignore_ascii_case("none") && !l.ends_with(':'))
+        .map(|l| l.to_string())
+        .collect()
+}
+
+/// Lightweight mid-meeting check: just the concrete decisions made so far
+#[tauri::command]
+async fn extract_decisions(state: State<'_, AppState>) -> Result<Vec<String>, AppError> {
+    let transcription = full_transcript(&state)?;
+    let model = state.selected_model.lock().map_err(|e| e.to_string())?.clone();
+    let llm = llm_client(&state)?;
+
+    if transcription.is_empty() {
+        return Err("No transcription available".into());
+    }
+
+    let prompt = format!(
+        r#"List the concrete decisions made in this meeting transcript.
+Only include things the participants agreed or decided, not ideas, questions or open discussion.
+Return ONLY a JSON array of short sentences, e.g. ["Ship v2 on Friday"]. Return [] if no decisions were made.
+
+TRANSCRIPT:
+{}"#,
+        format_transcript(&transcription)
+    );
+
+    let response = generate_llm(&llm, GenerationKind::Decisions, &model, &prompt, &DECISIONS_PARAMS, llm_fallback_model(&state)).await?;
+    Ok(parse_decisions(&response))
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MeetingRollup {
+    pub rollup: String,
+    pub meeting_ids: Vec<String>,
+}
+
+/// Parse an inclusive "YYYY-MM-DD" date range
+fn parse_date_range(start_date: &str, end_date: &str) -> Result<(chrono::NaiveDate, chrono::NaiveDate), String> {
+    let parse_date = |date: &str| {
+        chrono::NaiveDate::parse_from_str(date.trim(), "%Y-%m-%d")
+            .map_err(|e| format!("Invalid date '{}' (expected YYYY-MM-DD): {}", date, e))
+    };
+    let start = parse_date(start_date)?;
+    let end = parse_date(end_date)?;
+    if end < start {
+        return Err("End date is before start date".to_string());
+    }
+    Ok((start, end))
+}
+
+/// Roll up the summaries of all saved meetings between two dates ("YYYY-MM-DD", inclusive)
+/// into one digest. Meetings without a summary are left out.
+#[tauri::command]
+async fn summarize_meetings_in_range(
+    state: State<'_, AppState>,
+    start_date: String,
+    end_date: String,
+) -> Result<MeetingRollup, AppError> {
+    let (start, end) = parse_date_range(&start_date, &end_date)?;
+
+    let model = state.selected_model.lock().map_err(|e| e.to_string())?.clone();
+    let llm = llm_client(&state)?;
+    let params = state.settings.lock().map_err(|e| e.to_string())?.generation.extraction;
+
+    let (meeting_ids, sections): (Vec<String>, Vec<String>) = state.meetings_db.lock().map_err(|e| e.to_string())?
+        .meetings_in_range(start, end)
+        .into_iter()
+        .filter_map(|m| {
+            let summary = m.summary.as_ref()?.to_text();
+            let date = m.local_date().map(|d| d.to_string()).unwrap_or_default();
+            Some((m.id.clone(), format!("### {} ({})\n{}", m.title, date, summary)))
+        })
+        .unzip();
+
+    if meeting_ids.is_empty() {
+        return Err(format!("No summarized meetings between {} and {}", start, end).into());
+    }
+
+    let prompt = format!(
+        r#"Below are summaries of {} meetings held between {} and {}.
+Write a single digest of what happened across them:
+
+## HIGHLIGHTS
+- [Most important outcomes and themes]
+
+## DECISIONS
+- [Decisions made, with the meeting they came from]
+
+## OPEN ACTION ITEMS
+- [Action items with owners if mentioned]
+
+## RISKS & FOLLOW-UPS
+- [Blockers, recurring issues, unanswered questions]
+
+Merge duplicates across meetings. Be concise.
+
+MEETING SUMMARIES:
+{}"#,
+        meeting_ids.len(),
+        start,
+        end,
+        sections.join("\n\n")
+    );
+
+    let rollup = generate_llm(&llm, GenerationKind::Text, &model, &prompt, &params, llm_fallback_model(&state)).await?;
+    tracing::info!("Generated rollup for {} meetings ({} to {})", meeting_ids.len(), start, end);
+
+    Ok(MeetingRollup { rollup, meeting_ids })
+}
+
+/// What changed between an earlier meeting and a later one (e.g. two sessions of a recurring sync)
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct MeetingComparison {
+    pub earlier_id: String,
+    pub later_id: String,
+    /// Action items from the earlier meeting that are still open
+    pub carried_over: Vec<String>,
+    /// Action items first raised in the later meeting
+    pub new_items: Vec<String>,
+    /// Earlier action items reported done or dropped
+    pub resolved: Vec<String>,
+    /// Short account of progress and changed decisions
+    pub changes: String,
+}
+
+/// Parse the model's comparison (a JSON object, possibly wrapped in extra text).
+/// If it isn't JSON, the whole response is kept as the description of changes.
+fn parse_comparison(response: &str) -> MeetingComparison {
+    #[derive(Deserialize, Default)]
+    #[serde(default)]
+    struct Parsed {
+        carried_over: Vec<String>,
+        #[serde(alias = "new")]
+        new_items: Vec<String>,
+        resolved: Vec<String>,
+        changes: String,
+    }
+
+    let json = match (response.find('{'), response.rfind('}')) {
+        (Some(start), Some(end)) if end > start => &response[start..=end],
+        _ => response,
+    };
+    let clean = |items: Vec<String>| -> Vec<String> {
+        items.into_iter().map(|i| i.trim().to_string()).filter(|i| !i.is_empty()).collect()
+    };
+    match serde_json::from_str::<Parsed>(json) {
+        Ok(parsed) => MeetingComparison {
+            carried_over: clean(parsed.carried_over),
+            new_items: clean(parsed.new_items),
+            resolved: clean(parsed.resolved),
+            changes: parsed.changes.trim().to_string(),
+            ..Default::default()
+        },
+        Err(_) => MeetingComparison { changes: response.trim().to_string(), ..Default::default() },
+    }
+}
+
+/// Compare two saved meetings (e.g. a follow-up and the original): which action items were
+/// carried over, which are new and which were resolved. Order of the IDs doesn't matter;
+/// the earlier meeting is the baseline. Uses summaries where available, else transcripts.
+#[tauri::command]
+async fn compare_meetings(state: State<'_, AppState>, id_a: String, id_b: String) -> Result<MeetingComparison, AppError> {
+    if id_a == id_b {
+        return Err("Pick two different meetings to compare".into());
+    }
+
+    let model = state.selected_model.lock().map_err(|e| e.to_string())?.clone();
+    let llm = llm_client(&state)?;
+    let params = state.settings.lock().map_err(|e| e.to_string())?.generation.extraction;
+
+    let (earlier, later) = {
+        let db = state.meetings_db.lock().map_err(|e| e.to_string())?;
+        let get = |id: &str| db.get_meeting(id).cloned().ok_or_else(|| format!("Meeting not found: {}", id));
+        let (a, b) = (get(&id_a)?, get(&id_b)?);
+        let date = |m: &database::StoredMeeting| chrono::DateTime::parse_from_rfc3339(&m.date).ok();
+        if date(&b) < date(&a) { (b, a) } else { (a, b) }
+    };
+
+    let content = |m: &database::StoredMeeting| -> Result<String, String> {
+        let text = match &m.summary {
+            Some(summary) => summary.to_text(),
+            None => format_transcript(&m.app_transcript()),
+        };
+        if text.trim().is_empty() {
+            return Err(format!("Meeting '{}' has no summary or transcript to compare", m.title));
+        }
+        let date = m.local_date().map(|d| d.to_string()).unwrap_or_default();
+        Ok(format!("### {} ({})\n{}", m.title, date, text))
+    };
+
+    let prompt = format!(
+        r#"Compare these two meetings. The second is a later session (e.g. a follow-up) of the first.
+
+Return ONLY a JSON object:
+{{"carried_over": [...], "new": [...], "resolved": [...], "changes": "..."}}
+- carried_over: action items from the earlier meeting that are still open in the later one
+- new: action items first raised in the later meeting
+- resolved: earlier action items the later meeting reports as done or dropped
+- changes: 2-4 sentences on progress and any decisions that changed
+Use short sentences with owners if mentioned. Use [] for empty lists.
+
+EARLIER MEETING:
+{}
+
+LATER MEETING:
+{}"#,
+        content(&earlier)?,
+        content(&later)?
+    );
+
+    let response = generate_llm(&llm, GenerationKind::Text, &model, &prompt, &params, llm_fallback_model(&state)).await?;
+    tracing::info!("Compared meetings {} and {}", earlier.id, later.id);
+
+    Ok(MeetingComparison {
+        earlier_id: earlier.id,
+        later_id: later.id,
+        ..parse_comparison(&response)
+    })
+}
+
+/// Re-run the summary for a saved meeting (e.g. after switching models or fixing the transcript)
+#[tauri::command]
+async fn regenerate_summary_for_meeting(state: State<'_, AppState>, id: String) -> Result<MeetingSummary, AppError> {
+    let model = state.selected_model.lock().map_err(|e| e.to_string())?.clone();
+    let llm = llm_client(&state)?;
+
+    let meeting = state.meetings_db.lock().map_err(|e| e.to_string())?
+        .get_meeting(&id)
+        .cloned()
+        .ok_or_else(|| format!("Meeting not found: {}", id))?;
+
+    let params = state.settings.lock().map_err(|e| e.to_string())?.generation.extraction;
+    let summary = summarize_transcript(&llm, &model, &params, llm_fallback_model(&state), "", &meeting.notes, &meeting.app_transcript()).await?;
+
+    let mut updated = meeting;
+    updated.summary = Some(database::convert_summary(summary.clone()));
+    updated.updated_at = chrono::Utc::now().to_rfc3339();
+    state.meetings_db.lock().map_err(|e| e.to_string())?.update_meeting(&id, updated)?;
+
+    tracing::info!("Regenerated summary for meeting {}", id);
+    Ok(summary)
+}
+
+/// Parse the LLM's chapter list. Accepts a bare JSON array or one wrapped in extra text.
+fn parse_chapters(response: &str) -> Result<Vec<database::Chapter>, String> {
+    let json_start = response.find('[').unwrap_or(0);
+    let json_end = response.rfind(']').map(|i| i + 1).unwrap_or(response.len());
+    let json_str = response.get(json_start..json_end).unwrap_or("");
+
+    let chapters: Vec<database::Chapter> = serde_json::from_str::<Vec<database::Chapter>>(json_str)
+        .map_err(|e| format!("Failed to parse chapters: {}", e))?
+        .into_iter()
+        .filter(|c| !c.title.trim().is_empty())
+        .collect();
+
+    if chapters.is_empty() {
+        return Err("The model returned no chapters".to_string());
+    }
+    Ok(chapters)
+}
+
+/// Split a transcript into topical chapters.
+/// With `meeting_id`, uses the saved meeting's transcript and stores the chapters on it;
+/// otherwise uses the live transcript.
+#[tauri::command]
+async fn detect_chapters(
+    state: State<'_, AppState>,
+    meeting_id: Option<String>,
+) -> Result<Vec<database::Chapter>, AppError> {
+    let model = state.selected_model.lock().map_err(|e| e.to_string())?.clone();
+    let llm = llm_client(&state)?;
+
+    let transcription = match &meeting_id {
+        Some(id) => state.meetings_db.lock().map_err(|e| e.to_string())?
+            .get_meeting(id)
+            .map(|m| m.app_transcript())
+            .ok_or_else(|| format!("Meeting not found: {}", id))?,
+        None => full_transcript(&state)?,
+    };
+
+    if transcription.is_empty() {
+        return Err("No transcription to split into chapters".into());
+    }
+
+    let transcript_text = format_transcript(&transcription);
+
+    let prompt = format!(
+        r#"Split this meeting transcript into topical chapters, in order.
+Return ONLY a JSON array (no markdown, no explanation) like:
+[{{"title": "Short chapter title", "start_timestamp": "HH:MM:SS"}}]
+
+Use the bracketed timestamp of the first line of each chapter as start_timestamp.
+Use 2-8 chapters depending on length. Titles should be 2-6 words.
 
 MEETING TRANSCRIPT:
 {}"#,
         transcript_text
     );
 
-    let response = groq::generate(&api_key, &model, &prompt).await.map_err(|e| e.to_string())?;
-    eprintln!("Summary response from AI (first 500 chars): {}", &response.chars().take(500).collect::<String>());
-
-    // Try to parse JSON response
-    let summary: MeetingSummary = match serde_json::from_str(&response) {
-        Ok(s) => {
-            eprintln!("Successfully parsed JSON summary");
-            s
-        },
-        Err(e1) => {
-            eprintln!("Direct JSON parse failed: {}", e1);
-            // If JSON parsing fails, try to extract JSON from the response
-            let json_start = response.find('{').unwrap_or(0);
-            let json_end = response.rfind('}').map(|i| i + 1).unwrap_or(response.len());
-            let json_str = &response[json_start..json_end];
-
-            match serde_json::from_str(json_str) {
-                Ok(s) => {
-                    eprintln!("Successfully parsed extracted JSON");
-                    s
-                },
-                Err(e2) => {
-                    eprintln!("Extracted JSON parse failed: {}. Falling back to text parsing.", e2);
-                    // Fallback: parse the text format into structured data
-                    parse_text_summary(&response)
-                }
-            }
+    let params = state.settings.lock().map_err(|e| e.to_string())?.generation.extraction;
+    let response = generate_llm(&llm, GenerationKind::Chapters, &model, &prompt, &params, llm_fallback_model(&state)).await?;
+    // On a bad reply, leave any chapters already stored on the meeting alone
+    let chapters = parse_chapters(&response)?;
+
+    if let Some(id) = meeting_id {
+        let mut db = state.meetings_db.lock().map_err(|e| e.to_string())?;
+        if let Some(mut meeting) = db.get_meeting(&id).cloned() {
+            meeting.chapters = chapters.clone();
+            meeting.updated_at = chrono::Utc::now().to_rfc3339();
+            db.update_meeting(&id, meeting)?;
         }
-    };
-
-    // Store raw summary for backward compatibility
-    let raw = format!(
-        "## KEY POINTS\n{}\n\n## ACTION ITEMS\n{}\n\n## DECISIONS\n{}\n\n## NOTES\n{}",
-        summary.key_points.iter().map(|p| format!("• {}", p)).collect::<Vec<_>>().join("\n"),
-        if summary.action_items.is_empty() { "• None identified".to_string() } else { summary.action_items.iter().map(|p| format!("• {}", p)).collect::<Vec<_>>().join("\n") },
-        if summary.decisions.is_empty() { "• None identified".to_string() } else { summary.decisions.iter().map(|p| format!("• {}", p)).collect::<Vec<_>>().join("\n") },
-        if summary.notes.is_empty() { "• None".to_string() } else { summary.notes.iter().map(|p| format!("• {}", p)).collect::<Vec<_>>().join("\n") }
-    );
-    *state.summary.lock().map_err(|e| e.to_string())? = raw.clone();
+    }
 
-    // Return summary with raw_summary populated
-    Ok(MeetingSummary {
-        key_points: summary.key_points,
-        action_items: summary.action_items,
-        decisions: summary.decisions,
-        notes: summary.notes,
-        raw_summary: raw,
-    })
+    tracing::info!("Detected {} chapters", chapters.len());
+    Ok(chapters)
 }
 
 #[tauri::command]
 async fn generate_reply_suggestions(
     state: State<'_, AppState>,
     context: String,
-) -> Result<Vec<String>, String> {
+) -> Result<Vec<String>, AppError> {
     let model = state.selected_model.lock().map_err(|e| e.to_string())?.clone();
-    let api_key = state.groq_api_key.lock().map_err(|e| e.to_string())?.clone();
+    let llm = llm_client(&state)?;
     let transcription = state.transcription.lock().map_err(|e| e.to_string())?.clone();
 
     let recent_context: String = transcription
@@ -1091,7 +4731,8 @@ async fn generate_reply_suggestions(
         recent_context, context
     );
 
-    let response = groq::generate(&api_key, &model, &prompt).await.map_err(|e| e.to_string())?;
+    let params = state.settings.lock().map_err(|e| e.to_string())?.generation.replies;
+    let response = generate_llm(&llm, GenerationKind::Replies, &model, &prompt, &params, llm_fallback_model(&state)).await?;
 
     let replies: Vec<String> = response
         .lines()
@@ -1105,33 +4746,63 @@ async fn generate_reply_suggestions(
     Ok(replies)
 }
 
+/// The segment replies should respond to: the latest one from `target_speaker`
+/// (case-insensitive), or the latest one overall when no speaker is given
+fn reply_target<'a>(
+    transcription: &'a [TranscriptSegment],
+    target_speaker: Option<&str>,
+) -> Result<Option<&'a TranscriptSegment>, String> {
+    match target_speaker.map(str::trim).filter(|s| !s.is_empty()) {
+        Some(speaker) => transcription
+            .iter()
+            .rev()
+            .find(|s| s.speaker.eq_ignore_ascii_case(speaker))
+            .map(Some)
+            .ok_or_else(|| format!("{} hasn't said anything yet", speaker)),
+        None => Ok(transcription.last()),
+    }
+}
+
 #[tauri::command]
 async fn generate_auto_replies(
     state: State<'_, AppState>,
-) -> Result<Vec<String>, String> {
+    target_speaker: Option<String>,
+) -> Result<Vec<String>, AppError> {
     let model = state.selected_model.lock().map_err(|e| e.to_string())?.clone();
-    let api_key = state.groq_api_key.lock().map_err(|e| e.to_string())?.clone();
+    let llm = llm_client(&state)?;
     let transcription = state.transcription.lock().map_err(|e| e.to_string())?.clone();
     let meeting_context = state.meeting_context.lock().map_err(|e| e.to_string())?.clone();
 
-    if api_key.is_empty() {
-        return Err("Groq API key not set. Please add it in Settings.".to_string());
+    if llm.api_key.is_empty() {
+        return Err(AppError::new(ErrorCode::SetupRequired, format!("{} API key not set. Please add it in Settings.", llm.provider.name())));
     }
 
     if transcription.is_empty() {
-        return Err("No transcription available. Record or transcribe something first.".to_string());
+        return Err("No transcription available. Record or transcribe something first.".into());
     }
 
-    // Get full transcript for context (limit to last 20 segments for performance)
-    let context_segments: Vec<_> = transcription.iter().rev().take(20).rev().collect();
-    let full_context: String = context_segments
-        .iter()
-        .map(|s| format!("[{}] {}: {}", s.timestamp, s.speaker, s.text))
-        .collect::<Vec<_>>()
-        .join("\n");
+    // In focus mode, keep the current suggestions while you're the one talking
+    let trigger = *state.reply_trigger.lock().map_err(|e| e.to_string())?;
+    let last_mic = *state.last_mic_speech.lock().map_err(|e| e.to_string())?;
+    if trigger == settings::ReplyTrigger::OnParticipantFinal && mic_active(last_mic, std::time::Instant::now()) {
+        tracing::info!("Microphone active, keeping current reply suggestions");
+        return Ok(state.suggested_replies.lock().map_err(|e| e.to_string())?.clone());
+    }
 
-    // Detect the last speaker's intent
-    let last_segment = transcription.last().map(|s| s.text.clone()).unwrap_or_default();
+    // Get full transcript for context (limit to last 20 segments for performance)
+    let context_start = transcription.len().saturating_sub(20);
+    let full_context = format_transcript(&transcription[context_start..]);
+
+    // Respond to what the target speaker said last (or to whoever spoke last)
+    let target = reply_target(&transcription, target_speaker.as_deref())?;
+    let last_segment = target.map(|s| s.text.clone()).unwrap_or_default();
+    let target_section = match (&target_speaker, target) {
+        (Some(_), Some(segment)) => format!(
+            "YOU ARE REPLYING TO: {}\nFrame every suggestion as a response to {}; address them by name where it fits.\n\n",
+            segment.speaker, segment.speaker
+        ),
+        _ => String::new(),
+    };
 
     // Build meeting context section if provided
     let meeting_context_section = if !meeting_context.is_empty() {
@@ -1146,7 +4817,7 @@ async fn generate_auto_replies(
 {}CONVERSATION:
 {}
 
-JUST SAID: "{}"
+{}JUST SAID: "{}"
 
 Generate 6 tactical suggestions grouped by type. Mark the BEST one with ★.
 
@@ -1170,12 +4841,13 @@ MIRROR: The accessibility concern?
 PROBE: Who raised the PDF requirement?
 REFRAME: What if we phase the rollout?
 LABEL: Sounds like competing priorities"#,
-        meeting_context_section, full_context, last_segment
+        meeting_context_section, full_context, target_section, last_segment
     );
 
-    eprintln!("Generating contextual auto replies from transcript...");
-    let response = groq::generate(&api_key, &model, &prompt).await.map_err(|e| e.to_string())?;
-    eprintln!("Got response from Groq");
+    tracing::info!("Generating contextual auto replies from transcript...");
+    let params = state.settings.lock().map_err(|e| e.to_string())?.generation.replies;
+    let response = generate_llm(&llm, GenerationKind::Replies, &model, &prompt, &params, llm_fallback_model(&state)).await?;
+    tracing::info!("Got response from Groq");
 
     let replies: Vec<String> = response
         .lines()
@@ -1206,17 +4878,345 @@ LABEL: Sounds like competing priorities"#,
     Ok(replies)
 }
 
+/// Shortest automatic reply refresh interval, to stay inside LLM rate limits
+const MIN_AUTO_REFRESH_REPLIES_SECS: u64 = 10;
+
+#[derive(Debug, Clone, Serialize)]
+struct RepliesUpdatedEvent {
+    replies: Vec<String>,
+}
+
+/// Regenerate the reply suggestions from the newest segments, replacing the stale ones.
+/// Also emits `replies-updated`.
+#[tauri::command]
+async fn refresh_replies(app: AppHandle, state: State<'_, AppState>) -> Result<Vec<String>, AppError> {
+    let replies = generate_auto_replies(state, None).await?;
+    let _ = app.emit("replies-updated", RepliesUpdatedEvent { replies: replies.clone() });
+    Ok(replies)
+}
+
+/// How recently the microphone must have produced speech to count as "you're talking"
+const MIC_ACTIVE_WINDOW_MS: u64 = 2000;
+
+fn mic_active(last_mic_speech: Option<std::time::Instant>, now: std::time::Instant) -> bool {
+    last_mic_speech.is_some_and(|last| {
+        now.saturating_duration_since(last) < std::time::Duration::from_millis(MIC_ACTIVE_WINDOW_MS)
+    })
+}
+
+/// Choose when replies are generated: "always", or "on-participant-final" (focus mode) to
+/// skip generation while your microphone is active and refresh automatically only after a
+/// participant finishes speaking. Focus mode relies on Deepgram's mic/system audio split.
 #[tauri::command]
-async fn check_connection(state: State<'_, AppState>) -> Result<bool, String> {
-    let api_key = state.groq_api_key.lock().map_err(|e| e.to_string())?.clone();
-    groq::check_api_key(&api_key).await.map_err(|e| e.to_string())
+async fn set_reply_trigger(state: State<'_, AppState>, mode: String) -> Result<settings::ReplyTrigger, AppError> {
+    let trigger = settings::ReplyTrigger::parse(&mode)?;
+    *state.reply_trigger.lock().map_err(|e| e.to_string())? = trigger;
+
+    let mut settings = state.settings.lock().map_err(|e| e.to_string())?;
+    settings.reply_trigger = trigger;
+    if let Err(e) = settings.save() {
+        tracing::warn!("Failed to persist settings: {}", e);
+    }
+
+    Ok(trigger)
+}
+
+/// Get when replies are generated
+#[tauri::command]
+async fn get_reply_trigger(state: State<'_, AppState>) -> Result<settings::ReplyTrigger, AppError> {
+    Ok(*state.reply_trigger.lock().map_err(|e| e.to_string())?)
+}
+
+/// Refresh reply suggestions every `interval_secs` while transcribing (0 turns it off)
+#[tauri::command]
+async fn set_auto_refresh_replies(state: State<'_, AppState>, interval_secs: u64) -> Result<u64, AppError> {
+    if interval_secs != 0 && interval_secs < MIN_AUTO_REFRESH_REPLIES_SECS {
+        return Err(format!("Refresh interval must be at least {} seconds", MIN_AUTO_REFRESH_REPLIES_SECS).into());
+    }
+    *state.auto_refresh_replies_secs.lock().map_err(|e| e.to_string())? = interval_secs;
+
+    let mut settings = state.settings.lock().map_err(|e| e.to_string())?;
+    settings.auto_refresh_replies_secs = interval_secs;
+    if let Err(e) = settings.save() {
+        tracing::warn!("Failed to persist settings: {}", e);
+    }
+
+    Ok(interval_secs)
+}
+
+/// Get the automatic reply refresh interval in seconds (0 = off)
+#[tauri::command]
+async fn get_auto_refresh_replies(state: State<'_, AppState>) -> Result<u64, AppError> {
+    Ok(*state.auto_refresh_replies_secs.lock().map_err(|e| e.to_string())?)
+}
+
+/// Background task that keeps reply suggestions in step with the live conversation.
+/// Skips a refresh when nothing new was said since the last one.
+fn spawn_reply_refresh_task(app: AppHandle, session: u64) {
+    tokio::spawn(async move {
+        let mut last_refreshed_count: Option<usize> = None;
+        let mut last_run = std::time::Instant::now();
+        app.state::<AppState>().participant_final_pending.store(false, Ordering::SeqCst);
+
+        loop {
+            tokio::time::sleep(std::time::Duration::from_secs(1)).await;
+
+            let state = app.state::<AppState>();
+            if !live_session_active(&state, session) {
+                break;
+            }
+
+            let trigger = state.reply_trigger.lock().map(|t| *t).unwrap_or_default();
+            match trigger {
+                // Focus mode: whenever a participant finishes while you're not talking,
+                // independent of the refresh interval
+                settings::ReplyTrigger::OnParticipantFinal => {
+                    let last_mic = state.last_mic_speech.lock().map(|t| *t).unwrap_or(None);
+                    if !state.participant_final_pending.load(Ordering::SeqCst)
+                        || mic_active(last_mic, std::time::Instant::now())
+                    {
+                        continue;
+                    }
+                }
+                settings::ReplyTrigger::Always => {
+                    let interval = state.auto_refresh_replies_secs.lock().map(|v| *v).unwrap_or(0);
+                    if interval == 0 || last_run.elapsed().as_secs() < interval {
+                        continue;
+                    }
+                }
+            }
+
+            let count = archived_segment_count(&state).unwrap_or(0)
+                + state.transcription.lock().map(|t| t.len()).unwrap_or(0);
+            if count == 0 || last_refreshed_count == Some(count) {
+                continue;
+            }
+            state.participant_final_pending.store(false, Ordering::SeqCst);
+
+            last_run = std::time::Instant::now();
+            last_refreshed_count = Some(count);
+            if let Err(e) = refresh_replies(app.clone(), app.state()).await {
+                tracing::warn!("Automatic reply refresh failed: {}", e);
+            }
+        }
+    });
+}
+
+#[tauri::command]
+async fn check_connection(state: State<'_, AppState>) -> Result<bool, AppError> {
+    let llm = llm_client(&state)?;
+    match llm.provider {
+        LlmProvider::Groq => Ok(groq::check_api_key(&llm.api_key).await?),
+        LlmProvider::OpenAI => Ok(openai::check_api_key(&llm.api_key).await?),
+        LlmProvider::Demo => Ok(true),
+    }
+}
+
+/// Fixed prompt for `test_llm`: tiny so the test is quick and nearly free
+const TEST_LLM_PROMPT: &str = "Reply with the single word: ready";
+const TEST_LLM_MAX_TOKENS: u32 = 8;
+
+/// Outcome of a test generation against one provider and model
+#[derive(Debug, Clone, Serialize)]
+pub struct LlmTestResult {
+    pub provider: String,
+    pub model: String,
+    pub success: bool,
+    /// Time until the full reply arrived
+    pub latency_ms: u64,
+    /// None if the model returned no text
+    pub first_token_ms: Option<u64>,
+    pub response: String,
+    pub error: Option<String>,
+}
+
+/// Send a tiny fixed prompt to `provider` ("groq", "openai" or "ollama") with `model` and
+/// report whether it answered, the latency and the time to first token, for the settings
+/// "Test" button. A provider error is reported in the result rather than as a failure.
+#[tauri::command]
+async fn test_llm(state: State<'_, AppState>, provider: String, model: String) -> Result<LlmTestResult, AppError> {
+    let provider = provider.to_lowercase();
+    let model = model.trim().to_string();
+    if model.is_empty() {
+        return Err("Model cannot be empty".into());
+    }
+
+    let started = std::time::Instant::now();
+    let result = match provider.as_str() {
+        "groq" => {
+            let api_key = state.groq_api_key.lock().map_err(|e| e.to_string())?.clone();
+            if api_key.is_empty() {
+                return Err(AppError::new(ErrorCode::SetupRequired, "Groq API key not set. Please add it in Settings."));
+            }
+            groq::stream_chat(&api_key, &model, TEST_LLM_PROMPT, TEST_LLM_MAX_TOKENS).await
+        }
+        "openai" => {
+            let api_key = state.openai_api_key.lock().map_err(|e| e.to_string())?.clone();
+            if api_key.is_empty() {
+                return Err(AppError::new(ErrorCode::SetupRequired, "OpenAI API key not set. Add one in Settings"));
+            }
+            openai::stream_chat(&api_key, &model, TEST_LLM_PROMPT, TEST_LLM_MAX_TOKENS).await
+        }
+        "ollama" => ollama::stream_generate(&model, TEST_LLM_PROMPT, TEST_LLM_MAX_TOKENS).await,
+        _ => return Err(format!("Unknown LLM provider: {} (expected groq, openai or ollama)", provider).into()),
+    };
+    let latency_ms = started.elapsed().as_millis() as u64;
+
+    let (reply, error) = match result {
+        Ok(reply) if reply.text.trim().is_empty() => (reply, Some("Model returned an empty response".to_string())),
+        Ok(reply) => (reply, None),
+        Err(e) => (net::StreamedReply::default(), Some(e.to_string())),
+    };
+    match &error {
+        None => {
+            usage::record_tokens(&provider, tokens::estimate_tokens(TEST_LLM_PROMPT), tokens::estimate_tokens(&reply.text));
+            tracing::info!("LLM test {} / {} answered in {}ms", provider, model, latency_ms);
+        }
+        Some(e) => tracing::warn!("LLM test {} / {} failed: {}", provider, model, e),
+    }
+
+    Ok(LlmTestResult {
+        provider,
+        model,
+        success: error.is_none(),
+        latency_ms,
+        first_token_ms: reply.first_token.map(|d| d.as_millis() as u64),
+        response: reply.text.trim().to_string(),
+        error,
+    })
+}
+
+/// Collect an environment report (platform, devices, provider status) for bug reports
+#[tauri::command]
+async fn run_diagnostics(state: State<'_, AppState>) -> Result<diagnostics::Diagnostics, AppError> {
+    let groq_key = state.groq_api_key.lock().map_err(|e| e.to_string())?.clone();
+    let assemblyai_key = state.assemblyai_api_key.lock().map_err(|e| e.to_string())?.clone();
+    let deepgram_key = state.deepgram_api_key.lock().map_err(|e| e.to_string())?.clone();
+    let openai_key = state.openai_api_key.lock().map_err(|e| e.to_string())?.clone();
+
+    Ok(diagnostics::run(&groq_key, &assemblyai_key, &deepgram_key, &openai_key).await)
+}
+
+/// How often to look for a running meeting app when auto-hide is on
+const AUTO_HIDE_CHECK_INTERVAL_SECS: u64 = 5;
+
+#[derive(Debug, Clone, Serialize)]
+struct ScreenShareExclusionEvent {
+    excluded: bool,
+    meeting_app: Option<String>,
+}
+
+/// While `auto_hide_on_share` is on, exclude the window from screen capture whenever a
+/// meeting app is running and restore it when the app closes. Only exclusions applied
+/// here are undone, so a manual toggle is left alone. Emits `screen-share-exclusion-changed`.
+fn spawn_auto_hide_task(app: AppHandle) {
+    if !screen_share::is_supported() {
+        return;
+    }
+
+    tauri::async_runtime::spawn(async move {
+        loop {
+            tokio::time::sleep(std::time::Duration::from_secs(AUTO_HIDE_CHECK_INTERVAL_SECS)).await;
+
+            let state = app.state::<AppState>();
+            let enabled = state.settings.lock()
+                .map(|s| s.auto_hide_on_share)
+                .unwrap_or(false);
+            let auto_hidden = state.screen_share_exclusion.lock()
+                .map(|s| *s == Some(ExclusionSource::AutoHide))
+                .unwrap_or(false);
+            if !enabled && !auto_hidden {
+                continue;
+            }
+
+            let meeting_app = if enabled {
+                tauri::async_runtime::spawn_blocking(meeting_monitor::MeetingMonitor::detect_meeting_apps)
+                    .await
+                    .unwrap_or(None)
+            } else {
+                None
+            };
+            let should_hide = meeting_app.is_some();
+            if should_hide == auto_hidden {
+                continue;
+            }
+
+            let Some(window) = app.get_webview_window("main").map(|w| w.as_ref().window()) else {
+                continue;
+            };
+            // AppKit and Win32 window calls have to be made on the main thread
+            let exclusion = state.screen_share_exclusion.clone();
+            let (result_tx, result_rx) = tokio::sync::oneshot::channel();
+            let dispatched = app.run_on_main_thread(move || {
+                let _ = result_tx.send(apply_auto_hide(&window, &exclusion, should_hide));
+            });
+            let result = match dispatched {
+                Ok(()) => result_rx.await.unwrap_or_else(|_| Err("Main thread dropped the request".to_string())),
+                Err(e) => Err(e.to_string()),
+            };
+            match result {
+                // The user toggled the exclusion by hand, which takes precedence
+                Ok(false) => {}
+                Ok(true) => {
+                    tracing::info!("Auto screen share exclusion: {} ({:?})", should_hide, meeting_app);
+                    let _ = app.emit("screen-share-exclusion-changed", ScreenShareExclusionEvent {
+                        excluded: should_hide,
+                        meeting_app,
+                    });
+                }
+                Err(e) => tracing::warn!("Failed to toggle screen share exclusion: {}", e),
+            }
+        }
+    });
+}
+
+/// Exclude (or restore) the window for the auto-hide task. Only excludes a window nobody has
+/// excluded yet, and only restores one this task excluded. Returns whether anything changed.
+fn apply_auto_hide(
+    window: &tauri::Window,
+    exclusion: &Mutex<Option<ExclusionSource>>,
+    exclude: bool,
+) -> Result<bool, String> {
+    let mut current = exclusion.lock().map_err(|e| e.to_string())?;
+    let expected = if exclude { None } else { Some(ExclusionSource::AutoHide) };
+    if *current != expected {
+        return Ok(false);
+    }
+    screen_share::set_screen_share_exclusion(window, exclude)?;
+    *current = exclude.then_some(ExclusionSource::AutoHide);
+    Ok(true)
+}
+
+/// Enable/disable hiding the window from screen sharing while a meeting app is running
+#[tauri::command]
+async fn set_auto_hide_on_share(state: State<'_, AppState>, enabled: bool) -> Result<(), AppError> {
+    let mut settings = state.settings.lock().map_err(|e| e.to_string())?;
+    settings.auto_hide_on_share = enabled;
+    if let Err(e) = settings.save() {
+        tracing::warn!("Failed to persist settings: {}", e);
+    }
+    Ok(())
+}
+
+/// Whether the window is hidden from screen sharing automatically during meetings
+#[tauri::command]
+async fn get_auto_hide_on_share(state: State<'_, AppState>) -> Result<bool, AppError> {
+    Ok(state.settings.lock().map_err(|e| e.to_string())?.auto_hide_on_share)
 }
 
 /// Set screen share exclusion (hide window during screen sharing)
 #[tauri::command]
-fn set_screen_share_exclusion(window: tauri::Window, exclude: bool) -> Result<bool, String> {
-    screen_share::set_screen_share_exclusion(&window, exclude)?;
-    Ok(exclude)
+fn set_screen_share_exclusion(
+    state: State<'_, AppState>,
+    window: tauri::Window,
+    exclude: bool,
+) -> Result<screen_share::ExclusionResult, AppError> {
+    let mut current = state.screen_share_exclusion.lock().map_err(|e| e.to_string())?;
+    let result = screen_share::apply_exclusion(&window, exclude)?;
+    if result.supported {
+        *current = exclude.then_some(ExclusionSource::Manual);
+    }
+    Ok(result)
 }
 
 /// Check if screen share exclusion is supported on this platform
@@ -1228,7 +5228,7 @@ fn is_screen_share_exclusion_supported() -> bool {
 /// Get platform info about screen share exclusion support
 #[tauri::command]
 fn get_screen_share_platform_info() -> String {
-    screen_share::get_platform_info().to_string()
+    screen_share::get_platform_info()
 }
 
 /// Start mock transcription using pre-recorded audio files
@@ -1239,25 +5239,26 @@ async fn start_mock_transcription(
     test_audio_dir: String,
     state: State<'_, AppState>,
     app: AppHandle,
-) -> Result<String, String> {
+) -> Result<String, AppError> {
     // Check if already mock transcribing
     {
         let is_mock = state.is_mock_transcribing.lock().map_err(|e| e.to_string())?;
         if *is_mock {
-            return Err("Mock transcription already running".to_string());
+            return Err("Mock transcription already running".into());
         }
     }
 
     // Get API key
     let api_key = state.groq_api_key.lock().map_err(|e| e.to_string())?.clone();
     if api_key.is_empty() {
-        return Err("Groq API key not set. Please add it in Settings.".to_string());
+        return Err(AppError::new(ErrorCode::SetupRequired, "Groq API key not set. Please add it in Settings."));
     }
 
     // Set up stop signal
     let (stop_tx, stop_rx) = watch::channel(false);
     *state.mock_stop_signal.lock().map_err(|e| e.to_string())? = Some(stop_tx);
     *state.is_mock_transcribing.lock().map_err(|e| e.to_string())? = true;
+    mark_meeting_started(&state)?;
 
     let config = mock::MockConfig {
         test_audio_dir: test_audio_dir.clone(),
@@ -1269,8 +5270,8 @@ async fn start_mock_transcription(
     // Spawn the mock session
     tokio::spawn(async move {
         match mock::run_mock_session(config, &api_key, app, transcription_state, stop_rx).await {
-            Ok(_) => eprintln!("Mock transcription completed successfully"),
-            Err(e) => eprintln!("Mock transcription error: {}", e),
+            Ok(_) => tracing::info!("Mock transcription completed successfully"),
+            Err(e) => tracing::warn!("Mock transcription error: {}", e),
         }
 
         // Mark as not running
@@ -1284,14 +5285,83 @@ async fn start_mock_transcription(
 
 /// Stop mock transcription
 #[tauri::command]
-async fn stop_mock_transcription(state: State<'_, AppState>) -> Result<(), String> {
+async fn stop_mock_transcription(state: State<'_, AppState>) -> Result<(), AppError> {
     // Send stop signal
     if let Some(tx) = state.mock_stop_signal.lock().map_err(|e| e.to_string())?.take() {
         let _ = tx.send(true);
     }
 
     *state.is_mock_transcribing.lock().map_err(|e| e.to_string())? = false;
-    eprintln!("Mock transcription stopped");
+    tracing::info!("Mock transcription stopped");
+    Ok(())
+}
+
+// ============== Demo Mode ==============
+
+/// Play a scripted meeting with canned summaries and replies, without any API keys or
+/// network calls. The demo transcript can't be saved; `disable_demo_mode` clears it.
+#[tauri::command]
+async fn enable_demo_mode(state: State<'_, AppState>, app: AppHandle) -> Result<(), AppError> {
+    if *state.is_recording.lock().map_err(|e| e.to_string())?
+        || *state.is_live_transcribing.lock().map_err(|e| e.to_string())?
+        || *state.is_mock_transcribing.lock().map_err(|e| e.to_string())?
+    {
+        return Err(AppError::new(ErrorCode::InvalidState, "Stop the current recording before starting the demo"));
+    }
+    if state.demo_mode.swap(true, Ordering::SeqCst) {
+        return Err(AppError::new(ErrorCode::InvalidState, "Demo mode is already on"));
+    }
+
+    clear_transcription(state.clone()).await?;
+    mark_meeting_started(&state)?;
+    tracing::info!("Demo mode enabled");
+
+    let generation = state.demo_generation.fetch_add(1, Ordering::SeqCst) + 1;
+    let demo_generation = state.demo_generation.clone();
+    let transcription = state.transcription.clone();
+    tokio::spawn(async move {
+        let mut elapsed = 0;
+        for segment in demo::script() {
+            let offset = segment.start_ms.unwrap_or_default() / 1000;
+            tokio::time::sleep(std::time::Duration::from_secs(offset.saturating_sub(elapsed))).await;
+            elapsed = offset;
+
+            // Turned off, or off and on again with a new script playing
+            if demo_generation.load(Ordering::SeqCst) != generation {
+                return;
+            }
+            let _ = app.emit("transcript-update", TranscriptEvent {
+                text: segment.text.clone(),
+                timestamp: segment.timestamp.clone(),
+                speaker: segment.speaker.clone(),
+                is_final: true,
+            });
+            if let Ok(mut transcription) = transcription.lock() {
+                transcription.push(segment);
+            }
+        }
+        tracing::info!("Demo transcript finished");
+    });
+
+    Ok(())
+}
+
+/// Recording or transcribing would mix real audio into the scripted demo meeting
+fn ensure_demo_off(state: &AppState) -> Result<(), AppError> {
+    if state.demo_mode.load(Ordering::SeqCst) {
+        return Err(AppError::new(ErrorCode::InvalidState, "Turn off demo mode before recording a real meeting"));
+    }
+    Ok(())
+}
+
+/// Leave demo mode and clear the demo transcript and summaries
+#[tauri::command]
+async fn disable_demo_mode(state: State<'_, AppState>) -> Result<(), AppError> {
+    if state.demo_mode.swap(false, Ordering::SeqCst) {
+        state.demo_generation.fetch_add(1, Ordering::SeqCst);
+        clear_transcription(state.clone()).await?;
+        tracing::info!("Demo mode disabled");
+    }
     Ok(())
 }
 
@@ -1303,7 +5373,7 @@ async fn set_google_credentials(
     state: State<'_, AppState>,
     client_id: String,
     client_secret: String,
-) -> Result<bool, String> {
+) -> Result<bool, AppError> {
     *state.google_client_id.lock().map_err(|e| e.to_string())? = client_id.clone();
     *state.google_client_secret.lock().map_err(|e| e.to_string())? = client_secret.clone();
 
@@ -1313,83 +5383,245 @@ async fn set_google_credentials(
     settings.google_client_secret = client_secret;
     settings.save().map_err(|e| e.to_string())?;
 
-    Ok(true)
+    Ok(true)
+}
+
+/// Get Google OAuth auth URL
+#[tauri::command]
+async fn get_google_auth_url(state: State<'_, AppState>) -> Result<String, AppError> {
+    let client_id = state.google_client_id.lock().map_err(|e| e.to_string())?.clone();
+    let client_secret = state.google_client_secret.lock().map_err(|e| e.to_string())?.clone();
+
+    if client_id.is_empty() || client_secret.is_empty() {
+        return Err(AppError::new(ErrorCode::SetupRequired, "Google credentials not set. Please add them in Settings."));
+    }
+
+    let cal = calendar::GoogleCalendar::new(client_id, client_secret);
+    Ok(cal.get_auth_url())
+}
+
+/// Exchange Google OAuth code for tokens
+#[tauri::command]
+async fn exchange_google_code(state: State<'_, AppState>, code: String) -> Result<bool, AppError> {
+    let client_id = state.google_client_id.lock().map_err(|e| e.to_string())?.clone();
+    let client_secret = state.google_client_secret.lock().map_err(|e| e.to_string())?.clone();
+
+    if client_id.is_empty() || client_secret.is_empty() {
+        return Err(AppError::new(ErrorCode::SetupRequired, "Google credentials not set"));
+    }
+
+    let cal = calendar::GoogleCalendar::new(client_id, client_secret);
+    cal.exchange_code(&code).await?;
+    Ok(true)
+}
+
+/// Check if Google Calendar is connected
+#[tauri::command]
+fn is_calendar_connected() -> bool {
+    calendar::is_calendar_connected()
+}
+
+/// Disconnect the active Google account
+#[tauri::command]
+fn disconnect_calendar() -> Result<(), AppError> {
+    Ok(calendar::disconnect_calendar()?)
+}
+
+/// Connected Google accounts, marking the one calendar requests use.
+/// Connect another account by going through the OAuth flow again.
+#[tauri::command]
+async fn list_google_accounts(state: State<'_, AppState>, app: AppHandle) -> Result<Vec<calendar::GoogleAccount>, AppError> {
+    let client_id = state.google_client_id.lock().map_err(|e| e.to_string())?.clone();
+    let client_secret = state.google_client_secret.lock().map_err(|e| e.to_string())?.clone();
+    if !client_id.is_empty() && !client_secret.is_empty() {
+        let cal = calendar::GoogleCalendar::new(client_id, client_secret).with_app_handle(app);
+        if let Err(e) = cal.migrate_legacy_tokens().await {
+            tracing::warn!("Failed to migrate Google tokens: {}", e);
+        }
+    }
+
+    let active = calendar::active_account();
+    Ok(calendar::list_accounts()
+        .into_iter()
+        .map(|email| calendar::GoogleAccount { active: active.as_ref() == Some(&email), email })
+        .collect())
+}
+
+/// Use another connected Google account for calendar events and meeting auto-start
+#[tauri::command]
+fn switch_google_account(email: String) -> Result<(), AppError> {
+    calendar::set_active_account(&email)?;
+    tracing::info!("Switched Google account to {}", email);
+    Ok(())
 }
 
-/// Get Google OAuth auth URL
+/// Get upcoming calendar events
 #[tauri::command]
-async fn get_google_auth_url(state: State<'_, AppState>) -> Result<String, String> {
+async fn get_upcoming_events(state: State<'_, AppState>, app: AppHandle, limit: Option<u32>) -> Result<Vec<calendar::SimpleCalendarEvent>, AppError> {
     let client_id = state.google_client_id.lock().map_err(|e| e.to_string())?.clone();
     let client_secret = state.google_client_secret.lock().map_err(|e| e.to_string())?.clone();
 
     if client_id.is_empty() || client_secret.is_empty() {
-        return Err("Google credentials not set. Please add them in Settings.".to_string());
+        return Err(AppError::new(ErrorCode::SetupRequired, "Google credentials not set"));
     }
 
-    let cal = calendar::GoogleCalendar::new(client_id, client_secret);
-    Ok(cal.get_auth_url())
+    let cal = calendar::GoogleCalendar::new(client_id, client_secret).with_app_handle(app);
+    cal.get_upcoming_events(limit).await
 }
 
-/// Exchange Google OAuth code for tokens
+/// Get past calendar events
 #[tauri::command]
-async fn exchange_google_code(state: State<'_, AppState>, code: String) -> Result<bool, String> {
+async fn get_past_calendar_events(state: State<'_, AppState>, app: AppHandle, days: Option<i64>, limit: Option<u32>) -> Result<Vec<calendar::SimpleCalendarEvent>, AppError> {
     let client_id = state.google_client_id.lock().map_err(|e| e.to_string())?.clone();
     let client_secret = state.google_client_secret.lock().map_err(|e| e.to_string())?.clone();
 
     if client_id.is_empty() || client_secret.is_empty() {
-        return Err("Google credentials not set".to_string());
+        return Err(AppError::new(ErrorCode::SetupRequired, "Google credentials not set"));
     }
 
-    let cal = calendar::GoogleCalendar::new(client_id, client_secret);
-    cal.exchange_code(&code).await?;
-    Ok(true)
+    let cal = calendar::GoogleCalendar::new(client_id, client_secret).with_app_handle(app);
+    cal.get_past_events(days, limit).await
 }
 
-/// Check if Google Calendar is connected
+/// Calendars of the active Google account, marking the ones events come from
 #[tauri::command]
-fn is_calendar_connected() -> bool {
-    calendar::is_calendar_connected()
+async fn list_calendars(state: State<'_, AppState>, app: AppHandle) -> Result<Vec<calendar::CalendarInfo>, AppError> {
+    let client_id = state.google_client_id.lock().map_err(|e| e.to_string())?.clone();
+    let client_secret = state.google_client_secret.lock().map_err(|e| e.to_string())?.clone();
+
+    if client_id.is_empty() || client_secret.is_empty() {
+        return Err(AppError::new(ErrorCode::SetupRequired, "Google credentials not set"));
+    }
+
+    let cal = calendar::GoogleCalendar::new(client_id, client_secret).with_app_handle(app);
+    cal.list_calendars().await
 }
 
-/// Disconnect Google Calendar
+/// Choose which calendars events and auto-start use (empty = primary only)
 #[tauri::command]
-fn disconnect_calendar() -> Result<(), String> {
-    calendar::disconnect_calendar()
+fn set_active_calendars(ids: Vec<String>) -> Result<Vec<String>, AppError> {
+    calendar::set_active_calendars(ids)
 }
 
-/// Get upcoming calendar events
-#[tauri::command]
-async fn get_upcoming_events(state: State<'_, AppState>, limit: Option<u32>) -> Result<Vec<calendar::SimpleCalendarEvent>, String> {
-    let client_id = state.google_client_id.lock().map_err(|e| e.to_string())?.clone();
-    let client_secret = state.google_client_secret.lock().map_err(|e| e.to_string())?.clone();
+/// Meeting context for the LLM from a calendar event's title, attendees and description
+fn event_meeting_context(event: &calendar::SimpleCalendarEvent) -> String {
+    let mut context = format!("Meeting: {}", event.title);
+    if !event.attendees.is_empty() {
+        context.push_str(&format!("\nAttendees: {}", event.attendees.join(", ")));
+    }
+    if let Some(description) = event.description.as_deref().map(str::trim).filter(|d| !d.is_empty()) {
+        context.push_str(&format!("\nAgenda:\n{}", description));
+    }
+    context
+}
 
-    if client_id.is_empty() || client_secret.is_empty() {
-        return Err("Google credentials not set".to_string());
+/// Only web links are handed to the system opener, so a calendar entry can't launch
+/// local files or arbitrary app schemes
+fn validate_meeting_link(url: &str) -> Result<String, String> {
+    let url = url.trim();
+    let parsed = url::Url::parse(url).map_err(|e| format!("Invalid meeting link '{}': {}", url, e))?;
+    if !matches!(parsed.scheme(), "http" | "https") || parsed.host_str().is_none() {
+        return Err(format!("Meeting link must be an http(s) URL: {}", url));
     }
+    Ok(url.to_string())
+}
 
-    let cal = calendar::GoogleCalendar::new(client_id, client_secret);
-    cal.get_upcoming_events(limit).await
+fn open_link(app: &AppHandle, url: &str) -> Result<(), String> {
+    let url = validate_meeting_link(url)?;
+    app.opener().open_url(&url, None::<&str>).map_err(|e| e.to_string())
 }
 
-/// Get past calendar events
+/// Open a meeting link (Zoom, Meet, Teams, ...) in the default browser or app
+#[tauri::command]
+async fn open_meeting_link(app: AppHandle, url: String) -> Result<(), AppError> {
+    open_link(&app, &url)?;
+    tracing::info!("Opened meeting link {}", url.trim());
+    Ok(())
+}
+
+/// "Join and record" for a scheduled meeting: seeds the title, attendees and meeting
+/// context from the calendar event, opens its meeting link and starts live transcription
 #[tauri::command]
-async fn get_past_calendar_events(state: State<'_, AppState>, days: Option<i64>, limit: Option<u32>) -> Result<Vec<calendar::SimpleCalendarEvent>, String> {
+async fn start_meeting_for_event(
+    state: State<'_, AppState>,
+    app: AppHandle,
+    event_id: String,
+) -> Result<calendar::SimpleCalendarEvent, AppError> {
+    if *state.is_live_transcribing.lock().map_err(|e| e.to_string())? {
+        return Err("Live transcription is already running".into());
+    }
+
     let client_id = state.google_client_id.lock().map_err(|e| e.to_string())?.clone();
     let client_secret = state.google_client_secret.lock().map_err(|e| e.to_string())?.clone();
-
     if client_id.is_empty() || client_secret.is_empty() {
-        return Err("Google credentials not set".to_string());
+        return Err(AppError::new(ErrorCode::SetupRequired, "Google credentials not set"));
     }
 
-    let cal = calendar::GoogleCalendar::new(client_id, client_secret);
-    cal.get_past_events(days, limit).await
+    let cal = calendar::GoogleCalendar::new(client_id, client_secret).with_app_handle(app.clone());
+    let event = cal.get_event(&event_id).await?;
+
+    start_live_transcription(app.clone(), app.state()).await?;
+
+    // Seeded only once transcription is running, so a failed start leaves the previous
+    // meeting's details alone
+    *state.meeting_context.lock().map_err(|e| e.to_string())? = event_meeting_context(&event);
+    *state.meeting_attendees.lock().map_err(|e| e.to_string())? = event.attendees.clone();
+    *state.calendar_event.lock().map_err(|e| e.to_string())? = Some(event.clone());
+    state.meeting_monitor.mark_started(&event).await;
+
+    if let Some(link) = &event.meeting_link {
+        if let Err(e) = open_link(&app, link) {
+            tracing::warn!("Failed to open meeting link {}: {}", link, e);
+        }
+    }
+
+    tracing::info!("Started meeting for calendar event '{}'", event.title);
+    Ok(event)
 }
 
 // ============== Meeting Monitor Commands ==============
 
+/// Emitted when the started calendar meeting has ended
+#[derive(Debug, Clone, Serialize)]
+struct MeetingEndedEvent {
+    event_id: String,
+    title: String,
+    /// Recording/transcription was stopped because of it
+    auto_stopped: bool,
+}
+
+/// The started meeting is over: stop recording and live transcription if `auto_stop` is on
+/// and run the post-meeting steps, then emit `meeting-ended`
+async fn handle_meeting_end(app: &AppHandle, event: calendar::SimpleCalendarEvent, auto_stop: bool) {
+    let state = app.state::<AppState>();
+    let is_recording = state.is_recording.lock().map(|v| *v).unwrap_or(false);
+    let is_live = state.is_live_transcribing.lock().map(|v| *v).unwrap_or(false);
+    let auto_stopped = auto_stop && (is_recording || is_live);
+
+    tracing::info!("Meeting '{}' has ended", event.title);
+    if auto_stopped {
+        if is_recording {
+            if let Err(e) = stop_recording(app.clone(), app.state()).await {
+                tracing::warn!("Failed to auto-stop recording: {}", e);
+            }
+        }
+        if is_live {
+            if let Err(e) = stop_live_transcription(app.clone(), app.state()).await {
+                tracing::warn!("Failed to auto-stop live transcription: {}", e);
+            }
+        }
+    }
+
+    let _ = app.emit("meeting-ended", MeetingEndedEvent {
+        event_id: event.id,
+        title: event.title,
+        auto_stopped,
+    });
+}
+
 /// Get meeting monitor status
 #[tauri::command]
-async fn get_meeting_status(state: State<'_, AppState>) -> Result<meeting_monitor::MeetingStatus, String> {
+async fn get_meeting_status(state: State<'_, AppState>) -> Result<meeting_monitor::MeetingStatus, AppError> {
     Ok(state.meeting_monitor.get_status().await)
 }
 
@@ -1398,36 +5630,107 @@ async fn get_meeting_status(state: State<'_, AppState>) -> Result<meeting_monito
 async fn update_meeting_monitor_settings(
     state: State<'_, AppState>,
     settings: meeting_monitor::MeetingMonitorSettings,
-) -> Result<(), String> {
+) -> Result<(), AppError> {
     state.meeting_monitor.update_settings(settings).await;
     Ok(())
 }
 
 /// Get meeting monitor settings
 #[tauri::command]
-async fn get_meeting_monitor_settings(state: State<'_, AppState>) -> Result<meeting_monitor::MeetingMonitorSettings, String> {
+async fn get_meeting_monitor_settings(state: State<'_, AppState>) -> Result<meeting_monitor::MeetingMonitorSettings, AppError> {
     Ok(state.meeting_monitor.get_settings().await)
 }
 
 /// Reset meeting monitor trigger (useful when manually stopping)
 #[tauri::command]
-async fn reset_meeting_monitor_trigger(state: State<'_, AppState>) -> Result<(), String> {
+async fn reset_meeting_monitor_trigger(state: State<'_, AppState>) -> Result<(), AppError> {
     state.meeting_monitor.reset_trigger().await;
     Ok(())
 }
 
+/// Longest a meeting's auto-start can be snoozed
+const MAX_SNOOZE_MINUTES: i64 = 120;
+
+/// "Remind in N minutes": don't auto-start a calendar event until then.
+/// Returns when it will be offered again (RFC 3339).
+#[tauri::command]
+async fn snooze_meeting(state: State<'_, AppState>, event_id: String, minutes: i64) -> Result<String, AppError> {
+    if !(1..=MAX_SNOOZE_MINUTES).contains(&minutes) {
+        return Err(format!("Snooze must be between 1 and {} minutes (got {})", MAX_SNOOZE_MINUTES, minutes).into());
+    }
+    let until = state.meeting_monitor.snooze(&event_id, minutes).await;
+    tracing::info!("Snoozed auto-start of event {} for {} minutes", event_id, minutes);
+    Ok(until.to_rfc3339())
+}
+
+/// "Skip this meeting": never auto-start this calendar event
+#[tauri::command]
+async fn skip_meeting(state: State<'_, AppState>, event_id: String) -> Result<(), AppError> {
+    state.meeting_monitor.skip(&event_id).await;
+    tracing::info!("Skipping auto-start of event {}", event_id);
+    Ok(())
+}
+
 /// Manually check for meetings (for testing)
 #[tauri::command]
-async fn check_for_meetings_now(state: State<'_, AppState>) -> Result<bool, String> {
+async fn check_for_meetings_now(state: State<'_, AppState>, app: AppHandle) -> Result<bool, AppError> {
     let client_id = state.google_client_id.lock().map_err(|e| e.to_string())?.clone();
     let client_secret = state.google_client_secret.lock().map_err(|e| e.to_string())?.clone();
 
     if client_id.is_empty() || client_secret.is_empty() {
-        return Err("Google Calendar not connected".to_string());
+        return Err(AppError::new(ErrorCode::SetupRequired, "Google Calendar not connected"));
     }
 
-    let cal = calendar::GoogleCalendar::new(client_id, client_secret);
-    state.meeting_monitor.check_for_meetings(&cal).await
+    let cal = calendar::GoogleCalendar::new(client_id, client_secret).with_app_handle(app);
+    Ok(state.meeting_monitor.check_for_meetings(&cal).await?)
+}
+
+// ============== Meeting Template Commands ==============
+
+/// Create or update a meeting template
+#[tauri::command]
+async fn save_template(state: State<'_, AppState>, template: templates::MeetingTemplate) -> Result<templates::MeetingTemplate, AppError> {
+    if template.name.trim().is_empty() {
+        return Err("Template name cannot be empty".into());
+    }
+
+    let mut store = state.templates.lock().map_err(|e| e.to_string())?;
+    let saved = store.upsert(template);
+    store.save()?;
+
+    tracing::info!("Saved meeting template '{}' ({})", saved.name, saved.id);
+    Ok(saved)
+}
+
+/// List all meeting templates
+#[tauri::command]
+async fn list_templates(state: State<'_, AppState>) -> Result<Vec<templates::MeetingTemplate>, AppError> {
+    Ok(state.templates.lock().map_err(|e| e.to_string())?.templates.clone())
+}
+
+/// Delete a meeting template
+#[tauri::command]
+async fn delete_template(state: State<'_, AppState>, id: String) -> Result<(), AppError> {
+    let mut store = state.templates.lock().map_err(|e| e.to_string())?;
+    store.delete(&id)?;
+    Ok(store.save()?)
+}
+
+/// Seed the meeting context, summary template and attendees from a template.
+/// The context only applies to this meeting; the saved default context is left alone.
+#[tauri::command]
+async fn start_meeting_from_template(state: State<'_, AppState>, id: String) -> Result<templates::MeetingTemplate, AppError> {
+    let template = state.templates.lock().map_err(|e| e.to_string())?
+        .get(&id)
+        .cloned()
+        .ok_or_else(|| format!("Template not found: {}", id))?;
+
+    *state.meeting_context.lock().map_err(|e| e.to_string())? = template.meeting_context();
+    *state.summary_template.lock().map_err(|e| e.to_string())? = template.summary_template.clone();
+    *state.meeting_attendees.lock().map_err(|e| e.to_string())? = template.attendees.clone();
+
+    tracing::info!("Starting meeting from template '{}'", template.name);
+    Ok(template)
 }
 
 // ============== Meetings Database Commands ==============
@@ -1442,33 +5745,52 @@ async fn save_meeting(
     duration_seconds: Option<u64>,
     transcript: Option<Vec<TranscriptSegment>>,
     summary: Option<MeetingSummary>,
-) -> Result<String, String> {
+) -> Result<String, AppError> {
+    if state.demo_mode.load(Ordering::SeqCst) {
+        return Err("Demo meetings can't be saved. Turn off demo mode and record a real meeting.".into());
+    }
+
     // Use provided transcript or fall back to state
     let transcription = if let Some(t) = transcript {
         if t.is_empty() {
-            return Err("No transcription to save".to_string());
+            return Err("No transcription to save".into());
         }
         t
     } else {
-        let t = state.transcription.lock().map_err(|e| e.to_string())?.clone();
+        let t = full_transcript(&state)?;
         if t.is_empty() {
-            return Err("No transcription to save".to_string());
+            return Err("No transcription to save".into());
         }
         t
     };
 
-    eprintln!("Saving meeting '{}' with {} transcript segments", title, transcription.len());
+    tracing::info!("Saving meeting '{}' with {} transcript segments", title, transcription.len());
+
+    // Fall back to the attendees seeded by a meeting template
+    let attendees = if attendees.is_empty() {
+        state.meeting_attendees.lock().map_err(|e| e.to_string())?.clone()
+    } else {
+        attendees
+    };
+
+    // Fall back to the calendar event the meeting was started from
+    let event = state.calendar_event.lock().map_err(|e| e.to_string())?.clone();
+    let title = match &event {
+        Some(event) if title.trim().is_empty() => event.title.clone(),
+        _ => title,
+    };
+    let calendar_event_id = calendar_event_id.or_else(|| event.map(|e| e.id));
 
     let recording_path = state.current_recording_path.lock().map_err(|e| e.to_string())?.clone();
 
     // Use provided summary or fall back to state
     let meeting_summary = if let Some(s) = summary {
-        eprintln!("Using provided summary with {} key points, {} action items", s.key_points.len(), s.action_items.len());
+        tracing::info!("Using provided summary with {} key points, {} action items", s.key_points.len(), s.action_items.len());
         // If structured fields are empty but raw_summary exists, parse it
         if s.key_points.is_empty() && s.action_items.is_empty() && !s.raw_summary.is_empty() {
-            eprintln!("Parsing raw_summary into structured fields...");
+            tracing::info!("Parsing raw_summary into structured fields...");
             let parsed = parse_text_summary(&s.raw_summary);
-            eprintln!("Parsed: {} key points, {} action items, {} decisions, {} notes",
+            tracing::info!("Parsed: {} key points, {} action items, {} decisions, {} notes",
                 parsed.key_points.len(), parsed.action_items.len(), parsed.decisions.len(), parsed.notes.len());
             Some(parsed)
         } else {
@@ -1478,14 +5800,14 @@ async fn save_meeting(
         // Fall back to raw summary text from state
         let summary_text = state.summary.lock().map_err(|e| e.to_string())?.clone();
         if !summary_text.is_empty() {
-            eprintln!("Parsing state summary into structured fields...");
+            tracing::info!("Parsing state summary into structured fields...");
             Some(parse_text_summary(&summary_text))
         } else {
             None
         }
     };
 
-    let meeting = database::create_meeting_from_transcript(
+    let mut meeting = database::create_meeting_from_transcript(
         title,
         transcription,
         meeting_summary,
@@ -1494,20 +5816,41 @@ async fn save_meeting(
         recording_path,
         duration_seconds,
     );
+    meeting.notes = state.notes.lock().map_err(|e| e.to_string())?.clone();
+    meeting.bookmarks = state.bookmarks.lock().map_err(|e| e.to_string())?.clone();
+
+    // Replace the auto-saved copy of this meeting instead of adding a duplicate
+    let mut autosave_id = state.autosave_meeting_id.lock().map_err(|e| e.to_string())?;
+    let mut db = state.meetings_db.lock().map_err(|e| e.to_string())?;
+    if let Some(existing) = autosave_id.as_deref().and_then(|id| db.get_meeting(id)) {
+        meeting.id = existing.id.clone();
+        meeting.date = existing.date.clone();
+        meeting.created_at = existing.created_at.clone();
+        meeting.tags = existing.tags.clone();
+        // Keep what was derived from the auto-saved copy in the meantime
+        meeting.chapters = existing.chapters.clone();
+        meeting.alternate_transcripts = existing.alternate_transcripts.clone();
+        if meeting.summary.is_none() {
+            meeting.summary = existing.summary.clone();
+        }
+    }
 
     let meeting_id = meeting.id.clone();
-    eprintln!("Created meeting with ID: {}", meeting_id);
+    tracing::info!("Created meeting with ID: {}", meeting_id);
 
-    let mut db = state.meetings_db.lock().map_err(|e| e.to_string())?;
-    db.add_meeting(meeting)?;
-    eprintln!("Meeting saved to database");
+    db.upsert_meeting(meeting)?;
+    // Later auto-saves (e.g. while transcription keeps running) update this meeting
+    // until the transcript is cleared
+    *autosave_id = Some(meeting_id.clone());
+    tracing::info!("Meeting saved to database");
+    *state.calendar_event.lock().map_err(|e| e.to_string())? = None;
 
     Ok(meeting_id)
 }
 
 /// Get all saved meetings
 #[tauri::command]
-async fn get_saved_meetings(state: State<'_, AppState>, limit: Option<usize>) -> Result<Vec<database::StoredMeeting>, String> {
+async fn get_saved_meetings(state: State<'_, AppState>, limit: Option<usize>) -> Result<Vec<database::StoredMeeting>, AppError> {
     let db = state.meetings_db.lock().map_err(|e| e.to_string())?;
     let meetings = db.get_past_meetings(limit);
     Ok(meetings.into_iter().cloned().collect())
@@ -1515,33 +5858,145 @@ async fn get_saved_meetings(state: State<'_, AppState>, limit: Option<usize>) ->
 
 /// Get a specific meeting by ID
 #[tauri::command]
-async fn get_meeting_by_id(state: State<'_, AppState>, id: String) -> Result<Option<database::StoredMeeting>, String> {
+async fn get_meeting_by_id(state: State<'_, AppState>, id: String) -> Result<Option<database::StoredMeeting>, AppError> {
     let db = state.meetings_db.lock().map_err(|e| e.to_string())?;
     Ok(db.get_meeting(&id).cloned())
 }
 
 /// Delete a meeting
 #[tauri::command]
-async fn delete_meeting(state: State<'_, AppState>, id: String) -> Result<(), String> {
+async fn delete_meeting(state: State<'_, AppState>, id: String) -> Result<(), AppError> {
     let mut db = state.meetings_db.lock().map_err(|e| e.to_string())?;
-    db.delete_meeting(&id)
+    Ok(db.delete_meeting(&id)?)
 }
 
 /// Search meetings
 #[tauri::command]
-async fn search_meetings(state: State<'_, AppState>, query: String) -> Result<Vec<database::StoredMeeting>, String> {
+async fn search_meetings(
+    state: State<'_, AppState>,
+    query: String,
+    tags: Option<Vec<String>>,
+) -> Result<Vec<database::StoredMeeting>, AppError> {
     let db = state.meetings_db.lock().map_err(|e| e.to_string())?;
-    let meetings = db.search_meetings(&query);
+    let meetings = db.search_meetings(&query, &tags.unwrap_or_default());
     Ok(meetings.into_iter().cloned().collect())
 }
 
+/// Tag a saved meeting (e.g. by project or client). Returns the meeting's tags.
+#[tauri::command]
+async fn add_meeting_tag(state: State<'_, AppState>, id: String, tag: String) -> Result<Vec<String>, AppError> {
+    Ok(state.meetings_db.lock().map_err(|e| e.to_string())?.add_tag(&id, &tag)?)
+}
+
+/// Rename a speaker across a saved meeting's transcript (e.g. "A" -> "Priya"), so the
+/// new name also shows in talk-time stats. Returns the updated meeting.
+#[tauri::command]
+async fn relabel_speaker(
+    state: State<'_, AppState>,
+    meeting_id: String,
+    from_label: String,
+    to_name: String,
+) -> Result<database::StoredMeeting, AppError> {
+    Ok(state.meetings_db.lock().map_err(|e| e.to_string())?.relabel_speaker(&meeting_id, &from_label, &to_name)?)
+}
+
+/// Remove a tag from a saved meeting. Returns the meeting's tags.
+#[tauri::command]
+async fn remove_meeting_tag(state: State<'_, AppState>, id: String, tag: String) -> Result<Vec<String>, AppError> {
+    Ok(state.meetings_db.lock().map_err(|e| e.to_string())?.remove_tag(&id, &tag)?)
+}
+
+/// Get all saved meetings with a tag
+#[tauri::command]
+async fn list_meetings_by_tag(state: State<'_, AppState>, tag: String) -> Result<Vec<database::StoredMeeting>, AppError> {
+    let db = state.meetings_db.lock().map_err(|e| e.to_string())?;
+    Ok(db.meetings_with_tag(&tag).into_iter().cloned().collect())
+}
+
+/// Export a saved meeting as versioned JSON for use in other tools
+#[tauri::command]
+async fn export_meeting_json(state: State<'_, AppState>, id: String, path: String) -> Result<String, AppError> {
+    let content = {
+        let db = state.meetings_db.lock().map_err(|e| e.to_string())?;
+        let meeting = db.get_meeting(&id).ok_or_else(|| format!("Meeting not found: {}", id))?;
+        export::meeting_to_json(meeting)?
+    };
+
+    tokio::fs::write(&path, content).await.map_err(|e| format!("Failed to write meeting export: {}", e))?;
+    tracing::info!("Exported meeting {} to {}", id, path);
+    Ok(path)
+}
+
+/// Inclusive "YYYY-MM-DD" dates
+#[derive(Debug, Clone, Deserialize)]
+struct DateRange {
+    start: String,
+    end: String,
+}
+
+/// Export saved meetings as CSV, one row per meeting, oldest first.
+/// Without a date range every meeting is exported. Returns the number of rows written.
+#[tauri::command]
+async fn export_meetings_csv(
+    state: State<'_, AppState>,
+    path: String,
+    date_range: Option<DateRange>,
+) -> Result<usize, AppError> {
+    let (content, count) = {
+        let db = state.meetings_db.lock().map_err(|e| e.to_string())?;
+        let meetings = match &date_range {
+            Some(range) => {
+                let (start, end) = parse_date_range(&range.start, &range.end)?;
+                db.meetings_in_range(start, end)
+            }
+            None => {
+                let mut meetings = db.get_all_meetings();
+                meetings.reverse();
+                meetings
+            }
+        };
+        (export::meetings_to_csv(&meetings)?, meetings.len())
+    };
+
+    tokio::fs::write(&path, content).await.map_err(|e| format!("Failed to write meetings CSV: {}", e))?;
+    tracing::info!("Exported {} meetings to {}", count, path);
+    Ok(count)
+}
+
+/// Import a meeting JSON export into the meetings database. Returns the stored meeting ID.
+#[tauri::command]
+async fn import_meeting_json(state: State<'_, AppState>, path: String) -> Result<String, AppError> {
+    let content = tokio::fs::read_to_string(&path)
+        .await
+        .map_err(|e| format!("Failed to read meeting export: {}", e))?;
+    let mut meeting = export::meeting_from_json(&content)?;
+
+    let mut db = state.meetings_db.lock().map_err(|e| e.to_string())?;
+    // Don't overwrite an existing meeting if the same export is imported twice
+    if db.get_meeting(&meeting.id).is_some() {
+        meeting.id = database::generate_meeting_id();
+    }
+
+    let meeting_id = meeting.id.clone();
+    db.add_meeting(meeting)?;
+    tracing::info!("Imported meeting {} from {}", meeting_id, path);
+    Ok(meeting_id)
+}
+
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
+    logs::init();
+
     tauri::Builder::default()
         .plugin(tauri_plugin_opener::init())
+        .plugin(tauri_plugin_clipboard_manager::init())
         .manage(AppState::default())
         .setup(|app| {
             let app_handle = app.handle().clone();
+            app.state::<AppState>().log_buffer.attach(app_handle.clone());
+            let _ = app.state::<AppState>().app_handle.set(app_handle.clone());
+            spawn_auto_hide_task(app_handle.clone());
+            spawn_usage_save_task();
 
             // Start background task for meeting monitor
             tauri::async_runtime::spawn(async move {
@@ -1558,6 +6013,10 @@ pub fn run() {
                         continue;
                     }
 
+                    if let Some(event) = state.meeting_monitor.check_meeting_end().await {
+                        handle_meeting_end(&app_handle, event, settings.auto_stop_on_end).await;
+                    }
+
                     // Get calendar credentials
                     let client_id = state.google_client_id.lock().ok()
                         .map(|g| g.clone())
@@ -1572,26 +6031,27 @@ pub fn run() {
                     }
 
                     // Check for meetings
-                    let cal = calendar::GoogleCalendar::new(client_id, client_secret);
+                    let cal = calendar::GoogleCalendar::new(client_id, client_secret)
+                        .with_app_handle(app_handle.clone());
                     match state.meeting_monitor.check_for_meetings(&cal).await {
                         Ok(should_auto_start) => {
                             if should_auto_start {
-                                eprintln!("Meeting detected! Auto-starting transcription...");
+                                tracing::info!("Meeting detected! Auto-starting transcription...");
 
                                 // Emit event to frontend to auto-start
                                 if let Err(e) = app_handle.emit("meeting-auto-start", ()) {
-                                    eprintln!("Failed to emit meeting-auto-start event: {}", e);
+                                    tracing::warn!("Failed to emit meeting-auto-start event: {}", e);
                                 }
 
                                 // Get meeting status for event details
                                 let status = state.meeting_monitor.get_status().await;
                                 if let Err(e) = app_handle.emit("meeting-status-updated", status) {
-                                    eprintln!("Failed to emit meeting-status-updated event: {}", e);
+                                    tracing::warn!("Failed to emit meeting-status-updated event: {}", e);
                                 }
                             }
                         }
                         Err(e) => {
-                            eprintln!("Error checking for meetings: {}", e);
+                            tracing::warn!("Error checking for meetings: {}", e);
                         }
                     }
                 }
@@ -1605,36 +6065,140 @@ pub fn run() {
             start_live_transcription,
             stop_live_transcription,
             get_meeting_state,
+            get_status,
+            get_transcription,
+            get_summary,
+            get_latest_segments,
+            get_transcription_metrics,
+            get_usage_stats,
+            reset_usage_stats,
+            copy_summary_to_clipboard,
+            copy_transcript_to_clipboard,
+            import_transcript_file,
             set_groq_api_key,
             set_assemblyai_api_key,
             set_deepgram_api_key,
+            set_openai_api_key,
+            set_llm_provider,
+            list_whisper_models,
+            download_whisper_model,
+            set_whisper_model,
+            get_endpoint_settings,
+            set_endpoint_url,
+            set_proxy,
+            get_proxy,
             set_model,
             set_transcription_provider,
             set_meeting_context,
+            set_generation_params,
+            get_generation_params,
+            set_assemblyai_options,
+            set_batch_live_options,
+            set_alert_keywords,
+            get_alert_keywords,
+            set_min_segment_length,
+            get_min_segment_length,
+            set_interim_debounce_ms,
+            get_interim_debounce_ms,
+            set_save_interim,
+            get_save_interim,
+            set_no_speech_notice_secs,
+            get_no_speech_notice_secs,
+            set_max_live_segments,
+            get_max_live_segments,
+            set_max_recording_minutes,
+            get_max_recording_minutes,
+            set_post_meeting_actions,
+            get_post_meeting_actions,
+            set_assistant_persona,
+            get_assistant_persona,
+            set_primary_speaker,
+            get_recent_logs,
+            get_assemblyai_options,
+            cancel_assemblyai_job,
+            get_batch_live_options,
+            set_hybrid_transcription,
+            get_hybrid_transcription,
+            set_llm_fallback,
             get_transcription_providers,
             get_available_models,
             add_transcription,
             add_manual_transcript,
             clear_transcription,
+            add_note,
+            delete_note,
+            add_bookmark,
+            list_bookmarks,
+            edit_segment,
+            delete_segment,
+            merge_segments,
+            coalesce_same_speaker,
+            undo_transcript,
+            redo_transcript,
+            get_talk_time_stats,
+            analyze_speaking_style,
+            extract_keywords,
+            set_filler_words,
+            get_filler_words,
+            get_low_confidence_segments,
             transcribe_recording,
+            realign_transcript,
+            retranscribe,
             list_recordings,
+            is_recording_active,
+            set_recording_config,
+            get_recording_config,
+            set_voice_activation,
+            get_voice_activation,
+            test_microphone,
+            get_device_capabilities,
             get_recordings_folder,
+            transcribe_all_untranscribed,
+            transcribe_with_speakers,
+            extract_speaker_audio,
+            export_subtitles,
+            export_by_speaker,
             generate_summary,
+            estimate_tokens,
+            generate_custom_summary,
             generate_structured_summary,
+            extract_decisions,
+            summarize_meetings_in_range,
+            compare_meetings,
+            expand_summary,
+            regenerate_summary_for_meeting,
+            detect_chapters,
             generate_reply_suggestions,
             generate_auto_replies,
+            refresh_replies,
+            set_auto_refresh_replies,
+            get_auto_refresh_replies,
+            set_reply_trigger,
+            get_reply_trigger,
             check_connection,
+            test_llm,
+            run_diagnostics,
+            set_auto_hide_on_share,
+            get_auto_hide_on_share,
             set_screen_share_exclusion,
             is_screen_share_exclusion_supported,
             get_screen_share_platform_info,
             start_mock_transcription,
             stop_mock_transcription,
+            enable_demo_mode,
+            disable_demo_mode,
             // Calendar commands
             set_google_credentials,
             get_google_auth_url,
             exchange_google_code,
             is_calendar_connected,
             disconnect_calendar,
+            list_google_accounts,
+            switch_google_account,
+            list_calendars,
+            set_active_calendars,
+            start_meeting_for_event,
+            open_meeting_link,
             get_upcoming_events,
             get_past_calendar_events,
             // Meeting monitor commands
@@ -1642,22 +6206,51 @@ pub fn run() {
             update_meeting_monitor_settings,
             get_meeting_monitor_settings,
             reset_meeting_monitor_trigger,
+            snooze_meeting,
+            skip_meeting,
             check_for_meetings_now,
             // Meetings database commands
+            save_template,
+            list_templates,
+            delete_template,
+            start_meeting_from_template,
             save_meeting,
             get_saved_meetings,
             get_meeting_by_id,
             delete_meeting,
             search_meetings,
+            add_meeting_tag,
+            remove_meeting_tag,
+            relabel_speaker,
+            list_meetings_by_tag,
+            export_meeting_json,
+            export_meetings_csv,
+            import_meeting_json,
         ])
-        .run(tauri::generate_context!())
-        .expect("error while running tauri application");
+        .build(tauri::generate_context!())
+        .expect("error while building tauri application")
+        .run(|_app, event| {
+            if let tauri::RunEvent::Exit = event {
+                usage::save();
+            }
+        });
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_summary_to_markdown_skips_empty_sections() {
+        let summary = parse_text_summary(
+            "## KEY POINTS\n• Launch moved to May\n\n## ACTION ITEMS\n• Sam to update the plan\n\n## DECISIONS\n• None identified",
+        );
+        assert_eq!(
+            summary_to_markdown(&summary),
+            "## Key Points\n- Launch moved to May\n\n## Action Items\n- [ ] Sam to update the plan"
+        );
+    }
+
     #[test]
     fn test_clean_transcript_removes_fillers() {
         assert_eq!(
@@ -1695,6 +6288,380 @@ mod tests {
         assert_eq!(clean_transcript(""), "");
     }
 
+    fn segment(speaker: &str, timestamp: &str, text: &str) -> TranscriptSegment {
+        TranscriptSegment {
+            timestamp: timestamp.to_string(),
+            speaker: speaker.to_string(),
+            text: text.to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_merge_segments_joins_text_and_keeps_earlier_timestamp() {
+        let mut transcription = vec![
+            segment("Speaker", "10:00:00", "We should ship"),
+            segment("Speaker", "10:00:04", "the release on Friday"),
+        ];
+        merge_transcript_segments(&mut transcription, 1, 0).unwrap();
+
+        assert_eq!(transcription.len(), 1);
+        assert_eq!(transcription[0].text, "We should ship the release on Friday");
+        assert_eq!(transcription[0].timestamp, "10:00:00");
+    }
+
+    #[test]
+    fn test_coalesce_segments_merges_runs_of_one_speaker() {
+        let mut transcription = vec![
+            segment("A", "10:00:00", "So the plan"),
+            segment("A", "10:00:02", "is to ship Friday."),
+            segment("B", "10:00:05", "Works for me."),
+            segment("A", "10:00:07", "Great."),
+            segment("A", "10:00:08", "Thanks all."),
+        ];
+        transcription[0].confidence = Some(0.9);
+        transcription[1].confidence = Some(0.7);
+
+        let coalesced = coalesce_segments(&transcription);
+        let texts: Vec<(&str, &str, &str)> = coalesced
+            .iter()
+            .map(|s| (s.speaker.as_str(), s.timestamp.as_str(), s.text.as_str()))
+            .collect();
+        assert_eq!(texts, vec![
+            ("A", "10:00:00", "So the plan is to ship Friday."),
+            ("B", "10:00:05", "Works for me."),
+            ("A", "10:00:07", "Great. Thanks all."),
+        ]);
+        assert_eq!(coalesced[0].confidence, Some(0.7));
+        assert_eq!(transcription.len(), 5);
+    }
+
+    #[test]
+    fn test_merge_segments_rejects_different_speakers() {
+        let mut transcription = vec![
+            segment("You", "10:00:00", "Hello"),
+            segment("Participant", "10:00:02", "Hi"),
+        ];
+        assert!(merge_transcript_segments(&mut transcription, 0, 1).is_err());
+        assert_eq!(transcription.len(), 2);
+    }
+
+    #[test]
+    fn test_merge_segments_rejects_non_adjacent() {
+        let mut transcription = vec![
+            segment("You", "10:00:00", "One"),
+            segment("You", "10:00:02", "Two"),
+            segment("You", "10:00:04", "Three"),
+        ];
+        assert!(merge_transcript_segments(&mut transcription, 0, 2).is_err());
+        assert!(merge_transcript_segments(&mut transcription, 2, 3).is_err());
+    }
+
+    #[test]
+    fn test_parse_chapters_extracts_wrapped_json() {
+        let response = r#"Here are the chapters:
+[{"title": "Intro", "start_timestamp": "10:00:00"}, {"title": "Budget", "start_timestamp": "10:05:12"}]"#;
+        let chapters = parse_chapters(response).unwrap();
+
+        assert_eq!(chapters.len(), 2);
+        assert_eq!(chapters[1].title, "Budget");
+        assert_eq!(chapters[1].start_timestamp, "10:05:12");
+    }
+
+    #[test]
+    fn test_relabel_primary_speaker_only_touches_diarized_segments() {
+        let mut transcription = vec![
+            TranscriptSegment { speaker_id: Some(0), ..segment("You", "10:00:00", "Hi all") },
+            TranscriptSegment { speaker_id: Some(1), ..segment("Participant", "10:00:02", "Hey") },
+            segment("Speaker", "10:00:04", "Typed note"),
+        ];
+        relabel_primary_speaker(&mut transcription, 1);
+
+        assert_eq!(transcription[0].speaker, "Participant");
+        assert_eq!(transcription[1].speaker, "You");
+        assert_eq!(transcription[2].speaker, "Speaker");
+    }
+
+    #[test]
+    fn test_meets_min_length_counts_characters() {
+        let min = Arc::new(Mutex::new(Some(6)));
+        assert!(!meets_min_length("Mm.", &min, 0));
+        assert!(!meets_min_length("  Yeah ", &min, 0));
+        assert!(meets_min_length("Sounds good", &min, 0));
+
+        *min.lock().unwrap() = Some(0);
+        assert!(meets_min_length("Mm.", &min, 6));
+
+        // Unset: each path's default applies
+        *min.lock().unwrap() = None;
+        assert!(!meets_min_length("you", &min, settings::DEFAULT_BATCH_MIN_SEGMENT_LENGTH));
+        assert!(meets_min_length("Yes.", &min, settings::DEFAULT_STREAMING_MIN_SEGMENT_LENGTH));
+    }
+
+    #[test]
+    fn test_parse_chapters_invalid_response() {
+        assert!(parse_chapters("I couldn't find any topics").is_err());
+        assert!(parse_chapters("[]").is_err());
+        assert!(parse_chapters(r#"[{"title": " ", "start_timestamp": "10:00:00"}]"#).is_err());
+    }
+
+    #[test]
+    fn test_text_summary_prompt_includes_custom_instructions() {
+        let prompt = text_summary_prompt("", "  Focus on blockers and owners ", &[], "[10:00:00] You: Hi");
+        assert!(prompt.contains("USER INSTRUCTIONS"));
+        assert!(prompt.contains("Focus on blockers and owners\n\nMEETING TRANSCRIPT:"));
+        assert!(!text_summary_prompt("", "", &[], "[10:00:00] You: Hi").contains("USER INSTRUCTIONS"));
+    }
+
+    #[test]
+    fn test_event_meeting_context() {
+        let event = calendar::SimpleCalendarEvent {
+            id: "evt1".to_string(),
+            title: "Q3 planning".to_string(),
+            description: Some("  Review roadmap\nAssign owners ".to_string()),
+            start_time: "2024-03-05T09:00:00Z".to_string(),
+            end_time: "2024-03-05T10:00:00Z".to_string(),
+            attendees: vec!["Ana".to_string(), "Raj".to_string()],
+            meeting_link: None,
+            meeting_links: Vec::new(),
+            is_today: true,
+            is_past: false,
+            is_all_day: false,
+        };
+        assert_eq!(
+            event_meeting_context(&event),
+            "Meeting: Q3 planning\nAttendees: Ana, Raj\nAgenda:\nReview roadmap\nAssign owners"
+        );
+    }
+
+    #[test]
+    fn test_ends_mid_sentence() {
+        assert!(ends_mid_sentence("• The team agreed to move the launch to"));
+        assert!(ends_mid_sentence("## ACTION ITEMS\n- Sam to draft the"));
+        assert!(!ends_mid_sentence("• The launch moves to Friday.\n"));
+        assert!(!ends_mid_sentence("Any blockers?"));
+        assert!(!ends_mid_sentence(""));
+    }
+
+    #[test]
+    fn test_recording_title_and_time_from_filename() {
+        let (title, time) = recording_title_and_time("/Users/me/Documents/MeetingRecordings/meeting_20240315_143005.wav");
+        assert_eq!(title, "Recording 2024-03-15 14:30");
+        assert_eq!(time.as_deref(), Some("14:30:05"));
+
+        let (title, time) = recording_title_and_time("/tmp/standup.wav");
+        assert_eq!(title, "standup");
+        assert!(time.is_none());
+    }
+
+    #[test]
+    fn test_utterances_to_segments_keeps_speakers_and_timings() {
+        let utterances = vec![
+            assemblyai::Utterance { speaker: "A".to_string(), text: "Hi everyone".to_string(), start: 1_200, end: 2_400, confidence: Some(0.91) },
+            assemblyai::Utterance { speaker: "B".to_string(), text: "  ".to_string(), start: 2_500, end: 2_600, confidence: None },
+            assemblyai::Utterance { speaker: "B".to_string(), text: "Morning".to_string(), start: 65_000, end: 66_000, confidence: None },
+        ];
+        let segments = utterances_to_segments(&utterances, &audio::SkippedSpans::default());
+
+        assert_eq!(segments.len(), 2);
+        assert_eq!(segments[0].speaker, "A");
+        assert_eq!(segments[0].start_ms, Some(1_200));
+        assert_eq!(segments[0].end_ms, Some(2_400));
+        assert_eq!(segments[0].confidence, Some(0.91));
+        assert_eq!(segments[1].speaker, "B");
+        assert_eq!(segments[1].timestamp, "00:01:05");
+    }
+
+    #[test]
+    fn test_same_file_resolves_relative_paths() {
+        let dir = std::env::temp_dir().join(format!("vantage-same-file-{}", std::process::id()));
+        std::fs::create_dir_all(dir.join("sub")).unwrap();
+        let file = dir.join("meeting.wav");
+        std::fs::write(&file, b"RIFF").unwrap();
+        let roundabout = dir.join("sub").join("..").join("meeting.wav");
+
+        assert!(same_file(&file.to_string_lossy(), &roundabout.to_string_lossy()));
+        assert!(!same_file(&file.to_string_lossy(), &dir.join("other.wav").to_string_lossy()));
+        assert!(same_file("missing.wav", "missing.wav"));
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_mic_active_window() {
+        use std::time::{Duration, Instant};
+        let now = Instant::now() + Duration::from_secs(10);
+        assert!(!mic_active(None, now));
+        assert!(mic_active(Some(now - Duration::from_millis(500)), now));
+        assert!(!mic_active(Some(now - Duration::from_millis(MIC_ACTIVE_WINDOW_MS)), now));
+        assert_eq!(
+            settings::ReplyTrigger::parse("on-participant-final").unwrap(),
+            settings::ReplyTrigger::OnParticipantFinal
+        );
+        assert!(settings::ReplyTrigger::parse("sometimes").is_err());
+    }
+
+    #[test]
+    fn test_validate_meeting_link() {
+        assert_eq!(
+            validate_meeting_link(" https://meet.google.com/abc-defg-hij ").unwrap(),
+            "https://meet.google.com/abc-defg-hij"
+        );
+        assert!(validate_meeting_link("http://zoom.us/j/123").is_ok());
+        assert!(validate_meeting_link("file:///etc/passwd").is_err());
+        assert!(validate_meeting_link("zoommtg://zoom.us/join?confno=123").is_err());
+        assert!(validate_meeting_link("not a url").is_err());
+    }
+
+    #[test]
+    fn test_interim_position_matches_speaker_and_text() {
+        let segment = |speaker: &str, text: &str, is_final: bool| TranscriptSegment {
+            speaker: speaker.to_string(),
+            text: text.to_string(),
+            is_final,
+            ..Default::default()
+        };
+        let transcript = vec![
+            segment("You", "let's start", true),
+            segment("You", "so the plan", false),
+            segment("Participant", "so the plan", false),
+        ];
+
+        assert_eq!(interim_position(&transcript, "You", "so the plan"), Some(1));
+        assert_eq!(interim_position(&transcript, "Participant", "so the plan"), Some(2));
+        assert_eq!(interim_position(&transcript, "You", "let's start"), None);
+    }
+
+    #[test]
+    fn test_summary_cache_key_tracks_model_and_prompt() {
+        let key = summary_cache_key("llama3", "Summarize: hello");
+        assert_eq!(key, summary_cache_key("llama3", "Summarize: hello"));
+        assert_ne!(key, summary_cache_key("llama3", "Summarize: hello again"));
+        assert_ne!(key, summary_cache_key("gpt-4o", "Summarize: hello"));
+    }
+
+    #[test]
+    fn test_silence_tracker_notifies_once_per_quiet_stretch() {
+        use std::time::{Duration, Instant};
+        let start = Instant::now();
+        let mut silence = SilenceTracker::new(start);
+
+        assert_eq!(silence.check(start + Duration::from_secs(10), 30), None);
+        assert_eq!(silence.check(start + Duration::from_secs(31), 30), Some(31));
+        assert_eq!(silence.check(start + Duration::from_secs(60), 30), None);
+
+        silence.speech(start + Duration::from_secs(61));
+        assert_eq!(silence.check(start + Duration::from_secs(95), 30), Some(34));
+        assert_eq!(SilenceTracker::new(start).check(start + Duration::from_secs(99), 0), None);
+    }
+
+    #[test]
+    fn test_groq_segment_confidence() {
+        let segments = vec![
+            groq::WhisperSegment { avg_logprob: 0.0, start: 0.0, end: 4.0 },
+            groq::WhisperSegment { avg_logprob: (0.5f64).ln(), start: 4.0, end: 8.0 },
+            groq::WhisperSegment { avg_logprob: (0.1f64).ln(), start: 8.0, end: 10.0 },
+        ];
+        let confidence = groq::segment_confidence(&segments, 0.0).unwrap();
+        assert!((confidence - 0.5333333).abs() < 1e-6);
+
+        // Only the segments covering the new audio (from 7s) count
+        let confidence = groq::segment_confidence(&segments, 7.0).unwrap();
+        assert!((confidence - 0.3).abs() < 1e-6);
+        assert!(groq::segment_confidence(&segments, 10.0).is_none());
+        assert!(groq::segment_confidence(&[], 0.0).is_none());
+    }
+
+    #[test]
+    fn test_reply_target_prefers_named_speaker() {
+        let transcription = vec![
+            segment("Priya", "10:00:00", "Can we ship Friday?"),
+            segment("Sam", "10:00:05", "I think so."),
+        ];
+
+        assert_eq!(reply_target(&transcription, None).unwrap().unwrap().speaker, "Sam");
+        assert_eq!(reply_target(&transcription, Some("priya")).unwrap().unwrap().text, "Can we ship Friday?");
+        assert!(reply_target(&transcription, Some("Alex")).is_err());
+        assert!(reply_target(&[], None).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_recording_limit_stage() {
+        use std::time::Duration;
+        assert_eq!(recording_limit_stage(Duration::from_secs(0), 30), RecordingLimitStage::Running);
+        assert_eq!(recording_limit_stage(Duration::from_secs(29 * 60 - 1), 30), RecordingLimitStage::Running);
+        assert_eq!(recording_limit_stage(Duration::from_secs(29 * 60), 30), RecordingLimitStage::Warning);
+        assert_eq!(recording_limit_stage(Duration::from_secs(30 * 60), 30), RecordingLimitStage::Reached);
+        // A one-minute limit warns straight away
+        assert_eq!(recording_limit_stage(Duration::from_secs(5), 1), RecordingLimitStage::Warning);
+    }
+
+    #[test]
+    fn test_speaker_clip_path_is_outside_recordings_list() {
+        let recording = std::path::Path::new("recordings").join("meeting_20260302_100000.wav");
+        let clip = speaker_clip_path(&recording.to_string_lossy(), "Alice B.");
+        assert_eq!(
+            std::path::PathBuf::from(clip),
+            std::path::Path::new("recordings").join("speakers").join("meeting_20260302_100000_alice_b_.wav")
+        );
+    }
+
+    #[test]
+    fn test_low_confidence_segments_skips_unscored() {
+        let scored = |text: &str, confidence: Option<f32>| TranscriptSegment {
+            text: text.to_string(),
+            confidence,
+            ..Default::default()
+        };
+        let transcription = vec![
+            scored("clear", Some(0.95)),
+            scored("mumbled", Some(0.42)),
+            scored("groq batch", None),
+            scored("borderline", Some(0.7)),
+        ];
+
+        // 10 segments already archived
+        let low = low_confidence_segments(&transcription, 10, 0.7);
+        assert_eq!(low.len(), 1);
+        assert_eq!(low[0].index, 11);
+        assert_eq!(low[0].segment.text, "mumbled");
+    }
+
+    #[test]
+    fn test_match_alert_keywords_whole_word_case_insensitive() {
+        let keywords = vec!["Sam".to_string(), "action item".to_string(), "deadline".to_string()];
+
+        assert_eq!(match_alert_keywords("Thanks, sam. Next up?", &keywords), vec!["Sam"]);
+        assert_eq!(match_alert_keywords("That's an ACTION ITEM for Friday", &keywords), vec!["action item"]);
+        // No partial-word matches
+        assert!(match_alert_keywords("Samantha missed the deadlines", &keywords).is_empty());
+        assert!(match_alert_keywords("action on each item", &keywords).is_empty());
+    }
+
+    #[test]
+    fn test_parse_decisions() {
+        assert_eq!(
+            parse_decisions("Here you go:\n[\"Ship v2 on Friday\", \"Hire a designer\"]"),
+            vec!["Ship v2 on Friday", "Hire a designer"]
+        );
+        assert!(parse_decisions("[]").is_empty());
+        assert_eq!(parse_decisions("Decisions:\n- Move standup to 10am"), vec!["Move standup to 10am"]);
+    }
+
+    #[test]
+    fn test_parse_comparison() {
+        let comparison = parse_comparison(
+            "Sure:\n{\"carried_over\": [\"Dana to draft the RFC\"], \"new\": [\"Sam to book the venue\", \" \"], \"resolved\": [], \"changes\": \"Launch moved to June.\"}",
+        );
+        assert_eq!(comparison.carried_over, vec!["Dana to draft the RFC"]);
+        assert_eq!(comparison.new_items, vec!["Sam to book the venue"]);
+        assert!(comparison.resolved.is_empty());
+        assert_eq!(comparison.changes, "Launch moved to June.");
+
+        let fallback = parse_comparison("Not much changed.");
+        assert!(fallback.carried_over.is_empty());
+        assert_eq!(fallback.changes, "Not much changed.");
+    }
+
     // Tests for retry/exponential backoff logic
     #[test]
     fn test_exponential_backoff_calculation() {
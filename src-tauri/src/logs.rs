@@ -0,0 +1,158 @@
+//! In-app log buffer
+//!
+//! Log lines go to stderr as before and into a ring buffer of recent lines, so users
+//! can see transcription failures in the app without a terminal (the console is
+//! hidden in release builds on Windows). Each line is also emitted as a `log-line` event.
+
+use serde::Serialize;
+use std::cell::Cell;
+use std::collections::VecDeque;
+use std::fmt::Write as _;
+use std::sync::{Arc, Mutex, OnceLock};
+use tauri::{AppHandle, Emitter};
+use tracing::field::{Field, Visit};
+use tracing::{Event, Subscriber};
+use tracing_subscriber::filter::LevelFilter;
+use tracing_subscriber::layer::{Context, Layer};
+use tracing_subscriber::prelude::*;
+
+/// Number of recent log lines kept in memory
+const MAX_LOG_LINES: usize = 1000;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct LogLine {
+    pub timestamp: String,
+    pub level: String,
+    pub target: String,
+    pub message: String,
+}
+
+/// Shared ring buffer of recent log lines
+#[derive(Debug, Clone, Default)]
+pub struct LogBuffer {
+    lines: Arc<Mutex<VecDeque<LogLine>>>,
+    app: Arc<OnceLock<AppHandle>>,
+}
+
+impl LogBuffer {
+    /// Process-wide buffer that the tracing layer writes into
+    pub fn global() -> &'static LogBuffer {
+        static BUFFER: OnceLock<LogBuffer> = OnceLock::new();
+        BUFFER.get_or_init(LogBuffer::default)
+    }
+
+    /// Start emitting `log-line` events once the app is running
+    pub fn attach(&self, app: AppHandle) {
+        let _ = self.app.set(app);
+    }
+
+    fn push(&self, line: LogLine) {
+        if let Ok(mut lines) = self.lines.lock() {
+            lines.push_back(line.clone());
+            while lines.len() > MAX_LOG_LINES {
+                lines.pop_front();
+            }
+        }
+        if let Some(app) = self.app.get() {
+            let _ = app.emit("log-line", line);
+        }
+    }
+
+    /// Most recent lines, oldest first
+    pub fn recent(&self, limit: usize) -> Vec<LogLine> {
+        match self.lines.lock() {
+            Ok(lines) => lines.iter().skip(lines.len().saturating_sub(limit)).cloned().collect(),
+            Err(_) => Vec::new(),
+        }
+    }
+}
+
+/// Collects an event's message and any extra fields into one string
+#[derive(Default)]
+struct MessageVisitor {
+    message: String,
+    fields: String,
+}
+
+impl Visit for MessageVisitor {
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        if field.name() == "message" {
+            let _ = write!(self.message, "{:?}", value);
+        } else {
+            let _ = write!(self.fields, " {}={:?}", field.name(), value);
+        }
+    }
+}
+
+/// tracing layer that copies events into the log buffer
+struct BufferLayer {
+    buffer: LogBuffer,
+}
+
+thread_local! {
+    /// Set while pushing a line, so logging done by the event emitter can't recurse
+    static IN_LAYER: Cell<bool> = const { Cell::new(false) };
+}
+
+impl<S: Subscriber> Layer<S> for BufferLayer {
+    fn on_event(&self, event: &Event<'_>, _ctx: Context<'_, S>) {
+        if IN_LAYER.with(|flag| flag.replace(true)) {
+            return;
+        }
+
+        let mut visitor = MessageVisitor::default();
+        event.record(&mut visitor);
+        let metadata = event.metadata();
+        self.buffer.push(LogLine {
+            timestamp: chrono::Local::now().format("%H:%M:%S%.3f").to_string(),
+            level: metadata.level().to_string(),
+            target: metadata.target().to_string(),
+            message: visitor.message + &visitor.fields,
+        });
+
+        IN_LAYER.with(|flag| flag.set(false));
+    }
+}
+
+/// Install the global subscriber: stderr output plus the in-app buffer.
+/// Call once, before anything logs.
+pub fn init() {
+    let result = tracing_subscriber::registry()
+        .with(LevelFilter::INFO)
+        .with(tracing_subscriber::fmt::layer().with_writer(std::io::stderr))
+        .with(BufferLayer { buffer: LogBuffer::global().clone() })
+        .try_init();
+
+    if let Err(e) = result {
+        eprintln!("Failed to initialize logging: {}", e);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn line(message: &str) -> LogLine {
+        LogLine {
+            timestamp: "10:00:00.000".to_string(),
+            level: "INFO".to_string(),
+            target: "vantage".to_string(),
+            message: message.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_buffer_keeps_most_recent_lines() {
+        let buffer = LogBuffer::default();
+        for i in 0..(MAX_LOG_LINES + 5) {
+            buffer.push(line(&i.to_string()));
+        }
+
+        let recent = buffer.recent(usize::MAX);
+        assert_eq!(recent.len(), MAX_LOG_LINES);
+        assert_eq!(recent[0].message, "5");
+
+        let last_two = buffer.recent(2);
+        assert_eq!(last_two[1].message, (MAX_LOG_LINES + 4).to_string());
+    }
+}
@@ -1,10 +1,11 @@
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::sync::Arc;
 use tokio::sync::RwLock;
 use chrono::{DateTime, Utc, Duration};
 use std::process::Command;
 
-use crate::calendar::{GoogleCalendar, SimpleCalendarEvent};
+use crate::calendar::{parse_event_time, GoogleCalendar, SimpleCalendarEvent};
 
 /// Meeting monitor settings
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -17,6 +18,16 @@ pub struct MeetingMonitorSettings {
     pub detect_meeting_apps: bool,
     /// Auto-start based on calendar time
     pub auto_start_on_time: bool,
+    /// Minutes after a meeting's start time it can still auto-start (joining late)
+    #[serde(default = "default_late_start_minutes")]
+    pub late_start_minutes: i64,
+    /// Stop recording and transcription once the started meeting has ended
+    #[serde(default)]
+    pub auto_stop_on_end: bool,
+}
+
+fn default_late_start_minutes() -> i64 {
+    5
 }
 
 impl Default for MeetingMonitorSettings {
@@ -26,6 +37,8 @@ impl Default for MeetingMonitorSettings {
             start_buffer_minutes: 2,
             detect_meeting_apps: true,
             auto_start_on_time: true,
+            late_start_minutes: default_late_start_minutes(),
+            auto_stop_on_end: false,
         }
     }
 }
@@ -38,13 +51,52 @@ pub struct MeetingStatus {
     pub upcoming_meeting: Option<SimpleCalendarEvent>,
     pub minutes_until_meeting: Option<i64>,
     pub auto_start_triggered: bool,
+    /// When a snoozed upcoming meeting will be offered again (RFC 3339)
+    #[serde(default)]
+    pub snoozed_until: Option<String>,
+}
+
+/// What happened to a calendar event's auto-start, so it isn't offered again
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum EventChoice {
+    /// Auto-started, or started by hand
+    Started,
+    /// "Remind me later": offered again once this time has passed
+    Snoozed(DateTime<Utc>),
+    /// "Skip this meeting"
+    Skipped,
+}
+
+/// Whether an event may auto-start now: not started or skipped, and not snoozed.
+/// `Some(true)` means a snooze just ran out, so it's offered even outside the usual window.
+fn auto_start_allowed(choice: Option<EventChoice>, now: DateTime<Utc>) -> Option<bool> {
+    match choice {
+        None => Some(false),
+        Some(EventChoice::Snoozed(until)) if now >= until => Some(true),
+        Some(_) => None,
+    }
+}
+
+/// How long past its end time a meeting counts as over even with a meeting app still
+/// running, since Slack, Teams and Zoom often stay open all day
+const MEETING_END_GRACE_MINUTES: i64 = 15;
+
+/// A meeting is over once its calendar end time has passed and no meeting app is running,
+/// or once the grace period after the end time has passed
+fn meeting_has_ended(end_time: &str, now: DateTime<Utc>, app_running: bool) -> bool {
+    parse_event_time(end_time).is_some_and(|end| {
+        now >= end && (!app_running || now >= end + Duration::minutes(MEETING_END_GRACE_MINUTES))
+    })
 }
 
 /// Meeting monitor state
 pub struct MeetingMonitor {
     settings: Arc<RwLock<MeetingMonitorSettings>>,
     status: Arc<RwLock<MeetingStatus>>,
-    last_triggered_event_id: Arc<RwLock<Option<String>>>,
+    /// Per-event choices for upcoming events; entries are dropped once the event leaves the calendar window
+    event_choices: Arc<RwLock<HashMap<String, EventChoice>>>,
+    /// The event that was started (automatically or by hand), watched for its end
+    active_event: Arc<RwLock<Option<SimpleCalendarEvent>>>,
 }
 
 impl MeetingMonitor {
@@ -57,8 +109,10 @@ impl MeetingMonitor {
                 upcoming_meeting: None,
                 minutes_until_meeting: None,
                 auto_start_triggered: false,
+                snoozed_until: None,
             })),
-            last_triggered_event_id: Arc::new(RwLock::new(None)),
+            event_choices: Arc::new(RwLock::new(HashMap::new())),
+            active_event: Arc::new(RwLock::new(None)),
         }
     }
 
@@ -79,7 +133,7 @@ impl MeetingMonitor {
     }
 
     /// Check if any meeting apps are running
-    fn detect_meeting_apps() -> Option<String> {
+    pub fn detect_meeting_apps() -> Option<String> {
         let meeting_apps = vec![
             ("zoom.us", "Zoom"),
             ("Microsoft Teams", "Teams"),
@@ -158,50 +212,60 @@ impl MeetingMonitor {
         let now = Utc::now();
         let mut should_auto_start = false;
 
-        // Find the next upcoming meeting
+        let mut choices = self.event_choices.write().await;
+        choices.retain(|id, _| events.iter().any(|e| &e.id == id));
+
+        // Find the next upcoming meeting. All-day events (holidays, OOO) aren't meetings,
+        // and skipped ones are left out so the one after them is watched instead.
         let next_meeting = events.iter()
-            .filter(|e| !e.is_past)
-            .min_by_key(|e| &e.start_time);
-
-        if let Some(meeting) = next_meeting {
-            // Parse meeting start time
-            if let Ok(start_time) = meeting.start_time.parse::<DateTime<Utc>>() {
-                let time_until_meeting = start_time - now;
-                let minutes_until = time_until_meeting.num_minutes();
-
-                // Check if meeting is starting soon
-                let is_starting_soon = minutes_until <= settings.start_buffer_minutes && minutes_until >= -5;
-
-                // Check if this event was already triggered
-                let last_triggered = self.last_triggered_event_id.read().await;
-                let already_triggered = last_triggered.as_ref() == Some(&meeting.id);
-
-                // Detect meeting apps if enabled
-                let meeting_app_detected = if settings.detect_meeting_apps {
-                    Self::detect_meeting_apps()
-                } else {
-                    None
-                };
-
-                // Determine if we should auto-start
-                should_auto_start = !already_triggered && (
-                    (settings.auto_start_on_time && is_starting_soon) ||
-                    (settings.detect_meeting_apps && meeting_app_detected.is_some() && minutes_until <= 10)
-                );
-
-                // Update status
-                let mut status = self.status.write().await;
-                status.is_meeting_detected = is_starting_soon || meeting_app_detected.is_some();
-                status.meeting_app_running = meeting_app_detected;
-                status.upcoming_meeting = Some(meeting.clone());
-                status.minutes_until_meeting = Some(minutes_until);
-                status.auto_start_triggered = should_auto_start;
-
-                // Mark event as triggered if auto-starting
-                if should_auto_start {
-                    let mut last_triggered = self.last_triggered_event_id.write().await;
-                    *last_triggered = Some(meeting.id.clone());
-                }
+            .filter(|e| !e.is_past && !e.is_all_day)
+            .filter(|e| choices.get(&e.id) != Some(&EventChoice::Skipped))
+            .filter_map(|e| parse_event_time(&e.start_time).map(|start| (e, start)))
+            .min_by_key(|(_, start)| *start);
+
+        if let Some((meeting, start_time)) = next_meeting {
+            let time_until_meeting = start_time - now;
+            let minutes_until = time_until_meeting.num_minutes();
+
+            // Check if meeting is starting soon
+            let is_starting_soon = minutes_until <= settings.start_buffer_minutes
+                && minutes_until >= -settings.late_start_minutes;
+
+            // Started, skipped and snoozed events aren't auto-started again
+            let choice = choices.get(&meeting.id).copied();
+            let allowed = auto_start_allowed(choice, now);
+
+            // Detect meeting apps if enabled
+            let meeting_app_detected = if settings.detect_meeting_apps {
+                Self::detect_meeting_apps()
+            } else {
+                None
+            };
+
+            // Determine if we should auto-start
+            should_auto_start = match allowed {
+                None => false,
+                Some(snooze_ended) => snooze_ended
+                    || (settings.auto_start_on_time && is_starting_soon)
+                    || (settings.detect_meeting_apps && meeting_app_detected.is_some() && minutes_until <= 10),
+            };
+
+            // Update status
+            let mut status = self.status.write().await;
+            status.is_meeting_detected = is_starting_soon || meeting_app_detected.is_some();
+            status.meeting_app_running = meeting_app_detected;
+            status.upcoming_meeting = Some(meeting.clone());
+            status.minutes_until_meeting = Some(minutes_until);
+            status.auto_start_triggered = should_auto_start;
+            status.snoozed_until = match choice {
+                Some(EventChoice::Snoozed(until)) if !should_auto_start => Some(until.to_rfc3339()),
+                _ => None,
+            };
+
+            // Mark event as triggered if auto-starting
+            if should_auto_start {
+                choices.insert(meeting.id.clone(), EventChoice::Started);
+                *self.active_event.write().await = Some(meeting.clone());
             }
         } else {
             // No upcoming meetings
@@ -211,17 +275,94 @@ impl MeetingMonitor {
             status.upcoming_meeting = None;
             status.minutes_until_meeting = None;
             status.auto_start_triggered = false;
+            status.snoozed_until = None;
         }
 
         Ok(should_auto_start)
     }
 
-    /// Reset the last triggered event (useful when user manually stops)
+    /// Reset triggered events so they can auto-start again (useful when user manually stops).
+    /// Snoozed and skipped events stay as they are.
     pub async fn reset_trigger(&self) {
-        let mut last_triggered = self.last_triggered_event_id.write().await;
-        *last_triggered = None;
+        self.event_choices.write().await.retain(|_, choice| *choice != EventChoice::Started);
+        self.clear_active_event().await;
 
         let mut status = self.status.write().await;
         status.auto_start_triggered = false;
     }
+
+    /// "Start now": the event was started by hand, so don't auto-start it as well
+    pub async fn mark_started(&self, event: &SimpleCalendarEvent) {
+        self.event_choices.write().await.insert(event.id.clone(), EventChoice::Started);
+        *self.active_event.write().await = Some(event.clone());
+    }
+
+    /// The started meeting, once it has ended: its end time has passed and the meeting app
+    /// (if detection is on) has closed. Returned once; the meeting is no longer watched after.
+    pub async fn check_meeting_end(&self) -> Option<SimpleCalendarEvent> {
+        let detect_apps = self.settings.read().await.detect_meeting_apps;
+        let mut active = self.active_event.write().await;
+        let event = active.as_ref()?;
+
+        let app_running = detect_apps && Self::detect_meeting_apps().is_some();
+        if !meeting_has_ended(&event.end_time, Utc::now(), app_running) {
+            return None;
+        }
+        active.take()
+    }
+
+    /// Stop watching the started meeting for its end (the session was stopped or cleared)
+    pub async fn clear_active_event(&self) {
+        *self.active_event.write().await = None;
+    }
+
+    /// "Remind in N minutes": hold off auto-starting the event until then
+    pub async fn snooze(&self, event_id: &str, minutes: i64) -> DateTime<Utc> {
+        let until = Utc::now() + Duration::minutes(minutes);
+        self.event_choices.write().await.insert(event_id.to_string(), EventChoice::Snoozed(until));
+
+        let mut status = self.status.write().await;
+        if status.upcoming_meeting.as_ref().map(|m| m.id.as_str()) == Some(event_id) {
+            status.auto_start_triggered = false;
+            status.snoozed_until = Some(until.to_rfc3339());
+        }
+        until
+    }
+
+    /// "Skip this meeting": never auto-start the event
+    pub async fn skip(&self, event_id: &str) {
+        self.event_choices.write().await.insert(event_id.to_string(), EventChoice::Skipped);
+
+        let mut status = self.status.write().await;
+        if status.upcoming_meeting.as_ref().map(|m| m.id.as_str()) == Some(event_id) {
+            status.auto_start_triggered = false;
+            status.snoozed_until = None;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_auto_start_allowed_by_choice() {
+        let now = Utc::now();
+        assert_eq!(auto_start_allowed(None, now), Some(false));
+        assert_eq!(auto_start_allowed(Some(EventChoice::Started), now), None);
+        assert_eq!(auto_start_allowed(Some(EventChoice::Skipped), now), None);
+        assert_eq!(auto_start_allowed(Some(EventChoice::Snoozed(now + Duration::minutes(5))), now), None);
+        assert_eq!(auto_start_allowed(Some(EventChoice::Snoozed(now - Duration::seconds(1))), now), Some(true));
+    }
+
+    #[test]
+    fn test_meeting_has_ended_needs_end_time_and_closed_app() {
+        let now = parse_event_time("2026-03-02T10:31:00Z").unwrap();
+        assert!(meeting_has_ended("2026-03-02T10:30:00Z", now, false));
+        assert!(!meeting_has_ended("2026-03-02T10:30:00Z", now, true));
+        // An app left open all day doesn't keep the meeting going past the grace period
+        assert!(meeting_has_ended("2026-03-02T10:15:00Z", now, true));
+        assert!(!meeting_has_ended("2026-03-02T11:00:00Z", now, false));
+        assert!(!meeting_has_ended("not a time", now, false));
+    }
 }
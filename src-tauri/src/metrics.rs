@@ -0,0 +1,97 @@
+//! Live transcription latency and throughput
+//!
+//! Latency is the time from the end of an utterance in the audio stream to its final
+//! transcript arriving. Stream time is measured from the first audio chunk sent.
+//! Recorded by the Deepgram streaming client only; the other live paths don't report here.
+
+use serde::Serialize;
+use std::time::{Duration, Instant};
+
+/// Counters accumulated while a live transcription session runs
+#[derive(Debug, Default)]
+pub struct TranscriptionStats {
+    stream_started: Option<Instant>,
+    first_send: Option<Instant>,
+    bytes_sent: u64,
+    audio_ms_sent: u64,
+    latency_total_ms: u64,
+    latency_samples: u64,
+}
+
+/// Snapshot returned by `get_transcription_metrics`
+#[derive(Debug, Clone, Serialize)]
+pub struct TranscriptionMetrics {
+    /// None until a final transcript with word timings has arrived
+    pub average_latency_ms: Option<f64>,
+    pub latency_samples: u64,
+    pub audio_seconds: f64,
+    pub bytes_per_second: f64,
+    pub dropped_chunks: u64,
+}
+
+impl TranscriptionStats {
+    /// New connection: the provider's timestamps restart from zero
+    pub fn stream_restarted(&mut self) {
+        self.stream_started = None;
+    }
+
+    /// Record a chunk of audio sent to the provider
+    pub fn record_audio_sent(&mut self, bytes: usize, audio_ms: u64) {
+        let now = Instant::now();
+        if self.stream_started.is_none() {
+            // Stream time 0 is the start of this first chunk
+            self.stream_started = Some(now.checked_sub(Duration::from_millis(audio_ms)).unwrap_or(now));
+        }
+        self.first_send.get_or_insert(now);
+        self.bytes_sent += bytes as u64;
+        self.audio_ms_sent += audio_ms;
+    }
+
+    /// Record a final transcript whose last word ends at `end_ms` of stream time
+    pub fn record_final(&mut self, end_ms: u64) {
+        self.record_final_at(end_ms, Instant::now());
+    }
+
+    fn record_final_at(&mut self, end_ms: u64, received: Instant) {
+        let Some(started) = self.stream_started else {
+            return;
+        };
+        let spoken = started + Duration::from_millis(end_ms);
+        self.latency_total_ms += received.saturating_duration_since(spoken).as_millis() as u64;
+        self.latency_samples += 1;
+    }
+
+    pub fn snapshot(&self, dropped_chunks: u64) -> TranscriptionMetrics {
+        let sending_secs = self.first_send.map(|t| t.elapsed().as_secs_f64()).unwrap_or(0.0);
+        TranscriptionMetrics {
+            average_latency_ms: (self.latency_samples > 0)
+                .then(|| self.latency_total_ms as f64 / self.latency_samples as f64),
+            latency_samples: self.latency_samples,
+            audio_seconds: self.audio_ms_sent as f64 / 1000.0,
+            bytes_per_second: if sending_secs > 0.0 { self.bytes_sent as f64 / sending_secs } else { 0.0 },
+            dropped_chunks,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_latency_is_measured_from_end_of_speech() {
+        let mut stats = TranscriptionStats::default();
+        assert!(stats.snapshot(0).average_latency_ms.is_none());
+
+        stats.record_audio_sent(3200, 100);
+        let started = stats.stream_started.unwrap();
+        stats.record_final_at(2_000, started + Duration::from_millis(2_300));
+        stats.record_final_at(4_000, started + Duration::from_millis(4_500));
+
+        let metrics = stats.snapshot(3);
+        assert_eq!(metrics.average_latency_ms, Some(400.0));
+        assert_eq!(metrics.latency_samples, 2);
+        assert_eq!(metrics.audio_seconds, 0.1);
+        assert_eq!(metrics.dropped_chunks, 3);
+    }
+}
@@ -41,8 +41,8 @@ pub async fn run_mock_session(
     transcription_state: std::sync::Arc<std::sync::Mutex<Vec<TranscriptSegment>>>,
     stop_signal: watch::Receiver<bool>,
 ) -> Result<()> {
-    eprintln!("Starting mock transcription session...");
-    eprintln!("  Test audio dir: {}", config.test_audio_dir);
+    tracing::info!("Starting mock transcription session...");
+    tracing::info!("  Test audio dir: {}", config.test_audio_dir);
 
     let dir = std::path::Path::new(&config.test_audio_dir);
     if !dir.exists() {
@@ -53,7 +53,7 @@ pub async fn run_mock_session(
     let mut turn = 1;
     loop {
         if *stop_signal.borrow() {
-            eprintln!("Mock session stopped by signal");
+            tracing::info!("Mock session stopped by signal");
             break;
         }
 
@@ -68,13 +68,13 @@ pub async fn run_mock_session(
                     config.test_audio_dir
                 ));
             }
-            eprintln!("No more conversation turns found after turn {}", turn - 1);
+            tracing::info!("No more conversation turns found after turn {}", turn - 1);
             break;
         }
 
         // Transcribe "You" turn if exists
         if you_file.exists() {
-            eprintln!("Transcribing turn {} (You): {:?}", turn, you_file);
+            tracing::info!("Transcribing turn {} (You): {:?}", turn, you_file);
             match groq::transcribe_audio(api_key, you_file.to_str().unwrap()).await {
                 Ok(text) if !text.is_empty() => {
                     let timestamp = chrono::Local::now().format("%H:%M:%S").to_string();
@@ -94,8 +94,8 @@ pub async fn run_mock_session(
                         speaker: "You".to_string(),
                     });
                 }
-                Ok(_) => eprintln!("Empty transcription for {:?}", you_file),
-                Err(e) => eprintln!("Failed to transcribe {:?}: {}", you_file, e),
+                Ok(_) => tracing::info!("Empty transcription for {:?}", you_file),
+                Err(e) => tracing::warn!("Failed to transcribe {:?}: {}", you_file, e),
             }
 
             // Delay between turns
@@ -108,7 +108,7 @@ pub async fn run_mock_session(
 
         // Transcribe "Participant" turn if exists
         if participant_file.exists() {
-            eprintln!("Transcribing turn {} (Participant): {:?}", turn, participant_file);
+            tracing::info!("Transcribing turn {} (Participant): {:?}", turn, participant_file);
             match groq::transcribe_audio(api_key, participant_file.to_str().unwrap()).await {
                 Ok(text) if !text.is_empty() => {
                     let timestamp = chrono::Local::now().format("%H:%M:%S").to_string();
@@ -128,8 +128,8 @@ pub async fn run_mock_session(
                         speaker: "Participant".to_string(),
                     });
                 }
-                Ok(_) => eprintln!("Empty transcription for {:?}", participant_file),
-                Err(e) => eprintln!("Failed to transcribe {:?}: {}", participant_file, e),
+                Ok(_) => tracing::info!("Empty transcription for {:?}", participant_file),
+                Err(e) => tracing::warn!("Failed to transcribe {:?}: {}", participant_file, e),
             }
 
             // Delay between turns
@@ -139,6 +139,6 @@ pub async fn run_mock_session(
         turn += 1;
     }
 
-    eprintln!("Mock transcription session completed ({} turns)", turn - 1);
+    tracing::info!("Mock transcription session completed ({} turns)", turn - 1);
     Ok(())
 }
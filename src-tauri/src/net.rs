@@ -0,0 +1,337 @@
+//! Shared HTTP client and proxy support for all outbound connections
+//!
+//! Without an explicit proxy, reqwest honors the standard HTTP_PROXY / HTTPS_PROXY /
+//! ALL_PROXY / NO_PROXY environment variables. WebSockets don't go through reqwest, so
+//! `connect_websocket` tunnels them itself (HTTP CONNECT or SOCKS5).
+
+use anyhow::{anyhow, Result};
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
+use futures_util::StreamExt;
+use std::sync::RwLock;
+use std::time::{Duration, Instant};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpStream;
+use tokio_tungstenite::tungstenite::client::IntoClientRequest;
+use tokio_tungstenite::tungstenite::handshake::client::Response;
+use tokio_tungstenite::{MaybeTlsStream, WebSocketStream};
+
+/// Hosts that never go through an explicit proxy (the local Ollama server)
+const DEFAULT_NO_PROXY: &str = "localhost,127.0.0.1,::1";
+
+/// Explicit proxy URL (None = environment) and the client built for it
+static CLIENT: RwLock<Option<(Option<String>, reqwest::Client)>> = RwLock::new(None);
+
+/// Check a proxy URL: http(s):// or socks5(h)://, with a host. An https:// proxy works for
+/// HTTP requests only; live streaming WebSockets need an http:// or socks5:// one.
+pub fn validate_proxy(url: &str) -> Result<String, String> {
+    let url = url.trim().trim_end_matches('/');
+    let parsed = url::Url::parse(url).map_err(|e| format!("Invalid proxy URL '{}': {}", url, e))?;
+    if !matches!(parsed.scheme(), "http" | "https" | "socks5" | "socks5h") || parsed.host_str().is_none() {
+        return Err("Proxy URL must start with http://, https://, socks5:// or socks5h://".to_string());
+    }
+    Ok(url.to_string())
+}
+
+fn build_client(proxy: Option<&str>) -> Result<reqwest::Client, String> {
+    let mut builder = reqwest::Client::builder();
+    if let Some(url) = proxy {
+        let no_proxy = reqwest::NoProxy::from_env().or_else(|| reqwest::NoProxy::from_string(DEFAULT_NO_PROXY));
+        let proxy = reqwest::Proxy::all(url).map_err(|e| e.to_string())?.no_proxy(no_proxy);
+        builder = builder.proxy(proxy);
+    }
+    builder.build().map_err(|e| e.to_string())
+}
+
+/// Route all traffic through `proxy`, or back to the environment settings with None.
+/// Rebuilds the shared client.
+pub fn set_proxy(proxy: Option<&str>) -> Result<(), String> {
+    let proxy = proxy.map(validate_proxy).transpose()?;
+    let client = build_client(proxy.as_deref())?;
+    *CLIENT.write().map_err(|e| e.to_string())? = Some((proxy, client));
+    Ok(())
+}
+
+/// The shared HTTP client. Cheap to call: clones share one connection pool.
+pub fn client() -> reqwest::Client {
+    if let Some((_, client)) = CLIENT.read().ok().as_deref().and_then(Option::as_ref) {
+        return client.clone();
+    }
+
+    let client = build_client(None).unwrap_or_default();
+    if let Ok(mut slot) = CLIENT.write() {
+        slot.get_or_insert_with(|| (None, client.clone()));
+    }
+    client
+}
+
+/// Which failures `http_retry` retries, with exponential backoff (1s doubling to 30s)
+#[derive(Debug, Clone, Copy)]
+pub struct RetryPolicy {
+    pub max_attempts: u32,
+    /// Also retry connection failures, timeouts and 502/503/504, not just 429
+    pub transient_errors: bool,
+}
+
+impl RetryPolicy {
+    /// Rate limits only; other failures surface at once so a fallback provider can take over
+    pub const RATE_LIMIT: Self = Self { max_attempts: 5, transient_errors: false };
+    /// Rate limits plus flaky-network failures
+    pub const TRANSIENT: Self = Self { max_attempts: 4, transient_errors: true };
+}
+
+const RETRY_INITIAL_DELAY_MS: u64 = 1000;
+const RETRY_MAX_DELAY_MS: u64 = 30_000;
+
+fn is_transient_status(status: reqwest::StatusCode) -> bool {
+    matches!(status.as_u16(), 502..=504)
+}
+
+/// Wait before the next attempt: the backoff delay, or longer if the server asked for it
+fn retry_wait_ms(backoff_ms: u64, retry_after: Option<&str>) -> u64 {
+    retry_after
+        .and_then(|s| s.trim().parse::<u64>().ok())
+        .map(|secs| secs * 1000)
+        .unwrap_or(backoff_ms)
+        .max(backoff_ms)
+}
+
+/// Send a request, retrying per `policy`. `request` builds a fresh request for each attempt.
+/// Returns the last response once retries run out (the caller reports its status),
+/// or the transport error if the request never got a response.
+pub async fn http_retry(
+    label: &str,
+    policy: RetryPolicy,
+    request: impl Fn() -> reqwest::RequestBuilder,
+) -> Result<reqwest::Response, reqwest::Error> {
+    let mut backoff_ms = RETRY_INITIAL_DELAY_MS;
+    let max_attempts = policy.max_attempts.max(1);
+
+    for attempt in 1..=max_attempts {
+        let last_attempt = attempt == max_attempts;
+        let wait_ms = match request().send().await {
+            Ok(response) => {
+                let status = response.status();
+                let retryable = status == reqwest::StatusCode::TOO_MANY_REQUESTS
+                    || (policy.transient_errors && is_transient_status(status));
+                if !retryable || last_attempt {
+                    return Ok(response);
+                }
+                let retry_after = response.headers().get("retry-after").and_then(|h| h.to_str().ok());
+                let wait_ms = retry_wait_ms(backoff_ms, retry_after);
+                tracing::info!("{} returned {} (attempt {}/{}), retrying in {}ms", label, status, attempt, max_attempts, wait_ms);
+                wait_ms
+            }
+            Err(e) => {
+                let transient = policy.transient_errors && (e.is_connect() || e.is_timeout());
+                if !transient || last_attempt {
+                    return Err(e);
+                }
+                tracing::info!("{} request failed (attempt {}/{}), retrying in {}ms: {}", label, attempt, max_attempts, backoff_ms, e);
+                backoff_ms
+            }
+        };
+
+        tokio::time::sleep(std::time::Duration::from_millis(wait_ms)).await;
+        backoff_ms = (backoff_ms * 2).min(RETRY_MAX_DELAY_MS);
+    }
+
+    unreachable!("the last attempt always returns")
+}
+
+/// Text streamed back by an LLM, and how long after the request the first piece arrived
+#[derive(Debug, Clone, Default)]
+pub struct StreamedReply {
+    pub text: String,
+    pub first_token: Option<Duration>,
+}
+
+/// Append `chunk` to `buffer` and take out every complete line
+fn take_lines(buffer: &mut Vec<u8>, chunk: &[u8]) -> Vec<String> {
+    buffer.extend_from_slice(chunk);
+    let Some(last_newline) = buffer.iter().rposition(|&b| b == b'\n') else {
+        return Vec::new();
+    };
+    let rest = buffer.split_off(last_newline + 1);
+    let complete = std::mem::replace(buffer, rest);
+    String::from_utf8_lossy(&complete).lines().map(str::to_string).collect()
+}
+
+/// Read a line-delimited streaming response (SSE or NDJSON), joining the text `parse_line`
+/// pulls out of each line. `started` is when the request was sent.
+pub async fn read_streamed_text(
+    response: reqwest::Response,
+    started: Instant,
+    parse_line: impl Fn(&str) -> Option<String>,
+) -> Result<StreamedReply> {
+    let mut reply = StreamedReply::default();
+    let mut buffer = Vec::new();
+    let mut stream = response.bytes_stream();
+
+    let handle = |line: &str, reply: &mut StreamedReply| {
+        if let Some(text) = parse_line(line).filter(|t| !t.is_empty()) {
+            reply.first_token.get_or_insert_with(|| started.elapsed());
+            reply.text.push_str(&text);
+        }
+    };
+
+    while let Some(chunk) = stream.next().await {
+        for line in take_lines(&mut buffer, &chunk?) {
+            handle(&line, &mut reply);
+        }
+    }
+    handle(&String::from_utf8_lossy(&buffer), &mut reply);
+
+    Ok(reply)
+}
+
+fn explicit_proxy() -> Option<String> {
+    CLIENT.read().ok()?.as_ref()?.0.clone()
+}
+
+fn env_var(names: &[&str]) -> Option<String> {
+    names
+        .iter()
+        .find_map(|name| std::env::var(name).ok())
+        .filter(|value| !value.trim().is_empty())
+}
+
+/// Whether `host` matches a NO_PROXY-style list ("example.com,.corp,localhost", "*")
+fn bypasses_proxy(host: &str, no_proxy: &str) -> bool {
+    no_proxy.split(',').map(str::trim).filter(|e| !e.is_empty()).any(|entry| {
+        let entry = entry.trim_start_matches('.');
+        entry == "*" || host == entry || host.ends_with(&format!(".{}", entry))
+    })
+}
+
+/// Proxy for a WebSocket connection to `host`: the explicit setting, else the environment
+fn websocket_proxy(host: &str, secure: bool) -> Option<String> {
+    let no_proxy = env_var(&["NO_PROXY", "no_proxy"]).unwrap_or_else(|| DEFAULT_NO_PROXY.to_string());
+    if bypasses_proxy(host, &no_proxy) {
+        return None;
+    }
+
+    explicit_proxy().or_else(|| {
+        if secure {
+            env_var(&["HTTPS_PROXY", "https_proxy", "ALL_PROXY", "all_proxy"])
+        } else {
+            env_var(&["HTTP_PROXY", "http_proxy", "ALL_PROXY", "all_proxy"])
+        }
+    })
+}
+
+/// Open a TCP connection to `host:port` through the proxy. Talking TLS to the proxy itself
+/// isn't supported, so an https:// proxy is refused rather than sent a plain CONNECT.
+async fn tunnel(proxy: &str, host: &str, port: u16) -> Result<TcpStream> {
+    let proxy = url::Url::parse(proxy)?;
+    if proxy.scheme() == "https" {
+        return Err(anyhow!(
+            "Live streaming can't go through an https:// proxy. Use an http:// or socks5:// proxy instead."
+        ));
+    }
+    let proxy_host = proxy.host_str().ok_or_else(|| anyhow!("Proxy URL has no host"))?;
+    let username = urlencoding::decode(proxy.username())?.into_owned();
+    let password = urlencoding::decode(proxy.password().unwrap_or_default())?.into_owned();
+
+    if proxy.scheme().starts_with("socks5") {
+        let proxy_addr = (proxy_host, proxy.port().unwrap_or(1080));
+        let stream = if username.is_empty() {
+            tokio_socks::tcp::Socks5Stream::connect(proxy_addr, (host, port)).await?
+        } else {
+            tokio_socks::tcp::Socks5Stream::connect_with_password(proxy_addr, (host, port), &username, &password).await?
+        };
+        return Ok(stream.into_inner());
+    }
+
+    // HTTP CONNECT
+    // Proxies from the environment aren't validated, so the scheme may not have a default port
+    let proxy_port = proxy
+        .port_or_known_default()
+        .ok_or_else(|| anyhow!("Proxy URL has no port and no known default for '{}'", proxy.scheme()))?;
+    let mut stream = TcpStream::connect((proxy_host, proxy_port)).await?;
+    let mut request = format!("CONNECT {host}:{port} HTTP/1.1\r\nHost: {host}:{port}\r\n");
+    if !username.is_empty() {
+        let credentials = BASE64.encode(format!("{}:{}", username, password));
+        request.push_str(&format!("Proxy-Authorization: Basic {}\r\n", credentials));
+    }
+    request.push_str("\r\n");
+    stream.write_all(request.as_bytes()).await?;
+
+    // Read the proxy's response headers byte by byte so no tunneled data is consumed
+    let mut response = Vec::new();
+    let mut byte = [0u8; 1];
+    while !response.ends_with(b"\r\n\r\n") {
+        if stream.read(&mut byte).await? == 0 || response.len() > 8192 {
+            return Err(anyhow!("Proxy closed the connection during CONNECT"));
+        }
+        response.push(byte[0]);
+    }
+
+    let status_line = String::from_utf8_lossy(&response);
+    let status_line = status_line.lines().next().unwrap_or_default();
+    if status_line.split_whitespace().nth(1) != Some("200") {
+        return Err(anyhow!("Proxy refused CONNECT to {}:{}: {}", host, port, status_line));
+    }
+    Ok(stream)
+}
+
+/// `tokio_tungstenite::connect_async`, routed through the configured proxy if any
+pub async fn connect_websocket<R>(request: R) -> Result<(WebSocketStream<MaybeTlsStream<TcpStream>>, Response)>
+where
+    R: IntoClientRequest + Unpin,
+{
+    let request = request.into_client_request()?;
+    let uri = request.uri();
+    let host = uri.host().ok_or_else(|| anyhow!("WebSocket URL has no host"))?.to_string();
+    let secure = uri.scheme_str() == Some("wss");
+    let port = uri.port_u16().unwrap_or(if secure { 443 } else { 80 });
+
+    let Some(proxy) = websocket_proxy(&host, secure) else {
+        return Ok(tokio_tungstenite::connect_async(request).await?);
+    };
+
+    tracing::info!("Connecting to {} through proxy", host);
+    let stream = tunnel(&proxy, &host, port).await?;
+    Ok(tokio_tungstenite::client_async_tls(request, stream).await?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_proxy_and_no_proxy() {
+        assert_eq!(validate_proxy("http://proxy.corp:3128/").unwrap(), "http://proxy.corp:3128");
+        assert!(validate_proxy("socks5h://user:pw@10.0.0.1:1080").is_ok());
+        assert!(validate_proxy("ftp://proxy.corp").is_err());
+        assert!(validate_proxy("proxy.corp:3128").is_err());
+
+        assert!(bypasses_proxy("localhost", DEFAULT_NO_PROXY));
+        assert!(bypasses_proxy("api.internal.corp", "example.com, .corp"));
+        assert!(!bypasses_proxy("api.deepgram.com", "example.com,.corp"));
+    }
+
+    #[test]
+    fn test_retry_wait_and_transient_statuses() {
+        assert_eq!(retry_wait_ms(1000, None), 1000);
+        assert_eq!(retry_wait_ms(1000, Some("5")), 5000);
+        assert_eq!(retry_wait_ms(4000, Some("1")), 4000);
+        assert_eq!(retry_wait_ms(2000, Some("Wed, 21 Oct 2026 07:28:00 GMT")), 2000);
+
+        assert!(is_transient_status(reqwest::StatusCode::SERVICE_UNAVAILABLE));
+        assert!(!is_transient_status(reqwest::StatusCode::INTERNAL_SERVER_ERROR));
+        assert!(!is_transient_status(reqwest::StatusCode::UNAUTHORIZED));
+    }
+
+    #[test]
+    fn test_take_lines_keeps_partial_line_for_next_chunk() {
+        let mut buffer = Vec::new();
+        let first = take_lines(&mut buffer, b"data: {\"choices\":[{\"delta\":{\"content\":\"Hel\"}}]}\n\ndata: {\"choi");
+        let second = take_lines(&mut buffer, b"ces\":[{\"delta\":{\"content\":\"lo\"}}]}\ndata: [DONE]\n");
+
+        let text: String = first.iter().chain(&second).filter_map(|l| crate::groq::sse_delta(l)).collect();
+        assert_eq!(text, "Hello");
+        assert_eq!(first.len(), 2);
+        assert!(buffer.is_empty());
+        assert_eq!(crate::ollama::ndjson_response("{\"response\":\"ready\",\"done\":false}").as_deref(), Some("ready"));
+    }
+}
@@ -1,13 +1,22 @@
 use anyhow::{anyhow, Result};
 use serde::{Deserialize, Serialize};
 
-const OLLAMA_BASE_URL: &str = "http://localhost:11434";
+use crate::endpoints;
+use crate::net::{http_retry, RetryPolicy, StreamedReply};
 
 #[derive(Debug, Serialize)]
 struct GenerateRequest {
     model: String,
+    system: String,
     prompt: String,
     stream: bool,
+    options: GenerateOptions,
+}
+
+#[derive(Debug, Serialize)]
+struct GenerateOptions {
+    temperature: f32,
+    num_predict: u32,
 }
 
 #[derive(Debug, Deserialize)]
@@ -27,9 +36,9 @@ struct ModelInfo {
 
 /// Check if Ollama is running and accessible
 pub async fn check_connection() -> Result<bool> {
-    let client = reqwest::Client::new();
+    let client = crate::net::client();
     let response = client
-        .get(format!("{}/api/tags", OLLAMA_BASE_URL))
+        .get(format!("{}/api/tags", endpoints::ollama()))
         .timeout(std::time::Duration::from_secs(5))
         .send()
         .await;
@@ -42,9 +51,9 @@ pub async fn check_connection() -> Result<bool> {
 
 /// List available models from Ollama
 pub async fn list_models() -> Result<Vec<String>> {
-    let client = reqwest::Client::new();
+    let client = crate::net::client();
     let response = client
-        .get(format!("{}/api/tags", OLLAMA_BASE_URL))
+        .get(format!("{}/api/tags", endpoints::ollama()))
         .timeout(std::time::Duration::from_secs(10))
         .send()
         .await?;
@@ -61,17 +70,60 @@ pub async fn list_models() -> Result<Vec<String>> {
 }
 
 /// Generate a response using the specified model
-pub async fn generate(model: &str, prompt: &str) -> Result<String> {
-    let client = reqwest::Client::new();
+pub async fn generate(model: &str, system_prompt: &str, prompt: &str, temperature: f32, max_tokens: u32) -> Result<String> {
+    let client = crate::net::client();
 
     let request = GenerateRequest {
         model: model.to_string(),
+        system: system_prompt.to_string(),
         prompt: prompt.to_string(),
         stream: false,
+        options: GenerateOptions {
+            temperature,
+            num_predict: max_tokens,
+        },
     };
 
-    let response = client
-        .post(format!("{}/api/generate", OLLAMA_BASE_URL))
+    let response = http_retry("Ollama", RetryPolicy::TRANSIENT, || {
+        client
+            .post(format!("{}/api/generate", endpoints::ollama()))
+            .json(&request)
+            .timeout(std::time::Duration::from_secs(120))
+    })
+    .await?;
+
+    if !response.status().is_success() {
+        let error_text = response.text().await.unwrap_or_default();
+        return Err(anyhow!("Ollama API error: {}", error_text));
+    }
+
+    let result: GenerateResponse = response.json().await?;
+    Ok(result.response)
+}
+
+/// Text of one line of a streamed `/api/generate` response (NDJSON)
+pub(crate) fn ndjson_response(line: &str) -> Option<String> {
+    let chunk: serde_json::Value = serde_json::from_str(line.trim()).ok()?;
+    chunk["response"].as_str().map(str::to_string)
+}
+
+/// Stream one short completion, timing the first token. No retries, so the timing
+/// reflects a single request (including loading the model if it isn't in memory yet).
+pub async fn stream_generate(model: &str, prompt: &str, max_tokens: u32) -> Result<StreamedReply> {
+    let request = GenerateRequest {
+        model: model.to_string(),
+        system: String::new(),
+        prompt: prompt.to_string(),
+        stream: true,
+        options: GenerateOptions {
+            temperature: 0.0,
+            num_predict: max_tokens,
+        },
+    };
+
+    let started = std::time::Instant::now();
+    let response = crate::net::client()
+        .post(format!("{}/api/generate", endpoints::ollama()))
         .json(&request)
         .timeout(std::time::Duration::from_secs(120))
         .send()
@@ -82,8 +134,7 @@ pub async fn generate(model: &str, prompt: &str) -> Result<String> {
         return Err(anyhow!("Ollama API error: {}", error_text));
     }
 
-    let result: GenerateResponse = response.json().await?;
-    Ok(result.response)
+    crate::net::read_streamed_text(response, started, ndjson_response).await
 }
 
 /// Generate a chat completion with context
@@ -106,7 +157,7 @@ struct ChatResponse {
 }
 
 pub async fn chat(model: &str, messages: Vec<ChatMessage>) -> Result<String> {
-    let client = reqwest::Client::new();
+    let client = crate::net::client();
 
     let request = ChatRequest {
         model: model.to_string(),
@@ -114,12 +165,13 @@ pub async fn chat(model: &str, messages: Vec<ChatMessage>) -> Result<String> {
         stream: false,
     };
 
-    let response = client
-        .post(format!("{}/api/chat", OLLAMA_BASE_URL))
-        .json(&request)
-        .timeout(std::time::Duration::from_secs(120))
-        .send()
-        .await?;
+    let response = http_retry("Ollama", RetryPolicy::TRANSIENT, || {
+        client
+            .post(format!("{}/api/chat", endpoints::ollama()))
+            .json(&request)
+            .timeout(std::time::Duration::from_secs(120))
+    })
+    .await?;
 
     if !response.status().is_success() {
         let error_text = response.text().await.unwrap_or_default();
@@ -132,7 +184,7 @@ pub async fn chat(model: &str, messages: Vec<ChatMessage>) -> Result<String> {
 
 /// Pull a model from Ollama
 pub async fn pull_model(model: &str) -> Result<()> {
-    let client = reqwest::Client::new();
+    let client = crate::net::client();
 
     #[derive(Serialize)]
     struct PullRequest {
@@ -144,7 +196,7 @@ pub async fn pull_model(model: &str) -> Result<()> {
     };
 
     let response = client
-        .post(format!("{}/api/pull", OLLAMA_BASE_URL))
+        .post(format!("{}/api/pull", endpoints::ollama()))
         .json(&request)
         .timeout(std::time::Duration::from_secs(600)) // 10 minutes for large models
         .send()
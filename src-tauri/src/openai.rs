@@ -0,0 +1,234 @@
+use anyhow::{anyhow, Result};
+use std::path::Path;
+
+use crate::endpoints;
+use crate::error::ProviderError;
+use crate::net::{http_retry, RetryPolicy};
+use crate::groq::{self, ChatMessage, ChatRequest, ChatResponse, GenerationParams, WhisperResponse};
+
+/// Chat model used when switching the LLM provider to OpenAI
+pub const DEFAULT_MODEL: &str = "gpt-4o-mini";
+
+/// OpenAI chat models offered in the model picker
+pub fn get_available_models() -> Vec<(&'static str, &'static str)> {
+    vec![
+        ("gpt-4o-mini", "GPT-4o mini (Fast)"),
+        ("gpt-4o", "GPT-4o (Best)"),
+        ("gpt-4.1-mini", "GPT-4.1 mini"),
+        ("gpt-4.1", "GPT-4.1"),
+    ]
+}
+
+/// Context window of an OpenAI chat model, in tokens
+pub fn context_window(model: &str) -> Option<u32> {
+    if model.starts_with("gpt-4.1") {
+        Some(1_047_576)
+    } else if model.starts_with("gpt-4o") {
+        Some(128_000)
+    } else {
+        None
+    }
+}
+
+/// Whether a model ID is one of the OpenAI chat models we offer
+pub fn is_openai_model(model: &str) -> bool {
+    get_available_models().iter().any(|(id, _)| *id == model)
+}
+
+/// OpenAI could not be reached (network error, timeout or 5xx)
+#[derive(Debug)]
+pub struct ServiceUnavailable(pub String);
+
+impl std::fmt::Display for ServiceUnavailable {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "OpenAI unavailable: {}", self.0)
+    }
+}
+
+impl std::error::Error for ServiceUnavailable {}
+
+/// Check whether an error means OpenAI itself was unreachable (vs. a bad request or key)
+pub fn is_unavailable(err: &anyhow::Error) -> bool {
+    err.downcast_ref::<ServiceUnavailable>().is_some()
+}
+
+/// Generate a response using the OpenAI chat completions API with automatic rate limit retry
+pub async fn generate_with_params(
+    api_key: &str,
+    model: &str,
+    system_prompt: &str,
+    prompt: &str,
+    params: &GenerationParams,
+) -> Result<String> {
+    if api_key.is_empty() {
+        return Err(anyhow!("OpenAI API key not set. Add one in Settings"));
+    }
+
+    let client = crate::net::client();
+
+    let request = ChatRequest {
+        model: model.to_string(),
+        messages: vec![
+            ChatMessage {
+                role: "system".to_string(),
+                content: system_prompt.to_string(),
+            },
+            ChatMessage {
+                role: "user".to_string(),
+                content: prompt.to_string(),
+            },
+        ],
+        temperature: params.temperature,
+        max_tokens: params.max_tokens,
+    };
+
+    // Rate limits are retried with backoff; outages fail fast so the Ollama fallback can step in
+    let response = http_retry("OpenAI", RetryPolicy::RATE_LIMIT, || {
+        client
+            .post(format!("{}/chat/completions", endpoints::openai()))
+            .header("Authorization", format!("Bearer {}", api_key))
+            .header("Content-Type", "application/json")
+            .json(&request)
+            .timeout(std::time::Duration::from_secs(60))
+    })
+    .await
+    .map_err(|e| ServiceUnavailable(e.to_string()))?;
+
+    let status = response.status();
+    if status.is_server_error() {
+        let error_text = response.text().await.unwrap_or_default();
+        return Err(ServiceUnavailable(format!("{}: {}", status, error_text)).into());
+    }
+
+    if !status.is_success() {
+        let error_text = response.text().await.unwrap_or_default();
+        return Err(ProviderError::new(status, format!("OpenAI API error ({}): {}", status, error_text)).into());
+    }
+
+    let result: ChatResponse = response.json().await?;
+    result
+        .choices
+        .first()
+        .map(|c| c.message.content.clone())
+        .ok_or_else(|| anyhow!("No response from OpenAI"))
+}
+
+/// Stream one short completion with `model`, timing the first token
+pub async fn stream_chat(api_key: &str, model: &str, prompt: &str, max_tokens: u32) -> Result<crate::net::StreamedReply> {
+    if api_key.is_empty() {
+        return Err(anyhow!("OpenAI API key not set. Add one in Settings"));
+    }
+    groq::stream_chat_at("OpenAI", &endpoints::openai(), api_key, model, prompt, max_tokens).await
+}
+
+/// Check if API key is valid by listing models (doesn't consume tokens)
+pub async fn check_api_key(api_key: &str) -> Result<bool> {
+    if api_key.is_empty() {
+        return Ok(false);
+    }
+
+    let response = crate::net::client()
+        .get(format!("{}/models", endpoints::openai()))
+        .header("Authorization", format!("Bearer {}", api_key))
+        .timeout(std::time::Duration::from_secs(10))
+        .send()
+        .await;
+
+    match response {
+        Ok(res) => Ok(res.status().is_success()),
+        Err(_) => Ok(false),
+    }
+}
+
+/// Maximum file size for the OpenAI transcription API is 25MB; stay under it
+const MAX_WHISPER_FILE_SIZE: u64 = 24_000_000;
+
+/// Audio bytes per chunk when a recording is sent in pieces, leaving room for the header
+const MAX_CHUNK_AUDIO_BYTES: u64 = MAX_WHISPER_FILE_SIZE - groq::WAV_HEADER_PROBE as u64;
+
+/// Transcribe audio file using OpenAI's whisper-1.
+/// For files larger than MAX_WHISPER_FILE_SIZE, only transcribes the last portion
+pub async fn transcribe_audio(api_key: &str, file_path: &str) -> Result<String> {
+    if api_key.is_empty() {
+        return Err(anyhow!("OpenAI API key not set"));
+    }
+
+    let path = Path::new(file_path);
+    if !path.exists() {
+        return Err(anyhow!("Audio file not found: {}", file_path));
+    }
+
+    let file_size = tokio::fs::metadata(file_path).await?.len();
+    let file_bytes = if file_size <= MAX_WHISPER_FILE_SIZE {
+        tokio::fs::read(file_path).await?
+    } else {
+        tracing::info!("Large file detected ({}MB), extracting last {}MB for transcription",
+            file_size / 1_000_000, MAX_WHISPER_FILE_SIZE / 1_000_000);
+        groq::extract_recent_audio(file_path, MAX_WHISPER_FILE_SIZE as usize).await?
+    };
+
+    let file_name = path.file_name()
+        .and_then(|n| n.to_str())
+        .unwrap_or("audio.wav")
+        .to_string();
+
+    post_transcription(api_key, file_bytes, file_name).await
+}
+
+/// Transcribe a whole recording; large files are sent in chunks and the texts joined,
+/// rather than keeping only the last part like `transcribe_audio`
+pub async fn transcribe_recording(api_key: &str, file_path: &str) -> Result<String> {
+    if api_key.is_empty() {
+        return Err(anyhow!("OpenAI API key not set"));
+    }
+
+    let path = Path::new(file_path);
+    if !path.exists() {
+        return Err(anyhow!("Audio file not found: {}", file_path));
+    }
+
+    let file_name = path.file_name()
+        .and_then(|n| n.to_str())
+        .unwrap_or("audio.wav")
+        .to_string();
+
+    let mut texts = Vec::new();
+    let mut reader = groq::WavChunks::new(file_path, MAX_CHUNK_AUDIO_BYTES);
+    while let Some(chunk) = reader.next().await? {
+        texts.push(post_transcription(api_key, chunk, file_name.clone()).await?.trim().to_string());
+    }
+    Ok(texts.join(" ").trim().to_string())
+}
+
+/// Upload one WAV to whisper-1
+async fn post_transcription(api_key: &str, file_bytes: Vec<u8>, file_name: String) -> Result<String> {
+    let audio_seconds = crate::usage::wav_seconds(&file_bytes);
+
+    let file_part = reqwest::multipart::Part::bytes(file_bytes)
+        .file_name(file_name)
+        .mime_str("audio/wav")?;
+
+    let form = reqwest::multipart::Form::new()
+        .part("file", file_part)
+        .text("model", "whisper-1")
+        .text("response_format", "json")
+        .text("language", "en");
+
+    let response = crate::net::client()
+        .post(format!("{}/audio/transcriptions", endpoints::openai()))
+        .header("Authorization", format!("Bearer {}", api_key))
+        .multipart(form)
+        .timeout(std::time::Duration::from_secs(120))
+        .send()
+        .await?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let error_text = response.text().await.unwrap_or_default();
+        return Err(ProviderError::new(status, format!("OpenAI Whisper API error ({}): {}", status, error_text)).into());
+    }
+
+    let result: WhisperResponse = response.json().await?;
+    crate::usage::record_audio("openai", audio_seconds);
+    Ok(result.text)
+}
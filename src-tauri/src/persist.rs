@@ -0,0 +1,113 @@
+//! Crash-safe writes for JSON config files (settings, calendar tokens, templates)
+//!
+//! Content is written to a temp file next to the target, flushed to disk, then renamed
+//! over the target. A crash mid-write leaves either the old file or the new one,
+//! never a truncated file.
+//!
+//! Writers hold an advisory lock on a `.lock` file beside the target while they write and
+//! rename, so two app instances (or a command and the meeting monitor) take turns.
+
+use fs2::FileExt;
+use std::fs;
+use std::io::Write;
+use std::path::Path;
+
+/// Run blocking file IO from a command without stalling the other tasks on this tokio
+/// worker: they are handed to another thread while `f` runs. Plain call outside a runtime.
+pub fn blocking<T>(f: impl FnOnce() -> T) -> T {
+    match tokio::runtime::Handle::try_current() {
+        Ok(handle) if handle.runtime_flavor() == tokio::runtime::RuntimeFlavor::MultiThread => {
+            tokio::task::block_in_place(f)
+        }
+        _ => f(),
+    }
+}
+
+/// Atomically replace `path` with `content`, creating the parent directory if needed
+pub fn write_atomic(path: &Path, content: &str) -> Result<(), String> {
+    blocking(|| write_atomic_sync(path, content))
+}
+
+fn write_atomic_sync(path: &Path, content: &str) -> Result<(), String> {
+    let parent = path
+        .parent()
+        .ok_or_else(|| format!("Invalid path: {:?}", path))?;
+    fs::create_dir_all(parent)
+        .map_err(|e| format!("Failed to create config directory: {}", e))?;
+
+    let file_name = path
+        .file_name()
+        .ok_or_else(|| format!("Invalid path: {:?}", path))?
+        .to_string_lossy();
+    let tmp_path = parent.join(format!(".{}.tmp", file_name));
+
+    // Released when the file is closed at the end of this function
+    let lock_file = fs::OpenOptions::new()
+        .create(true)
+        .truncate(false)
+        .write(true)
+        .open(parent.join(format!(".{}.lock", file_name)))
+        .map_err(|e| format!("Failed to open lock file: {}", e))?;
+    lock_file
+        .lock_exclusive()
+        .map_err(|e| format!("Failed to lock {:?}: {}", path, e))?;
+
+    let result = (|| -> std::io::Result<()> {
+        let mut file = fs::File::create(&tmp_path)?;
+        file.write_all(content.as_bytes())?;
+        file.sync_all()?;
+        fs::rename(&tmp_path, path)
+    })();
+
+    if let Err(e) = result {
+        let _ = fs::remove_file(&tmp_path);
+        return Err(e.to_string());
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_write_atomic_replaces_file_without_leftovers() {
+        let dir = std::env::temp_dir().join(format!("vantage-persist-test-{}", std::process::id()));
+        let path = dir.join("settings.json");
+
+        write_atomic(&path, "{\"a\": 1}").unwrap();
+        write_atomic(&path, "{\"a\": 2}").unwrap();
+
+        assert_eq!(fs::read_to_string(&path).unwrap(), "{\"a\": 2}");
+        // Only the target and its (empty) lock file remain
+        let mut entries: Vec<String> = fs::read_dir(&dir).unwrap()
+            .map(|e| e.unwrap().file_name().to_string_lossy().into_owned())
+            .collect();
+        entries.sort();
+        assert_eq!(entries, [".settings.json.lock", "settings.json"]);
+
+        // Another process holding the lock makes the writer wait
+        let lock = fs::File::open(dir.join(".settings.json.lock")).unwrap();
+        lock.lock_exclusive().unwrap();
+        let writer = std::thread::spawn({
+            let path = path.clone();
+            move || write_atomic(&path, "{\"a\": 3}").unwrap()
+        });
+        std::thread::sleep(std::time::Duration::from_millis(100));
+        assert_eq!(fs::read_to_string(&path).unwrap(), "{\"a\": 2}");
+        lock.unlock().unwrap();
+        writer.join().unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "{\"a\": 3}");
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+    async fn test_blocking_runs_inside_and_outside_runtime() {
+        let other = tokio::spawn(async { 1 });
+        assert_eq!(blocking(|| 2), 2);
+        assert_eq!(other.await.unwrap(), 1);
+        assert_eq!(std::thread::spawn(|| blocking(|| 3)).join().unwrap(), 3);
+    }
+}
@@ -4,11 +4,11 @@ use cpal::traits::{DeviceTrait, HostTrait, StreamTrait};
 use futures_util::{SinkExt, StreamExt};
 use serde::{Deserialize, Serialize};
 use std::sync::{
-    atomic::{AtomicBool, Ordering},
+    atomic::{AtomicBool, AtomicU64, Ordering},
     Arc, Mutex,
 };
 use tokio::sync::mpsc;
-use tokio_tungstenite::{connect_async, tungstenite::Message};
+use tokio_tungstenite::tungstenite::Message;
 
 const ASSEMBLYAI_REALTIME_URL: &str = "wss://api.assemblyai.com/v2/realtime/ws";
 
@@ -23,7 +23,7 @@ struct CreateRealtimeTokenResponse {
 }
 
 async fn get_temporary_token(api_key: &str) -> Result<String> {
-    let client = reqwest::Client::new();
+    let client = crate::net::client();
     let response = client
         .post("https://api.assemblyai.com/v2/realtime/token")
         .header("Authorization", api_key)
@@ -60,6 +60,7 @@ pub enum RealtimeMessage {
 pub struct RealtimeTranscriber {
     is_running: Arc<AtomicBool>,
     transcript_sender: mpsc::Sender<String>,
+    dropped_chunks: Arc<AtomicU64>,
 }
 
 impl RealtimeTranscriber {
@@ -67,9 +68,17 @@ impl RealtimeTranscriber {
         Self {
             is_running: Arc::new(AtomicBool::new(false)),
             transcript_sender,
+            dropped_chunks: Arc::new(AtomicU64::new(0)),
         }
     }
 
+    /// Count dropped audio chunks in a shared counter (`AppState::dropped_audio_chunks`,
+    /// watched by `spawn_audio_drop_monitor` in lib.rs)
+    pub fn with_dropped_chunk_counter(mut self, dropped_chunks: Arc<AtomicU64>) -> Self {
+        self.dropped_chunks = dropped_chunks;
+        self
+    }
+
     pub async fn start(&self, api_key: &str) -> Result<()> {
         if self.is_running.load(Ordering::SeqCst) {
             return Err(anyhow!("Already running"));
@@ -85,26 +94,26 @@ impl RealtimeTranscriber {
         let config = device.default_input_config()?;
         let sample_rate = config.sample_rate().0;
 
-        eprintln!("Connecting to AssemblyAI with sample_rate: {}", sample_rate);
+        tracing::info!("Connecting to AssemblyAI with sample_rate: {}", sample_rate);
 
         // Get temporary token for real-time API
-        eprintln!("Getting temporary token...");
+        tracing::info!("Getting temporary token...");
         let temp_token = get_temporary_token(api_key).await?;
-        eprintln!("Got temporary token");
+        tracing::info!("Got temporary token");
 
         // Connect to AssemblyAI WebSocket with temporary token
         let url = format!("{}?sample_rate={}&token={}", ASSEMBLYAI_REALTIME_URL, sample_rate, temp_token);
-        eprintln!("Connecting to WebSocket...");
-        let (ws_stream, _) = connect_async(&url).await.map_err(|e| {
-            eprintln!("WebSocket connection failed: {}", e);
+        tracing::info!("Connecting to WebSocket...");
+        let (ws_stream, _) = crate::net::connect_websocket(url.as_str()).await.map_err(|e| {
+            tracing::warn!("WebSocket connection failed: {}", e);
             anyhow!("WebSocket connection failed: {}", e)
         })?;
 
-        eprintln!("Connected to AssemblyAI WebSocket!");
+        tracing::info!("Connected to AssemblyAI WebSocket!");
         let (mut write, mut read) = ws_stream.split();
 
-        // Channel for audio data
-        let (audio_tx, mut audio_rx) = mpsc::channel::<Vec<u8>>(100);
+        // Queue for audio data; drops the oldest chunk rather than blocking capture
+        let (audio_tx, mut audio_rx) = crate::audio_queue::channel(100, self.dropped_chunks.clone());
         let is_running = self.is_running.clone();
         let transcript_sender = self.transcript_sender.clone();
 
@@ -115,82 +124,40 @@ impl RealtimeTranscriber {
         std::thread::spawn(move || {
             let buffer: Arc<Mutex<Vec<u8>>> = Arc::new(Mutex::new(Vec::new()));
             let buffer_clone = buffer.clone();
-            let audio_tx_f32 = audio_tx.clone();
 
             // Calculate buffer size for ~250ms of audio
             let buffer_size = (sample_rate_for_buffer as usize / 4) * 2; // 250ms worth of 16-bit samples
 
-            let err_fn = |err| eprintln!("Audio stream error: {}", err);
-
-            let stream_result = match config.sample_format() {
-                cpal::SampleFormat::F32 => {
-                    let buffer_clone_inner = buffer_clone.clone();
-                    let audio_tx_inner = audio_tx_f32.clone();
-                    device.build_input_stream(
-                        &config.into(),
-                        move |data: &[f32], _: &cpal::InputCallbackInfo| {
-                            // Convert f32 to i16 bytes (little endian)
-                            let bytes: Vec<u8> = data.iter()
-                                .flat_map(|&s| {
-                                    let sample_i16 = (s * 32767.0) as i16;
-                                    sample_i16.to_le_bytes().to_vec()
-                                })
-                                .collect();
-
-                            if let Ok(mut buf) = buffer_clone_inner.lock() {
-                                buf.extend(bytes);
-                                if buf.len() >= buffer_size {
-                                    let chunk: Vec<u8> = buf.drain(..).collect();
-                                    let _ = audio_tx_inner.blocking_send(chunk);
-                                }
-                            }
-                        },
-                        err_fn,
-                        None,
-                    )
-                },
-                cpal::SampleFormat::I16 => {
-                    let buffer_clone_inner = buffer_clone.clone();
-                    let audio_tx_inner = audio_tx.clone();
-                    device.build_input_stream(
-                        &config.into(),
-                        move |data: &[i16], _: &cpal::InputCallbackInfo| {
-                            let bytes: Vec<u8> = data.iter()
-                                .flat_map(|&s| s.to_le_bytes().to_vec())
-                                .collect();
-
-                            if let Ok(mut buf) = buffer_clone_inner.lock() {
-                                buf.extend(bytes);
-                                if buf.len() >= buffer_size {
-                                    let chunk: Vec<u8> = buf.drain(..).collect();
-                                    let _ = audio_tx_inner.blocking_send(chunk);
-                                }
-                            }
-                        },
-                        err_fn,
-                        None,
-                    )
+            let stream_result = crate::audio::build_pcm16_input_stream(
+                &device,
+                &config,
+                move |data: &[i16]| {
+                    let bytes: Vec<u8> = data.iter().flat_map(|&s| s.to_le_bytes()).collect();
+
+                    if let Ok(mut buf) = buffer_clone.lock() {
+                        buf.extend(bytes);
+                        if buf.len() >= buffer_size {
+                            let chunk: Vec<u8> = buf.drain(..).collect();
+                            audio_tx.send(chunk);
+                        }
+                    }
                 },
-                _ => {
-                    eprintln!("Unsupported sample format");
-                    return;
-                }
-            };
+            );
 
             match stream_result {
                 Ok(stream) => {
                     if let Err(e) = stream.play() {
-                        eprintln!("Failed to play stream: {}", e);
+                        tracing::warn!("Failed to play stream: {}", e);
                         return;
                     }
-                    eprintln!("Audio capture started!");
+                    tracing::info!("Audio capture started!");
                     while is_running_audio.load(Ordering::SeqCst) {
                         std::thread::sleep(std::time::Duration::from_millis(10));
                     }
-                    eprintln!("Audio capture stopped");
+                    tracing::info!("Audio capture stopped");
                 }
                 Err(e) => {
-                    eprintln!("Failed to build audio stream: {}", e);
+                    tracing::warn!("Failed to build audio stream: {}", e);
                 }
             }
         });
@@ -198,14 +165,14 @@ impl RealtimeTranscriber {
         // Task to send audio to WebSocket
         let is_running_send = is_running.clone();
         tokio::spawn(async move {
-            eprintln!("Audio sender task started");
+            tracing::info!("Audio sender task started");
             while is_running_send.load(Ordering::SeqCst) {
                 match audio_rx.recv().await {
                     Some(bytes) => {
                         let encoded = BASE64.encode(&bytes);
                         let msg = serde_json::json!({ "audio_data": encoded });
                         if let Err(e) = write.send(Message::Text(msg.to_string())).await {
-                            eprintln!("Failed to send audio: {}", e);
+                            tracing::warn!("Failed to send audio: {}", e);
                             break;
                         }
                     }
@@ -214,36 +181,36 @@ impl RealtimeTranscriber {
             }
             // Send terminate message
             let _ = write.send(Message::Text(r#"{"terminate_session": true}"#.to_string())).await;
-            eprintln!("Audio sender task ended");
+            tracing::info!("Audio sender task ended");
         });
 
         // Task to receive transcripts
         let is_running_recv = is_running.clone();
         tokio::spawn(async move {
-            eprintln!("Transcript receiver task started");
+            tracing::info!("Transcript receiver task started");
             while is_running_recv.load(Ordering::SeqCst) {
                 match read.next().await {
                     Some(Ok(Message::Text(text))) => {
-                        eprintln!("Received from AssemblyAI: {}", &text[..text.len().min(100)]);
+                        tracing::info!("Received from AssemblyAI: {}", &text[..text.len().min(100)]);
                         match serde_json::from_str::<RealtimeMessage>(&text) {
                             Ok(msg) => {
                                 match msg {
                                     RealtimeMessage::SessionBegins { session_id } => {
-                                        eprintln!("Session started: {}", session_id);
+                                        tracing::info!("Session started: {}", session_id);
                                     }
                                     RealtimeMessage::FinalTranscript { text } => {
                                         if !text.is_empty() {
-                                            eprintln!("Final transcript: {}", text);
+                                            tracing::info!("Final transcript: {}", text);
                                             let _ = transcript_sender.send(text).await;
                                         }
                                     }
                                     RealtimeMessage::PartialTranscript { text } => {
                                         if !text.is_empty() {
-                                            eprintln!("Partial: {}", text);
+                                            tracing::info!("Partial: {}", text);
                                         }
                                     }
                                     RealtimeMessage::SessionTerminated => {
-                                        eprintln!("Session terminated");
+                                        tracing::info!("Session terminated");
                                         break;
                                     }
                                     RealtimeMessage::Unknown => {
@@ -252,22 +219,22 @@ impl RealtimeTranscriber {
                                 }
                             }
                             Err(e) => {
-                                eprintln!("Failed to parse message: {}", e);
+                                tracing::warn!("Failed to parse message: {}", e);
                             }
                         }
                     }
                     Some(Ok(_)) => {}
                     Some(Err(e)) => {
-                        eprintln!("WebSocket error: {}", e);
+                        tracing::warn!("WebSocket error: {}", e);
                         break;
                     }
                     None => {
-                        eprintln!("WebSocket closed");
+                        tracing::info!("WebSocket closed");
                         break;
                     }
                 }
             }
-            eprintln!("Transcript receiver task ended");
+            tracing::info!("Transcript receiver task ended");
         });
 
         Ok(())
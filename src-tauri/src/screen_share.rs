@@ -1,13 +1,76 @@
 //! Screen Share Exclusion Module
 //!
 //! This module provides functionality to hide the app window during screen sharing.
-//! - macOS: Uses NSWindow.sharingType = .none (macOS 12.0+)
+//! - macOS: Uses NSWindow.sharingType = .none (macOS 12.0+, checked at runtime)
 //! - Windows: Uses SetWindowDisplayAffinity API
 //! - Linux: Not supported natively
 
+use serde::Serialize;
 #[allow(unused_imports)]
 use tauri::Window;
 
+/// Outcome of a screen share exclusion request, so the UI can disable the
+/// toggle on unsupported platforms instead of showing an error
+#[derive(Debug, Clone, Serialize)]
+pub struct ExclusionResult {
+    /// The window is now in the requested state
+    pub applied: bool,
+    pub supported: bool,
+    pub platform_message: String,
+}
+
+/// First macOS release where `NSWindowSharingNone` hides the window from capture
+const MIN_MACOS_VERSION: (i64, i64) = (12, 0);
+
+/// Mirrors `NSOperatingSystemVersion`
+#[cfg(target_os = "macos")]
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+struct NSOperatingSystemVersion {
+    major: i64,
+    minor: i64,
+    patch: i64,
+}
+
+/// Running macOS version as (major, minor, patch), read from NSProcessInfo
+#[cfg(target_os = "macos")]
+fn macos_version() -> (i64, i64, i64) {
+    use objc::{class, msg_send, sel, sel_impl};
+
+    unsafe {
+        let process_info: cocoa::base::id = msg_send![class!(NSProcessInfo), processInfo];
+        let version: NSOperatingSystemVersion = msg_send![process_info, operatingSystemVersion];
+        (version.major, version.minor, version.patch)
+    }
+}
+
+#[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+fn macos_version_supported(version: (i64, i64, i64)) -> bool {
+    (version.0, version.1) >= MIN_MACOS_VERSION
+}
+
+#[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+fn format_macos_version(version: (i64, i64, i64)) -> String {
+    if version.2 == 0 {
+        format!("{}.{}", version.0, version.1)
+    } else {
+        format!("{}.{}.{}", version.0, version.1, version.2)
+    }
+}
+
+#[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+fn macos_platform_info(version: (i64, i64, i64)) -> String {
+    let running = format_macos_version(version);
+    if macos_version_supported(version) {
+        format!("macOS {}: Supported", running)
+    } else {
+        format!(
+            "macOS {}: Not supported (requires macOS {}.{}+)",
+            running, MIN_MACOS_VERSION.0, MIN_MACOS_VERSION.1
+        )
+    }
+}
+
 /// Sets whether the window should be excluded from screen capture/sharing
 ///
 /// # Arguments
@@ -22,6 +85,17 @@ pub fn set_screen_share_exclusion(window: &Window, exclude: bool) -> Result<(),
     use objc::runtime::YES;
     use objc::{msg_send, sel, sel_impl};
 
+    // Older releases accept setSharingType: but still show the window in captures
+    let version = macos_version();
+    if !macos_version_supported(version) {
+        return Err(format!(
+            "Screen share exclusion requires macOS {}.{} or later (running {})",
+            MIN_MACOS_VERSION.0,
+            MIN_MACOS_VERSION.1,
+            format_macos_version(version)
+        ));
+    }
+
     // Get the native NSWindow handle
     let ns_window = window
         .ns_window()
@@ -37,7 +111,7 @@ pub fn set_screen_share_exclusion(window: &Window, exclude: bool) -> Result<(),
         let _: () = msg_send![ns_window as cocoa::base::id, setSharingType: sharing_type];
     }
 
-    eprintln!("Screen share exclusion set to: {}", exclude);
+    tracing::info!("Screen share exclusion set to: {}", exclude);
     Ok(())
 }
 
@@ -64,7 +138,7 @@ pub fn set_screen_share_exclusion(window: &Window, exclude: bool) -> Result<(),
             .map_err(|e| format!("SetWindowDisplayAffinity failed: {}", e))?;
     }
 
-    eprintln!("Screen share exclusion set to: {}", exclude);
+    tracing::info!("Screen share exclusion set to: {}", exclude);
     Ok(())
 }
 
@@ -73,7 +147,7 @@ pub fn set_screen_share_exclusion(_window: &Window, exclude: bool) -> Result<(),
     // Linux doesn't have native support for this feature
     // Some Wayland compositors might support it in the future
     if exclude {
-        eprintln!("Warning: Screen share exclusion is not supported on Linux");
+        tracing::info!("Warning: Screen share exclusion is not supported on Linux");
         Err("Screen share exclusion is not supported on Linux".to_string())
     } else {
         Ok(())
@@ -85,30 +159,73 @@ pub fn set_screen_share_exclusion(_window: &Window, _exclude: bool) -> Result<()
     Err("Screen share exclusion is not supported on this platform".to_string())
 }
 
+/// Apply screen share exclusion, reporting unsupported platforms as a result rather than an error.
+/// Errors are only returned when a supported platform fails to apply the setting.
+pub fn apply_exclusion(window: &Window, exclude: bool) -> Result<ExclusionResult, String> {
+    if !is_supported() {
+        return Ok(ExclusionResult {
+            // Nothing is ever excluded here, so "visible" is always already in effect
+            applied: !exclude,
+            supported: false,
+            platform_message: get_platform_info(),
+        });
+    }
+
+    set_screen_share_exclusion(window, exclude)?;
+    Ok(ExclusionResult {
+        applied: true,
+        supported: true,
+        platform_message: get_platform_info(),
+    })
+}
+
 /// Check if screen share exclusion is supported on this platform
 pub fn is_supported() -> bool {
-    cfg!(any(target_os = "macos", target_os = "windows"))
+    #[cfg(target_os = "macos")]
+    {
+        macos_version_supported(macos_version())
+    }
+
+    #[cfg(not(target_os = "macos"))]
+    {
+        cfg!(target_os = "windows")
+    }
 }
 
 /// Get platform-specific information about screen share exclusion
-pub fn get_platform_info() -> &'static str {
+pub fn get_platform_info() -> String {
     #[cfg(target_os = "macos")]
     {
-        return "macOS: Supported (requires macOS 12.0+)";
+        return macos_platform_info(macos_version());
     }
 
     #[cfg(target_os = "windows")]
     {
-        return "Windows: Supported (requires Windows 10 version 2004+)";
+        return "Windows: Supported (requires Windows 10 version 2004+)".to_string();
     }
 
     #[cfg(target_os = "linux")]
     {
-        return "Linux: Not supported";
+        return "Linux: Not supported".to_string();
     }
 
     #[cfg(not(any(target_os = "macos", target_os = "windows", target_os = "linux")))]
     {
-        return "Unknown platform: Not supported";
+        return "Unknown platform: Not supported".to_string();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_macos_platform_info_reports_running_version() {
+        assert_eq!(macos_platform_info((14, 2, 1)), "macOS 14.2.1: Supported");
+        assert_eq!(macos_platform_info((12, 0, 0)), "macOS 12.0: Supported");
+        assert_eq!(
+            macos_platform_info((11, 7, 0)),
+            "macOS 11.7: Not supported (requires macOS 12.0+)"
+        );
     }
 }
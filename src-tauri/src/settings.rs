@@ -2,11 +2,53 @@ use serde::{Deserialize, Serialize};
 use std::fs;
 use std::path::PathBuf;
 
+use crate::assemblyai::TranscriptionOptions;
+use crate::endpoints::EndpointSettings;
+use crate::persist;
+use crate::groq::GenerationParams;
+
 /// Environment variable names for API keys
 /// These take priority over settings file
 pub const ENV_GROQ_API_KEY: &str = "VANTAGE_GROQ_API_KEY";
 pub const ENV_DEEPGRAM_API_KEY: &str = "VANTAGE_DEEPGRAM_API_KEY";
 pub const ENV_ASSEMBLYAI_API_KEY: &str = "VANTAGE_ASSEMBLYAI_API_KEY";
+pub const ENV_OPENAI_API_KEY: &str = "VANTAGE_OPENAI_API_KEY";
+/// LLM model and provider ("groq" or "openai"), for scripted/CI and Docker runs without the UI
+pub const ENV_MODEL: &str = "VANTAGE_MODEL";
+pub const ENV_LLM_PROVIDER: &str = "VANTAGE_LLM_PROVIDER";
+
+/// Alternative names accepted for the same overrides, checked after the VANTAGE_* name
+const ENV_ALIASES: &[(&str, &str)] = &[
+    (ENV_GROQ_API_KEY, "MEETBETTER_GROQ_KEY"),
+    (ENV_DEEPGRAM_API_KEY, "MEETBETTER_DEEPGRAM_KEY"),
+    (ENV_ASSEMBLYAI_API_KEY, "MEETBETTER_ASSEMBLYAI_KEY"),
+    (ENV_MODEL, "MEETBETTER_MODEL"),
+    (ENV_LLM_PROVIDER, "MEETBETTER_PROVIDER"),
+];
+
+/// Batch live segments shorter than this many characters are dropped unless a minimum is
+/// configured, filtering fragments Whisper hallucinates on silence ("you", ".")
+pub const DEFAULT_BATCH_MIN_SEGMENT_LENGTH: usize = 6;
+/// Streaming keeps every final unless a minimum is configured, so "Yes." or "No." survive
+pub const DEFAULT_STREAMING_MIN_SEGMENT_LENGTH: usize = 0;
+
+/// Interim results are coalesced to at most one per this many milliseconds per channel
+pub const DEFAULT_INTERIM_DEBOUNCE_MS: u64 = 250;
+
+/// Batch live transcription reports "no speech" after this many silent seconds
+pub const DEFAULT_NO_SPEECH_NOTICE_SECS: u64 = 30;
+
+/// Words and phrases counted as fillers in speaking-style reports
+pub const DEFAULT_FILLER_WORDS: &[&str] = &["um", "uh", "like", "you know"];
+
+/// System prompt for summaries, replies and other generation, unless the user sets their own
+pub const DEFAULT_ASSISTANT_PERSONA: &str = "You are a helpful meeting assistant. Be concise and professional.";
+
+/// Bounds for the batch live loop, so we neither hammer the API nor send tiny clips
+pub const MIN_BATCH_CHECK_INTERVAL_MS: u64 = 1000;
+pub const MAX_BATCH_CHECK_INTERVAL_MS: u64 = 30_000;
+pub const MIN_BATCH_AUDIO_BYTES: u64 = 16_000;
+pub const MAX_BATCH_AUDIO_BYTES: u64 = 2_000_000;
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct AppSettings {
@@ -17,18 +59,277 @@ pub struct AppSettings {
     #[serde(default)]
     pub deepgram_api_key: String,
     #[serde(default)]
+    pub openai_api_key: String,
+    #[serde(default)]
     pub selected_model: String,
     #[serde(default)]
     pub transcription_provider: String,
+    /// Chat completion provider for summaries and replies ("groq" or "openai")
+    #[serde(default)]
+    pub llm_provider: String,
     #[serde(default)]
     pub meeting_context: String,
     #[serde(default)]
     pub google_client_id: String,
     #[serde(default)]
     pub google_client_secret: String,
+    #[serde(default)]
+    pub generation: GenerationSettings,
+    /// System prompt for the LLM assistant (empty = default persona)
+    #[serde(default)]
+    pub assistant_persona: String,
+    /// Fall back to a local Ollama model when Groq is unreachable
+    #[serde(default)]
+    pub llm_fallback_enabled: bool,
+    /// Ollama model for fallback (empty = first installed model)
+    #[serde(default)]
+    pub ollama_model: String,
+    /// AssemblyAI speaker labels and poll interval
+    #[serde(default)]
+    pub assemblyai: TranscriptionOptions,
+    /// Words/phrases that trigger a keyword alert during live transcription
+    #[serde(default)]
+    pub alert_keywords: Vec<String>,
+    /// Filler words/phrases counted by the speaking-style report (None = default list)
+    #[serde(default)]
+    pub filler_words: Option<Vec<String>>,
+    /// Hide the window from screen sharing while a meeting app is running
+    #[serde(default)]
+    pub auto_hide_on_share: bool,
+    /// Minimum characters for a live transcript segment to be kept (None = per-path default)
+    #[serde(default)]
+    pub min_segment_length: Option<usize>,
+    /// Minimum time between interim transcript updates per channel (None = default)
+    #[serde(default)]
+    pub interim_debounce_ms: Option<u64>,
+    /// Keep interim (not yet final) results in the transcript too; off = finals only
+    #[serde(default)]
+    pub save_interim: bool,
+    /// Live transcript segments kept in memory before older ones move to disk (0 = no cap)
+    #[serde(default)]
+    pub max_live_segments: usize,
+    /// Recording/transcription is stopped automatically after this many minutes (None = unlimited)
+    #[serde(default)]
+    pub max_recording_minutes: Option<u32>,
+    /// WAV bit depth, channels and sample rate for new recordings
+    #[serde(default)]
+    pub recording: crate::audio::RecordingConfig,
+    /// Only write recording audio while the input is above a level threshold
+    #[serde(default)]
+    pub voice_activation: crate::audio::VoiceActivation,
+    /// Regenerate reply suggestions this often while transcribing (0 = off).
+    /// Only used by `ReplyTrigger::Always`; focus mode fires on participant finals instead.
+    #[serde(default)]
+    pub auto_refresh_replies_secs: u64,
+    /// Whether reply suggestions are generated on every refresh or only after a participant speaks
+    #[serde(default)]
+    pub reply_trigger: ReplyTrigger,
+    /// Re-run low-confidence Groq transcriptions through AssemblyAI
+    #[serde(default)]
+    pub hybrid_transcription: HybridTranscription,
+    /// Steps run automatically when live transcription stops
+    #[serde(default)]
+    pub post_meeting: PostMeetingActions,
+    /// Check interval and minimum new audio for batch live transcription (Groq, OpenAI, ...)
+    #[serde(default)]
+    pub batch_live: BatchLiveOptions,
+    /// Seconds without speech before `no-speech-detected` is emitted (None = default, 0 = never)
+    #[serde(default)]
+    pub no_speech_notice_secs: Option<u64>,
+    /// Local Whisper model size for offline transcription (empty = default)
+    #[serde(default)]
+    pub whisper_model: String,
+    /// API base URL overrides for proxies and compatible gateways
+    #[serde(default)]
+    pub endpoints: EndpointSettings,
+    /// Proxy for all outbound connections (empty = use HTTP(S)_PROXY/ALL_PROXY from the environment)
+    #[serde(default)]
+    pub proxy_url: String,
+}
+
+/// LLM sampling parameters per task type
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GenerationSettings {
+    /// Summaries, chapters and other structured extraction
+    #[serde(default = "GenerationParams::extraction")]
+    pub extraction: GenerationParams,
+    /// Reply suggestions
+    #[serde(default = "GenerationParams::replies")]
+    pub replies: GenerationParams,
+}
+
+impl Default for GenerationSettings {
+    fn default() -> Self {
+        Self {
+            extraction: GenerationParams::extraction(),
+            replies: GenerationParams::replies(),
+        }
+    }
+}
+
+/// How often the batch live loop checks the recording, and how much new audio it needs
+/// before sending it for transcription. Shorter intervals lower latency; larger minimums
+/// mean fewer API calls.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub struct BatchLiveOptions {
+    #[serde(default = "default_batch_check_interval_ms")]
+    pub check_interval_ms: u64,
+    #[serde(default = "default_batch_min_audio_bytes")]
+    pub min_audio_bytes: u64,
+}
+
+fn default_batch_check_interval_ms() -> u64 {
+    4000
+}
+
+fn default_batch_min_audio_bytes() -> u64 {
+    48_000
+}
+
+impl Default for BatchLiveOptions {
+    fn default() -> Self {
+        Self {
+            check_interval_ms: default_batch_check_interval_ms(),
+            min_audio_bytes: default_batch_min_audio_bytes(),
+        }
+    }
+}
+
+impl BatchLiveOptions {
+    pub fn validate(&self) -> Result<(), String> {
+        if !(MIN_BATCH_CHECK_INTERVAL_MS..=MAX_BATCH_CHECK_INTERVAL_MS).contains(&self.check_interval_ms) {
+            return Err(format!(
+                "Check interval must be between {} and {} ms",
+                MIN_BATCH_CHECK_INTERVAL_MS, MAX_BATCH_CHECK_INTERVAL_MS
+            ));
+        }
+        if !(MIN_BATCH_AUDIO_BYTES..=MAX_BATCH_AUDIO_BYTES).contains(&self.min_audio_bytes) {
+            return Err(format!(
+                "Minimum audio must be between {} and {} bytes",
+                MIN_BATCH_AUDIO_BYTES, MAX_BATCH_AUDIO_BYTES
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// When automatic reply refreshes fire
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum ReplyTrigger {
+    /// Every refresh interval, whoever is talking
+    #[default]
+    Always,
+    /// Once a participant finishes a sentence and your microphone is quiet, regardless of
+    /// the refresh interval
+    OnParticipantFinal,
+}
+
+impl ReplyTrigger {
+    pub fn parse(mode: &str) -> Result<Self, String> {
+        match mode.trim().to_lowercase().replace('-', "_").as_str() {
+            "always" => Ok(ReplyTrigger::Always),
+            "on_participant_final" => Ok(ReplyTrigger::OnParticipantFinal),
+            _ => Err(format!("Unknown reply trigger: {} (expected always or on-participant-final)", mode)),
+        }
+    }
+}
+
+/// Hybrid batch transcription: Groq first, escalating to AssemblyAI when Groq's
+/// confidence is below `threshold` (needs an AssemblyAI key)
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub struct HybridTranscription {
+    #[serde(default)]
+    pub enabled: bool,
+    #[serde(default = "default_hybrid_threshold")]
+    pub threshold: f32,
+}
+
+fn default_hybrid_threshold() -> f32 {
+    0.6
+}
+
+impl Default for HybridTranscription {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            threshold: default_hybrid_threshold(),
+        }
+    }
+}
+
+impl HybridTranscription {
+    pub fn validate(&self) -> Result<(), String> {
+        if !(0.0..=1.0).contains(&self.threshold) {
+            return Err("Confidence threshold must be between 0 and 1".to_string());
+        }
+        Ok(())
+    }
+}
+
+/// Post-meeting pipeline, run when live transcription stops
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
+pub struct PostMeetingActions {
+    #[serde(default)]
+    pub enabled: bool,
+    /// Generate a structured summary (key points, action items, decisions)
+    #[serde(default)]
+    pub generate_summary: bool,
+    /// Save the meeting to the database
+    #[serde(default)]
+    pub save_meeting: bool,
+    /// POST the processed meeting as JSON to this URL (empty = no webhook)
+    #[serde(default)]
+    pub webhook_url: String,
+}
+
+impl PostMeetingActions {
+    pub fn validate(&self) -> Result<(), String> {
+        let url = self.webhook_url.trim();
+        if !url.is_empty() && !url.starts_with("http://") && !url.starts_with("https://") {
+            return Err("Webhook URL must start with http:// or https://".to_string());
+        }
+        Ok(())
+    }
 }
 
 impl AppSettings {
+    /// Interim debounce interval in milliseconds
+    pub fn interim_debounce_ms(&self) -> u64 {
+        self.interim_debounce_ms.unwrap_or(DEFAULT_INTERIM_DEBOUNCE_MS)
+    }
+
+    /// Silence before a "no speech" notice, in seconds
+    pub fn no_speech_notice_secs(&self) -> u64 {
+        self.no_speech_notice_secs.unwrap_or(DEFAULT_NO_SPEECH_NOTICE_SECS)
+    }
+
+    /// Filler words/phrases for speaking-style reports
+    pub fn filler_words(&self) -> Vec<String> {
+        match &self.filler_words {
+            Some(words) => words.clone(),
+            None => DEFAULT_FILLER_WORDS.iter().map(|w| w.to_string()).collect(),
+        }
+    }
+
+    /// System prompt for the LLM assistant
+    pub fn assistant_persona(&self) -> &str {
+        if self.assistant_persona.trim().is_empty() {
+            DEFAULT_ASSISTANT_PERSONA
+        } else {
+            &self.assistant_persona
+        }
+    }
+
+    /// Local Whisper model size
+    pub fn whisper_model(&self) -> String {
+        if self.whisper_model.is_empty() {
+            crate::whisper_local::DEFAULT_MODEL_SIZE.to_string()
+        } else {
+            self.whisper_model.clone()
+        }
+    }
+
     /// Get the path to the settings file
     fn get_settings_path() -> Option<PathBuf> {
         dirs::config_dir().map(|mut path| {
@@ -52,12 +353,12 @@ impl AppSettings {
     /// Load settings from config file only
     fn load_from_file() -> Self {
         let Some(path) = Self::get_settings_path() else {
-            eprintln!("Could not determine config directory");
+            tracing::warn!("Could not determine config directory");
             return Self::default();
         };
 
         if !path.exists() {
-            eprintln!("Settings file does not exist, using defaults");
+            tracing::info!("Settings file does not exist, using defaults");
             return Self::default();
         }
 
@@ -65,46 +366,63 @@ impl AppSettings {
             Ok(content) => {
                 match serde_json::from_str(&content) {
                     Ok(settings) => {
-                        eprintln!("Settings loaded from {:?}", path);
+                        tracing::info!("Settings loaded from {:?}", path);
                         settings
                     }
                     Err(e) => {
-                        eprintln!("Failed to parse settings: {}", e);
+                        tracing::warn!("Failed to parse settings: {}", e);
                         Self::default()
                     }
                 }
             }
             Err(e) => {
-                eprintln!("Failed to read settings file: {}", e);
+                tracing::warn!("Failed to read settings file: {}", e);
                 Self::default()
             }
         }
     }
 
-    /// Apply environment variable overrides for API keys
-    /// Environment variables take priority over config file
+    /// Apply environment variable overrides.
+    /// Precedence: environment > settings file > default.
     fn apply_env_overrides(&mut self) {
-        // Groq API key
-        if let Ok(key) = std::env::var(ENV_GROQ_API_KEY) {
-            if !key.is_empty() {
-                eprintln!("Using Groq API key from environment variable");
-                self.groq_api_key = key;
+        self.apply_overrides(|name| std::env::var(name).ok());
+    }
+
+    fn apply_overrides(&mut self, lookup: impl Fn(&str) -> Option<String>) {
+        let value = |name: &str| {
+            let alias = ENV_ALIASES.iter().find(|(n, _)| *n == name).map(|(_, alias)| *alias);
+            std::iter::once(name)
+                .chain(alias)
+                .filter_map(&lookup)
+                .map(|v| v.trim().to_string())
+                .find(|v| !v.is_empty())
+        };
+
+        let keys = [
+            (ENV_GROQ_API_KEY, "Groq", &mut self.groq_api_key),
+            (ENV_DEEPGRAM_API_KEY, "Deepgram", &mut self.deepgram_api_key),
+            (ENV_ASSEMBLYAI_API_KEY, "AssemblyAI", &mut self.assemblyai_api_key),
+            (ENV_OPENAI_API_KEY, "OpenAI", &mut self.openai_api_key),
+        ];
+        for (name, provider, field) in keys {
+            if let Some(key) = value(name) {
+                tracing::info!("Using {} API key from environment variable", provider);
+                *field = key;
             }
         }
 
-        // Deepgram API key
-        if let Ok(key) = std::env::var(ENV_DEEPGRAM_API_KEY) {
-            if !key.is_empty() {
-                eprintln!("Using Deepgram API key from environment variable");
-                self.deepgram_api_key = key;
-            }
+        if let Some(model) = value(ENV_MODEL) {
+            tracing::info!("Using model {} from environment variable", model);
+            self.selected_model = model;
         }
 
-        // AssemblyAI API key
-        if let Ok(key) = std::env::var(ENV_ASSEMBLYAI_API_KEY) {
-            if !key.is_empty() {
-                eprintln!("Using AssemblyAI API key from environment variable");
-                self.assemblyai_api_key = key;
+        if let Some(provider) = value(ENV_LLM_PROVIDER) {
+            let provider = provider.to_lowercase();
+            if matches!(provider.as_str(), "groq" | "openai") {
+                tracing::info!("Using LLM provider {} from environment variable", provider);
+                self.llm_provider = provider;
+            } else {
+                tracing::warn!("Ignoring unknown LLM provider from environment: {}", provider);
             }
         }
     }
@@ -114,19 +432,66 @@ impl AppSettings {
         let path = Self::get_settings_path()
             .ok_or_else(|| "Could not determine config directory".to_string())?;
 
-        // Create parent directory if it doesn't exist
-        if let Some(parent) = path.parent() {
-            fs::create_dir_all(parent)
-                .map_err(|e| format!("Failed to create config directory: {}", e))?;
-        }
-
         let content = serde_json::to_string_pretty(self)
             .map_err(|e| format!("Failed to serialize settings: {}", e))?;
 
-        fs::write(&path, content)
+        persist::write_atomic(&path, &content)
             .map_err(|e| format!("Failed to write settings file: {}", e))?;
 
-        eprintln!("Settings saved to {:?}", path);
+        tracing::info!("Settings saved to {:?}", path);
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_env_overrides_take_priority() {
+        let env: std::collections::HashMap<&str, &str> = [
+            ("VANTAGE_GROQ_API_KEY", " "),
+            ("MEETBETTER_GROQ_KEY", "gsk_env"),
+            ("MEETBETTER_MODEL", "llama-3.3-70b-versatile"),
+            ("VANTAGE_LLM_PROVIDER", "Claude"),
+        ]
+        .into_iter()
+        .collect();
+        let mut settings = AppSettings {
+            groq_api_key: "gsk_saved".to_string(),
+            deepgram_api_key: "dg_saved".to_string(),
+            llm_provider: "openai".to_string(),
+            ..Default::default()
+        };
+
+        settings.apply_overrides(|name| env.get(name).map(|v| v.to_string()));
+
+        assert_eq!(settings.groq_api_key, "gsk_env");
+        assert_eq!(settings.deepgram_api_key, "dg_saved");
+        assert_eq!(settings.selected_model, "llama-3.3-70b-versatile");
+        // Unknown providers are ignored rather than breaking generation
+        assert_eq!(settings.llm_provider, "openai");
+    }
+
+    #[test]
+    fn test_post_meeting_webhook_url() {
+        let mut actions = PostMeetingActions::default();
+        assert!(actions.validate().is_ok());
+        actions.webhook_url = "https://hooks.example.com/meeting".to_string();
+        assert!(actions.validate().is_ok());
+        actions.webhook_url = "hooks.example.com".to_string();
+        assert!(actions.validate().is_err());
+    }
+
+    #[test]
+    fn test_batch_live_options_bounds() {
+        assert!(BatchLiveOptions::default().validate().is_ok());
+        assert!(BatchLiveOptions { check_interval_ms: 2000, ..Default::default() }.validate().is_ok());
+        assert!(BatchLiveOptions { check_interval_ms: 100, ..Default::default() }.validate().is_err());
+        assert!(BatchLiveOptions { min_audio_bytes: 10_000_000, ..Default::default() }.validate().is_err());
+
+        let settings: AppSettings = serde_json::from_str(r#"{"batch_live": {"check_interval_ms": 2000}}"#).unwrap();
+        assert_eq!(settings.batch_live.check_interval_ms, 2000);
+        assert_eq!(settings.batch_live.min_audio_bytes, 48_000);
+    }
+}
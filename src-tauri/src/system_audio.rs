@@ -3,9 +3,10 @@
 
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{Arc, Mutex};
-use tokio::sync::mpsc;
 use anyhow::{anyhow, Result};
 
+use crate::audio_queue::AudioSender;
+
 #[cfg(target_os = "macos")]
 mod macos {
     /// Get the system audio loopback device if available
@@ -28,7 +29,7 @@ mod macos {
             for device in host.input_devices().ok()? {
                 if let Ok(name) = device.name() {
                     if name.contains(priority_name) {
-                        eprintln!("Found system audio device: {}", name);
+                        tracing::info!("Found system audio device: {}", name);
                         return Some(device);
                     }
                 }
@@ -68,6 +69,84 @@ pub struct SourcedAudioChunk {
     pub source: AudioSource,
 }
 
+/// Input config for a loopback device, chosen independently of the mic.
+/// Uses `preferred_rate` (the mic's rate) when the device supports it in a sample format
+/// `audio::build_f32_input_stream` handles, otherwise the device's default config; callers
+/// resample with `LinearResampler` to match the mic.
+pub fn system_input_config(device: &cpal::Device, preferred_rate: u32) -> Result<cpal::SupportedStreamConfig> {
+    use cpal::traits::DeviceTrait;
+
+    let matching = device.supported_input_configs()?.find(|c| {
+        matches!(c.sample_format(), cpal::SampleFormat::F32 | cpal::SampleFormat::I16 | cpal::SampleFormat::U16)
+            && c.min_sample_rate().0 <= preferred_rate
+            && c.max_sample_rate().0 >= preferred_rate
+    });
+
+    if let Some(range) = matching {
+        return Ok(range.with_sample_rate(cpal::SampleRate(preferred_rate)));
+    }
+
+    let default = device.default_input_config()?;
+    tracing::info!(
+        "System audio device doesn't support {} Hz, using {} Hz",
+        preferred_rate,
+        default.sample_rate().0
+    );
+    Ok(default)
+}
+
+/// Average interleaved frames down to a single channel
+pub fn downmix(data: &[f32], channels: usize) -> Vec<f32> {
+    if channels <= 1 {
+        return data.to_vec();
+    }
+    data.chunks(channels)
+        .map(|frame| frame.iter().sum::<f32>() / frame.len() as f32)
+        .collect()
+}
+
+/// Streaming linear-interpolation resampler for mono audio.
+/// Keeps the last input sample so consecutive callbacks join up without gaps.
+pub struct LinearResampler {
+    /// Input samples advanced per output sample
+    step: f64,
+    /// Position of the next output sample; 0 is the last sample of the previous chunk
+    pos: f64,
+    last: f32,
+}
+
+impl LinearResampler {
+    pub fn new(from_rate: u32, to_rate: u32) -> Self {
+        Self {
+            step: from_rate as f64 / to_rate.max(1) as f64,
+            pos: 0.0,
+            last: 0.0,
+        }
+    }
+
+    pub fn process(&mut self, input: &[f32]) -> Vec<f32> {
+        if self.step == 1.0 {
+            return input.to_vec();
+        }
+
+        let mut output = Vec::with_capacity((input.len() as f64 / self.step) as usize + 1);
+        while self.pos < input.len() as f64 {
+            let index = self.pos.floor() as usize;
+            let frac = (self.pos - index as f64) as f32;
+            let a = if index == 0 { self.last } else { input[index - 1] };
+            let b = input[index];
+            output.push(a + (b - a) * frac);
+            self.pos += self.step;
+        }
+
+        self.pos -= input.len() as f64;
+        if let Some(&last) = input.last() {
+            self.last = last;
+        }
+        output
+    }
+}
+
 /// Dual audio capturer that captures both microphone and system audio
 pub struct DualAudioCapturer {
     is_running: Arc<AtomicBool>,
@@ -78,10 +157,10 @@ impl DualAudioCapturer {
     pub fn new() -> Self {
         let has_system_audio = get_system_audio_device().is_some();
         if has_system_audio {
-            eprintln!("System audio capture available");
+            tracing::info!("System audio capture available");
         } else {
-            eprintln!("No system audio device found. Install BlackHole for full speaker separation.");
-            eprintln!("  Download: https://github.com/ExistentialAudio/BlackHole");
+            tracing::info!("No system audio device found. Install BlackHole for full speaker separation.");
+            tracing::info!("  Download: https://github.com/ExistentialAudio/BlackHole");
         }
 
         Self {
@@ -98,7 +177,7 @@ impl DualAudioCapturer {
     /// Audio is sent as interleaved stereo: left channel = mic, right channel = system
     pub fn start(
         &self,
-        audio_tx: mpsc::Sender<Vec<u8>>,
+        audio_tx: AudioSender,
         sample_rate: u32,
     ) -> Result<()> {
         use cpal::traits::{DeviceTrait, HostTrait, StreamTrait};
@@ -116,13 +195,13 @@ impl DualAudioCapturer {
             .default_input_device()
             .ok_or_else(|| anyhow!("No microphone found"))?;
 
-        eprintln!("Microphone: {:?}", mic_device.name());
+        tracing::info!("Microphone: {:?}", mic_device.name());
 
         // Get system audio device (if available)
         let system_device = get_system_audio_device();
 
         if let Some(ref dev) = system_device {
-            eprintln!("System audio: {:?}", dev.name());
+            tracing::info!("System audio: {:?}", dev.name());
         }
 
         let is_running = self.is_running.clone();
@@ -136,57 +215,50 @@ impl DualAudioCapturer {
             // Buffer size for ~100ms of audio
             let buffer_samples = (sample_rate as usize / 10) as usize;
 
-            // Build microphone stream
+            // Build microphone stream in the mic's own config, mixed down and resampled to mono
             let mic_buffer_clone = mic_buffer.clone();
-            let mic_config = cpal::StreamConfig {
-                channels: 1,
-                sample_rate: cpal::SampleRate(sample_rate),
-                buffer_size: cpal::BufferSize::Default,
-            };
-
-            let mic_stream = mic_device.build_input_stream(
-                &mic_config,
-                move |data: &[f32], _: &cpal::InputCallbackInfo| {
-                    let samples: Vec<i16> = data
+            let mic_stream = mic_device.default_input_config().map_err(anyhow::Error::from).and_then(|mic_config| {
+                let mic_channels = mic_config.channels() as usize;
+                let mut resampler = LinearResampler::new(mic_config.sample_rate().0, sample_rate);
+                crate::audio::build_f32_input_stream(&mic_device, &mic_config, move |data: &[f32]| {
+                    let samples: Vec<i16> = resampler
+                        .process(&downmix(data, mic_channels))
                         .iter()
-                        .map(|&s| (s.clamp(-1.0, 1.0) * 32767.0) as i16)
+                        .map(|&s| crate::audio::f32_to_i16(s))
                         .collect();
 
                     if let Ok(mut buf) = mic_buffer_clone.lock() {
                         buf.extend(samples);
                     }
-                },
-                |err| eprintln!("Mic stream error: {}", err),
-                None,
-            );
+                })
+            });
+            if let Err(ref e) = mic_stream {
+                tracing::warn!("Failed to build mic stream: {}", e);
+            }
 
             // Build system audio stream (if available)
             let system_stream = if let Some(sys_dev) = system_device {
                 let system_buffer_clone = system_buffer.clone();
-                let sys_config = cpal::StreamConfig {
-                    channels: 1, // We'll take just one channel
-                    sample_rate: cpal::SampleRate(sample_rate),
-                    buffer_size: cpal::BufferSize::Default,
-                };
-
-                match sys_dev.build_input_stream(
-                    &sys_config,
-                    move |data: &[f32], _: &cpal::InputCallbackInfo| {
-                        let samples: Vec<i16> = data
+                // The loopback device may not support the mic's rate; resample to match it
+                let stream = system_input_config(&sys_dev, sample_rate).and_then(|sys_config| {
+                    let sys_channels = sys_config.channels() as usize;
+                    let mut resampler = LinearResampler::new(sys_config.sample_rate().0, sample_rate);
+                    crate::audio::build_f32_input_stream(&sys_dev, &sys_config, move |data: &[f32]| {
+                        let samples: Vec<i16> = resampler
+                            .process(&downmix(data, sys_channels))
                             .iter()
-                            .map(|&s| (s.clamp(-1.0, 1.0) * 32767.0) as i16)
+                            .map(|&s| crate::audio::f32_to_i16(s))
                             .collect();
 
                         if let Ok(mut buf) = system_buffer_clone.lock() {
                             buf.extend(samples);
                         }
-                    },
-                    |err| eprintln!("System audio stream error: {}", err),
-                    None,
-                ) {
+                    })
+                });
+                match stream {
                     Ok(stream) => Some(stream),
                     Err(e) => {
-                        eprintln!("Failed to build system audio stream: {}", e);
+                        tracing::warn!("Failed to build system audio stream: {}", e);
                         None
                     }
                 }
@@ -197,17 +269,17 @@ impl DualAudioCapturer {
             // Start streams
             if let Ok(ref stream) = mic_stream {
                 if let Err(e) = stream.play() {
-                    eprintln!("Failed to start mic stream: {}", e);
+                    tracing::warn!("Failed to start mic stream: {}", e);
                     return;
                 }
-                eprintln!("Microphone capture started");
+                tracing::info!("Microphone capture started");
             }
 
             if let Some(ref stream) = system_stream {
                 if let Err(e) = stream.play() {
-                    eprintln!("Failed to start system audio stream: {}", e);
+                    tracing::warn!("Failed to start system audio stream: {}", e);
                 }
-                eprintln!("System audio capture started");
+                tracing::info!("System audio capture started");
             }
 
             // Main loop: mix audio into stereo and send
@@ -256,12 +328,12 @@ impl DualAudioCapturer {
                 }
 
                 // Send the stereo audio
-                if audio_tx.blocking_send(stereo_bytes).is_err() {
+                if !audio_tx.send(stereo_bytes) {
                     break;
                 }
             }
 
-            eprintln!("Dual audio capture stopped");
+            tracing::info!("Dual audio capture stopped");
         });
 
         Ok(())
@@ -301,3 +373,31 @@ pub fn list_audio_devices() -> Vec<String> {
 
     devices
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resampler_converts_rate_across_chunks() {
+        let mut resampler = LinearResampler::new(48_000, 16_000);
+        let chunk = vec![0.5f32; 480];
+
+        let total: usize = (0..10).map(|_| resampler.process(&chunk).len()).sum();
+        assert_eq!(total, 1600);
+
+        // After the first chunk, a constant signal stays constant
+        assert!(resampler.process(&chunk).iter().all(|&s| (s - 0.5).abs() < 1e-6));
+    }
+
+    #[test]
+    fn test_resampler_passthrough_at_same_rate() {
+        let mut resampler = LinearResampler::new(44_100, 44_100);
+        assert_eq!(resampler.process(&[0.1, 0.2, 0.3]), vec![0.1, 0.2, 0.3]);
+    }
+
+    #[test]
+    fn test_downmix_averages_frames() {
+        assert_eq!(downmix(&[0.2, 0.4, -1.0, 1.0], 2), vec![0.3, 0.0]);
+    }
+}
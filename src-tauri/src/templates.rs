@@ -0,0 +1,167 @@
+//! Reusable meeting templates (agenda, default attendees, summary structure)
+//!
+//! Stored as JSON next to the meetings database so recurring meetings
+//! like a weekly standup can start with the same context every time.
+
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+
+use crate::persist;
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct MeetingTemplate {
+    /// Empty when saving a new template; one is generated
+    #[serde(default)]
+    pub id: String,
+    pub name: String,
+    #[serde(default)]
+    pub agenda: Vec<String>,
+    #[serde(default)]
+    pub attendees: Vec<String>,
+    /// Extra instructions for the summary, e.g. "Group updates by person: yesterday / today / blockers"
+    #[serde(default)]
+    pub summary_template: String,
+    #[serde(default)]
+    pub created_at: String,
+    #[serde(default)]
+    pub updated_at: String,
+}
+
+impl MeetingTemplate {
+    /// Meeting context seeded from the template name and agenda
+    pub fn meeting_context(&self) -> String {
+        let mut context = format!("Meeting: {}", self.name);
+        if !self.agenda.is_empty() {
+            context.push_str("\nAgenda:");
+            for item in &self.agenda {
+                context.push_str(&format!("\n- {}", item));
+            }
+        }
+        context
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct TemplateStore {
+    pub templates: Vec<MeetingTemplate>,
+}
+
+impl TemplateStore {
+    fn get_path() -> Option<PathBuf> {
+        dirs::config_dir().map(|mut path| {
+            path.push("vantage");
+            path.push("templates.json");
+            path
+        })
+    }
+
+    pub fn load() -> Self {
+        let Some(path) = Self::get_path() else {
+            tracing::warn!("Could not determine config directory for meeting templates");
+            return Self::default();
+        };
+
+        if !path.exists() {
+            return Self::default();
+        }
+
+        match fs::read_to_string(&path) {
+            Ok(content) => {
+                match serde_json::from_str(&content) {
+                    Ok(store) => store,
+                    Err(e) => {
+                        tracing::warn!("Failed to parse meeting templates: {}", e);
+                        Self::default()
+                    }
+                }
+            }
+            Err(e) => {
+                tracing::warn!("Failed to read meeting templates: {}", e);
+                Self::default()
+            }
+        }
+    }
+
+    pub fn save(&self) -> Result<(), String> {
+        let path = Self::get_path()
+            .ok_or_else(|| "Could not determine config directory".to_string())?;
+
+        let content = serde_json::to_string_pretty(self)
+            .map_err(|e| format!("Failed to serialize templates: {}", e))?;
+
+        persist::write_atomic(&path, &content)
+            .map_err(|e| format!("Failed to write templates file: {}", e))?;
+
+        Ok(())
+    }
+
+    /// Insert a new template or replace the one with the same id.
+    /// Returns the stored template (with id and timestamps filled in).
+    pub fn upsert(&mut self, mut template: MeetingTemplate) -> MeetingTemplate {
+        let now = Utc::now().to_rfc3339();
+        template.updated_at = now.clone();
+
+        if let Some(pos) = self.templates.iter().position(|t| !template.id.is_empty() && t.id == template.id) {
+            template.created_at = self.templates[pos].created_at.clone();
+            self.templates[pos] = template.clone();
+        } else {
+            if template.id.is_empty() {
+                template.id = format!("template_{}", Utc::now().format("%Y%m%d_%H%M%S_%3f"));
+            }
+            template.created_at = now;
+            self.templates.push(template.clone());
+        }
+
+        template
+    }
+
+    pub fn get(&self, id: &str) -> Option<&MeetingTemplate> {
+        self.templates.iter().find(|t| t.id == id)
+    }
+
+    pub fn delete(&mut self, id: &str) -> Result<(), String> {
+        let pos = self.templates.iter().position(|t| t.id == id)
+            .ok_or_else(|| format!("Template not found: {}", id))?;
+        self.templates.remove(pos);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn standup() -> MeetingTemplate {
+        MeetingTemplate {
+            name: "Weekly standup".to_string(),
+            agenda: vec!["Updates".to_string(), "Blockers".to_string()],
+            attendees: vec!["alex@example.com".to_string()],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_upsert_assigns_id_then_replaces() {
+        let mut store = TemplateStore::default();
+        let saved = store.upsert(standup());
+        assert!(!saved.id.is_empty());
+
+        let mut edited = saved.clone();
+        edited.agenda.push("Demos".to_string());
+        let updated = store.upsert(edited);
+
+        assert_eq!(store.templates.len(), 1);
+        assert_eq!(updated.created_at, saved.created_at);
+        assert_eq!(store.get(&saved.id).unwrap().agenda.len(), 3);
+    }
+
+    #[test]
+    fn test_meeting_context_lists_agenda() {
+        assert_eq!(
+            standup().meeting_context(),
+            "Meeting: Weekly standup\nAgenda:\n- Updates\n- Blockers"
+        );
+    }
+}
@@ -0,0 +1,50 @@
+//! Approximate token counts for prompts
+//!
+//! Providers tokenize differently (Llama, GPT), so this is a heuristic rather than an exact
+//! tokenizer: about four characters per token for ASCII words, one per punctuation mark or
+//! non-ASCII character. Good enough to tell whether a transcript fits a context window.
+
+/// Assumed context window when the model's isn't known (e.g. the live model list wasn't fetched)
+pub const DEFAULT_CONTEXT_WINDOW: u32 = 8_192;
+
+/// Approximate number of tokens in `text`
+pub fn estimate_tokens(text: &str) -> usize {
+    text.split_whitespace().map(word_tokens).sum()
+}
+
+fn word_tokens(word: &str) -> usize {
+    let mut tokens = 0;
+    let mut run = 0usize;
+    for c in word.chars() {
+        if c.is_ascii_alphanumeric() {
+            run += 1;
+        } else {
+            tokens += run.div_ceil(4) + 1;
+            run = 0;
+        }
+    }
+    tokens + run.div_ceil(4)
+}
+
+/// Tokens a prompt can use while leaving room for `max_tokens` of output
+pub fn prompt_budget(context_window: u32, max_tokens: u32) -> usize {
+    context_window.saturating_sub(max_tokens) as usize
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_estimate_tokens() {
+        assert_eq!(estimate_tokens(""), 0);
+        assert_eq!(estimate_tokens("Hi team"), 2);
+        // "everyone" is two tokens, the comma one more
+        assert_eq!(estimate_tokens("Morning, everyone"), 5);
+        assert_eq!(estimate_tokens("Q3 launch: ship it!"), 7);
+        assert_eq!(estimate_tokens("café"), 2);
+
+        assert_eq!(prompt_budget(8_192, 1_024), 7_168);
+        assert_eq!(prompt_budget(512, 1_024), 0);
+    }
+}
@@ -0,0 +1,163 @@
+//! On-disk spill file for long live transcripts
+//!
+//! Once the live transcript passes the configured segment cap, its oldest segments are
+//! appended here (one JSON segment per line) and dropped from memory. Anything that
+//! needs the whole meeting (summaries, export, saving) reads this file back first.
+
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::PathBuf;
+
+use crate::{persist, TranscriptSegment};
+
+#[derive(Debug, Default)]
+pub struct TranscriptArchive {
+    path: Option<PathBuf>,
+    len: usize,
+}
+
+impl TranscriptArchive {
+    /// Archive in the config folder. Starts empty: a file left by a previous run is discarded.
+    pub fn new() -> Self {
+        let path = dirs::config_dir().map(|mut path| {
+            path.push("vantage");
+            path.push("live_transcript_archive.jsonl");
+            path
+        });
+        let mut archive = Self { path, len: 0 };
+        archive.clear();
+        archive
+    }
+
+    #[cfg(test)]
+    fn at(path: PathBuf) -> Self {
+        let mut archive = Self { path: Some(path), len: 0 };
+        archive.clear();
+        archive
+    }
+
+    /// Number of segments moved to disk
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Append segments after the ones already archived
+    pub fn append(&mut self, segments: &[TranscriptSegment]) -> Result<(), String> {
+        let path = self.path.as_ref().ok_or_else(|| "Could not determine config directory".to_string())?;
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+        }
+
+        let lines = Self::to_lines(segments)?;
+
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .map_err(|e| format!("Failed to open transcript archive: {}", e))?;
+        file.write_all(lines.as_bytes())
+            .map_err(|e| format!("Failed to write transcript archive: {}", e))?;
+
+        self.len += segments.len();
+        Ok(())
+    }
+
+    /// One JSON segment per line
+    fn to_lines(segments: &[TranscriptSegment]) -> Result<String, String> {
+        let mut lines = String::new();
+        for segment in segments {
+            lines.push_str(&serde_json::to_string(segment).map_err(|e| e.to_string())?);
+            lines.push('\n');
+        }
+        Ok(lines)
+    }
+
+    /// All archived segments, oldest first
+    pub fn load(&self) -> Result<Vec<TranscriptSegment>, String> {
+        if self.len == 0 {
+            return Ok(Vec::new());
+        }
+        let path = self.path.as_ref().ok_or_else(|| "Could not determine config directory".to_string())?;
+        let content = fs::read_to_string(path)
+            .map_err(|e| format!("Failed to read transcript archive: {}", e))?;
+
+        content
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(|line| {
+                serde_json::from_str::<TranscriptSegment>(line)
+                    .map(|segment| TranscriptSegment { is_final: true, ..segment })
+                    .map_err(|e| format!("Corrupt transcript archive: {}", e))
+            })
+            .collect()
+    }
+
+    /// Apply a change to every archived segment (e.g. relabeling speakers).
+    /// The file is replaced atomically, so a failed write leaves the old archive in place.
+    pub fn update(&mut self, change: impl Fn(&mut [TranscriptSegment])) -> Result<(), String> {
+        if self.len == 0 {
+            return Ok(());
+        }
+        let path = self.path.clone().ok_or_else(|| "Could not determine config directory".to_string())?;
+        let mut segments = self.load()?;
+        change(&mut segments);
+
+        let lines = Self::to_lines(&segments)?;
+        persist::write_atomic(&path, &lines)
+            .map_err(|e| format!("Failed to write transcript archive: {}", e))?;
+
+        self.len = segments.len();
+        Ok(())
+    }
+
+    /// Forget everything archived (new meeting)
+    pub fn clear(&mut self) {
+        if let Some(path) = &self.path {
+            let _ = fs::remove_file(path);
+        }
+        self.len = 0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn segment(text: &str) -> TranscriptSegment {
+        TranscriptSegment {
+            timestamp: "10:00:00".to_string(),
+            speaker: "Speaker 1".to_string(),
+            text: text.to_string(),
+            is_final: true,
+            speaker_id: Some(1),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_append_load_update_and_clear() {
+        let path = std::env::temp_dir().join(format!("vantage-archive-test-{}.jsonl", std::process::id()));
+        let mut archive = TranscriptArchive::at(path.clone());
+
+        archive.append(&[segment("first"), segment("second")]).unwrap();
+        archive.append(&[segment("third")]).unwrap();
+        assert_eq!(archive.len(), 3);
+
+        let texts: Vec<String> = archive.load().unwrap().into_iter().map(|s| s.text).collect();
+        assert_eq!(texts, ["first", "second", "third"]);
+
+        archive.update(|segments| segments.iter_mut().for_each(|s| s.speaker = "You".to_string())).unwrap();
+        let loaded = archive.load().unwrap();
+        assert_eq!(loaded.len(), 3);
+        assert!(loaded.iter().all(|s| s.speaker == "You" && s.is_final));
+
+        archive.clear();
+        assert!(archive.is_empty());
+        assert!(archive.load().unwrap().is_empty());
+        assert!(!path.exists());
+    }
+}
@@ -0,0 +1,154 @@
+//! Undo/redo history for manual transcript edits
+//!
+//! Stores full snapshots of the transcript taken before each edit.
+//! The undo stack is capped so long editing sessions don't grow memory unbounded.
+//! Live transcription keeps appending while the user edits, so undo and redo only replace
+//! the part of the transcript the edit produced and keep anything appended after it.
+
+use crate::TranscriptSegment;
+
+/// Maximum number of undo states kept
+const MAX_HISTORY: usize = 50;
+
+/// A transcript to restore, in place of the first `replaces` segments of the current one
+#[derive(Debug)]
+struct Snapshot {
+    segments: Vec<TranscriptSegment>,
+    replaces: usize,
+}
+
+#[derive(Debug, Default)]
+pub struct TranscriptHistory {
+    undo_stack: Vec<Snapshot>,
+    redo_stack: Vec<Snapshot>,
+}
+
+/// `snapshot` followed by the segments of `current` appended after the first `replaces`
+fn restore(current: Vec<TranscriptSegment>, snapshot: Snapshot) -> (Vec<TranscriptSegment>, Snapshot) {
+    let mut edited = current;
+    let newer = edited.split_off(snapshot.replaces.min(edited.len()));
+    let inverse = Snapshot { replaces: snapshot.segments.len(), segments: edited };
+    let mut restored = snapshot.segments;
+    restored.extend(newer);
+    (restored, inverse)
+}
+
+impl TranscriptHistory {
+    /// Record the transcript as it was before an edit, and how long the edit left it.
+    /// A new edit invalidates anything that was undone.
+    pub fn record(&mut self, before: Vec<TranscriptSegment>, edited_len: usize) {
+        self.undo_stack.push(Snapshot { segments: before, replaces: edited_len });
+        if self.undo_stack.len() > MAX_HISTORY {
+            self.undo_stack.remove(0);
+        }
+        self.redo_stack.clear();
+    }
+
+    /// Step back one edit. Returns the transcript to restore, if any.
+    pub fn undo(&mut self, current: Vec<TranscriptSegment>) -> Option<Vec<TranscriptSegment>> {
+        let (previous, inverse) = restore(current, self.undo_stack.pop()?);
+        self.redo_stack.push(inverse);
+        Some(previous)
+    }
+
+    /// Re-apply the last undone edit. Returns the transcript to restore, if any.
+    pub fn redo(&mut self, current: Vec<TranscriptSegment>) -> Option<Vec<TranscriptSegment>> {
+        let (next, inverse) = restore(current, self.redo_stack.pop()?);
+        self.undo_stack.push(inverse);
+        Some(next)
+    }
+
+    /// The first segments of the transcript were moved out of memory: strip them from the
+    /// snapshots too. Edits to those segments can't be undone any more, so the snapshot
+    /// before such an edit and everything older is dropped.
+    pub fn archive_front(&mut self, archived: &[TranscriptSegment]) {
+        let keeps = |snapshot: &Snapshot| snapshot.segments.starts_with(archived);
+        if let Some(last_changed) = self.undo_stack.iter().rposition(|s| !keeps(s)) {
+            self.undo_stack.drain(..=last_changed);
+        }
+        if !self.redo_stack.iter().all(keeps) {
+            self.redo_stack.clear();
+        }
+        for snapshot in self.undo_stack.iter_mut().chain(self.redo_stack.iter_mut()) {
+            snapshot.segments.drain(..archived.len());
+            snapshot.replaces = snapshot.replaces.saturating_sub(archived.len());
+        }
+    }
+
+    /// Drop all history (e.g. when starting a new meeting)
+    pub fn clear(&mut self) {
+        self.undo_stack.clear();
+        self.redo_stack.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn transcript(texts: &[&str]) -> Vec<TranscriptSegment> {
+        texts
+            .iter()
+            .map(|t| TranscriptSegment {
+                text: t.to_string(),
+                ..Default::default()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_undo_then_redo_restores_states() {
+        let mut history = TranscriptHistory::default();
+        let before = transcript(&["a", "b"]);
+        let after = transcript(&["a b"]);
+        history.record(before.clone(), after.len());
+
+        let undone = history.undo(after.clone()).unwrap();
+        assert_eq!(undone[0].text, "a");
+
+        let redone = history.redo(undone).unwrap();
+        assert_eq!(redone[0].text, "a b");
+        assert!(history.redo(redone).is_none());
+    }
+
+    #[test]
+    fn test_archive_front_keeps_later_edits() {
+        let mut history = TranscriptHistory::default();
+        // Edit to "a", then an edit after it
+        history.record(transcript(&["x", "b", "c"]), 3);
+        history.record(transcript(&["a", "b", "c"]), 2);
+
+        history.archive_front(&transcript(&["a"]));
+        assert_eq!(history.undo_stack.len(), 1);
+        let undone = history.undo(transcript(&["b c"])).unwrap();
+        assert_eq!(undone.iter().map(|s| s.text.as_str()).collect::<Vec<_>>(), ["b", "c"]);
+        assert!(history.undo(undone).is_none());
+    }
+
+    #[test]
+    fn test_history_is_capped() {
+        let mut history = TranscriptHistory::default();
+        for i in 0..(MAX_HISTORY + 10) {
+            history.record(transcript(&[&i.to_string()]), 1);
+        }
+        assert_eq!(history.undo_stack.len(), MAX_HISTORY);
+        assert_eq!(history.undo_stack[0].segments[0].text, "10");
+    }
+
+    #[test]
+    fn test_undo_keeps_segments_appended_since_the_edit() {
+        let texts = |t: &[TranscriptSegment]| t.iter().map(|s| s.text.clone()).collect::<Vec<_>>();
+        let mut history = TranscriptHistory::default();
+        // "a" and "b" merged, then live transcription added "c"
+        history.record(transcript(&["a", "b"]), 1);
+
+        let undone = history.undo(transcript(&["a b", "c"])).unwrap();
+        assert_eq!(texts(&undone), ["a", "b", "c"]);
+
+        // "d" arrived after the undo
+        let mut current = undone;
+        current.extend(transcript(&["d"]));
+        let redone = history.redo(current).unwrap();
+        assert_eq!(texts(&redone), ["a b", "c", "d"]);
+    }
+}
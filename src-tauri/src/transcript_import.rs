@@ -0,0 +1,149 @@
+//! Import transcripts exported by Zoom, Teams and other tools (WebVTT / SRT)
+//!
+//! Speakers come from WebVTT voice tags (`<v Jane Doe>`, used by Teams) or a
+//! "Name: text" prefix in the cue text (used by Zoom).
+
+use crate::TranscriptSegment;
+
+/// Longest "Name:" prefix we treat as a speaker, so sentences with a colon aren't split
+const MAX_SPEAKER_NAME_WORDS: usize = 4;
+
+/// Parse "HH:MM:SS.mmm", "MM:SS.mmm" or the SRT "HH:MM:SS,mmm" form into milliseconds
+fn parse_cue_time(time: &str) -> Option<u64> {
+    let time = time.trim().replace(',', ".");
+    let (clock, millis) = time.split_once('.').unwrap_or((time.as_str(), "0"));
+    let millis: u64 = format!("{:0<3}", millis).get(..3)?.parse().ok()?;
+
+    let parts: Vec<u64> = clock
+        .split(':')
+        .map(|p| p.parse::<u64>().ok())
+        .collect::<Option<Vec<_>>>()?;
+    let seconds = match parts.as_slice() {
+        [h, m, s] => h * 3600 + m * 60 + s,
+        [m, s] => m * 60 + s,
+        _ => return None,
+    };
+    Some(seconds * 1000 + millis)
+}
+
+/// Parse a "start --> end" timing line. Cue settings after the end time are ignored.
+fn parse_timing_line(line: &str) -> Option<(u64, u64)> {
+    let (start, rest) = line.split_once("-->")?;
+    let end = rest.split_whitespace().next()?;
+    Some((parse_cue_time(start)?, parse_cue_time(end)?))
+}
+
+/// Remove formatting tags (`<i>`, `<c.color>`, `</v>`, timestamps...)
+fn strip_tags(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut in_tag = false;
+    for c in text.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' if in_tag => in_tag = false,
+            _ if !in_tag => out.push(c),
+            _ => {}
+        }
+    }
+    out
+}
+
+/// Split cue text into (speaker, text) from a voice tag or a "Name: text" prefix
+fn split_speaker(text: &str) -> (Option<String>, String) {
+    let trimmed = text.trim();
+    if let Some(rest) = trimmed.strip_prefix("<v") {
+        if let Some((name, body)) = rest.split_once('>') {
+            // "<v.loud Jane Doe>" carries classes before the name
+            let name = name.trim_start_matches(|c: char| c != ' ').trim();
+            if !name.is_empty() {
+                return (Some(name.to_string()), strip_tags(body).trim().to_string());
+            }
+        }
+    }
+
+    let plain = strip_tags(trimmed);
+    if let Some((name, body)) = plain.split_once(": ") {
+        let name = name.trim();
+        let words = name.split_whitespace().count();
+        if words > 0
+            && words <= MAX_SPEAKER_NAME_WORDS
+            && !name.chars().any(|c| c.is_ascii_digit() || matches!(c, '.' | '!' | '?' | ','))
+        {
+            return (Some(name.to_string()), body.trim().to_string());
+        }
+    }
+    (None, plain.trim().to_string())
+}
+
+/// Format milliseconds from the start of the meeting as "HH:MM:SS"
+fn format_offset(ms: u64) -> String {
+    let secs = ms / 1000;
+    format!("{:02}:{:02}:{:02}", secs / 3600, (secs / 60) % 60, secs % 60)
+}
+
+/// Parse WebVTT or SRT content into transcript segments, one per cue.
+/// Timestamps are offsets from the start of the recording.
+pub fn parse_subtitles(content: &str) -> Vec<TranscriptSegment> {
+    let content = content.trim_start_matches('\u{feff}').replace("\r\n", "\n");
+    let mut segments = Vec::new();
+
+    for block in content.split("\n\n") {
+        let mut lines = block.lines().skip_while(|l| !l.contains("-->"));
+        let Some((start_ms, end_ms)) = lines.next().and_then(parse_timing_line) else {
+            // Header, NOTE, STYLE or malformed block
+            continue;
+        };
+
+        let text = lines.map(str::trim).filter(|l| !l.is_empty()).collect::<Vec<_>>().join(" ");
+        let (speaker, text) = split_speaker(&text);
+        if text.is_empty() {
+            continue;
+        }
+
+        segments.push(TranscriptSegment {
+            timestamp: format_offset(start_ms),
+            speaker: speaker.unwrap_or_else(|| "Speaker".to_string()),
+            text,
+            is_final: true,
+            start_ms: Some(start_ms),
+            end_ms: Some(end_ms),
+            ..Default::default()
+        });
+    }
+
+    segments
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_zoom_vtt_with_speaker_prefix() {
+        let vtt = "WEBVTT\n\n1\n00:00:01.500 --> 00:00:04.000\nJohn Smith: Hello everyone\n\n2\n00:01:02.000 --> 00:01:05.250\nMaria Garcia: Let's start\n";
+        let segments = parse_subtitles(vtt);
+
+        assert_eq!(segments.len(), 2);
+        assert_eq!(segments[0].speaker, "John Smith");
+        assert_eq!(segments[0].text, "Hello everyone");
+        assert_eq!(segments[0].start_ms, Some(1_500));
+        assert_eq!(segments[1].timestamp, "00:01:02");
+        assert_eq!(segments[1].end_ms, Some(65_250));
+        assert_eq!(segments[1].speaker, "Maria Garcia");
+    }
+
+    #[test]
+    fn test_parse_teams_voice_tags_and_srt() {
+        let vtt = "WEBVTT\r\n\r\nNOTE exported\r\n\r\nab12-3\r\n00:00:05.000 --> 00:00:06.000 align:start\r\n<v Jane Doe>Can you <i>see</i> my screen?</v>\r\n";
+        let segments = parse_subtitles(vtt);
+        assert_eq!(segments.len(), 1);
+        assert_eq!(segments[0].speaker, "Jane Doe");
+        assert_eq!(segments[0].text, "Can you see my screen?");
+
+        let srt = "1\n00:00:01,000 --> 00:00:02,500\nWe shipped it. Finally: done\nsecond line\n";
+        let segments = parse_subtitles(srt);
+        assert_eq!(segments[0].speaker, "Speaker");
+        assert_eq!(segments[0].text, "We shipped it. Finally: done second line");
+        assert_eq!(segments[0].end_ms, Some(2_500));
+    }
+}
@@ -0,0 +1,210 @@
+//! Cumulative usage per provider (audio minutes transcribed, LLM tokens), persisted
+//! across sessions so users can watch their consumption against free-tier limits
+//!
+//! Provider clients and the generate path record into a process-wide store, the same
+//! way they read their endpoints, so no app state has to be threaded through them.
+//! Counters live in memory and are written out every `SAVE_INTERVAL_SECS` and on exit,
+//! rather than on every chunk.
+
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+
+use crate::{audio, persist};
+
+/// Counters for one provider
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct ProviderUsage {
+    #[serde(default)]
+    pub audio_minutes: f64,
+    #[serde(default)]
+    pub transcription_requests: u64,
+    /// Estimated from text length (see `tokens::estimate_tokens`)
+    #[serde(default)]
+    pub prompt_tokens: u64,
+    #[serde(default)]
+    pub completion_tokens: u64,
+    #[serde(default)]
+    pub llm_requests: u64,
+}
+
+/// Usage since `since`, keyed by provider ("groq", "openai", "assemblyai", ...)
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct UsageStats {
+    #[serde(default)]
+    pub since: String,
+    #[serde(default)]
+    pub providers: BTreeMap<String, ProviderUsage>,
+}
+
+impl UsageStats {
+    fn new() -> Self {
+        Self { since: Utc::now().to_rfc3339(), providers: BTreeMap::new() }
+    }
+
+    fn get_path() -> Option<PathBuf> {
+        dirs::config_dir().map(|mut path| {
+            path.push("vantage");
+            path.push("usage.json");
+            path
+        })
+    }
+
+    fn load() -> Self {
+        let Some(path) = Self::get_path() else {
+            tracing::warn!("Could not determine config directory for usage stats");
+            return Self::new();
+        };
+
+        if !path.exists() {
+            return Self::new();
+        }
+
+        match fs::read_to_string(&path) {
+            Ok(content) => match serde_json::from_str(&content) {
+                Ok(stats) => stats,
+                Err(e) => {
+                    tracing::warn!("Failed to parse usage stats: {}", e);
+                    Self::new()
+                }
+            },
+            Err(e) => {
+                tracing::warn!("Failed to read usage stats: {}", e);
+                Self::new()
+            }
+        }
+    }
+
+    fn save(&self) -> Result<(), String> {
+        let path = Self::get_path()
+            .ok_or_else(|| "Could not determine config directory".to_string())?;
+
+        let content = serde_json::to_string_pretty(self)
+            .map_err(|e| format!("Failed to serialize usage stats: {}", e))?;
+
+        persist::write_atomic(&path, &content)
+            .map_err(|e| format!("Failed to write usage file: {}", e))
+    }
+
+    pub fn add_audio(&mut self, provider: &str, seconds: f64) {
+        let usage = self.providers.entry(provider.to_string()).or_default();
+        usage.audio_minutes += seconds.max(0.0) / 60.0;
+        usage.transcription_requests += 1;
+    }
+
+    pub fn add_tokens(&mut self, provider: &str, prompt_tokens: usize, completion_tokens: usize) {
+        let usage = self.providers.entry(provider.to_string()).or_default();
+        usage.prompt_tokens += prompt_tokens as u64;
+        usage.completion_tokens += completion_tokens as u64;
+        usage.llm_requests += 1;
+    }
+}
+
+/// How often changed counters are written to disk
+pub const SAVE_INTERVAL_SECS: u64 = 60;
+
+/// Loaded from disk on first use
+static USAGE: Mutex<Option<UsageStats>> = Mutex::new(None);
+/// Counters changed since they were last saved
+static DIRTY: AtomicBool = AtomicBool::new(false);
+
+/// Run `f` on the stats, loading them first if needed
+fn with_stats<T>(f: impl FnOnce(&mut UsageStats) -> T) -> T {
+    // Read the file without holding the lock, off the async worker
+    let loaded = USAGE.lock().unwrap_or_else(|e| e.into_inner()).is_some();
+    let from_disk = (!loaded).then(|| persist::blocking(UsageStats::load));
+
+    let mut guard = USAGE.lock().unwrap_or_else(|e| e.into_inner());
+    let stats = match from_disk {
+        Some(stats) => guard.get_or_insert(stats),
+        None => guard.get_or_insert_with(UsageStats::new),
+    };
+    f(stats)
+}
+
+/// Apply `f` to the stored stats; they are saved on the next `save`
+fn update(f: impl FnOnce(&mut UsageStats)) {
+    with_stats(f);
+    DIRTY.store(true, Ordering::SeqCst);
+}
+
+/// Write the counters to disk if they changed since the last save
+pub fn save() {
+    if !DIRTY.swap(false, Ordering::SeqCst) {
+        return;
+    }
+    let stats = with_stats(|stats| stats.clone());
+    if let Err(e) = stats.save() {
+        tracing::warn!("Failed to persist usage stats: {}", e);
+        DIRTY.store(true, Ordering::SeqCst);
+    }
+}
+
+/// Record `seconds` of audio sent to a transcription provider
+pub fn record_audio(provider: &str, seconds: f64) {
+    update(|stats| stats.add_audio(provider, seconds));
+}
+
+/// Record one LLM call's prompt and completion tokens
+pub fn record_tokens(provider: &str, prompt_tokens: usize, completion_tokens: usize) {
+    update(|stats| stats.add_tokens(provider, prompt_tokens, completion_tokens));
+}
+
+/// Current totals
+pub fn snapshot() -> UsageStats {
+    with_stats(|stats| stats.clone())
+}
+
+/// Clear all counters and start a new period from now
+pub fn reset() -> Result<UsageStats, String> {
+    let mut guard = USAGE.lock().unwrap_or_else(|e| e.into_inner());
+    let stats = UsageStats::new();
+    stats.save()?;
+    *guard = Some(stats.clone());
+    DIRTY.store(false, Ordering::SeqCst);
+    Ok(stats)
+}
+
+/// Duration of WAV bytes about to be uploaded, from the byte rate in the header.
+/// Works for a recording still being written, whose header sizes aren't final yet.
+pub fn wav_seconds(bytes: &[u8]) -> f64 {
+    let Some(layout) = audio::wav_layout(bytes) else {
+        return 0.0;
+    };
+    bytes.len().saturating_sub(layout.data_offset) as f64 / layout.byte_rate as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_usage_accumulates_per_provider() {
+        let mut stats = UsageStats::default();
+        stats.add_audio("groq", 90.0);
+        stats.add_audio("groq", 30.0);
+        stats.add_tokens("groq", 1000, 200);
+        stats.add_tokens("openai", 50, 10);
+
+        let groq = &stats.providers["groq"];
+        assert_eq!(groq.audio_minutes, 2.0);
+        assert_eq!(groq.transcription_requests, 2);
+        assert_eq!((groq.prompt_tokens, groq.completion_tokens, groq.llm_requests), (1000, 200, 1));
+        assert_eq!(stats.providers["openai"].audio_minutes, 0.0);
+
+        // 1 second of 16 kHz mono 16-bit audio
+        let spec = hound::WavSpec { channels: 1, sample_rate: 16_000, bits_per_sample: 16, sample_format: hound::SampleFormat::Int };
+        let mut cursor = std::io::Cursor::new(Vec::new());
+        let mut writer = hound::WavWriter::new(&mut cursor, spec).unwrap();
+        for _ in 0..16_000 {
+            writer.write_sample(0i16).unwrap();
+        }
+        writer.finalize().unwrap();
+        assert_eq!(wav_seconds(cursor.get_ref()), 1.0);
+        assert_eq!(wav_seconds(&[0u8; 10]), 0.0);
+    }
+}
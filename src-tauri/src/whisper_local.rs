@@ -0,0 +1,248 @@
+//! Offline transcription with whisper.cpp
+//!
+//! GGML models are downloaded once into the config folder. Inference needs the
+//! `local-whisper` cargo feature (it builds whisper.cpp); without it, models can still
+//! be listed and downloaded but transcription returns an error.
+
+use anyhow::{anyhow, Result};
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+use tokio::io::AsyncWriteExt;
+
+use crate::system_audio::{downmix, LinearResampler};
+
+const MODEL_BASE_URL: &str = "https://huggingface.co/ggerganov/whisper.cpp/resolve/main";
+
+/// whisper.cpp expects 16kHz mono f32 samples
+const WHISPER_SAMPLE_RATE: u32 = 16_000;
+
+/// Model used when none has been chosen: a good speed/accuracy tradeoff on laptops
+pub const DEFAULT_MODEL_SIZE: &str = "base";
+
+/// Supported model sizes with their approximate download size in MB
+const MODEL_SIZES: &[(&str, u64)] = &[
+    ("tiny", 75),
+    ("base", 142),
+    ("small", 466),
+    ("medium", 1500),
+];
+
+#[derive(Debug, Clone, Serialize)]
+pub struct WhisperModelInfo {
+    pub size: String,
+    pub size_mb: u64,
+    pub downloaded: bool,
+    pub path: Option<String>,
+}
+
+fn models_dir() -> Option<PathBuf> {
+    dirs::config_dir().map(|mut path| {
+        path.push("vantage");
+        path.push("whisper-models");
+        path
+    })
+}
+
+/// Local path of a model, whether or not it has been downloaded yet
+pub fn model_path(size: &str) -> Result<PathBuf> {
+    if !MODEL_SIZES.iter().any(|(s, _)| *s == size) {
+        let sizes: Vec<&str> = MODEL_SIZES.iter().map(|(s, _)| *s).collect();
+        return Err(anyhow!("Unknown Whisper model size '{}' (expected one of: {})", size, sizes.join(", ")));
+    }
+    let dir = models_dir().ok_or_else(|| anyhow!("Could not determine config directory"))?;
+    Ok(dir.join(format!("ggml-{}.bin", size)))
+}
+
+pub fn is_downloaded(size: &str) -> bool {
+    model_path(size).map(|p| p.exists()).unwrap_or(false)
+}
+
+/// All supported models and whether each has been downloaded
+pub fn list_models() -> Vec<WhisperModelInfo> {
+    MODEL_SIZES
+        .iter()
+        .map(|(size, size_mb)| {
+            let path = model_path(size).ok().filter(|p| p.exists());
+            WhisperModelInfo {
+                size: size.to_string(),
+                size_mb: *size_mb,
+                downloaded: path.is_some(),
+                path: path.map(|p| p.display().to_string()),
+            }
+        })
+        .collect()
+}
+
+/// Download a GGML model, calling `on_progress(downloaded_bytes, total_bytes)` as it goes.
+/// Writes to a `.part` file first so an interrupted download is never mistaken for a model.
+pub async fn download_model<F>(size: &str, mut on_progress: F) -> Result<PathBuf>
+where
+    F: FnMut(u64, Option<u64>),
+{
+    let path = model_path(size)?;
+    if let Some(dir) = path.parent() {
+        tokio::fs::create_dir_all(dir).await?;
+    }
+
+    let url = format!("{}/ggml-{}.bin", MODEL_BASE_URL, size);
+    tracing::info!("Downloading Whisper model {} from {}", size, url);
+
+    let mut response = crate::net::client().get(&url).send().await?;
+    if !response.status().is_success() {
+        return Err(anyhow!("Model download failed ({})", response.status()));
+    }
+
+    let total = response.content_length();
+    let part_path = path.with_extension("bin.part");
+    let mut file = tokio::fs::File::create(&part_path).await?;
+    let mut downloaded: u64 = 0;
+
+    while let Some(chunk) = response.chunk().await? {
+        file.write_all(&chunk).await?;
+        downloaded += chunk.len() as u64;
+        on_progress(downloaded, total);
+    }
+    file.flush().await?;
+    drop(file);
+
+    tokio::fs::rename(&part_path, &path).await?;
+    tracing::info!("Whisper model {} saved to {:?} ({}MB)", size, path, downloaded / 1_000_000);
+    Ok(path)
+}
+
+/// Read a WAV file as 16kHz mono samples.
+/// Handles recordings still in progress, whose header doesn't have the data size yet.
+fn load_audio(path: &Path) -> Result<Vec<f32>> {
+    let reader = hound::WavReader::open(path)?;
+    let spec = reader.spec();
+
+    let samples: Vec<f32> = if reader.len() > 0 {
+        match spec.sample_format {
+            hound::SampleFormat::Float => reader.into_samples::<f32>().collect::<std::result::Result<_, _>>()?,
+            hound::SampleFormat::Int => {
+                let scale = (1i64 << (spec.bits_per_sample - 1)) as f32;
+                reader
+                    .into_samples::<i32>()
+                    .map(|s| s.map(|v| v as f32 / scale))
+                    .collect::<std::result::Result<_, _>>()?
+            }
+        }
+    } else if matches!(
+        (spec.sample_format, spec.bits_per_sample),
+        (hound::SampleFormat::Int, 16) | (hound::SampleFormat::Int, 24) | (hound::SampleFormat::Float, 32)
+    ) {
+        let bytes = std::fs::read(path)?;
+        let layout = crate::audio::wav_layout(&bytes).ok_or_else(|| anyhow!("Not a valid WAV file"))?;
+        let data = bytes.get(layout.data_offset..).unwrap_or_default();
+        match spec.bits_per_sample {
+            16 => data
+                .chunks_exact(2)
+                .map(|b| i16::from_le_bytes([b[0], b[1]]) as f32 / 32768.0)
+                .collect(),
+            24 => data
+                .chunks_exact(3)
+                .map(|b| (i32::from_le_bytes([0, b[0], b[1], b[2]]) >> 8) as f32 / 8_388_608.0)
+                .collect(),
+            _ => data
+                .chunks_exact(4)
+                .map(|b| f32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+                .collect(),
+        }
+    } else {
+        Vec::new()
+    };
+
+    let mono = downmix(&samples, spec.channels as usize);
+    Ok(LinearResampler::new(spec.sample_rate, WHISPER_SAMPLE_RATE).process(&mono))
+}
+
+#[cfg(feature = "local-whisper")]
+fn run_whisper(model: &Path, audio: &[f32]) -> Result<String> {
+    use whisper_rs::{FullParams, SamplingStrategy, WhisperContext, WhisperContextParameters};
+
+    let model_str = model.to_str().ok_or_else(|| anyhow!("Invalid model path"))?;
+    let ctx = WhisperContext::new_with_params(model_str, WhisperContextParameters::default())
+        .map_err(|e| anyhow!("Failed to load Whisper model: {}", e))?;
+    let mut state = ctx.create_state().map_err(|e| anyhow!("Failed to create Whisper state: {}", e))?;
+
+    let threads = std::thread::available_parallelism().map(|n| n.get()).unwrap_or(4).min(8);
+    let mut params = FullParams::new(SamplingStrategy::Greedy { best_of: 1 });
+    params.set_language(Some("en"));
+    params.set_n_threads(threads as i32);
+    params.set_print_special(false);
+    params.set_print_progress(false);
+    params.set_print_realtime(false);
+    params.set_print_timestamps(false);
+
+    state.full(params, audio).map_err(|e| anyhow!("Whisper transcription failed: {}", e))?;
+
+    let segments = state.full_n_segments().map_err(|e| anyhow!("{}", e))?;
+    let mut text = String::new();
+    for i in 0..segments {
+        let segment = state.full_get_segment_text(i).map_err(|e| anyhow!("{}", e))?;
+        text.push_str(segment.trim());
+        text.push(' ');
+    }
+    Ok(text.trim().to_string())
+}
+
+#[cfg(not(feature = "local-whisper"))]
+fn run_whisper(_model: &Path, _audio: &[f32]) -> Result<String> {
+    Err(anyhow!("This build doesn't include local Whisper support (enable the local-whisper feature)"))
+}
+
+/// Transcribe a WAV file with a downloaded model. Runs on a blocking thread.
+pub async fn transcribe_file(model_size: &str, file_path: &str) -> Result<String> {
+    let model = model_path(model_size)?;
+    if !model.exists() {
+        return Err(anyhow!("Whisper model '{}' is not downloaded yet", model_size));
+    }
+
+    let file_path = PathBuf::from(file_path);
+    if !file_path.exists() {
+        return Err(anyhow!("Audio file not found: {}", file_path.display()));
+    }
+
+    tokio::task::spawn_blocking(move || {
+        let audio = load_audio(&file_path)?;
+        if audio.is_empty() {
+            return Ok(String::new());
+        }
+        run_whisper(&model, &audio)
+    })
+    .await?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_load_audio_downmixes_and_resamples_to_16k() {
+        let path = std::env::temp_dir().join(format!("vantage-whisper-test-{}.wav", std::process::id()));
+        let spec = hound::WavSpec {
+            channels: 2,
+            sample_rate: 48_000,
+            bits_per_sample: 16,
+            sample_format: hound::SampleFormat::Int,
+        };
+        let mut writer = hound::WavWriter::create(&path, spec).unwrap();
+        for _ in 0..48_000 {
+            writer.write_sample(16384i16).unwrap();
+            writer.write_sample(16384i16).unwrap();
+        }
+        writer.finalize().unwrap();
+
+        let audio = load_audio(&path).unwrap();
+        let _ = std::fs::remove_file(&path);
+
+        // One second of audio
+        assert!((15_990..=16_010).contains(&audio.len()), "got {} samples", audio.len());
+        assert!((audio[100] - 0.5).abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_unknown_model_size_is_rejected() {
+        assert!(model_path("huge").is_err());
+    }
+}
@@ -62,6 +62,20 @@ interface StoredMeeting {
 
 type ViewMode = 'home' | 'meeting-detail' | 'transcript-view';
 
+// Error returned by backend commands
+interface AppError {
+  code: string;
+  message: string;
+}
+
+function isAppError(error: unknown): error is AppError {
+  return typeof error === "object" && error !== null && "code" in error && "message" in error;
+}
+
+function errorMessage(error: unknown): string {
+  return isAppError(error) ? error.message : String(error);
+}
+
 function App() {
   // Core state
   const [isLiveTranscribing, setIsLiveTranscribing] = useState(false);
@@ -399,7 +413,7 @@ function App() {
       }
     } catch (error) {
       console.error("Failed to connect calendar:", error);
-      alert("Failed to connect calendar: " + error);
+      alert("Failed to connect calendar: " + errorMessage(error));
     } finally {
       setIsConnectingCalendar(false);
     }
@@ -435,8 +449,14 @@ function App() {
 
   const handleToggleScreenShare = async (enabled: boolean) => {
     try {
-      await invoke("set_screen_share_exclusion", { exclude: enabled });
-      setHideFromScreenShare(enabled);
+      const result = await invoke<{ applied: boolean; supported: boolean; platform_message: string }>(
+        "set_screen_share_exclusion",
+        { exclude: enabled }
+      );
+      setHideFromScreenShare(result.applied ? enabled : false);
+      if (!result.supported) {
+        console.info(result.platform_message);
+      }
     } catch (error) {
       console.error("Failed to toggle screen share exclusion:", error);
     }
@@ -453,7 +473,7 @@ function App() {
       lastReplyGenerationTime.current = 0;
     } catch (error) {
       console.error("Failed to start live transcription:", error);
-      alert("Failed to start: " + error);
+      alert("Failed to start: " + errorMessage(error));
     }
   };
 
@@ -496,7 +516,7 @@ function App() {
         setIsMockTranscribing(true);
       } catch (err) {
         console.error('Failed to start mock:', err);
-        alert('Mock transcription failed: ' + err);
+        alert('Mock transcription failed: ' + errorMessage(err));
       }
     }
   };
@@ -510,8 +530,8 @@ function App() {
       setSuggestedReplies(replies);
     } catch (error) {
       console.error("Failed to generate replies:", error);
-      const errorMsg = String(error);
-      if (errorMsg.includes("rate") || errorMsg.includes("429")) {
+      const errorMsg = errorMessage(error);
+      if (isAppError(error) && error.code === "rate_limited") {
         setReplyError("Rate limited - waiting before retrying");
       } else {
         setReplyError(errorMsg.substring(0, 100));
@@ -619,7 +639,7 @@ function App() {
       alert("Meeting saved successfully! You can find it in Past Meetings.");
     } catch (error) {
       console.error("Failed to save meeting:", error);
-      alert("Failed to save meeting: " + error);
+      alert("Failed to save meeting: " + errorMessage(error));
     }
   };
 